@@ -0,0 +1,63 @@
+use criterion::Criterion;
+
+use rune::sync::Arc;
+use rune::{Context, Options, Vm};
+
+criterion::criterion_group!(benches, lowering);
+
+const SOURCE: &str = r#"
+pub fn main(n) {
+    let scale = 4;
+    let offset = scale * 2 + 1;
+    let verbose = false;
+    let total = 0;
+    let i = 0;
+
+    while i < n {
+        let step = i;
+
+        if verbose {
+            total += 1000;
+        }
+
+        total += step * scale + offset;
+        i += 1;
+    }
+
+    total
+}
+"#;
+
+fn vm(level: u8) -> Vm {
+    let context = Context::with_default_modules().expect("Failed to build context");
+
+    let mut options = Options::default();
+    options
+        .parse_option(&format!("lowering={level}"))
+        .expect("Failed to parse option");
+
+    let mut sources = crate::sources(SOURCE);
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()
+        .expect("Program to compile successfully");
+
+    let context = Arc::try_new(context.runtime().expect("Failed to build runtime"))
+        .expect("Failed to allocate context");
+    let unit = Arc::try_new(unit).expect("Failed to allocate unit");
+    Vm::new(context, unit)
+}
+
+fn lowering(b: &mut Criterion) {
+    let entry = rune::Hash::type_hash(["main"]);
+
+    for level in [0, 1, 2, 3] {
+        let mut vm = vm(level);
+
+        b.bench_function(&format!("lowering_{level}"), |b| {
+            b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+        });
+    }
+}
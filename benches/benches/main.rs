@@ -54,6 +54,7 @@ mod benchmarks {
     pub mod brainfuck;
     pub mod external_functions;
     pub mod fib;
+    pub mod lowering;
 }
 
 criterion::criterion_main! {
@@ -64,4 +65,5 @@ criterion::criterion_main! {
     benchmarks::brainfuck::benches,
     benchmarks::fib::benches,
     benchmarks::external_functions::benches,
+    benchmarks::lowering::benches,
 }
@@ -27,17 +27,798 @@
 //! }
 //! ```
 
-use rune::{ContextError, Module};
+// Documentation copied from the Tokio project under the MIT license.
+// See: https://github.com/tokio-rs/tokio/blob/master/LICENSE
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::{String, Vec};
+use rune::runtime::{Bytes, Formatter, Mut, Ref, Value, VmError};
+use rune::{nested_try, Any, ContextError, Module};
+
 use std::io;
+use std::path::Path;
 use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// The size of the buffer used when reading files in chunks.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// A module for asynchronous filesystem operations.
+///
+/// If the module is constructed with `stdio` enabled, [`File::stdin`],
+/// [`File::stdout`] and [`File::stderr`] are also provided, which give access
+/// to the standard streams of the current process.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::fs)]
+pub fn module(stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.function_meta(read_to_string)?;
+    m.function_meta(read)?;
+    m.function_meta(write)?;
+    m.function_meta(create_dir_all)?;
+    m.function_meta(remove_file)?;
+    m.function_meta(rename)?;
+    m.function_meta(metadata)?;
+    m.function_meta(read_dir)?;
+
+    m.ty::<Metadata>()?;
+    m.function_meta(Metadata::is_dir__meta)?;
+    m.function_meta(Metadata::is_file__meta)?;
+    m.function_meta(Metadata::is_symlink__meta)?;
+    m.function_meta(Metadata::len__meta)?;
+    m.function_meta(Metadata::is_readonly__meta)?;
+    m.function_meta(Metadata::debug_fmt__meta)?;
+
+    m.ty::<ReadDir>()?;
+    m.function_meta(ReadDir::next__meta)?;
+    m.function_meta(ReadDir::debug_fmt__meta)?;
+
+    m.ty::<DirEntry>()?;
+    m.function_meta(DirEntry::path__meta)?;
+    m.function_meta(DirEntry::file_name__meta)?;
+    m.function_meta(DirEntry::metadata__meta)?;
+    m.function_meta(DirEntry::debug_fmt__meta)?;
+
+    m.ty::<File>()?;
+    m.function_meta(File::open__meta)?;
+    m.function_meta(File::create__meta)?;
+    m.function_meta(File::read__meta)?;
+    m.function_meta(File::read_to_end__meta)?;
+    m.function_meta(File::read_to_string__meta)?;
+    m.function_meta(File::write_all__meta)?;
+    m.function_meta(File::flush__meta)?;
+    m.function_meta(File::sync_all__meta)?;
+    m.function_meta(File::metadata__meta)?;
+    m.function_meta(File::debug_fmt__meta)?;
+
+    if stdio {
+        m.function_meta(File::stdin__meta)?;
+        m.function_meta(File::stdout__meta)?;
+        m.function_meta(File::stderr__meta)?;
+    }
+
+    Ok(m)
+}
+
+/// Reads the entire contents of a file into a string.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let contents = fs::read_to_string("address.txt").await?;
+/// println!("{contents}");
+/// ```
+#[rune::function]
+async fn read_to_string(path: Ref<str>) -> alloc::Result<io::Result<String>> {
+    let contents = nested_try!(fs::read(&*path).await);
+    let vec = Vec::try_from(contents)?;
+    Ok(bytes_to_string(vec))
+}
+
+/// Reads the entire contents of a file into bytes.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let contents = fs::read("address.txt").await?;
+/// println!("{}", contents.len());
+/// ```
+#[rune::function]
+async fn read(path: Ref<str>) -> alloc::Result<io::Result<Bytes>> {
+    let contents = nested_try!(fs::read(&*path).await);
+    Ok(Ok(Bytes::from_vec(Vec::try_from(contents)?)))
+}
+
+/// Creates a future that will open a file for writing and write the entire
+/// contents of `contents` to it.
+///
+/// The contents can either be a string or bytes.
+///
+/// This function will create a file if it does not exist, and will entirely
+/// replace its contents if it does.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::write("foo.txt", b"Hello world!").await?;
+/// fs::write("bar.txt", "Hello world!").await?;
+/// ```
+#[rune::function]
+async fn write(path: Ref<str>, contents: Value) -> Result<io::Result<()>, VmError> {
+    let contents = contents_to_vec(&contents)?;
+    Ok(fs::write(&*path, contents).await)
+}
+
+/// Recursively creates a directory and all of its parent components if they
+/// are missing.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * If any directory in the path specified by `path` does not already exist
+///   and it could not be created otherwise. The specific error conditions for
+///   when a directory is being created (after it is determined to not exist)
+///   are outlined by `fs::create_dir`.
+///
+/// Notable exception is made for situations where any of the directories
+/// specified in the `path` could not be created as it was being created
+/// concurrently. Such cases are considered to be successful. That is, calling
+/// `create_dir_all` concurrently from multiple threads or processes is
+/// guaranteed not to fail due to a race condition with itself.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::create_dir_all("/some/dir").await?;
+/// ```
+#[rune::function]
+async fn create_dir_all(path: Ref<str>) -> io::Result<()> {
+    fs::create_dir_all(&*path).await
+}
+
+/// Removes a file from the filesystem.
+///
+/// Note that there is no guarantee that the file is immediately deleted (e.g.
+/// depending on platform, other open file descriptors may prevent immediate
+/// removal).
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::remove_file("a.txt").await?;
+/// ```
+#[rune::function]
+async fn remove_file(path: Ref<str>) -> io::Result<()> {
+    fs::remove_file(&*path).await
+}
+
+/// Renames a file or directory to a new name, replacing the original file if
+/// `to` already exists.
+///
+/// This will not work if the new name is on a different mount point.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::rename("a.txt", "b.txt").await?;
+/// ```
+#[rune::function]
+async fn rename(from: Ref<str>, to: Ref<str>) -> io::Result<()> {
+    fs::rename(&*from, &*to).await
+}
+
+/// Given a path, queries the file system to get information about a file,
+/// directory, etc.
+///
+/// This function will traverse symbolic links to query information about the
+/// destination file.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let metadata = fs::metadata("foo.txt").await?;
+///
+/// if metadata.is_file() {
+///     println!("{} bytes", metadata.len());
+/// }
+/// ```
+#[rune::function]
+async fn metadata(path: Ref<str>) -> io::Result<Metadata> {
+    let inner = fs::metadata(&*path).await?;
+    Ok(Metadata { inner })
+}
+
+/// Returns a stream over the entries within a directory.
+///
+/// Entries are fetched one at a time by calling [`ReadDir::next`], which
+/// follows the same protocol as `Stream::next` in `std::stream`. Each entry is
+/// a result, since reading it might fail.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let entries = fs::read_dir(".").await?;
+///
+/// while let Some(entry) = entries.next().await {
+///     println!("{}", entry?.path());
+/// }
+/// ```
+#[rune::function]
+async fn read_dir(path: Ref<str>) -> io::Result<ReadDir> {
+    let inner = fs::read_dir(&*path).await?;
+    Ok(ReadDir { inner })
+}
+
+/// Metadata information about a file.
+///
+/// This structure is returned from the [`metadata`] function or method and
+/// represents known metadata about a file such as its permissions, size,
+/// modification times, etc.
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// Returns `true` if this metadata is for a directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let metadata = fs::metadata("foo/").await?;
+    /// assert!(metadata.is_dir());
+    /// ```
+    #[rune::function(keep)]
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Returns `true` if this metadata is for a regular file.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let metadata = fs::metadata("foo.txt").await?;
+    /// assert!(metadata.is_file());
+    /// ```
+    #[rune::function(keep)]
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// Returns `true` if this metadata is for a symbolic link.
+    ///
+    /// Note that [`fs::metadata`] follows symbolic links, so this can only be
+    /// true for metadata retrieved through a directory entry.
+    #[rune::function(keep)]
+    fn is_symlink(&self) -> bool {
+        self.inner.is_symlink()
+    }
+
+    /// Returns the size of the file, in bytes, this metadata is for.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let metadata = fs::metadata("foo.txt").await?;
+    /// assert_eq!(metadata.len(), 0);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// Returns `true` if these permissions describe a readonly (unwritable)
+    /// file.
+    #[rune::function(keep)]
+    fn is_readonly(&self) -> bool {
+        self.inner.permissions().readonly()
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// Reads the entries in a directory.
+///
+/// This is returned by [`read_dir`] and is consumed like an asynchronous
+/// stream by repeatedly calling [`ReadDir::next`].
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct ReadDir {
+    inner: fs::ReadDir,
+}
+
+impl ReadDir {
+    /// Returns the next entry in the directory stream, or an error if it
+    /// could not be read.
+    ///
+    /// Once the stream is exhausted, `None` is returned.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancellation safe.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await {
+    ///     println!("{}", entry?.file_name());
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::next)]
+    async fn next(mut this: Mut<Self>) -> Option<io::Result<DirEntry>> {
+        match this.inner.next_entry().await {
+            Ok(Some(inner)) => Some(Ok(DirEntry { inner })),
+            Ok(None) => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
 
-/// Construct the `fs` module.
-pub fn module(_stdio: bool) -> Result<Module, ContextError> {
-    let mut module = Module::with_crate("fs")?;
-    module.function("read_to_string", read_to_string).build()?;
-    Ok(module)
+/// Entries returned by the [`ReadDir`] stream.
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct DirEntry {
+    inner: fs::DirEntry,
 }
 
-async fn read_to_string(path: String) -> io::Result<String> {
-    fs::read_to_string(&path).await
+impl DirEntry {
+    /// Returns the full path to the file that this entry represents.
+    ///
+    /// The full path is created by joining the original path to `read_dir`
+    /// with the filename of this entry.
+    ///
+    /// Any non-UTF-8 sequences in the path are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await {
+    ///     println!("{}", entry?.path());
+    /// }
+    /// ```
+    #[rune::function(keep)]
+    fn path(&self) -> alloc::Result<String> {
+        path_to_string(&self.inner.path())
+    }
+
+    /// Returns the bare file name of this directory entry without any other
+    /// leading path component.
+    ///
+    /// Any non-UTF-8 sequences in the file name are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await {
+    ///     println!("{}", entry?.file_name());
+    /// }
+    /// ```
+    #[rune::function(keep)]
+    fn file_name(&self) -> alloc::Result<String> {
+        String::try_from(self.inner.file_name().to_string_lossy().as_ref())
+    }
+
+    /// Returns the metadata for the file that this entry points at.
+    ///
+    /// This function will not traverse symlinks if this entry points at a
+    /// symlink.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await {
+    ///     let entry = entry?;
+    ///     let metadata = entry.metadata().await?;
+    ///     println!("{}: {}", entry.path(), metadata.len());
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::metadata)]
+    async fn metadata(this: Ref<Self>) -> io::Result<Metadata> {
+        let inner = this.inner.metadata().await?;
+        Ok(Metadata { inner })
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+#[derive(Debug)]
+enum FileKind {
+    File(fs::File),
+    Stdin(tokio::io::Stdin),
+    Stdout(tokio::io::Stdout),
+    Stderr(tokio::io::Stderr),
+}
+
+impl FileKind {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            FileKind::File(file) => file.read(buf).await,
+            FileKind::Stdin(stdin) => stdin.read(buf).await,
+            FileKind::Stdout(..) | FileKind::Stderr(..) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cannot read from an output stream",
+            )),
+        }
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            FileKind::File(file) => file.write_all(buf).await,
+            FileKind::Stdout(stdout) => stdout.write_all(buf).await,
+            FileKind::Stderr(stderr) => stderr.write_all(buf).await,
+            FileKind::Stdin(..) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cannot write to an input stream",
+            )),
+        }
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        match self {
+            FileKind::File(file) => file.flush().await,
+            FileKind::Stdout(stdout) => stdout.flush().await,
+            FileKind::Stderr(stderr) => stderr.flush().await,
+            FileKind::Stdin(..) => Ok(()),
+        }
+    }
+
+    fn file(&self) -> io::Result<&fs::File> {
+        match self {
+            FileKind::File(file) => Ok(file),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "operation is only supported for files",
+            )),
+        }
+    }
+}
+
+/// A reference to an open file on the filesystem.
+///
+/// A file can be read from and written to in chunks through the methods
+/// provided on it, which allows large files to be streamed without loading
+/// them into memory in full.
+///
+/// Files are automatically closed when they go out of scope. Errors detected
+/// on closing are ignored by the implementation of `Drop`. Use the method
+/// [`File::sync_all`] if these errors must be manually handled.
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct File {
+    inner: FileKind,
+}
+
+impl File {
+    /// Attempts to open a file in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if called from outside of the Tokio
+    /// runtime or if path does not already exist. Other errors may also be
+    /// returned according to `OpenOptions::open`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::open("foo.txt").await?;
+    /// let contents = file.read_to_end().await?;
+    /// ```
+    #[rune::function(keep, path = Self::open)]
+    async fn open(path: Ref<str>) -> io::Result<Self> {
+        let file = fs::File::open(&*path).await?;
+
+        Ok(Self {
+            inner: FileKind::File(file),
+        })
+    }
+
+    /// Opens a file in write-only mode.
+    ///
+    /// This function will create a file if it does not exist, and will truncate
+    /// it if it does.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::create("foo.txt").await?;
+    /// file.write_all(b"hello, world!").await?;
+    /// ```
+    #[rune::function(keep, path = Self::create)]
+    async fn create(path: Ref<str>) -> io::Result<Self> {
+        let file = fs::File::create(&*path).await?;
+
+        Ok(Self {
+            inner: FileKind::File(file),
+        })
+    }
+
+    /// Constructs a handle to the standard input of the current process.
+    ///
+    /// This is only available if the module was installed with `stdio`
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let stdin = File::stdin();
+    /// let line = stdin.read(1024).await?;
+    /// ```
+    #[rune::function(keep, path = Self::stdin)]
+    fn stdin() -> Self {
+        Self {
+            inner: FileKind::Stdin(tokio::io::stdin()),
+        }
+    }
+
+    /// Constructs a handle to the standard output of the current process.
+    ///
+    /// This is only available if the module was installed with `stdio`
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let stdout = File::stdout();
+    /// stdout.write_all("Hello World\n").await?;
+    /// ```
+    #[rune::function(keep, path = Self::stdout)]
+    fn stdout() -> Self {
+        Self {
+            inner: FileKind::Stdout(tokio::io::stdout()),
+        }
+    }
+
+    /// Constructs a handle to the standard error of the current process.
+    ///
+    /// This is only available if the module was installed with `stdio`
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let stderr = File::stderr();
+    /// stderr.write_all("Something went wrong\n").await?;
+    /// ```
+    #[rune::function(keep, path = Self::stderr)]
+    fn stderr() -> Self {
+        Self {
+            inner: FileKind::Stderr(tokio::io::stderr()),
+        }
+    }
+
+    /// Reads at most `len` bytes from the file.
+    ///
+    /// This performs a single read, so fewer than `len` bytes might be
+    /// returned even if the end of the file has not been reached. An empty
+    /// collection of bytes is returned once the end of the file has been
+    /// reached, which makes this suitable for streaming through large files.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::open("large.bin").await?;
+    ///
+    /// loop {
+    ///     let chunk = file.read(4096).await?;
+    ///
+    ///     if chunk.is_empty() {
+    ///         break;
+    ///     }
+    ///
+    ///     println!("read {} bytes", chunk.len());
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::read)]
+    async fn read(mut this: Mut<Self>, len: usize) -> alloc::Result<io::Result<Bytes>> {
+        let mut chunk = [0u8; BUFFER_SIZE];
+        let n = len.min(BUFFER_SIZE);
+        let n = nested_try!(this.inner.read(&mut chunk[..n]).await);
+        Ok(Ok(Bytes::from_vec(Vec::try_from(&chunk[..n])?)))
+    }
+
+    /// Reads all remaining bytes until the end of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::open("foo.txt").await?;
+    /// let contents = file.read_to_end().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_to_end)]
+    async fn read_to_end(mut this: Mut<Self>) -> alloc::Result<io::Result<Bytes>> {
+        let buf = nested_try!(read_to_end_inner(&mut this.inner).await?);
+        Ok(Ok(Bytes::from_vec(buf)))
+    }
+
+    /// Reads all remaining bytes until the end of the file and interprets them
+    /// as a UTF-8 string.
+    ///
+    /// # Errors
+    ///
+    /// Errors with `InvalidData` if the contents of the file is not valid
+    /// UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::open("foo.txt").await?;
+    /// let contents = file.read_to_string().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_to_string)]
+    async fn read_to_string(mut this: Mut<Self>) -> alloc::Result<io::Result<String>> {
+        let buf = nested_try!(read_to_end_inner(&mut this.inner).await?);
+        Ok(bytes_to_string(buf))
+    }
+
+    /// Writes the entire contents of `contents` to the file.
+    ///
+    /// The contents can either be a string or bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::create("foo.txt").await?;
+    /// file.write_all(b"hello, ").await?;
+    /// file.write_all("world!").await?;
+    /// file.flush().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::write_all)]
+    async fn write_all(mut this: Mut<Self>, contents: Value) -> Result<io::Result<()>, VmError> {
+        let contents = contents_to_vec(&contents)?;
+        Ok(this.inner.write_all(&contents).await)
+    }
+
+    /// Flushes any buffered data.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::create("foo.txt").await?;
+    /// file.write_all(b"hello, world!").await?;
+    /// file.flush().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::flush)]
+    async fn flush(mut this: Mut<Self>) -> io::Result<()> {
+        this.inner.flush().await
+    }
+
+    /// Attempts to sync all OS-internal metadata to disk.
+    ///
+    /// This function will attempt to ensure that all in-core data reaches the
+    /// filesystem before returning.
+    ///
+    /// This is only supported for files opened through [`File::open`] or
+    /// [`File::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::create("foo.txt").await?;
+    /// file.write_all(b"hello, world!").await?;
+    /// file.sync_all().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::sync_all)]
+    async fn sync_all(this: Ref<Self>) -> io::Result<()> {
+        this.inner.file()?.sync_all().await
+    }
+
+    /// Queries metadata about the underlying file.
+    ///
+    /// This is only supported for files opened through [`File::open`] or
+    /// [`File::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use fs::File;
+    ///
+    /// let file = File::open("foo.txt").await?;
+    /// let metadata = file.metadata().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::metadata)]
+    async fn metadata(this: Ref<Self>) -> io::Result<Metadata> {
+        let inner = this.inner.file()?.metadata().await?;
+        Ok(Metadata { inner })
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+async fn read_to_end_inner(file: &mut FileKind) -> alloc::Result<io::Result<Vec<u8>>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; BUFFER_SIZE];
+
+    loop {
+        let n = nested_try!(file.read(&mut chunk).await);
+
+        if n == 0 {
+            break;
+        }
+
+        buf.try_extend_from_slice(&chunk[..n])?;
+    }
+
+    Ok(Ok(buf))
+}
+
+fn bytes_to_string(buf: Vec<u8>) -> io::Result<String> {
+    match String::from_utf8(buf) {
+        Ok(string) => Ok(string),
+        Err(..) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )),
+    }
+}
+
+fn path_to_string(path: &Path) -> alloc::Result<String> {
+    String::try_from(path.to_string_lossy().as_ref())
+}
+
+/// Coerce contents which are either a string or bytes into a vector.
+fn contents_to_vec(contents: &Value) -> Result<Vec<u8>, VmError> {
+    if let Ok(bytes) = contents.borrow_ref::<Bytes>() {
+        return Ok(Vec::try_from(bytes.as_slice())?);
+    }
+
+    let string = contents.borrow_string_ref()?;
+    Ok(Vec::try_from(string.as_bytes())?)
 }
@@ -34,6 +34,7 @@ use core::hash::Hash;
 
 use rune::alloc;
 use rune::alloc::fmt::TryWrite;
+use rune::alloc::String;
 use rune::runtime::{Formatter, Hasher, Mut, VmError};
 use rune::{docstring, item, Any, ContextError, Module, ToConstValue};
 
@@ -213,6 +214,73 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     m.function_meta(Instant::clone__meta)?;
     m.implement_trait::<Instant>(item!(::std::clone::Clone))?;
 
+    m.ty::<SystemTime>()?;
+    m.function_meta(SystemTime::now__meta)?;
+    m.function_meta(SystemTime::from_unix_timestamp__meta)?;
+    m.function_meta(SystemTime::from_unix_timestamp_millis__meta)?;
+    m.function_meta(SystemTime::unix_timestamp__meta)?;
+    m.function_meta(SystemTime::unix_timestamp_millis__meta)?;
+    m.function_meta(SystemTime::duration_since__meta)?;
+    m.function_meta(SystemTime::elapsed__meta)?;
+    m.function_meta(SystemTime::add__meta)?;
+    m.function_meta(SystemTime::add_assign__meta)?;
+    m.function_meta(SystemTime::sub__meta)?;
+    m.function_meta(SystemTime::sub_assign__meta)?;
+    m.function_meta(SystemTime::partial_eq__meta)?;
+    m.implement_trait::<SystemTime>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(SystemTime::eq__meta)?;
+    m.implement_trait::<SystemTime>(item!(::std::cmp::Eq))?;
+    m.function_meta(SystemTime::partial_cmp__meta)?;
+    m.implement_trait::<SystemTime>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(SystemTime::cmp__meta)?;
+    m.implement_trait::<SystemTime>(item!(::std::cmp::Ord))?;
+    m.function_meta(SystemTime::hash__meta)?;
+    m.function_meta(SystemTime::debug_fmt__meta)?;
+    m.function_meta(SystemTime::clone__meta)?;
+    m.implement_trait::<SystemTime>(item!(::std::clone::Clone))?;
+
+    m.ty::<DateTime>()?;
+    m.function_meta(DateTime::now__meta)?;
+    m.function_meta(DateTime::parse_rfc3339__meta)?;
+    m.function_meta(DateTime::from_unix_timestamp__meta)?;
+    m.function_meta(DateTime::from_system_time__meta)?;
+    m.function_meta(DateTime::to_system_time__meta)?;
+    m.function_meta(DateTime::unix_timestamp__meta)?;
+    m.function_meta(DateTime::unix_timestamp_millis__meta)?;
+    m.function_meta(DateTime::to_rfc3339__meta)?;
+    m.function_meta(DateTime::year__meta)?;
+    m.function_meta(DateTime::month__meta)?;
+    m.function_meta(DateTime::day__meta)?;
+    m.function_meta(DateTime::hour__meta)?;
+    m.function_meta(DateTime::minute__meta)?;
+    m.function_meta(DateTime::second__meta)?;
+    m.function_meta(DateTime::nanosecond__meta)?;
+    m.function_meta(DateTime::offset_minutes__meta)?;
+    m.function_meta(DateTime::with_offset_minutes__meta)?;
+    m.function_meta(DateTime::to_utc__meta)?;
+    m.function_meta(DateTime::duration_since__meta)?;
+    m.function_meta(DateTime::add__meta)?;
+    m.function_meta(DateTime::add_assign__meta)?;
+    m.function_meta(DateTime::sub__meta)?;
+    m.function_meta(DateTime::sub_assign__meta)?;
+    m.function_meta(DateTime::partial_eq__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(DateTime::eq__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::Eq))?;
+    m.function_meta(DateTime::partial_cmp__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(DateTime::cmp__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::Ord))?;
+    m.function_meta(DateTime::hash__meta)?;
+    m.function_meta(DateTime::display_fmt__meta)?;
+    m.function_meta(DateTime::debug_fmt__meta)?;
+    m.function_meta(DateTime::clone__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::clone::Clone))?;
+
+    m.ty::<DateTimeParseError>()?;
+    m.function_meta(DateTimeParseError::display_fmt__meta)?;
+    m.function_meta(DateTimeParseError::debug_fmt__meta)?;
+
     m.ty::<Interval>()?;
     m.function("tick", Interval::tick)
         .build_associated::<Interval>()?;
@@ -1314,3 +1382,1428 @@ impl Instant {
         Self { inner: self.inner }
     }
 }
+
+/// A measurement of the system clock, useful for talking to external entities
+/// like the file system or other processes.
+///
+/// Distinct from the [`Instant`] type, this time measurement **is not
+/// monotonic**. This means that you can save a file to the file system, then
+/// save another file to the file system, **and the second file has a
+/// `SystemTime` measurement earlier than the first**. In other words, an
+/// operation that happens after another operation in real time may have an
+/// earlier `SystemTime`!
+///
+/// Although a `SystemTime` cannot be directly inspected, it can be converted
+/// to and from a UNIX timestamp, or into a calendar [`DateTime`].
+///
+/// # Examples
+///
+/// ```rune
+/// use time::SystemTime;
+///
+/// let now = SystemTime::now();
+/// let epoch = SystemTime::from_unix_timestamp(0)?;
+///
+/// assert!(epoch < now);
+/// ```
+#[derive(Debug, Clone, Copy, Any)]
+#[rune(item = ::time)]
+pub struct SystemTime {
+    inner: std::time::SystemTime,
+}
+
+impl SystemTime {
+    /// Converts [`SystemTime`] into a [`std::time::SystemTime`].
+    pub fn into_std(self) -> std::time::SystemTime {
+        self.inner
+    }
+
+    /// Creates a [`SystemTime`] from a [`std::time::SystemTime`].
+    pub fn from_std(time: std::time::SystemTime) -> Self {
+        Self { inner: time }
+    }
+
+    /// Returns the system time corresponding to "now".
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let now = SystemTime::now();
+    /// ```
+    #[rune::function(keep, path = Self::now)]
+    pub fn now() -> Self {
+        Self {
+            inner: std::time::SystemTime::now(),
+        }
+    }
+
+    /// Constructs a system time from the number of whole seconds since the
+    /// UNIX epoch, which might be negative.
+    ///
+    /// Returns `None` if the time can't be represented on this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let time = SystemTime::from_unix_timestamp(1_000_000_000)?;
+    /// assert_eq!(time.unix_timestamp(), 1_000_000_000);
+    /// ```
+    #[rune::function(keep, path = Self::from_unix_timestamp)]
+    pub fn from_unix_timestamp(secs: i64) -> Option<Self> {
+        let inner = civil::to_system_time(secs, 0)?;
+        Some(Self { inner })
+    }
+
+    /// Constructs a system time from the number of milliseconds since the UNIX
+    /// epoch, which might be negative.
+    ///
+    /// Returns `None` if the time can't be represented on this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let time = SystemTime::from_unix_timestamp_millis(1_500)?;
+    /// assert_eq!(time.unix_timestamp(), 1);
+    /// assert_eq!(time.unix_timestamp_millis(), 1_500);
+    /// ```
+    #[rune::function(keep, path = Self::from_unix_timestamp_millis)]
+    pub fn from_unix_timestamp_millis(millis: i64) -> Option<Self> {
+        let secs = millis.div_euclid(1000);
+        let nanos = millis.rem_euclid(1000) as u32 * 1_000_000;
+        let inner = civil::to_system_time(secs, nanos)?;
+        Some(Self { inner })
+    }
+
+    /// Returns the number of whole seconds since the UNIX epoch.
+    ///
+    /// Times before the epoch are rounded down, so the result is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let time = SystemTime::from_unix_timestamp(-10)?;
+    /// assert_eq!(time.unix_timestamp(), -10);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp(&self) -> i64 {
+        civil::from_system_time(self.inner).0
+    }
+
+    /// Returns the number of whole milliseconds since the UNIX epoch.
+    ///
+    /// Times before the epoch are rounded down, so the result is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let time = SystemTime::from_unix_timestamp(2)?;
+    /// assert_eq!(time.unix_timestamp_millis(), 2_000);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        let (secs, nanos) = civil::from_system_time(self.inner);
+        secs.saturating_mul(1000)
+            .saturating_add((nanos / 1_000_000) as i64)
+    }
+
+    /// Returns the amount of time elapsed from an earlier point in time.
+    ///
+    /// Returns `None` if `earlier` is later than this time, which can happen
+    /// since the system clock is not monotonic.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::from_unix_timestamp(10)?;
+    /// let second = SystemTime::from_unix_timestamp(15)?;
+    ///
+    /// assert_eq!(second.duration_since(first), Some(Duration::from_secs(5)));
+    /// assert_eq!(first.duration_since(second), None);
+    /// ```
+    #[rune::function(instance, keep)]
+    pub fn duration_since(&self, earlier: &SystemTime) -> Option<Duration> {
+        let inner = self.inner.duration_since(earlier.inner).ok()?;
+        Some(Duration { inner })
+    }
+
+    /// Returns the difference between the clock time when this system time was
+    /// created, and the current clock time.
+    ///
+    /// Returns `None` if the system clock has been adjusted to be earlier than
+    /// this time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let time = SystemTime::now();
+    /// time::sleep(Duration::from_secs(1)).await;
+    ///
+    /// let elapsed = time.elapsed()?;
+    /// ```
+    #[rune::function(instance, keep)]
+    pub fn elapsed(&self) -> Option<Duration> {
+        let inner = self.inner.elapsed().ok()?;
+        Some(Duration { inner })
+    }
+
+    /// Add a duration to this system time and return a new system time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    #[inline]
+    fn add(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self.inner.checked_add(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when adding duration to system time",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Add a duration to this system time in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first.clone();
+    /// second += Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    #[inline]
+    fn add_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        let Some(inner) = self.inner.checked_add(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when adding duration to system time",
+            ));
+        };
+
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Subtract a duration from this system time and return a new system time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first - Duration::SECOND;
+    ///
+    /// assert!(second < first);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    #[inline]
+    fn sub(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self.inner.checked_sub(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from system time",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Subtract a duration from this system time in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first.clone();
+    /// second -= Duration::SECOND;
+    ///
+    /// assert!(second < first);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        let Some(inner) = self.inner.checked_sub(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from system time",
+            ));
+        };
+
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Test two system times for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::partial_eq;
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert_eq!(partial_eq(first, first), true);
+    /// assert_eq!(partial_eq(first, second), false);
+    /// assert_eq!(partial_eq(second, first), false);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Test two system times for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert_eq!(eq(first, first), true);
+    /// assert_eq!(eq(first, second), false);
+    /// assert_eq!(eq(second, first), false);
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a partial ordered comparison between two system times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::partial_cmp;
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// assert_eq!(partial_cmp(first, second), Some(Ordering::Less));
+    /// assert_eq!(partial_cmp(second, first), Some(Ordering::Greater));
+    /// assert_eq!(partial_cmp(first, first), Some(Ordering::Equal));
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a totally ordered comparison between two system times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert_eq!(cmp(first, second), Ordering::Less);
+    /// assert_eq!(cmp(second, first), Ordering::Greater);
+    /// assert_eq!(cmp(first, first), Ordering::Equal);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Hash the system time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::SystemTime;
+    ///
+    /// let now = SystemTime::now();
+    ///
+    /// assert_eq!(hash(now), hash(now));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.inner.hash(hasher);
+    }
+
+    /// Write a debug representation of the system time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::SystemTime;
+    ///
+    /// let now = SystemTime::now();
+    ///
+    /// println!("{now:?}");
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+
+    /// Clone the current system time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, SystemTime};
+    ///
+    /// let first = SystemTime::now();
+    /// let second = first.clone();
+    /// second += Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner }
+    }
+}
+
+/// A calendar date and time with a fixed offset from UTC.
+///
+/// Date times can be parsed from and formatted as [RFC 3339], converted to and
+/// from UNIX timestamps, and shifted by a [`Duration`]. The supported range of
+/// years is `0000` to `9999`, which is what RFC 3339 can represent.
+///
+/// Comparison and hashing is based on the instant in time a date time
+/// represents, so two date times with different offsets describing the same
+/// instant are equal.
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+///
+/// # Examples
+///
+/// ```rune
+/// use time::{DateTime, Duration};
+///
+/// let a = DateTime::parse_rfc3339("2024-02-29T12:30:00Z")?;
+/// let b = DateTime::parse_rfc3339("2024-02-29T14:30:00+02:00")?;
+///
+/// assert_eq!(a, b);
+/// assert_eq!(a.unix_timestamp(), 1709209800);
+///
+/// let later = a + Duration::from_secs(86400);
+/// assert_eq!(later.to_rfc3339(), "2024-03-01T12:30:00Z");
+/// ```
+#[derive(Debug, Clone, Copy, Any)]
+#[rune(item = ::time)]
+pub struct DateTime {
+    /// Whole seconds since the UNIX epoch, in UTC.
+    secs: i64,
+    /// Nanoseconds within the second.
+    nanos: u32,
+    /// Offset from UTC in seconds.
+    offset: i32,
+}
+
+impl DateTime {
+    /// Construct a date time, checking that its local time is in range.
+    fn checked_new(secs: i64, nanos: u32, offset: i32) -> Option<Self> {
+        let local = secs.checked_add(offset as i64)?;
+
+        if !(civil::MIN_SECS..=civil::MAX_SECS).contains(&local) {
+            return None;
+        }
+
+        Some(Self {
+            secs,
+            nanos,
+            offset,
+        })
+    }
+
+    /// The local time of this date time in seconds since the UNIX epoch.
+    fn local_secs(&self) -> i64 {
+        self.secs + self.offset as i64
+    }
+
+    fn date(&self) -> (i64, u32, u32) {
+        civil::civil_from_days(self.local_secs().div_euclid(civil::SECS_PER_DAY))
+    }
+
+    fn seconds_of_day(&self) -> u32 {
+        self.local_secs().rem_euclid(civil::SECS_PER_DAY) as u32
+    }
+
+    fn checked_add_duration(&self, duration: tokio::time::Duration) -> Option<Self> {
+        let secs = self
+            .secs
+            .checked_add(i64::try_from(duration.as_secs()).ok()?)?;
+        let nanos = self.nanos + duration.subsec_nanos();
+
+        if nanos >= NANOS_PER_SEC {
+            Self::checked_new(secs.checked_add(1)?, nanos - NANOS_PER_SEC, self.offset)
+        } else {
+            Self::checked_new(secs, nanos, self.offset)
+        }
+    }
+
+    fn checked_sub_duration(&self, duration: tokio::time::Duration) -> Option<Self> {
+        let secs = self
+            .secs
+            .checked_sub(i64::try_from(duration.as_secs()).ok()?)?;
+
+        if self.nanos < duration.subsec_nanos() {
+            let nanos = self.nanos + NANOS_PER_SEC - duration.subsec_nanos();
+            Self::checked_new(secs.checked_sub(1)?, nanos, self.offset)
+        } else {
+            Self::checked_new(secs, self.nanos - duration.subsec_nanos(), self.offset)
+        }
+    }
+
+    fn write_rfc3339<W>(&self, out: &mut W) -> alloc::Result<()>
+    where
+        W: ?Sized + TryWrite,
+    {
+        let (year, month, day) = self.date();
+        let time = self.seconds_of_day();
+
+        write!(
+            out,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            time / 3600,
+            time / 60 % 60,
+            time % 60
+        )?;
+
+        if self.nanos != 0 {
+            if self.nanos.is_multiple_of(1_000_000) {
+                write!(out, ".{:03}", self.nanos / 1_000_000)?;
+            } else if self.nanos.is_multiple_of(1_000) {
+                write!(out, ".{:06}", self.nanos / 1_000)?;
+            } else {
+                write!(out, ".{:09}", self.nanos)?;
+            }
+        }
+
+        if self.offset == 0 {
+            return write!(out, "Z");
+        }
+
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let offset = self.offset.unsigned_abs();
+        write!(out, "{sign}{:02}:{:02}", offset / 3600, offset / 60 % 60)
+    }
+
+    /// Returns the current date time in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let now = DateTime::now();
+    /// assert_eq!(now.offset_minutes(), 0);
+    /// ```
+    #[rune::function(keep, path = Self::now)]
+    pub fn now() -> Self {
+        let (secs, nanos) = civil::from_system_time(std::time::SystemTime::now());
+
+        Self {
+            secs,
+            nanos,
+            offset: 0,
+        }
+    }
+
+    /// Parses an [RFC 3339] date time, such as `2024-02-29T12:30:00.5+01:00`.
+    ///
+    /// The offset of the input is preserved and used when the date time is
+    /// formatted or its calendar components are accessed.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:00.5+01:00")?;
+    ///
+    /// assert_eq!(date.year(), 2024);
+    /// assert_eq!(date.month(), 2);
+    /// assert_eq!(date.day(), 29);
+    /// assert_eq!(date.hour(), 12);
+    /// assert_eq!(date.nanosecond(), 500_000_000);
+    /// assert_eq!(date.offset_minutes(), 60);
+    ///
+    /// assert!(DateTime::parse_rfc3339("2023-02-29T00:00:00Z").is_err());
+    /// ```
+    #[rune::function(keep, path = Self::parse_rfc3339)]
+    pub fn parse_rfc3339(input: &str) -> Result<Self, DateTimeParseError> {
+        let (secs, nanos, offset) = civil::parse_rfc3339(input)?;
+
+        match Self::checked_new(secs, nanos, offset) {
+            Some(date) => Ok(date),
+            None => Err(DateTimeParseError::new("date time out of range")),
+        }
+    }
+
+    /// Constructs a UTC date time from the number of whole seconds since the
+    /// UNIX epoch, which might be negative.
+    ///
+    /// Returns `None` if the date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::from_unix_timestamp(0)?;
+    /// assert_eq!(date.to_rfc3339(), "1970-01-01T00:00:00Z");
+    /// ```
+    #[rune::function(keep, path = Self::from_unix_timestamp)]
+    pub fn from_unix_timestamp(secs: i64) -> Option<Self> {
+        Self::checked_new(secs, 0, 0)
+    }
+
+    /// Constructs a UTC date time from a [`SystemTime`].
+    ///
+    /// Returns `None` if the date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, SystemTime};
+    ///
+    /// let time = SystemTime::from_unix_timestamp(86400)?;
+    /// let date = DateTime::from_system_time(time)?;
+    /// assert_eq!(date.to_rfc3339(), "1970-01-02T00:00:00Z");
+    /// ```
+    #[rune::function(keep, path = Self::from_system_time)]
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let (secs, nanos) = civil::from_system_time(time.inner);
+        Self::checked_new(secs, nanos, 0)
+    }
+
+    /// Converts this date time into a [`SystemTime`].
+    ///
+    /// Returns `None` if the time can't be represented on this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, SystemTime};
+    ///
+    /// let date = DateTime::parse_rfc3339("1970-01-01T00:01:00Z")?;
+    /// let time = date.to_system_time()?;
+    /// assert_eq!(time, SystemTime::from_unix_timestamp(60)?);
+    /// ```
+    #[rune::function(keep)]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let inner = civil::to_system_time(self.secs, self.nanos)?;
+        Some(SystemTime { inner })
+    }
+
+    /// Returns the number of whole seconds since the UNIX epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("1969-12-31T23:59:59.5Z")?;
+    /// assert_eq!(date.unix_timestamp(), -1);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the number of whole milliseconds since the UNIX epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("1970-01-01T00:00:01.25Z")?;
+    /// assert_eq!(date.unix_timestamp_millis(), 1250);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        self.secs * 1000 + (self.nanos / 1_000_000) as i64
+    }
+
+    /// Formats this date time according to [RFC 3339].
+    ///
+    /// This is the same format which is used when the date time is displayed.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29t12:30:00.100-05:30")?;
+    /// assert_eq!(date.to_rfc3339(), "2024-02-29T12:30:00.100-05:30");
+    /// assert_eq!(`${date}`, "2024-02-29T12:30:00.100-05:30");
+    /// ```
+    #[rune::function(keep)]
+    pub fn to_rfc3339(&self) -> alloc::Result<String> {
+        let mut out = String::new();
+        self.write_rfc3339(&mut out)?;
+        Ok(out)
+    }
+
+    /// Returns the year in the local time of this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-12-31T23:00:00-02:00")?;
+    /// assert_eq!(date.year(), 2024);
+    /// assert_eq!(date.to_utc().year(), 2025);
+    /// ```
+    #[rune::function(keep)]
+    pub fn year(&self) -> i64 {
+        self.date().0
+    }
+
+    /// Returns the month, starting from 1, in the local time of this date
+    /// time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:00Z")?;
+    /// assert_eq!(date.month(), 2);
+    /// ```
+    #[rune::function(keep)]
+    pub fn month(&self) -> u32 {
+        self.date().1
+    }
+
+    /// Returns the day of the month, starting from 1, in the local time of
+    /// this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:00Z")?;
+    /// assert_eq!(date.day(), 29);
+    /// ```
+    #[rune::function(keep)]
+    pub fn day(&self) -> u32 {
+        self.date().2
+    }
+
+    /// Returns the hour of the day in the local time of this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:15Z")?;
+    /// assert_eq!(date.hour(), 12);
+    /// ```
+    #[rune::function(keep)]
+    pub fn hour(&self) -> u32 {
+        self.seconds_of_day() / 3600
+    }
+
+    /// Returns the minute of the hour in the local time of this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:15Z")?;
+    /// assert_eq!(date.minute(), 30);
+    /// ```
+    #[rune::function(keep)]
+    pub fn minute(&self) -> u32 {
+        self.seconds_of_day() / 60 % 60
+    }
+
+    /// Returns the second of the minute in the local time of this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:15Z")?;
+    /// assert_eq!(date.second(), 15);
+    /// ```
+    #[rune::function(keep)]
+    pub fn second(&self) -> u32 {
+        self.seconds_of_day() % 60
+    }
+
+    /// Returns the nanoseconds within the second.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:15.000001Z")?;
+    /// assert_eq!(date.nanosecond(), 1000);
+    /// ```
+    #[rune::function(keep)]
+    pub fn nanosecond(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Returns the offset from UTC in minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:15-01:30")?;
+    /// assert_eq!(date.offset_minutes(), -90);
+    /// ```
+    #[rune::function(keep)]
+    pub fn offset_minutes(&self) -> i32 {
+        self.offset / 60
+    }
+
+    /// Returns the same instant in time expressed with a different offset from
+    /// UTC, given in minutes.
+    ///
+    /// Returns `None` if the offset is a day or more, or if the resulting date
+    /// time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:00Z")?;
+    /// let local = date.with_offset_minutes(120)?;
+    ///
+    /// assert_eq!(local.to_rfc3339(), "2024-02-29T14:30:00+02:00");
+    /// assert_eq!(local, date);
+    /// ```
+    #[rune::function(keep)]
+    pub fn with_offset_minutes(&self, minutes: i32) -> Option<Self> {
+        if minutes.unsigned_abs() >= 24 * 60 {
+            return None;
+        }
+
+        Self::checked_new(self.secs, self.nanos, minutes * 60)
+    }
+
+    /// Returns the same instant in time expressed in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-29T12:30:00+01:00")?;
+    /// assert_eq!(date.to_utc().to_rfc3339(), "2024-02-29T11:30:00Z");
+    /// ```
+    #[rune::function(keep)]
+    pub fn to_utc(&self) -> Self {
+        Self {
+            secs: self.secs,
+            nanos: self.nanos,
+            offset: 0,
+        }
+    }
+
+    /// Returns the amount of time elapsed from an earlier date time.
+    ///
+    /// Returns `None` if `earlier` is later than this date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:30:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T12:31:00.5Z")?;
+    ///
+    /// assert_eq!(second.duration_since(first), Some(Duration::from_millis(60_500)));
+    /// assert_eq!(first.duration_since(second), None);
+    /// ```
+    #[rune::function(instance, keep)]
+    pub fn duration_since(&self, earlier: &DateTime) -> Option<Duration> {
+        let mut secs = self.secs.checked_sub(earlier.secs)?;
+
+        let nanos = if self.nanos < earlier.nanos {
+            secs = secs.checked_sub(1)?;
+            self.nanos + NANOS_PER_SEC - earlier.nanos
+        } else {
+            self.nanos - earlier.nanos
+        };
+
+        let secs = u64::try_from(secs).ok()?;
+
+        Some(Duration {
+            inner: tokio::time::Duration::new(secs, nanos),
+        })
+    }
+
+    /// Add a duration to this date time and return a new date time.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-28T12:00:00Z")?;
+    /// let date = date + Duration::from_secs(86400);
+    ///
+    /// assert_eq!(date.to_rfc3339(), "2024-02-29T12:00:00Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    #[inline]
+    fn add(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(date) = self.checked_add_duration(rhs.inner) else {
+            return Err(VmError::panic("overflow when adding duration to date time"));
+        };
+
+        Ok(date)
+    }
+
+    /// Add a duration to this date time in place.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-02-28T12:00:00Z")?;
+    /// date += Duration::from_secs(86400);
+    ///
+    /// assert_eq!(date.to_rfc3339(), "2024-02-29T12:00:00Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    #[inline]
+    fn add_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        let Some(date) = self.checked_add_duration(rhs.inner) else {
+            return Err(VmError::panic("overflow when adding duration to date time"));
+        };
+
+        *self = date;
+        Ok(())
+    }
+
+    /// Subtract a duration from this date time and return a new date time.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-03-01T00:00:00Z")?;
+    /// let date = date - Duration::from_millis(1);
+    ///
+    /// assert_eq!(date.to_rfc3339(), "2024-02-29T23:59:59.999Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    #[inline]
+    fn sub(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(date) = self.checked_sub_duration(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from date time",
+            ));
+        };
+
+        Ok(date)
+    }
+
+    /// Subtract a duration from this date time in place.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date time is outside of the supported range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let date = DateTime::parse_rfc3339("2024-03-01T00:00:00Z")?;
+    /// date -= Duration::from_secs(1);
+    ///
+    /// assert_eq!(date.to_rfc3339(), "2024-02-29T23:59:59Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        let Some(date) = self.checked_sub_duration(rhs.inner) else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from date time",
+            ));
+        };
+
+        *self = date;
+        Ok(())
+    }
+
+    /// Test two date times for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::partial_eq;
+    /// use time::DateTime;
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T13:00:00Z")?;
+    ///
+    /// assert_eq!(partial_eq(first, first), true);
+    /// assert_eq!(partial_eq(first, second), false);
+    /// assert_eq!(partial_eq(second, first), false);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        self.key() == rhs.key()
+    }
+
+    /// Test two date times for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::DateTime;
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T13:00:00+01:00")?;
+    ///
+    /// assert_eq!(eq(first, second), true);
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.key() == rhs.key()
+    }
+
+    /// Perform a partial ordered comparison between two date times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::partial_cmp;
+    /// use time::DateTime;
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T12:00:00-01:00")?;
+    ///
+    /// assert!(first < second);
+    /// assert_eq!(partial_cmp(first, second), Some(Ordering::Less));
+    /// assert_eq!(partial_cmp(second, first), Some(Ordering::Greater));
+    /// assert_eq!(partial_cmp(first, first), Some(Ordering::Equal));
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.key(), &rhs.key())
+    }
+
+    /// Perform a totally ordered comparison between two date times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::DateTime;
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T12:00:01Z")?;
+    ///
+    /// assert_eq!(cmp(first, second), Ordering::Less);
+    /// assert_eq!(cmp(second, first), Ordering::Greater);
+    /// assert_eq!(cmp(first, first), Ordering::Equal);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.key(), &rhs.key())
+    }
+
+    /// Hash the date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::DateTime;
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-02-29T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-02-29T13:00:00+01:00")?;
+    ///
+    /// assert_eq!(hash(first), hash(second));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.key().hash(hasher);
+    }
+
+    /// Write the date time formatted according to RFC 3339.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let date = DateTime::from_unix_timestamp(0)?;
+    ///
+    /// assert_eq!(format!("{date}"), "1970-01-01T00:00:00Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        self.write_rfc3339(f)
+    }
+
+    /// Write a debug representation of the date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let now = DateTime::now();
+    ///
+    /// println!("{now:?}");
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        self.write_rfc3339(f)
+    }
+
+    /// Clone the current date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::now();
+    /// let second = first.clone();
+    /// second += Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        *self
+    }
+
+    /// The key used when comparing and hashing date times.
+    #[inline]
+    fn key(&self) -> (i64, u32) {
+        (self.secs, self.nanos)
+    }
+}
+
+/// An error raised when parsing a [`DateTime`] fails.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::DateTime;
+///
+/// let Err(error) = DateTime::parse_rfc3339("yesterday") else {
+///     panic!("expected parsing to fail");
+/// };
+///
+/// assert_eq!(`${error}`, "invalid RFC 3339 date time: expected digit");
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::time)]
+pub struct DateTimeParseError {
+    message: &'static str,
+}
+
+impl DateTimeParseError {
+    const fn new(message: &'static str) -> Self {
+        Self { message }
+    }
+
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "invalid RFC 3339 date time: {}", self.message)
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Calendar and UNIX timestamp conversions.
+///
+/// The date algorithms are based on the proleptic Gregorian calendar as
+/// described in <https://howardhinnant.github.io/date_algorithms.html>.
+mod civil {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{DateTimeParseError, NANOS_PER_SEC};
+
+    /// Seconds since the UNIX epoch at `0000-01-01T00:00:00`.
+    pub(super) const MIN_SECS: i64 = -62_167_219_200;
+    /// Seconds since the UNIX epoch at `9999-12-31T23:59:59`.
+    pub(super) const MAX_SECS: i64 = 253_402_300_799;
+    pub(super) const SECS_PER_DAY: i64 = 86_400;
+
+    /// Convert a UNIX timestamp into a system time.
+    pub(super) fn to_system_time(secs: i64, nanos: u32) -> Option<SystemTime> {
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+        };
+
+        time.checked_add(Duration::from_nanos(nanos as u64))
+    }
+
+    /// Convert a system time into a UNIX timestamp, rounding down.
+    pub(super) fn from_system_time(time: SystemTime) -> (i64, u32) {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => (
+                i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+                d.subsec_nanos(),
+            ),
+            Err(e) => {
+                let d = e.duration();
+                let secs = i64::try_from(d.as_secs()).unwrap_or(i64::MAX);
+
+                if d.subsec_nanos() == 0 {
+                    (-secs, 0)
+                } else {
+                    (-secs - 1, NANOS_PER_SEC - d.subsec_nanos())
+                }
+            }
+        }
+    }
+
+    /// Number of days since the UNIX epoch for the given date.
+    pub(super) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let mp = (month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The date corresponding to the given number of days since the UNIX
+    /// epoch.
+    pub(super) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let doe = days - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = yoe + era * 400;
+        (if month <= 2 { year + 1 } else { year }, month, day)
+    }
+
+    fn is_leap_year(year: i64) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    fn days_in_month(year: i64, month: u32) -> u32 {
+        match month {
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    struct Parser<'a> {
+        input: &'a [u8],
+        pos: usize,
+    }
+
+    impl Parser<'_> {
+        fn peek(&self) -> Option<u8> {
+            self.input.get(self.pos).copied()
+        }
+
+        fn expect(
+            &mut self,
+            expected: &[u8],
+            message: &'static str,
+        ) -> Result<u8, DateTimeParseError> {
+            match self.peek() {
+                Some(b) if expected.contains(&b) => {
+                    self.pos += 1;
+                    Ok(b)
+                }
+                _ => Err(DateTimeParseError::new(message)),
+            }
+        }
+
+        fn digits(&mut self, count: usize) -> Result<u32, DateTimeParseError> {
+            let mut value = 0;
+
+            for _ in 0..count {
+                let b = self.expect(b"0123456789", "expected digit")?;
+                value = value * 10 + (b - b'0') as u32;
+            }
+
+            Ok(value)
+        }
+
+        fn fraction(&mut self) -> Result<u32, DateTimeParseError> {
+            let mut nanos = 0;
+            let mut count = 0;
+
+            while let Some(b @ b'0'..=b'9') = self.peek() {
+                self.pos += 1;
+
+                // Digits beyond nanosecond precision are truncated.
+                if count < 9 {
+                    nanos = nanos * 10 + (b - b'0') as u32;
+                    count += 1;
+                }
+            }
+
+            if count == 0 {
+                return Err(DateTimeParseError::new("expected fractional seconds"));
+            }
+
+            Ok(nanos * 10u32.pow(9 - count))
+        }
+    }
+
+    /// Parse an RFC 3339 date time into a UNIX timestamp, nanoseconds and an
+    /// offset in seconds.
+    pub(super) fn parse_rfc3339(input: &str) -> Result<(i64, u32, i32), DateTimeParseError> {
+        let mut p = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+        };
+
+        let year = p.digits(4)? as i64;
+        p.expect(b"-", "expected `-`")?;
+        let month = p.digits(2)?;
+        p.expect(b"-", "expected `-`")?;
+        let day = p.digits(2)?;
+        p.expect(b"Tt ", "expected `T`")?;
+        let hour = p.digits(2)?;
+        p.expect(b":", "expected `:`")?;
+        let minute = p.digits(2)?;
+        p.expect(b":", "expected `:`")?;
+        let second = p.digits(2)?;
+
+        let nanos = if p.peek() == Some(b'.') {
+            p.pos += 1;
+            p.fraction()?
+        } else {
+            0
+        };
+
+        let offset = match p.expect(b"Zz+-", "expected offset")? {
+            b'Z' | b'z' => 0,
+            sign => {
+                let hours = p.digits(2)?;
+                p.expect(b":", "expected `:`")?;
+                let minutes = p.digits(2)?;
+
+                if hours >= 24 || minutes >= 60 {
+                    return Err(DateTimeParseError::new("offset out of range"));
+                }
+
+                let offset = (hours * 3600 + minutes * 60) as i32;
+
+                if sign == b'-' {
+                    -offset
+                } else {
+                    offset
+                }
+            }
+        };
+
+        if p.pos != p.input.len() {
+            return Err(DateTimeParseError::new("unexpected trailing input"));
+        }
+
+        if !(1..=12).contains(&month) {
+            return Err(DateTimeParseError::new("month out of range"));
+        }
+
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(DateTimeParseError::new("day out of range"));
+        }
+
+        if hour >= 24 || minute >= 60 || second >= 60 {
+            return Err(DateTimeParseError::new("time out of range"));
+        }
+
+        let local = days_from_civil(year, month, day) * SECS_PER_DAY
+            + (hour * 3600 + minute * 60 + second) as i64;
+
+        Ok((local - offset as i64, nanos, offset))
+    }
+}
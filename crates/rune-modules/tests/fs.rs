@@ -0,0 +1,212 @@
+#![cfg(feature = "fs")]
+
+use std::path::{Path, PathBuf};
+
+use rune::alloc::{String, Vec};
+use rune::runtime::Bytes;
+use rune::sync::Arc;
+use rune::{Context, Diagnostics, Source, Sources, Value, Vm};
+
+/// A temporary directory which is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("rune-modules-fs-{name}-{}", std::process::id()));
+
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("creating temporary directory");
+        Self(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    fn as_str(&self) -> &str {
+        self.0.to_str().expect("temporary directory is not UTF-8")
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compile the given script with the `fs` module installed and call its `main`
+/// function with the given directory.
+async fn run<T>(dir: &TempDir, script: &str) -> rune::support::Result<T>
+where
+    T: rune::FromValue,
+{
+    let mut context = Context::with_default_modules()?;
+    context.install(rune_modules::fs::module(false)?)?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(script)?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    if !diagnostics.is_empty() {
+        let mut out = rune::termcolor::Buffer::no_color();
+        diagnostics.emit(&mut out, &sources)?;
+        panic!("{}", std::string::String::from_utf8_lossy(out.as_slice()));
+    }
+
+    let mut vm = Vm::new(runtime, Arc::try_new(unit?)?);
+    let output = vm.async_call(["main"], (dir.as_str(),)).await?;
+    let output: Result<T, Value> = rune::from_value(output)?;
+
+    match output {
+        Ok(output) => Ok(output),
+        Err(error) => panic!("script failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+async fn write_and_read() -> rune::support::Result<()> {
+    let dir = TempDir::new("write-and-read");
+
+    let (contents, bytes, string): (Bytes, Bytes, String) = run(
+        &dir,
+        r#"
+        use fs::File;
+
+        pub async fn main(dir) {
+            let path = `${dir}/hello.txt`;
+            fs::write(path, "hello world").await?;
+            fs::create_dir_all(`${dir}/a/b`).await?;
+            fs::rename(path, `${dir}/a/b/hello.txt`).await?;
+
+            let file = File::open(`${dir}/a/b/hello.txt`).await?;
+            let bytes = file.read(5).await?;
+            let contents = fs::read(`${dir}/a/b/hello.txt`).await?;
+            Ok((contents, bytes, file.read_to_string().await?))
+        }
+        "#,
+    )
+    .await?;
+
+    assert_eq!(contents.as_slice(), b"hello world");
+    assert_eq!(bytes.as_slice(), b"hello");
+    assert_eq!(string, " world");
+    assert!(dir.path().join("a/b/hello.txt").is_file());
+    assert!(!dir.path().join("hello.txt").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_in_chunks() -> rune::support::Result<()> {
+    let dir = TempDir::new("read-in-chunks");
+    let data = (0..32 * 1024)
+        .map(|n| n as u8)
+        .collect::<std::vec::Vec<_>>();
+    std::fs::write(dir.path().join("data.bin"), &data)?;
+
+    let (first, total): (Bytes, usize) = run(
+        &dir,
+        r#"
+        use fs::File;
+
+        pub async fn main(dir) {
+            let file = File::open(`${dir}/data.bin`).await?;
+            let first = file.read(1024 * 1024).await?;
+            let total = first.len();
+
+            loop {
+                let chunk = file.read(1000).await?;
+
+                if chunk.is_empty() {
+                    break;
+                }
+
+                assert!(chunk.len() <= 1000);
+                total += chunk.len();
+            }
+
+            Ok((first, total))
+        }
+        "#,
+    )
+    .await?;
+
+    // A single read never fills more than what was returned by the underlying
+    // file, so the first read does not consume the whole file.
+    assert!(!first.is_empty());
+    assert!(first.len() < data.len());
+    assert_eq!(first.as_slice(), &data[..first.len()]);
+    assert_eq!(total, data.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_dir_stream() -> rune::support::Result<()> {
+    let dir = TempDir::new("read-dir-stream");
+    std::fs::write(dir.path().join("a.txt"), "a")?;
+    std::fs::write(dir.path().join("b.txt"), "bb")?;
+    std::fs::create_dir(dir.path().join("c"))?;
+
+    let entries: Vec<(String, bool, u64)> = run(
+        &dir,
+        r#"
+        pub async fn main(dir) {
+            let entries = [];
+            let stream = fs::read_dir(dir).await?;
+
+            while let Some(entry) = stream.next().await {
+                let entry = entry?;
+                let metadata = entry.metadata().await?;
+                let len = if metadata.is_file() { metadata.len() } else { 0 };
+                entries.push((entry.file_name(), metadata.is_dir(), len));
+            }
+
+            assert!(stream.next().await.is_none());
+            entries.sort();
+            Ok(entries)
+        }
+        "#,
+    )
+    .await?;
+
+    let entries = entries
+        .iter()
+        .map(|(name, is_dir, len)| (name.as_str(), *is_dir, *len))
+        .collect::<std::vec::Vec<_>>();
+
+    assert_eq!(
+        entries,
+        [("a.txt", false, 1), ("b.txt", false, 2), ("c", true, 0)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn errors() -> rune::support::Result<()> {
+    let dir = TempDir::new("errors");
+
+    let (missing, removed): (bool, bool) = run(
+        &dir,
+        r#"
+        pub async fn main(dir) {
+            let missing = fs::read_to_string(`${dir}/missing.txt`).await.is_err();
+            fs::write(`${dir}/file.txt`, b"data").await?;
+            fs::remove_file(`${dir}/file.txt`).await?;
+            let removed = fs::metadata(`${dir}/file.txt`).await.is_err();
+            Ok((missing, removed))
+        }
+        "#,
+    )
+    .await?;
+
+    assert!(missing);
+    assert!(removed);
+    Ok(())
+}
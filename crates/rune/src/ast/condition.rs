@@ -5,12 +5,21 @@ use crate::ast::prelude::*;
 fn ast_parse() {
     rt::<ast::Condition>("true");
     rt::<ast::Condition>("let [a, ..] = v");
+    rt::<ast::Condition>("let Some(a) = v && a > 3");
+    rt::<ast::Condition>("let Some(a) = v && let [b, ..] = a && b == 1 + 2");
+
+    let condition = rt::<ast::Condition>("let a = b + c");
+    assert!(matches!(condition, ast::Condition::ExprLet(..)));
+
+    let condition = rt::<ast::Condition>("let a = b && c");
+    assert!(matches!(condition, ast::Condition::Chain(..)));
 }
 
 /// The condition in an if statement.
 ///
 /// * `true`.
 /// * `let Some(<pat>) = <expr>`.
+/// * `let Some(<pat>) = <expr> && <condition>`.
 #[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
 pub enum Condition {
@@ -18,13 +27,50 @@ pub enum Condition {
     Expr(ast::Expr),
     /// A pattern match.
     ExprLet(ast::ExprLet),
+    /// A chain of conditions starting with a pattern match.
+    Chain(ast::ConditionChain),
 }
 
 impl Parse for Condition {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
-        Ok(match p.nth(0)? {
-            K![let] => Self::ExprLet(ast::ExprLet::parse_without_eager_brace(p)?),
-            _ => Self::Expr(ast::Expr::parse_without_eager_brace(p)?),
-        })
+        if !matches!(p.nth(0)?, K![let]) {
+            return Ok(Self::Expr(ast::Expr::parse_without_eager_brace(p)?));
+        }
+
+        let first = ast::ExprLet::parse_chain_link(p)?;
+
+        if !p.peek::<T![&&]>()? {
+            return Ok(Self::ExprLet(first));
+        }
+
+        let mut rest = Vec::new();
+
+        while let Some(and) = p.parse::<Option<T![&&]>>()? {
+            let link = match p.nth(0)? {
+                K![let] => Self::ExprLet(ast::ExprLet::parse_chain_link(p)?),
+                _ => Self::Expr(ast::Expr::parse_chain_link(p)?),
+            };
+
+            rest.try_push((and, link))?;
+        }
+
+        Ok(Self::Chain(ast::ConditionChain { first, rest }))
     }
 }
+
+/// A chain of conditions separated by `&&`, which starts with a pattern match.
+///
+/// * `let Some(<pat>) = <expr> && <expr>`.
+/// * `let Some(<pat>) = <expr> && let <pat> = <expr>`.
+///
+/// Bindings introduced by a pattern are visible to the conditions which follow
+/// it.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ConditionChain {
+    /// The pattern match which starts the chain.
+    pub first: ast::ExprLet,
+    /// The conditions which follow, each preceded by `&&`.
+    #[rune(iter)]
+    pub rest: Vec<(T![&&], ast::Condition)>,
+}
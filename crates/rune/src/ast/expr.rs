@@ -86,6 +86,10 @@ pub(crate) const EAGER_BINARY: EagerBinary = EagerBinary(true);
 /// Indicates that an expression should not be parsed as a binary expression.
 pub(crate) const NOT_EAGER_BINARY: EagerBinary = EagerBinary(false);
 
+/// The lowest precedence of a binary operator which is part of a link in a
+/// condition chain, which is the one right above `&&`.
+const CHAIN_PRECEDENCE: usize = 5;
+
 impl ops::Deref for EagerBinary {
     type Target = bool;
 
@@ -328,6 +332,25 @@ impl Expr {
         Self::parse_with(p, NOT_EAGER_BRACE, EAGER_BINARY, CALLABLE)
     }
 
+    /// Parse an expression which is a link in a condition chain, like `x > 3`
+    /// in `if let Some(x) = value && x > 3 { .. }`.
+    ///
+    /// Parsing stops before any binary operator which binds as loosely as or
+    /// more loosely than `&&`.
+    pub(crate) fn parse_chain_link(p: &mut Parser<'_>) -> Result<Self> {
+        let mut attributes = p.parse()?;
+
+        let expr = primary(p, &mut attributes, NOT_EAGER_BRACE, CALLABLE)?;
+        let lookahead = ast::BinOp::from_peeker(p.peeker());
+        let expr = binary(p, expr, lookahead, CHAIN_PRECEDENCE, NOT_EAGER_BRACE)?;
+
+        if let Some(span) = attributes.option_span() {
+            return Err(compile::Error::unsupported(span, "attributes"));
+        }
+
+        Ok(expr)
+    }
+
     /// Helper to perform a parse with the given meta.
     pub(crate) fn parse_with_meta(
         p: &mut Parser<'_>,
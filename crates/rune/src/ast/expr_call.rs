@@ -5,6 +5,15 @@ use crate::ast::prelude::*;
 fn ast_parse() {
     rt::<ast::ExprCall>("test()");
     rt::<ast::ExprCall>("(foo::bar)()");
+    rt::<ast::ExprCall>("connect(host, port: 8080)");
+    rt::<ast::ExprCall>("connect(host: \"localhost\", port: 8080,)");
+    rt::<ast::ExprCall>("foo::bar(baz::qux)");
+
+    let arg = rt::<ast::CallArg>("port: 8080");
+    assert!(arg.name.is_some());
+
+    let arg = rt::<ast::CallArg>("port");
+    assert!(arg.name.is_none());
 }
 
 /// A call expression.
@@ -21,10 +30,38 @@ pub struct ExprCall {
     #[rune(meta)]
     pub expr: Box<ast::Expr>,
     /// The arguments of the function call.
-    pub args: ast::Parenthesized<ast::Expr, T![,]>,
+    pub args: ast::Parenthesized<ast::CallArg, T![,]>,
     /// Opaque identifier related with call.
     #[rune(skip)]
     pub(crate) id: ItemId,
 }
 
 expr_parse!(Call, ExprCall, "call expression");
+
+/// A single argument to a call expression.
+///
+/// * `<expr>`.
+/// * `<ident>: <expr>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct CallArg {
+    /// The name of the argument if it is passed by name.
+    #[rune(iter)]
+    pub name: Option<(ast::Ident, T![:])>,
+    /// The value of the argument.
+    pub expr: ast::Expr,
+}
+
+impl Parse for CallArg {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        let name = match (p.nth(0)?, p.nth(1)?) {
+            (K![ident], K![:]) => Some((p.parse()?, p.parse()?)),
+            _ => None,
+        };
+
+        Ok(Self {
+            name,
+            expr: p.parse()?,
+        })
+    }
+}
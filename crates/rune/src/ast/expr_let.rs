@@ -45,15 +45,18 @@ impl ExprLet {
         })
     }
 
-    /// Parse a let expression without eager bracing.
-    pub(crate) fn parse_without_eager_brace(parser: &mut Parser<'_>) -> Result<Self> {
+    /// Parse a let expression which is a link in a condition chain.
+    ///
+    /// The expression being matched stops before the first `&&`, which
+    /// separates it from the next link.
+    pub(crate) fn parse_chain_link(parser: &mut Parser<'_>) -> Result<Self> {
         Ok(Self {
             attributes: Vec::new(),
             let_token: parser.parse()?,
             mut_token: parser.parse()?,
             pat: parser.parse()?,
             eq: parser.parse()?,
-            expr: Box::try_new(ast::Expr::parse_without_eager_brace(parser)?)?,
+            expr: Box::try_new(ast::Expr::parse_chain_link(parser)?)?,
         })
     }
 }
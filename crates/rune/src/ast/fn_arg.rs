@@ -6,9 +6,14 @@ fn ast_parse() {
     rt::<ast::FnArg>("self");
     rt::<ast::FnArg>("_");
     rt::<ast::FnArg>("abc");
+    rt::<ast::FnArg>("port = 80");
+    rt::<ast::FnArg>("host = \"localhost\"");
+    rt::<ast::FnArg>("a: i64");
+    rt::<ast::FnArg>("(a, b): (i64, i64)");
+    rt::<ast::FnArg>("port: i64 = 80");
 }
 
-/// A single argument in a closure.
+/// A single argument in a function or closure.
 #[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
 pub enum FnArg {
@@ -16,13 +21,98 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(FnArgTyped),
+    /// Function argument is a pattern binding with a default value.
+    Default(Box<FnArgDefault>),
+}
+
+impl FnArg {
+    /// Parse a function argument which is not allowed to have a default
+    /// value, like the argument of a closure.
+    pub(crate) fn parse_without_default(p: &mut Parser<'_>) -> Result<Self> {
+        if p.peek::<T![self]>()? {
+            return Ok(Self::SelfValue(p.parse()?));
+        }
+
+        let pat = ast::Pat::parse_annotated(p)?;
+
+        if !p.peek::<T![:]>()? {
+            return Ok(Self::Pat(pat));
+        }
+
+        Ok(Self::Typed(FnArgTyped {
+            pat,
+            colon: p.parse()?,
+            ty: p.parse()?,
+        }))
+    }
+
+    /// Get the type annotation of the argument, if it has one.
+    pub(crate) fn ty(&self) -> Option<&ast::Type> {
+        match self {
+            Self::SelfValue(..) | Self::Pat(..) => None,
+            Self::Typed(arg) => Some(&arg.ty),
+            Self::Default(arg) => arg.ty.as_ref().map(|(_, ty)| ty),
+        }
+    }
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
-        Ok(match p.nth(0)? {
-            K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
-        })
+        let arg = Self::parse_without_default(p)?;
+
+        if !p.peek::<T![=]>()? {
+            return Ok(arg);
+        }
+
+        let (pat, ty) = match arg {
+            Self::Pat(pat) => (pat, None),
+            Self::Typed(arg) => (arg.pat, Some((arg.colon, arg.ty))),
+            arg => return Ok(arg),
+        };
+
+        Ok(Self::Default(Box::try_new(FnArgDefault {
+            pat,
+            ty,
+            eq: p.parse()?,
+            default: p.parse()?,
+            id: ItemId::ROOT,
+        })?))
     }
 }
+
+/// A function argument with a type annotation.
+///
+/// * `<pat>: <type>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct FnArgTyped {
+    /// The pattern binding of the argument.
+    pub pat: ast::Pat,
+    /// The `:` token.
+    pub colon: T![:],
+    /// The type of the argument.
+    pub ty: ast::Type,
+}
+
+/// A function argument with a default value.
+///
+/// * `<pat> = <expr>`.
+/// * `<pat>: <type> = <expr>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct FnArgDefault {
+    /// The pattern binding of the argument.
+    pub pat: ast::Pat,
+    /// The optional type annotation of the argument.
+    #[rune(iter)]
+    pub ty: Option<(T![:], ast::Type)>,
+    /// The `=` token.
+    pub eq: T![=],
+    /// The default value of the argument.
+    pub default: ast::Expr,
+    /// Opaque identifier for the constant holding the default value.
+    #[rune(skip)]
+    pub(crate) id: ItemId,
+}
@@ -37,6 +37,12 @@ fn ast_parse() {
     assert!(item.async_token.is_none());
     assert!(item.const_token.is_some());
 
+    let item = rt::<ast::ItemFn>("fn connect(host, port = 80) {}");
+    assert!(matches!(
+        item.args.last(),
+        Some((ast::FnArg::Default(..), _))
+    ));
+
     let item_with_type = rt::<ast::ItemFn>("pub async fn hello(foo, bar) -> Type {}");
     assert!(item_with_type.output.is_some());
 }
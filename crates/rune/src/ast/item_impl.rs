@@ -1,3 +1,5 @@
+use core::mem::replace;
+
 use crate::ast::prelude::*;
 
 #[test]
@@ -9,6 +11,10 @@ fn ast_parse() {
         "#[variant(enum_= \"SuperHero\", x = \"1\")] impl Foo { fn test(self) { } }",
     );
     rt::<ast::ItemImpl>("#[xyz] impl Foo { #[jit] fn test(self) { } }");
+
+    let item = rt::<ast::ItemImpl>("impl PartialEq for Foo { fn eq(self, other) { true } }");
+    assert!(item.trait_.is_some());
+    assert_eq!(item.functions.len(), 1);
 }
 
 /// An impl item.
@@ -20,6 +26,9 @@ pub struct ItemImpl {
     pub attributes: Vec<ast::Attribute>,
     /// The `impl` keyword.
     pub impl_: T![impl],
+    /// The trait being implemented, as in `impl Trait for Type`.
+    #[rune(option)]
+    pub trait_: Option<(ast::Path, T![for])>,
     /// Path of the implementation.
     pub path: ast::Path,
     /// The open brace.
@@ -38,7 +47,13 @@ impl ItemImpl {
         attributes: Vec<ast::Attribute>,
     ) -> Result<Self> {
         let impl_ = parser.parse()?;
-        let path = parser.parse()?;
+        let mut path = parser.parse::<ast::Path>()?;
+
+        let trait_ = match parser.parse::<Option<T![for]>>()? {
+            Some(for_) => Some((replace(&mut path, parser.parse()?), for_)),
+            None => None,
+        };
+
         let open = parser.parse()?;
 
         let mut functions = Vec::new();
@@ -52,6 +67,7 @@ impl ItemImpl {
         Ok(Self {
             attributes,
             impl_,
+            trait_,
             path,
             open,
             functions,
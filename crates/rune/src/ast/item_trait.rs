@@ -0,0 +1,137 @@
+use crate::ast::prelude::*;
+
+#[test]
+#[cfg(not(miri))]
+fn ast_parse() {
+    rt::<ast::ItemTrait>("trait Foo {}");
+    rt::<ast::ItemTrait>("pub trait Foo { fn test(self); }");
+
+    let item = rt::<ast::ItemTrait>(
+        "#[doc = \"A trait\"] trait Foo { fn test(self); fn other(self, a) -> Type { self.test() } }",
+    );
+
+    assert_eq!(item.attributes.len(), 1);
+    assert_eq!(item.items.len(), 2);
+    assert!(matches!(item.items[0], ast::TraitItem::Required(..)));
+    assert!(matches!(item.items[1], ast::TraitItem::Fn(..)));
+
+    let item = rt::<ast::TraitItem>("async fn test(self);");
+    assert!(matches!(
+        item,
+        ast::TraitItem::Required(ast::TraitFn {
+            async_token: Some(..),
+            ..
+        })
+    ));
+}
+
+/// A trait item.
+#[derive(Debug, TryClone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+#[rune(parse = "meta_only")]
+#[non_exhaustive]
+pub struct ItemTrait {
+    /// The attributes of the `trait` item.
+    #[rune(iter, meta)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the `trait` item.
+    #[rune(option, meta)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub name: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// The functions declared in the trait.
+    #[rune(iter)]
+    pub items: Vec<ast::TraitItem>,
+    /// The close brace.
+    pub close: T!['}'],
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+/// A function declared inside of a trait.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum TraitItem {
+    /// A function with a default implementation.
+    Fn(ast::ItemFn),
+    /// A function which must be provided by implementors.
+    Required(ast::TraitFn),
+}
+
+impl TraitItem {
+    /// The name of the function.
+    pub(crate) fn name(&self) -> &ast::Ident {
+        match self {
+            Self::Fn(item) => &item.name,
+            Self::Required(item) => &item.name,
+        }
+    }
+}
+
+impl Parse for TraitItem {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        let attributes = p.parse::<Vec<ast::Attribute>>()?;
+        let async_token = p.parse::<Option<T![async]>>()?;
+        let fn_token = p.parse()?;
+        let name = p.parse()?;
+        let args = p.parse()?;
+        let output = p.parse()?;
+
+        if let Some(semi) = p.parse::<Option<T![;]>>()? {
+            return Ok(Self::Required(TraitFn {
+                attributes,
+                async_token,
+                fn_token,
+                name,
+                args,
+                output,
+                semi,
+            }));
+        }
+
+        Ok(Self::Fn(ast::ItemFn {
+            attributes,
+            visibility: ast::Visibility::Inherited,
+            const_token: None,
+            async_token,
+            fn_token,
+            name,
+            args,
+            output,
+            body: p.parse()?,
+            id: Default::default(),
+        }))
+    }
+}
+
+impl Peek for TraitItem {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        matches!(p.nth(0), K![#] | K![async] | K![fn])
+    }
+}
+
+/// A function signature without a body, as declared in a trait.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct TraitFn {
+    /// The attributes of the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The optional `async` keyword.
+    #[rune(iter)]
+    pub async_token: Option<T![async]>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The function type.
+    #[rune(option)]
+    pub output: Option<(T![->], ast::Type)>,
+    /// The terminating semi-colon.
+    pub semi: T![;],
+}
@@ -6,11 +6,16 @@ fn ast_parse() {
     rt::<ast::Local>("let x = 1;");
     rt::<ast::Local>("#[attr] let a = f();");
     rt::<ast::Local>("let a = b{}().foo[0].await;");
+    rt::<ast::Local>("let Some(a) = b else { return; };");
+    rt::<ast::Local>("let x: i64 = 1;");
+    rt::<ast::Local>("let (a, b): (i64, String) = f();");
 }
 
 /// A local variable declaration.
 ///
 /// * `let <pattern> = <expr>;`
+/// * `let <pattern>: <type> = <expr>;`
+/// * `let <pattern> = <expr> else { <diverging> };`
 #[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 #[non_exhaustive]
 pub struct Local {
@@ -23,16 +28,28 @@ pub struct Local {
     #[rune(iter)]
     pub mut_token: Option<T![mut]>,
     /// The name of the binding.
+    #[rune(parse_with = parse_pat)]
     pub pat: ast::Pat,
+    /// The optional type annotation of the binding.
+    #[rune(iter)]
+    pub ty: Option<(T![:], ast::Type)>,
     /// The equality keyword.
     pub eq: T![=],
     /// The expression the binding is assigned to.
     #[rune(parse_with = parse_expr)]
     pub expr: ast::Expr,
+    /// The `else` block which is evaluated if the pattern doesn't match. It
+    /// must not complete normally.
+    #[rune(iter)]
+    pub else_branch: Option<(T![else], ast::Block)>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
 
+fn parse_pat(p: &mut Parser<'_>) -> Result<ast::Pat> {
+    ast::Pat::parse_annotated(p)
+}
+
 fn parse_expr(p: &mut Parser<'_>) -> Result<ast::Expr> {
     ast::Expr::parse_with(
         p,
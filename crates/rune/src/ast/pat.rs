@@ -39,8 +39,17 @@ pub enum Pat {
     Rest(PatRest),
 }
 
-impl Parse for Pat {
-    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+impl Pat {
+    /// Parse a pattern which might be followed by a type annotation, like the
+    /// pattern of a function argument or a local binding.
+    ///
+    /// In this position `a: b` is not parsed as a binding, and the colon is
+    /// left for the caller to parse the type annotation.
+    pub(crate) fn parse_annotated(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, false)
+    }
+
+    fn parse_with(p: &mut Parser<'_>, bindings: bool) -> Result<Self> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
         match p.nth(0)? {
@@ -148,7 +157,7 @@ impl Parse for Pat {
                         ident: ast::ObjectIdent::Named(path),
                         items: p.parse()?,
                     }),
-                    K![:] => Self::Binding(PatBinding {
+                    K![:] if bindings => Self::Binding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::Path(path),
                         colon: p.parse()?,
@@ -164,6 +173,13 @@ impl Parse for Pat {
     }
 }
 
+impl Parse for Pat {
+    #[inline]
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, true)
+    }
+}
+
 impl Peek for Pat {
     fn peek(p: &mut Peeker<'_>) -> bool {
         match p.nth(0) {
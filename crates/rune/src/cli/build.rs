@@ -0,0 +1,135 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::string::String;
+
+use anyhow::{Context, Result};
+
+use crate::cli::naming::Naming;
+use crate::cli::{
+    loader, visitor, AssetKind, CommandBase, Config, Entry, EntryPoint, ExitCode, Io, SharedFlags,
+};
+use crate::Options;
+
+mod cli {
+    use std::path::PathBuf;
+    use std::vec::Vec;
+
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {
+        /// Leave debug information out of the built units.
+        ///
+        /// This makes units smaller, but errors raised while running them can
+        /// no longer be mapped back to their sources.
+        #[arg(long)]
+        pub(super) strip_debug: bool,
+        /// Output directory to write built units to.
+        #[arg(long, short = 'o')]
+        pub(super) output: Option<PathBuf>,
+        /// Explicit paths to build.
+        pub(super) build_path: Vec<PathBuf>,
+    }
+}
+
+pub(super) use cli::Flags;
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_workspace(&self, kind: AssetKind) -> bool {
+        matches!(kind, AssetKind::Bin)
+    }
+
+    #[inline]
+    fn describe(&self) -> &str {
+        "Building"
+    }
+
+    #[inline]
+    fn paths(&self) -> &[PathBuf] {
+        &self.build_path
+    }
+}
+
+pub(super) fn run<'p, I>(
+    io: &mut Io<'_>,
+    entry: &mut Entry<'_>,
+    c: &Config,
+    flags: &Flags,
+    shared: &SharedFlags,
+    options: &Options,
+    entries: I,
+) -> Result<ExitCode>
+where
+    I: IntoIterator<Item = EntryPoint<'p>>,
+{
+    let root = match &flags.output {
+        Some(root) => root.clone(),
+        None => match &c.manifest_root {
+            Some(path) => path.join("target").join("rune-build"),
+            None => match std::env::var_os("CARGO_TARGET_DIR") {
+                Some(target) => {
+                    let mut target = PathBuf::from(target);
+                    target.push("rune-build");
+                    target
+                }
+                None => {
+                    let mut target = PathBuf::new();
+                    target.push("target");
+                    target.push("rune-build");
+                    target
+                }
+            },
+        },
+    };
+
+    let context = shared.context(entry, c, None)?;
+
+    let mut naming = Naming::default();
+
+    for e in entries {
+        let mut options = options.clone();
+
+        if e.is_argument() {
+            options.script = true;
+        }
+
+        let load = loader::load(io, &context, shared, &options, &e, visitor::Attribute::None)?;
+
+        let item = naming.item(&e)?;
+
+        let mut name = String::new();
+
+        for (n, component) in item.iter().enumerate() {
+            if n > 0 {
+                name.push('-');
+            }
+
+            write!(name, "{component}")?;
+        }
+
+        name.push_str(".rnu");
+
+        let bytes = load.unit.encode_artifact(flags.strip_debug)?;
+
+        fs::create_dir_all(&root)
+            .with_context(|| format!("creating directory: {}", root.display()))?;
+
+        let path = root.join(name);
+
+        fs::write(&path, &bytes[..])
+            .with_context(|| format!("writing unit: {}", path.display()))?;
+
+        writeln!(
+            io.stdout,
+            "Built: {} -> {}",
+            e.path().display(),
+            path.display()
+        )?;
+    }
+
+    Ok(ExitCode::Success)
+}
@@ -1,10 +1,11 @@
 use std::io::Write;
-use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 
-use crate::cli::{visitor, AssetKind, CommandBase, Config, Entry, ExitCode, Io, SharedFlags};
+use crate::cli::{
+    visitor, AssetKind, CommandBase, Config, Entry, EntryPoint, ExitCode, Io, SharedFlags,
+};
 use crate::compile::FileSourceLoader;
 use crate::{Diagnostics, Options, Source, Sources};
 
@@ -20,6 +21,10 @@ mod cli {
         /// Exit with a non-zero exit-code even for warnings
         #[arg(long)]
         pub(super) warnings_are_errors: bool,
+        /// Check type annotations against native signatures and script
+        /// definitions.
+        #[arg(long)]
+        pub(super) types: bool,
         /// Explicit paths to check.
         pub(super) check_path: Vec<PathBuf>,
     }
@@ -56,8 +61,9 @@ pub(super) fn run(
     flags: &Flags,
     shared: &SharedFlags,
     options: &Options,
-    path: &Path,
+    e: &EntryPoint<'_>,
 ) -> Result<ExitCode> {
+    let path = e.path();
     writeln!(io.stdout, "Checking: {}", path.display())?;
 
     let context = shared.context(entry, c, None)?;
@@ -68,6 +74,7 @@ pub(super) fn run(
     let mut sources = Sources::new();
 
     sources.insert(source)?;
+    e.insert_dependencies(&mut sources)?;
 
     let mut diagnostics = if shared.warnings || flags.warnings_are_errors {
         Diagnostics::new()
@@ -78,10 +85,16 @@ pub(super) fn run(
     let mut test_finder = visitor::FunctionVisitor::new(visitor::Attribute::None);
     let mut source_loader = FileSourceLoader::new();
 
+    let mut options = options.clone();
+
+    if flags.types {
+        options.type_checks(true);
+    }
+
     let _ = crate::prepare(&mut sources)
         .with_context(&context)
         .with_diagnostics(&mut diagnostics)
-        .with_options(options)
+        .with_options(&options)
         .with_visitor(&mut test_finder)?
         .with_source_loader(&mut source_loader)
         .build();
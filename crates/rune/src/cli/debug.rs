@@ -0,0 +1,333 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+
+use crate::cli::{AssetKind, CommandBase, ExitCode, Io};
+use crate::runtime::{DebugOutcome, DebugStep, Debugger, Vm, VmExecution};
+use crate::sync::Arc;
+use crate::{Context, Hash, SourceId, Sources, Unit};
+
+mod cli {
+    use std::path::PathBuf;
+    use std::string::String;
+    use std::vec::Vec;
+
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {
+        /// Add a breakpoint before starting, either as `<line>` in the main
+        /// source or as `<source>:<line>`.
+        #[arg(short, long = "break")]
+        pub(super) breakpoints: Vec<String>,
+        /// Explicit paths to debug.
+        pub(super) debug_path: Vec<PathBuf>,
+    }
+}
+
+pub(super) use cli::Flags;
+
+const HELP: &str = "\
+Commands:
+  b, break <line>     Add a breakpoint at <line> or <source>:<line>
+  d, delete <id>      Delete the breakpoint with the given identifier
+  breakpoints         List all breakpoints
+  c, continue         Continue until a breakpoint is hit
+  s, step             Step to the next line, entering function calls
+  n, next             Step to the next line, stepping over function calls
+  o, out              Run until the current function returns
+  l, locals           Print the local variables of the current function
+  p, print <name>     Print the local variable with the given name
+  bt, backtrace       Print the call stack
+  h, help             Print this help
+  q, quit             Stop debugging";
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_workspace(&self, kind: AssetKind) -> bool {
+        matches!(kind, AssetKind::Bin)
+    }
+
+    #[inline]
+    fn describe(&self) -> &str {
+        "Debugging"
+    }
+
+    #[inline]
+    fn paths(&self) -> &[PathBuf] {
+        &self.debug_path
+    }
+}
+
+pub(super) async fn run(
+    io: &mut Io<'_>,
+    args: &Flags,
+    context: &Context,
+    unit: Arc<Unit>,
+    sources: &Sources,
+    entry: Hash,
+) -> Result<ExitCode> {
+    if unit.debug_info().is_none() {
+        return Err(anyhow!(
+            "Debugging requires the unit to be built with debug info"
+        ));
+    }
+
+    let mut debugger = Debugger::new(&unit, sources)?;
+
+    for breakpoint in &args.breakpoints {
+        add_breakpoint(io, &mut debugger, sources, breakpoint)?;
+    }
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let mut vm = Vm::new(runtime, unit);
+    let mut execution: VmExecution<_> = vm.execute(entry, ())?;
+
+    writeln!(io.stdout, "Type `help` for help, and `quit` to exit")?;
+
+    let mut step = if args.breakpoints.is_empty() {
+        None
+    } else {
+        Some(DebugStep::Continue)
+    };
+
+    loop {
+        if let Some(step) = step.take() {
+            let outcome = match execution.debug(&mut debugger, step).await {
+                Ok(outcome) => outcome,
+                Err(error) => {
+                    error.emit(io.stdout, sources)?;
+                    return Ok(ExitCode::VmError);
+                }
+            };
+
+            match outcome {
+                DebugOutcome::Breakpoint(id) => {
+                    writeln!(io.stdout, "Hit breakpoint #{id}")?;
+                }
+                DebugOutcome::Step => {}
+                DebugOutcome::Yielded(value) => {
+                    execution
+                        .vm()
+                        .with(|| writeln!(io.stdout, "Yielded {value:?}"))?;
+                }
+                DebugOutcome::Complete(value) => {
+                    execution
+                        .vm()
+                        .with(|| writeln!(io.stdout, "== {value:?}"))?;
+                    return Ok(ExitCode::Success);
+                }
+                DebugOutcome::Limited => {
+                    writeln!(io.stdout, "Execution budget exhausted")?;
+                    return Ok(ExitCode::VmError);
+                }
+            }
+        }
+
+        print_location(io, &debugger, execution.vm(), sources)?;
+
+        write!(io.stdout, "(debug) ")?;
+        io.stdout.flush()?;
+
+        let mut line = std::string::String::new();
+
+        if io.stdin.read_line(&mut line)? == 0 {
+            writeln!(io.stdout)?;
+            break;
+        }
+
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match command {
+            "" => {}
+            "q" | "quit" => break,
+            "h" | "help" => {
+                writeln!(io.stdout, "{HELP}")?;
+            }
+            "b" | "break" => {
+                add_breakpoint(io, &mut debugger, sources, rest)?;
+            }
+            "d" | "delete" => match rest.parse::<usize>() {
+                Ok(id) if debugger.remove_breakpoint(id) => {
+                    writeln!(io.stdout, "Deleted breakpoint #{id}")?;
+                }
+                _ => {
+                    writeln!(io.stdout, "No breakpoint `{rest}`")?;
+                }
+            },
+            "breakpoints" => {
+                for b in debugger.breakpoints() {
+                    let name = sources
+                        .get(b.location.source_id)
+                        .map(|s| s.name())
+                        .unwrap_or("?");
+
+                    writeln!(io.stdout, "#{} at {name}:{}", b.id, b.location.line + 1)?;
+                }
+            }
+            "c" | "continue" => {
+                step = Some(DebugStep::Continue);
+            }
+            "s" | "step" => {
+                step = Some(DebugStep::Into);
+            }
+            "n" | "next" => {
+                step = Some(DebugStep::Over);
+            }
+            "o" | "out" | "finish" => {
+                step = Some(DebugStep::Out);
+            }
+            "l" | "locals" => {
+                let vm = execution.vm();
+                let locals = vm.locals()?;
+
+                if locals.is_empty() {
+                    writeln!(io.stdout, "No locals")?;
+                }
+
+                vm.with(|| {
+                    for (name, value) in locals {
+                        writeln!(io.stdout, "{name} = {value:?}")?;
+                    }
+
+                    Ok::<_, std::io::Error>(())
+                })?;
+            }
+            "p" | "print" => {
+                let vm = execution.vm();
+                let locals = vm.locals()?;
+
+                match locals.iter().find(|(name, _)| *name == rest) {
+                    Some((name, value)) => {
+                        vm.with(|| writeln!(io.stdout, "{name} = {value:?}"))?;
+                    }
+                    None => {
+                        writeln!(io.stdout, "No local named `{rest}`")?;
+                    }
+                }
+            }
+            "bt" | "backtrace" => {
+                backtrace(io, &debugger, execution.vm(), sources)?;
+            }
+            _ => {
+                writeln!(io.stdout, "Unknown command `{command}`, try `help`")?;
+            }
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Parse and add a breakpoint in the form `<line>` or `<source>:<line>`.
+fn add_breakpoint(
+    io: &mut Io<'_>,
+    debugger: &mut Debugger,
+    sources: &Sources,
+    breakpoint: &str,
+) -> Result<()> {
+    let (source_id, line) = match breakpoint.rsplit_once(':') {
+        Some((name, line)) => {
+            let source_id = sources
+                .source_ids()
+                .find(|id| sources.get(*id).is_some_and(|s| s.name() == name));
+
+            let Some(source_id) = source_id else {
+                writeln!(io.stdout, "No source named `{name}`")?;
+                return Ok(());
+            };
+
+            (source_id, line)
+        }
+        None => (SourceId::new(0), breakpoint),
+    };
+
+    let Some(line) = line.parse::<usize>().ok().filter(|line| *line > 0) else {
+        writeln!(io.stdout, "Bad line number `{line}`")?;
+        return Ok(());
+    };
+
+    let Some(id) = debugger.add_breakpoint(source_id, line - 1)? else {
+        writeln!(io.stdout, "No code at or after line {line}")?;
+        return Ok(());
+    };
+
+    if let Some(b) = debugger.breakpoints().find(|b| b.id == id) {
+        writeln!(
+            io.stdout,
+            "Breakpoint #{id} at line {}",
+            b.location.line + 1
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Print the current location of the virtual machine.
+fn print_location(io: &mut Io<'_>, debugger: &Debugger, vm: &Vm, sources: &Sources) -> Result<()> {
+    let Some(location) = debugger.location(vm.ip()) else {
+        writeln!(io.stdout, "At instruction {:04}", vm.ip())?;
+        return Ok(());
+    };
+
+    let Some(source) = sources.get(location.source_id) else {
+        return Ok(());
+    };
+
+    let text = source
+        .as_str()
+        .lines()
+        .nth(location.line)
+        .unwrap_or_default();
+
+    writeln!(
+        io.stdout,
+        "{}:{}: {}",
+        source.name(),
+        location.line + 1,
+        text.trim()
+    )?;
+
+    Ok(())
+}
+
+/// Print the call stack of the virtual machine, innermost call first.
+fn backtrace(io: &mut Io<'_>, debugger: &Debugger, vm: &Vm, sources: &Sources) -> Result<()> {
+    let ips = core::iter::once(vm.ip()).chain(vm.call_frames().iter().rev().map(|f| f.ip));
+
+    for (n, ip) in ips.enumerate() {
+        let function = vm.unit().debug_info().and_then(|debug| {
+            let start = debug
+                .functions_rev
+                .keys()
+                .copied()
+                .filter(|&start| start <= ip)
+                .max()?;
+
+            debug.function_at(start)
+        });
+
+        write!(io.stdout, "#{n} ")?;
+
+        match function {
+            Some((_, signature)) => write!(io.stdout, "{signature}")?,
+            None => write!(io.stdout, "?")?,
+        }
+
+        if let Some(location) = debugger.location(ip) {
+            let name = sources
+                .get(location.source_id)
+                .map(|s| s.name())
+                .unwrap_or("?");
+
+            write!(io.stdout, " at {name}:{}", location.line + 1)?;
+        }
+
+        writeln!(io.stdout)?;
+    }
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{anyhow, Context as _, Result};
 
 use crate::alloc::{Vec, VecDeque};
-use crate::cli::{visitor, Io, SharedFlags};
+use crate::cli::{visitor, EntryPoint, Io, SharedFlags};
 use crate::compile::FileSourceLoader;
 use crate::sync::Arc;
 use crate::{Context, Diagnostics, Hash, ItemBuf, Options, Source, Sources, Unit};
@@ -23,9 +23,10 @@ pub(super) fn load(
     context: &Context,
     shared: &SharedFlags,
     options: &Options,
-    path: &Path,
+    e: &EntryPoint<'_>,
     attribute: visitor::Attribute,
 ) -> Result<Load> {
+    let path = e.path();
     let bytecode_path = path.with_extension("rnc");
 
     let source =
@@ -33,8 +34,10 @@ pub(super) fn load(
 
     let mut sources = Sources::new();
     sources.insert(source)?;
+    e.insert_dependencies(&mut sources)?;
 
-    let use_cache = options.bytecode && should_cache_be_used(path, &bytecode_path)?;
+    let use_cache = options.bytecode
+        && should_cache_be_used(sources.iter().filter_map(Source::path), &bytecode_path)?;
 
     // TODO: how do we deal with tests discovery for bytecode loading
     let maybe_unit = if use_cache {
@@ -100,17 +103,25 @@ pub(super) fn load(
     })
 }
 
-/// Test if path `a` is newer than path `b`.
-fn should_cache_be_used(source: &Path, cached: &Path) -> io::Result<bool> {
-    let source = fs::metadata(source)?;
-
+/// Test if the `cached` path is newer than all of the given source paths,
+/// which includes the libraries of any dependencies.
+fn should_cache_be_used<'a>(
+    sources: impl IntoIterator<Item = &'a Path>,
+    cached: &Path,
+) -> io::Result<bool> {
     let cached = match fs::metadata(cached) {
-        Ok(cached) => cached,
+        Ok(cached) => cached.modified()?,
         Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
         Err(error) => return Err(error),
     };
 
-    Ok(source.modified()? < cached.modified()?)
+    for source in sources {
+        if fs::metadata(source)?.modified()? >= cached {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
 }
 
 pub(super) fn recurse_paths(
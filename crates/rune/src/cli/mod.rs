@@ -5,21 +5,26 @@
 //! * Generate documentation using types only available in your context.
 //! * Build a language server, which is aware of things only available in your
 //!   context.
+//! * Run an interactive REPL with access to your own modules.
 
 mod ace;
 mod benches;
+mod build;
 mod check;
+mod debug;
 mod doc;
 mod format;
 mod languageserver;
 mod loader;
 mod naming;
 mod out;
+mod repl;
 mod run;
 mod tests;
 mod visitor;
 
 use std::fmt;
+use std::fs;
 use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
@@ -37,10 +42,11 @@ use tracing_subscriber::filter::EnvFilter;
 
 use crate::compile::ParseOptionError;
 use crate::modules::capture_io::CaptureIo;
+use crate::sync::Arc;
 use crate::termcolor::{ColorChoice, StandardStream};
-use crate::{Context, ContextError, Hash, ItemBuf, Options};
+use crate::{Context, ContextError, Hash, ItemBuf, Options, Sources, Unit};
 
-use self::out::{Color, Io, Stream};
+use self::out::{Color, Io, ReadLine, Stream};
 
 /// Default about splash.
 const DEFAULT_ABOUT: &str = "The Rune Language Interpreter";
@@ -213,10 +219,11 @@ impl<'a> Entry<'a> {
             ColorArgument::Never => ColorChoice::Never,
         };
 
+        let mut stdin = std::io::stdin();
         let mut stdout = StandardStream::stdout(choice);
         let mut stderr = StandardStream::stderr(choice);
 
-        let mut io = Io::new(&mut stdout, &mut stderr);
+        let mut io = Io::new(&mut stdin, &mut stdout, &mut stderr);
 
         tracing_subscriber::fmt()
             .with_env_filter(EnvFilter::from_default_env())
@@ -259,6 +266,22 @@ impl EntryPoint<'_> {
             EntryPoint::Package(..) => false,
         }
     }
+
+    /// Insert the libraries of the dependencies of the entrypoint into the
+    /// given sources.
+    pub(crate) fn insert_dependencies(&self, sources: &mut crate::Sources) -> Result<()> {
+        let EntryPoint::Package(p) = self else {
+            return Ok(());
+        };
+
+        for lib in &p.dependencies {
+            let source = crate::Source::from_path(&lib.path)
+                .with_context(|| format!("reading file: {}", lib.path.display()))?;
+            sources.insert_dependency(&lib.name, source)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl fmt::Display for EntryPoint<'_> {
@@ -422,6 +445,8 @@ trait CommandBase {
 enum Command {
     /// Run checks but do not execute
     Check(CommandShared<check::Flags>),
+    /// Build units which can be run without their sources
+    Build(CommandShared<build::Flags>),
     /// Build documentation.
     Doc(CommandShared<doc::Flags>),
     /// Build ace autocompletion.
@@ -432,37 +457,47 @@ enum Command {
     Bench(CommandShared<benches::Flags>),
     /// Run the designated script
     Run(CommandShared<run::Flags>),
+    /// Run the designated script in an interactive debugger
+    Debug(CommandShared<debug::Flags>),
     /// Format the provided file
     Fmt(CommandShared<format::Flags>),
     /// Run a language server.
     LanguageServer(SharedFlags),
+    /// Run an interactive read-eval-print loop.
+    Repl(SharedFlags),
     /// Helper command to generate type hashes.
     Hash(HashFlags),
 }
 
 impl Command {
-    const ALL: [&'static str; 9] = [
+    const ALL: [&'static str; 12] = [
         "check",
+        "build",
         "doc",
         "ace",
         "test",
         "bench",
         "run",
+        "debug",
         "fmt",
         "languageserver",
+        "repl",
         "hash",
     ];
 
     fn as_command_base_mut(&mut self) -> Option<(&mut SharedFlags, &mut dyn CommandBase)> {
         let (shared, command): (_, &mut dyn CommandBase) = match self {
             Command::Check(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Build(shared) => (&mut shared.shared, &mut shared.command),
             Command::Doc(shared) => (&mut shared.shared, &mut shared.command),
             Command::Ace(shared) => (&mut shared.shared, &mut shared.command),
             Command::Test(shared) => (&mut shared.shared, &mut shared.command),
             Command::Bench(shared) => (&mut shared.shared, &mut shared.command),
             Command::Run(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Debug(shared) => (&mut shared.shared, &mut shared.command),
             Command::Fmt(shared) => (&mut shared.shared, &mut shared.command),
             Command::LanguageServer(..) => return None,
+            Command::Repl(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -472,13 +507,16 @@ impl Command {
     fn as_command_shared_ref(&self) -> Option<CommandSharedRef<'_>> {
         let (shared, command): (_, &dyn CommandBase) = match self {
             Command::Check(shared) => (&shared.shared, &shared.command),
+            Command::Build(shared) => (&shared.shared, &shared.command),
             Command::Doc(shared) => (&shared.shared, &shared.command),
             Command::Ace(shared) => (&shared.shared, &shared.command),
             Command::Test(shared) => (&shared.shared, &shared.command),
             Command::Bench(shared) => (&shared.shared, &shared.command),
             Command::Run(shared) => (&shared.shared, &shared.command),
+            Command::Debug(shared) => (&shared.shared, &shared.command),
             Command::Fmt(shared) => (&shared.shared, &shared.command),
             Command::LanguageServer(..) => return None,
+            Command::Repl(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -850,6 +888,9 @@ async fn main_with_out(io: &mut Io<'_>, entry: &mut Entry<'_>, mut args: Args) -
 
     let mut entries = alloc::Vec::new();
 
+    // A prebuilt unit is run on its own, so there are no sources to find.
+    let prebuilt = matches!(cmd, Command::Run(f) if f.command.unit.is_some());
+
     if let Some(cmd) = cmd.as_command_shared_ref() {
         if cmd.shared.list_options {
             writeln!(
@@ -888,7 +929,9 @@ async fn main_with_out(io: &mut Io<'_>, entry: &mut Entry<'_>, mut args: Args) -
             return Ok(ExitCode::Success);
         }
 
-        populate_config(io, &mut c, &mut inputs, cmd)?;
+        if !prebuilt {
+            populate_config(io, &mut c, &mut inputs, cmd)?;
+        }
 
         let build_paths = inputs.build_paths(cmd, &mut c)?;
 
@@ -955,12 +998,16 @@ where
                     options.script = true;
                 }
 
-                match check::run(io, entry, c, &f.command, &f.shared, &options, e.path())? {
+                match check::run(io, entry, c, &f.command, &f.shared, &options, &e)? {
                     ExitCode::Success => (),
                     other => return Ok(other),
                 }
             }
         }
+        Command::Build(f) => {
+            let options = f.options()?;
+            return build::run(io, entry, c, &f.command, &f.shared, &options, entries);
+        }
         Command::Doc(f) => {
             let options = f.options()?;
             return doc::run(io, entry, c, &f.command, &f.shared, &options, entries);
@@ -999,7 +1046,7 @@ where
                     &context,
                     &f.shared,
                     &options,
-                    e.path(),
+                    &e,
                     visitor::Attribute::Bench,
                 )?;
 
@@ -1023,6 +1070,23 @@ where
             let options = f.options()?;
             let context = f.shared.context(entry, c, None)?;
 
+            if let Some(path) = &f.command.unit {
+                let bytes =
+                    fs::read(path).with_context(|| format!("reading unit: {}", path.display()))?;
+
+                let unit = Unit::decode_artifact(&bytes, &context.runtime()?)
+                    .with_context(|| format!("loading unit: {}", path.display()))?;
+
+                let entry = if unit.function(&Hash::type_hash(["main"])).is_some() {
+                    Hash::type_hash(["main"])
+                } else {
+                    Hash::EMPTY
+                };
+
+                let unit = Arc::try_new(unit)?;
+                return run::run(io, c, &f.command, &context, unit, &Sources::new(), entry).await;
+            }
+
             for e in entries {
                 let mut options = options.clone();
 
@@ -1035,7 +1099,7 @@ where
                     &context,
                     &f.shared,
                     &options,
-                    e.path(),
+                    &e,
                     visitor::Attribute::None,
                 )?;
 
@@ -1052,10 +1116,45 @@ where
                 }
             }
         }
+        Command::Debug(f) => {
+            let options = f.options()?;
+            let context = f.shared.context(entry, c, None)?;
+
+            for e in entries {
+                let mut options = options.clone();
+
+                if e.is_argument() {
+                    options.script = true;
+                }
+
+                let load = loader::load(
+                    io,
+                    &context,
+                    &f.shared,
+                    &options,
+                    &e,
+                    visitor::Attribute::None,
+                )?;
+
+                let entry = if e.is_argument() {
+                    Hash::EMPTY
+                } else {
+                    Hash::type_hash(["main"])
+                };
+
+                match debug::run(io, &f.command, &context, load.unit, &load.sources, entry).await? {
+                    ExitCode::Success => (),
+                    other => return Ok(other),
+                }
+            }
+        }
         Command::LanguageServer(shared) => {
             let context = shared.context(entry, c, None)?;
             languageserver::run(context).await?;
         }
+        Command::Repl(shared) => {
+            return repl::run(io, entry, c, shared).await;
+        }
         Command::Hash(args) => {
             use rand::prelude::*;
 
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::io::{self, Write};
+use std::string::String;
 
 use crate::termcolor::{self, ColorSpec, StandardStream, WriteColor};
 
@@ -43,15 +44,44 @@ impl Color {
     }
 }
 
+/// Input which is read one line at a time.
+pub(super) trait ReadLine {
+    /// Read a line into `buf`, returning the number of bytes read or zero once
+    /// the input has been exhausted.
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize>;
+}
+
+impl ReadLine for io::Stdin {
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        // Standard input is only locked while a line is being read, so that
+        // scripts can read from it as well.
+        io::Stdin::read_line(self, buf)
+    }
+}
+
+impl ReadLine for &[u8] {
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        io::BufRead::read_line(self, buf)
+    }
+}
+
 pub(super) struct Io<'io> {
+    pub(super) stdin: &'io mut dyn ReadLine,
     pub(super) stdout: &'io mut StandardStream,
     pub(super) stderr: &'io mut StandardStream,
     colors: Option<Colors>,
 }
 
 impl<'io> Io<'io> {
-    pub(super) fn new(stdout: &'io mut StandardStream, stderr: &'io mut StandardStream) -> Self {
+    pub(super) fn new(
+        stdin: &'io mut dyn ReadLine,
+        stdout: &'io mut StandardStream,
+        stderr: &'io mut StandardStream,
+    ) -> Self {
         Self {
+            stdin,
             stdout,
             stderr,
             colors: None,
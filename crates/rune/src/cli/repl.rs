@@ -0,0 +1,465 @@
+#[cfg(test)]
+mod tests;
+
+use anyhow::Result;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::ast::{self, Spanned};
+use crate::cli::{Config, Entry, ExitCode, Io, ReadLine, SharedFlags};
+use crate::runtime::{Inline, RuntimeContext};
+use crate::sync::Arc;
+use crate::termcolor::WriteColor;
+use crate::{Context, Diagnostics, Hash, ItemBuf, Options, Source, SourceId, Sources, Value, Vm};
+
+/// The name of the function which input is compiled into.
+const REPL_FN: &str = "__repl";
+
+const HELP: &str = "\
+Commands:
+  :type <expr>  Evaluate an expression and print its type
+  :doc <item>   Print the documentation of a native item, like `std::iter::range`
+  :help         Print this help
+  :quit         Exit the repl
+
+Bindings introduced with `let` and items such as `fn` and `struct` are kept
+between inputs. Input which ends in the middle of an expression continues on the
+next line, enter an empty line to evaluate it anyway.";
+
+pub(super) async fn run(
+    io: &mut Io<'_>,
+    entry: &mut Entry<'_>,
+    c: &Config,
+    shared: &SharedFlags,
+) -> Result<ExitCode> {
+    let mut options = Options::from_default_env()?;
+
+    for option in &shared.compiler_option {
+        options.parse_option(option)?;
+    }
+
+    let context = shared.context(entry, c, None)?;
+    let mut repl = Repl::new(&context, &options, shared.warnings)?;
+    repl.run(io.stdin, io.stdout).await?;
+    Ok(ExitCode::Success)
+}
+
+/// What to output after evaluating an input.
+#[derive(Clone, Copy)]
+enum Output {
+    /// Output the value.
+    Value,
+    /// Output the type of the value.
+    Type,
+}
+
+/// An item declared in the repl.
+#[derive(TryClone)]
+struct Item {
+    /// The name of the item, if it has one. Items with the same name replace
+    /// each other.
+    name: Option<String>,
+    /// The source of the item.
+    source: String,
+}
+
+/// An input which has been split up into its components.
+#[derive(Default)]
+struct Input {
+    /// Items declared in the input.
+    items: Vec<Item>,
+    /// Names of the bindings declared in the input.
+    bindings: Vec<String>,
+    /// Statements to evaluate.
+    body: String,
+    /// The trailing expression which is the value of the input.
+    value: Option<String>,
+}
+
+struct Repl<'a> {
+    context: &'a Context,
+    runtime: Arc<RuntimeContext>,
+    options: &'a Options,
+    warnings: bool,
+    /// Items declared so far.
+    items: Vec<Item>,
+    /// Bindings declared so far.
+    bindings: Vec<(String, Value)>,
+}
+
+impl<'a> Repl<'a> {
+    fn new(context: &'a Context, options: &'a Options, warnings: bool) -> Result<Self> {
+        Ok(Self {
+            context,
+            runtime: Arc::try_new(context.runtime()?)?,
+            options,
+            warnings,
+            items: Vec::new(),
+            bindings: Vec::new(),
+        })
+    }
+
+    /// Read and evaluate input until it is exhausted or the user quits.
+    async fn run<O>(&mut self, stdin: &mut dyn ReadLine, o: &mut O) -> Result<()>
+    where
+        O: WriteColor,
+    {
+        writeln!(o, "Type `:help` for help, and `:quit` to exit")?;
+
+        let mut buffer = std::string::String::new();
+
+        loop {
+            write!(o, "{}", if buffer.is_empty() { "> " } else { ". " })?;
+            o.flush()?;
+
+            let mut line = std::string::String::new();
+
+            if stdin.read_line(&mut line)? == 0 {
+                writeln!(o)?;
+                break;
+            }
+
+            if buffer.is_empty() {
+                let line = line.trim();
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                if let Some(command) = line.strip_prefix(':') {
+                    let (command, rest) = command.split_once(' ').unwrap_or((command, ""));
+
+                    match command {
+                        "q" | "quit" => break,
+                        "h" | "help" => {
+                            writeln!(o, "{HELP}")?;
+                        }
+                        "t" | "type" => {
+                            self.eval(o, rest, true, Output::Type).await?;
+                        }
+                        "d" | "doc" => {
+                            self.doc(o, rest.trim())?;
+                        }
+                        _ => {
+                            writeln!(o, "Unknown command `:{command}`, try `:help`")?;
+                        }
+                    }
+
+                    continue;
+                }
+            }
+
+            let force = !buffer.is_empty() && line.trim().is_empty();
+            buffer.push_str(&line);
+
+            if self.eval(o, &buffer, force, Output::Value).await? {
+                buffer.clear();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the given input, returning `false` if more input is needed.
+    async fn eval<O>(&mut self, o: &mut O, input: &str, force: bool, output: Output) -> Result<bool>
+    where
+        O: WriteColor,
+    {
+        let input = match parse(input) {
+            Ok(input) => input,
+            Err((_, true)) if !force => return Ok(false),
+            Err((error, _)) => {
+                let mut sources = Sources::new();
+                let source_id = sources.insert(Source::new("<repl>", wrap(input))?)?;
+
+                let mut diagnostics = Diagnostics::new();
+                diagnostics.error(source_id, error)?;
+                diagnostics.emit(o, &sources)?;
+                return Ok(true);
+            }
+        };
+
+        let mut items = self.items.try_clone()?;
+
+        for item in input.items {
+            match items
+                .iter_mut()
+                .find(|i| i.name.is_some() && i.name == item.name)
+            {
+                Some(existing) => *existing = item,
+                None => items.try_push(item)?,
+            }
+        }
+
+        let mut names = self
+            .bindings
+            .iter()
+            .map(|(name, _)| name.try_clone())
+            .try_collect::<crate::alloc::Result<Vec<_>>>()??;
+
+        for name in input.bindings {
+            if !names.contains(&name) {
+                names.try_push(name)?;
+            }
+        }
+
+        let mut source = String::new();
+
+        for item in &items {
+            source.try_push_str(&item.source)?;
+            source.try_push('\n')?;
+        }
+
+        let arguments = self.bindings.iter().map(|(name, _)| name.as_str());
+        let arguments: String = arguments.try_join(", ")?;
+        let returned: String = names.iter().map(|name| name.as_str()).try_join(", ")?;
+        let value = input.value.as_deref().unwrap_or("()");
+
+        source.try_push_str(&format!(
+            "pub async fn {REPL_FN}({arguments}) {{\n{}\nlet __value = {value};\n(__value, [{returned}])\n}}\n",
+            input.body
+        ))?;
+
+        let mut sources = Sources::new();
+        sources.insert(Source::new("<repl>", &source)?)?;
+
+        let mut diagnostics = if self.warnings {
+            Diagnostics::new()
+        } else {
+            Diagnostics::without_warnings()
+        };
+
+        let unit = crate::prepare(&mut sources)
+            .with_context(self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(self.options)
+            .build();
+
+        if !diagnostics.is_empty() {
+            diagnostics.emit(o, &sources)?;
+        }
+
+        let Ok(unit) = unit else {
+            return Ok(true);
+        };
+
+        self.items = items;
+
+        let arguments = self
+            .bindings
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect::<std::vec::Vec<_>>();
+
+        let mut vm = Vm::new(self.runtime.clone(), Arc::try_new(unit)?);
+
+        let result = vm
+            .async_call(Hash::type_hash([REPL_FN]), arguments)
+            .await
+            .and_then(|value| Ok(crate::from_value::<(Value, std::vec::Vec<Value>)>(value)?));
+
+        let (value, values) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                error.emit(o, &sources)?;
+                return Ok(true);
+            }
+        };
+
+        match output {
+            Output::Value => {
+                if !matches!(value.as_inline(), Some(Inline::Unit)) {
+                    vm.with(|| writeln!(o, "{value:?}"))?;
+                }
+
+                self.bindings = names.into_iter().zip(values).try_collect()?;
+            }
+            Output::Type => {
+                writeln!(o, "{}", value.type_info())?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Print the documentation of the given native item.
+    fn doc<O>(&self, o: &mut O, item: &str) -> Result<()>
+    where
+        O: WriteColor,
+    {
+        let item = match item.parse::<ItemBuf>() {
+            Ok(item) => item,
+            Err(error) => {
+                writeln!(o, "Bad item `{item}`: {error}")?;
+                return Ok(());
+            }
+        };
+
+        let Some(metas) = self.context.lookup_meta(&item) else {
+            writeln!(o, "No native item `{item}`")?;
+            return Ok(());
+        };
+
+        for meta in metas {
+            if let Some(signature) = meta.kind.as_signature() {
+                write!(o, "fn {item}({})", meta.docs.args().join(", "))?;
+
+                let return_type = signature
+                    .return_type
+                    .base
+                    .as_non_empty()
+                    .and_then(|hash| self.context.lookup_meta_by_hash(hash.get()).next())
+                    .and_then(|meta| meta.item.as_deref());
+
+                if let Some(return_type) = return_type {
+                    write!(o, " -> {return_type}")?;
+                }
+
+                writeln!(o)?;
+            } else {
+                writeln!(o, "{item}")?;
+            }
+
+            for line in meta.docs.lines() {
+                writeln!(o, "   {line}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Wrap input in a block so that it can be parsed as a sequence of statements.
+fn wrap(input: &str) -> std::string::String {
+    format!("{{{input}\n}}")
+}
+
+/// Parse the given input.
+///
+/// On errors, the returned flag indicates if the input is incomplete and more
+/// of it should be read.
+fn parse(input: &str) -> Result<Input, (crate::compile::Error, bool)> {
+    let text = wrap(input);
+
+    let error = match crate::parse::parse_all::<ast::Block>(&text, SourceId::EMPTY, false) {
+        Ok(block) => return split(&text, &block).map_err(|e| (e.into(), false)),
+        Err(error) => error,
+    };
+
+    if error.span().end.into_usize() < text.len() {
+        return Err((error, false));
+    }
+
+    if is_unbalanced(input) {
+        return Err((error, true));
+    }
+
+    // Permit leaving out the trailing semi-colon of a `let` binding.
+    let text = wrap(&format!("{};", input.trim_end()));
+
+    match crate::parse::parse_all::<ast::Block>(&text, SourceId::EMPTY, false) {
+        Ok(block) => split(&text, &block).map_err(|e| (e.into(), false)),
+        Err(..) => Err((error, false)),
+    }
+}
+
+/// Test if the input has more opening than closing delimiters.
+fn is_unbalanced(input: &str) -> bool {
+    let mut depth = 0isize;
+
+    for c in input.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth > 0
+}
+
+/// Split a parsed block into the components of an input.
+fn split(text: &str, block: &ast::Block) -> crate::alloc::Result<Input> {
+    let mut input = Input::default();
+    let mut it = block.statements.iter().peekable();
+
+    while let Some(stmt) = it.next() {
+        let source = &text[stmt.span().range()];
+
+        match stmt {
+            ast::Stmt::Item(item, semi) => {
+                let name = match item {
+                    ast::Item::Fn(item) => Some(&item.name),
+                    ast::Item::Enum(item) => Some(&item.name),
+                    ast::Item::Struct(item) => Some(&item.ident),
+                    ast::Item::Mod(item) => Some(&item.name),
+                    ast::Item::Const(item) => Some(&item.name),
+                    ast::Item::Trait(item) => Some(&item.name),
+                    _ => None,
+                };
+
+                let name = match name {
+                    Some(name) => Some(text[name.span().range()].try_to_owned()?),
+                    None => None,
+                };
+
+                let mut source = text[item.span().range()].try_to_owned()?;
+
+                if semi.is_some() {
+                    source.try_push(';')?;
+                }
+
+                input.items.try_push(Item { name, source })?;
+            }
+            ast::Stmt::Expr(..) if it.peek().is_none() => {
+                input.value = Some(source.try_to_owned()?);
+            }
+            stmt => {
+                if let ast::Stmt::Local(local) = stmt {
+                    bindings(text, &local.pat, &mut input.bindings)?;
+                }
+
+                input.body.try_push_str(source)?;
+                input.body.try_push('\n')?;
+            }
+        }
+    }
+
+    Ok(input)
+}
+
+/// Collect the names of the variables bound by a pattern.
+fn bindings(text: &str, pat: &ast::Pat, out: &mut Vec<String>) -> crate::alloc::Result<()> {
+    match pat {
+        ast::Pat::Path(pat) => {
+            if let Some(ident) = pat.path.try_as_ident() {
+                let name = text[ident.span().range()].try_to_owned()?;
+
+                if !out.contains(&name) {
+                    out.try_push(name)?;
+                }
+            }
+        }
+        ast::Pat::Vec(pat) => {
+            for (pat, _) in pat.items.iter() {
+                bindings(text, pat, out)?;
+            }
+        }
+        ast::Pat::Tuple(pat) => {
+            for (pat, _) in pat.items.iter() {
+                bindings(text, pat, out)?;
+            }
+        }
+        ast::Pat::Object(pat) => {
+            for (pat, _) in pat.items.iter() {
+                bindings(text, pat, out)?;
+            }
+        }
+        ast::Pat::Binding(pat) => {
+            bindings(text, &pat.pat, out)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::termcolor::Buffer;
+use crate::{Context, Options};
+
+use super::{parse, Repl};
+
+/// Run the repl over the given input and collect its output.
+async fn repl(input: &str) -> anyhow::Result<std::string::String> {
+    let context = Context::with_default_modules()?;
+    let options = Options::default();
+    let mut repl = Repl::new(&context, &options, false)?;
+
+    let mut stdin = input.as_bytes();
+    let mut out = Buffer::no_color();
+    repl.run(&mut stdin, &mut out).await?;
+    Ok(std::string::String::from_utf8(out.into_inner())?)
+}
+
+#[test]
+fn test_parse() {
+    let input = parse("let (a, [b, c]) = value; fn f() {} f()").unwrap();
+    assert_eq!(input.bindings, ["a", "b", "c"]);
+    assert_eq!(input.items.len(), 1);
+    assert_eq!(input.items[0].name.as_deref(), Some("f"));
+    assert_eq!(input.value.as_deref(), Some("f()"));
+
+    // The trailing semi-colon of a binding can be left out.
+    let input = parse("let a = 1").unwrap();
+    assert_eq!(input.bindings, ["a"]);
+    assert!(input.value.is_none());
+
+    assert!(matches!(parse("fn f() {"), Err((_, true))));
+    assert!(matches!(parse("[1, 2"), Err((_, true))));
+    assert!(matches!(parse("let = 1;"), Err((_, false))));
+    assert!(matches!(parse("}"), Err((_, false))));
+}
+
+#[tokio::test]
+async fn test_repl() -> anyhow::Result<()> {
+    let output = repl(
+        "\
+let a = 1
+a + 1
+fn add(a, b) {
+    a + b
+}
+add(a, 2)
+let a = a * 10;
+a
+:quit
+a
+",
+    )
+    .await?;
+
+    assert_eq!(
+        output,
+        "\
+Type `:help` for help, and `:quit` to exit
+> > 2
+> . . > 3
+> > 10
+> "
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repl_commands() -> anyhow::Result<()> {
+    let output = repl(":type 1 + 2\n:what\n").await?;
+
+    assert_eq!(
+        output,
+        "\
+Type `:help` for help, and `:quit` to exit
+> ::std::i64
+> Unknown command `:what`, try `:help`
+> \n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repl_errors() -> anyhow::Result<()> {
+    let output = repl("let a = 1\nmissing\nfn f() {\n\na\n").await?;
+
+    assert!(output.contains("missing"), "{output}");
+    assert!(output.ends_with("> 1\n> \n"), "{output}");
+    Ok(())
+}
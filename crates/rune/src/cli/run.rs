@@ -5,12 +5,15 @@ use std::time::Instant;
 use anyhow::{anyhow, Result};
 
 use crate::cli::{AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
-use crate::runtime::{UnitStorage, VmError, VmExecution, VmOutcome};
+use crate::runtime::{Profiler, UnitStorage, VmError, VmExecution, VmOutcome};
 use crate::sync::Arc;
 use crate::{Context, Hash, Sources, Unit, Value, Vm};
 
+mod profile;
+
 mod cli {
     use std::path::PathBuf;
+    use std::string::String;
     use std::vec::Vec;
 
     use clap::Parser;
@@ -67,6 +70,19 @@ mod cli {
         /// implies `--trace`.
         #[arg(long)]
         pub(super) trace_limit: Option<usize>,
+        /// Profile the script, writing folded stacks which can be rendered as
+        /// a flamegraph and printing the functions which took the most time.
+        #[arg(long)]
+        pub(super) profile: bool,
+        /// The path to write folded stacks to when profiling.
+        #[arg(long, default_value = "rune.folded")]
+        pub(super) profile_output: PathBuf,
+        /// The number of functions to print when profiling.
+        #[arg(long, default_value_t = 10)]
+        pub(super) profile_top: usize,
+        /// Run a unit built with `rune build` instead of compiling sources.
+        #[arg(long, conflicts_with = "run_path")]
+        pub(crate) unit: Option<PathBuf>,
         /// Explicit paths to run.
         pub(super) run_path: Vec<PathBuf>,
     }
@@ -247,7 +263,15 @@ pub(super) async fn run(
 
     let last = Instant::now();
 
+    let profiler = if args.profile {
+        Some(Arc::try_new(Profiler::new())?)
+    } else {
+        None
+    };
+
     let mut vm = Vm::new(runtime, unit);
+    vm.set_profiler(profiler.clone());
+
     let mut execution: VmExecution<_> = vm.execute(entry, ())?;
 
     let result = if args.trace {
@@ -295,6 +319,19 @@ pub(super) async fn run(
         }
     };
 
+    if let Some(profiler) = &profiler {
+        let profile = profiler.profile()?;
+        profile::write_folded(context, &profile, &args.profile_output)?;
+
+        writeln!(
+            io.stdout,
+            "# profile (folded stacks written to {})",
+            args.profile_output.display()
+        )?;
+
+        profile::write_top(io.stdout, context, &profile, args.profile_top)?;
+    }
+
     let exit = if let Some(error) = errored {
         error.emit(io.stdout, sources)?;
         ExitCode::VmError
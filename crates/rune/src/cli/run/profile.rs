@@ -0,0 +1,138 @@
+//! Reporting of profiles collected with `--profile`.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::string::{String, ToString};
+use std::time::Duration;
+use std::vec::Vec;
+
+use anyhow::{Context as _, Result};
+
+use crate::runtime::{Profile, ProfileFrame};
+use crate::Context;
+
+#[derive(Default)]
+struct FunctionStats {
+    self_time: Duration,
+    total_time: Duration,
+    instructions: u64,
+}
+
+/// Write the folded stacks of a profile to `output`, weighted by the number
+/// of nanoseconds spent in each stack.
+pub(super) fn write_folded(context: &Context, profile: &Profile, output: &Path) -> Result<()> {
+    let names = names(context, profile);
+
+    let f = File::create(output).with_context(|| output.display().to_string())?;
+    let mut out = BufWriter::new(f);
+
+    for stack in &profile.stacks {
+        let nanos = stack.time.as_nanos();
+
+        if nanos == 0 || stack.frames.is_empty() {
+            continue;
+        }
+
+        let mut first = true;
+
+        for &frame in &stack.frames {
+            if !first {
+                write!(out, ";")?;
+            }
+
+            first = false;
+            write!(out, "{}", names[frame])?;
+        }
+
+        writeln!(out, " {nanos}")?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Write a table of the `top` functions with the most time spent in them.
+pub(super) fn write_top(
+    out: &mut dyn Write,
+    context: &Context,
+    profile: &Profile,
+    top: usize,
+) -> Result<()> {
+    let names = names(context, profile);
+    let mut functions = HashMap::<&str, FunctionStats>::new();
+
+    for stack in &profile.stacks {
+        let Some(&last) = stack.frames.last() else {
+            continue;
+        };
+
+        let stats = functions.entry(names[last].as_str()).or_default();
+        stats.self_time += stack.time;
+        stats.instructions += stack.instructions;
+
+        let mut seen = Vec::new();
+
+        for &frame in &stack.frames {
+            let name = names[frame].as_str();
+
+            // Recursive functions only count the time of a stack once.
+            if seen.contains(&name) {
+                continue;
+            }
+
+            seen.push(name);
+            functions.entry(name).or_default().total_time += stack.time;
+        }
+    }
+
+    let mut functions = functions.into_iter().collect::<Vec<_>>();
+    functions.sort_by(|a, b| b.1.self_time.cmp(&a.1.self_time).then(a.0.cmp(b.0)));
+
+    writeln!(
+        out,
+        "{:>12} {:>12} {:>14}  function",
+        "self", "total", "instructions"
+    )?;
+
+    for (name, stats) in functions.into_iter().take(top) {
+        writeln!(
+            out,
+            "{:>10.3}ms {:>10.3}ms {:>14}  {name}",
+            stats.self_time.as_secs_f64() * 1000.0,
+            stats.total_time.as_secs_f64() * 1000.0,
+            stats.instructions
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Resolve display names for every frame in the profile.
+fn names(context: &Context, profile: &Profile) -> Vec<String> {
+    let mut names = Vec::with_capacity(profile.frames.len());
+
+    for frame in &profile.frames {
+        let name = match frame {
+            ProfileFrame::Function(item) if item.is_empty() => String::from("<root>"),
+            ProfileFrame::Function(item) => item.to_string(),
+            ProfileFrame::Native(hash) => {
+                let item = context
+                    .lookup_meta_by_hash(*hash)
+                    .find_map(|meta| meta.item.as_ref());
+
+                match item {
+                    Some(item) => item.to_string(),
+                    None => format!("<native {hash}>"),
+                }
+            }
+            _ => String::from("<unknown>"),
+        };
+
+        // Semicolons separate frames in the folded format.
+        names.push(name.replace(';', ":"));
+    }
+
+    names
+}
@@ -1,11 +1,18 @@
+use std::collections::BTreeMap;
 use std::fmt;
-use std::io::Write;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::mem::take;
+use std::path::PathBuf;
 use std::slice;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 
+use crate::alloc;
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::cli::naming::Naming;
@@ -17,15 +24,32 @@ use crate::cli::{
 use crate::compile::FileSourceLoader;
 use crate::doc::{TestKind, TestParams};
 use crate::modules::capture_io::CaptureIo;
-use crate::runtime::{Repr, Value, Vm, VmError, VmOutcome};
+use crate::runtime::{Coverage, Repr, RuntimeContext, Value, Vm, VmOutcome};
 use crate::sync::Arc;
+use crate::termcolor::{Buffer, WriteColor};
 use crate::{Diagnostics, Hash, Item, ItemBuf, Source, Sources, TypeHash, Unit};
 
+mod coverage;
+mod report;
+
+#[cfg(test)]
+mod tests;
+
 mod cli {
+    use std::path::PathBuf;
     use std::string::String;
     use std::vec::Vec;
 
-    use clap::Parser;
+    use clap::{Parser, ValueEnum};
+
+    /// The format of a machine-readable test report.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum Format {
+        /// A JSON document.
+        Json,
+        /// A JUnit XML document.
+        Junit,
+    }
 
     #[derive(Parser, Debug, Clone)]
     #[command(rename_all = "kebab-case")]
@@ -39,10 +63,28 @@ mod cli {
         /// Break on the first test failed.
         #[arg(long)]
         pub fail_fast: bool,
+        /// The number of threads to run tests on.
+        #[arg(long, short = 'j', default_value_t = 1)]
+        pub jobs: usize,
         /// Skip building dynamic lib tests from entrypoints. This means only
         /// tests found in runtime contexts will be run.
         #[arg(long)]
         pub skip_lib_tests: bool,
+        /// Write a machine-readable report of the executed tests in the given
+        /// format to the path specified by `--output`.
+        #[arg(long, requires = "output")]
+        pub format: Option<Format>,
+        /// The path to write the test report to.
+        #[arg(long, short = 'o', requires = "format")]
+        pub output: Option<PathBuf>,
+        /// Record which lines of the tested scripts are executed, writing an
+        /// lcov report and an HTML summary to the coverage directory.
+        #[arg(long)]
+        pub coverage: bool,
+        /// The directory to write coverage reports to. Defaults to
+        /// `target/rune-coverage`.
+        #[arg(long, requires = "coverage")]
+        pub coverage_dir: Option<PathBuf>,
         /// Filter tests by name.
         pub filters: Vec<String>,
     }
@@ -109,7 +151,6 @@ where
 {
     let start = Instant::now();
 
-    let mut executed = 0usize;
     let mut skipped = 0usize;
     let mut build_errors = 0usize;
     let mut skipped_entries = 0usize;
@@ -159,6 +200,7 @@ where
         };
 
         sources.insert(source)?;
+        e.insert_dependencies(&mut sources)?;
 
         let mut diagnostics = if shared.warnings || flags.warnings_are_errors {
             Diagnostics::new()
@@ -262,7 +304,51 @@ where
     }
 
     let runtime = Arc::try_new(context.runtime()?)?;
-    let mut failed = Vec::new();
+    let color = io.stdout.supports_color();
+
+    let mut covered = std::vec::Vec::new();
+
+    let coverage = if flags.coverage {
+        let mut coverage = Coverage::new();
+
+        for case in batches.iter().flat_map(|b| &b.cases) {
+            if covered
+                .iter()
+                .any(|(unit, _)| Arc::ptr_eq(unit, &case.unit))
+            {
+                continue;
+            }
+
+            coverage.track(case.unit.clone())?;
+            covered.push((case.unit.clone(), case.sources.clone()));
+        }
+
+        Some(Arc::try_new(coverage)?)
+    } else {
+        None
+    };
+
+    let mut workers = Vec::new();
+
+    if flags.jobs > 1 {
+        for _ in 0..flags.jobs {
+            let capture = CaptureIo::new();
+            let context = shared.context(entry, c, Some(&capture))?;
+            let runtime = Arc::try_new(context.runtime()?)?;
+
+            workers.try_push(Worker {
+                runtime,
+                capture,
+                coverage: coverage.clone(),
+            })?;
+        }
+    }
+
+    let mut report = Report {
+        executed: 0,
+        failed: Vec::new(),
+        suites: Vec::new(),
+    };
 
     for batch in batches {
         if batch.cases.is_empty() {
@@ -290,7 +376,7 @@ where
             if !flags.quiet && !all_ignored {
                 section.append(format_args!(" {} {}", batch.cases.len(), batch.kind))?;
 
-                if let Some(entry) = batch.entry {
+                if let Some(entry) = &batch.entry {
                     section.append(format_args!(" from {entry}"))?;
                 }
             }
@@ -298,7 +384,20 @@ where
             section.close()?;
         }
 
-        for mut case in batch.cases {
+        let mut name = batch.kind.try_to_string()?;
+
+        if let Some(entry) = &batch.entry {
+            write!(name, " from {entry}")?;
+        }
+
+        report.suites.try_push(report::Suite {
+            name,
+            cases: Vec::new(),
+        })?;
+
+        let mut cases = Vec::new();
+
+        for case in batch.cases {
             if case.filtered {
                 skipped = skipped.wrapping_add(1);
                 continue;
@@ -308,30 +407,38 @@ where
                 continue;
             }
 
-            let mut vm = Vm::new(runtime.clone(), case.unit.clone());
-            case.execute(&mut vm, &capture).await?;
-            executed = executed.wrapping_add(1);
-
-            if case.outcome.is_ok() {
-                if flags.quiet {
-                    write!(io.stdout, ".")?;
-                } else {
-                    case.emit(io)?;
-                }
+            cases.try_push(case)?;
+        }
 
-                continue;
-            }
+        if workers.is_empty() {
+            for mut case in cases {
+                let mut vm = Vm::new(runtime.clone(), case.unit.clone());
+                vm.set_coverage(coverage.clone());
+                case.execute(&mut vm, &capture, color).await?;
 
-            if flags.quiet {
-                write!(io.stdout, "f")?;
+                if !report.case(io, flags, case)? {
+                    break;
+                }
             }
+        } else {
+            execute_parallel(&workers, cases, color, |case| report.case(io, flags, case))?;
+        }
+    }
 
-            failed.try_push(case)?;
+    let Report {
+        executed,
+        failed,
+        suites,
+    } = report;
 
-            if flags.fail_fast {
-                break;
-            }
-        }
+    if let (Some(format), Some(output)) = (flags.format, &flags.output) {
+        let f = match File::create(output) {
+            Ok(f) => f,
+            Err(error) => return Err(error).context(output.display().try_to_string()?),
+        };
+        let mut out = BufWriter::new(f);
+        report::write(&mut out, format, &suites)?;
+        out.flush()?;
     }
 
     if flags.quiet {
@@ -392,6 +499,31 @@ where
 
     writeln!(io.stdout, " in {:.3} seconds", elapsed.as_secs_f64())?;
 
+    if let Some(recorded) = &coverage {
+        let dir = match &flags.coverage_dir {
+            Some(dir) => dir.clone(),
+            None => match &c.manifest_root {
+                Some(path) => path.join("target").join("rune-coverage"),
+                None => match std::env::var_os("CARGO_TARGET_DIR") {
+                    Some(target) => PathBuf::from(target).join("rune-coverage"),
+                    None => PathBuf::from("target").join("rune-coverage"),
+                },
+            },
+        };
+
+        let summary = coverage::write(&dir, recorded, &covered)?;
+
+        let mut section = io.section("Coverage", Stream::Stdout, Color::Highlight)?;
+        section.append(format_args!(
+            " {}/{} lines ({:.1}%) written to {}",
+            summary.hit,
+            summary.found,
+            coverage::percent(summary.hit, summary.found),
+            dir.display()
+        ))?;
+        section.close()?;
+    }
+
     if build_errors == 0 && failures == 0 {
         Ok(ExitCode::Success)
     } else {
@@ -399,6 +531,142 @@ where
     }
 }
 
+/// The state of a thread used to run tests in parallel.
+struct Worker {
+    runtime: Arc<RuntimeContext>,
+    capture: CaptureIo,
+    coverage: Option<Arc<Coverage>>,
+}
+
+impl Worker {
+    fn run(
+        &self,
+        queue: &Mutex<impl Iterator<Item = (usize, TestCase)>>,
+        stop: &AtomicBool,
+        tx: mpsc::Sender<Result<(usize, TestCase)>>,
+        color: bool,
+    ) {
+        let rt = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(rt) => rt,
+            Err(error) => {
+                _ = tx.send(Err(error.into()));
+                return;
+            }
+        };
+
+        while !stop.load(Ordering::Acquire) {
+            let Some((index, mut case)) = queue.lock().ok().and_then(|mut q| q.next()) else {
+                break;
+            };
+
+            let mut vm = Vm::new(self.runtime.clone(), case.unit.clone());
+            vm.set_coverage(self.coverage.clone());
+            let result = rt.block_on(case.execute(&mut vm, &self.capture, color));
+
+            if tx.send(result.map(|()| (index, case))).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Execute the given cases on the specified workers, reporting them in the
+/// order in which they were provided.
+///
+/// Reporting stops once `report` returns `false`.
+fn execute_parallel(
+    workers: &[Worker],
+    cases: Vec<TestCase>,
+    color: bool,
+    mut report: impl FnMut(TestCase) -> Result<bool>,
+) -> Result<()> {
+    let queue = Mutex::new(cases.into_iter().enumerate());
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for worker in workers {
+            let tx = tx.clone();
+            let queue = &queue;
+            let stop = &stop;
+            s.spawn(move || worker.run(queue, stop, tx, color));
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0usize;
+
+        let result = 'done: {
+            for result in &rx {
+                let (index, case) = match result {
+                    Ok(result) => result,
+                    Err(error) => break 'done Err(error),
+                };
+
+                pending.insert(index, case);
+
+                while let Some(case) = pending.remove(&next) {
+                    next = next.wrapping_add(1);
+
+                    match report(case) {
+                        Ok(true) => {}
+                        Ok(false) => break 'done Ok(()),
+                        Err(error) => break 'done Err(error),
+                    }
+                }
+            }
+
+            Ok(())
+        };
+
+        stop.store(true, Ordering::Release);
+        result
+    })
+}
+
+/// Collected results from executed tests.
+struct Report {
+    executed: usize,
+    failed: Vec<TestCase>,
+    /// Suites recorded for the machine-readable report.
+    suites: Vec<report::Suite>,
+}
+
+impl Report {
+    /// Report a single executed test case, returning `false` if no more tests
+    /// should be run.
+    fn case(&mut self, io: &mut Io<'_>, flags: &Flags, case: TestCase) -> Result<bool> {
+        self.executed = self.executed.wrapping_add(1);
+
+        if flags.format.is_some() {
+            if let Some(suite) = self.suites.last_mut() {
+                suite.cases.try_push(case.to_report()?)?;
+            }
+        }
+
+        if case.outcome.is_ok() {
+            if flags.quiet {
+                write!(io.stdout, ".")?;
+            } else {
+                case.emit(io)?;
+            }
+
+            return Ok(true);
+        }
+
+        if flags.quiet {
+            write!(io.stdout, "f")?;
+        }
+
+        self.failed.try_push(case)?;
+        Ok(!flags.fail_fast)
+    }
+}
+
 fn populate_doc_tests(
     io: &mut Io,
     artifacts: crate::doc::Artifacts,
@@ -478,10 +746,19 @@ fn populate_doc_tests(
 #[derive(Debug)]
 enum Outcome {
     Ok,
-    Panic(VmError),
+    /// The test panicked.
+    Panic {
+        /// The error message.
+        message: String,
+        /// Where the error was raised, if known.
+        location: Option<report::Location>,
+        /// The rendered diagnostics of the error.
+        rendered: Vec<u8>,
+    },
     ExpectedPanic,
     None,
-    Err(Value),
+    /// The test returned an error, with the debug representation of the error.
+    Err(String),
 }
 
 impl Outcome {
@@ -499,6 +776,7 @@ struct TestCase {
     params: TestParams,
     outcome: Outcome,
     output: Vec<u8>,
+    duration: Duration,
     filtered: bool,
 }
 
@@ -521,16 +799,21 @@ impl TestCase {
             params,
             outcome: Outcome::Ok,
             output: Vec::new(),
+            duration: Duration::ZERO,
             filtered,
         }
     }
 
-    async fn execute(&mut self, vm: &mut Vm, capture_io: &CaptureIo) -> Result<()> {
+    async fn execute(&mut self, vm: &mut Vm, capture_io: &CaptureIo, color: bool) -> Result<()> {
+        let start = Instant::now();
+
         let result = match vm.execute(self.hash, ()) {
             Ok(mut execution) => execution.resume().await.and_then(VmOutcome::into_complete),
             Err(err) => Err(err),
         };
 
+        self.duration = start.elapsed();
+
         capture_io.drain_into(&mut self.output)?;
 
         self.outcome = match result {
@@ -541,7 +824,7 @@ impl TestCase {
 
                         match &*result {
                             Ok(..) => Outcome::Ok,
-                            Err(error) => Outcome::Err(error.clone()),
+                            Err(error) => Outcome::Err(try_format!("{error:?}")),
                         }
                     }
                     Option::<Value>::HASH => {
@@ -556,11 +839,30 @@ impl TestCase {
                 },
                 _ => Outcome::Ok,
             },
-            Err(e) => Outcome::Panic(e),
+            Err(error) => {
+                let mut buffer = if color {
+                    Buffer::ansi()
+                } else {
+                    Buffer::no_color()
+                };
+
+                error.emit(&mut buffer, &self.sources)?;
+
+                let location = match error.first_location() {
+                    Some(l) => self.find_location(&l.unit, l.ip)?,
+                    None => None,
+                };
+
+                Outcome::Panic {
+                    message: try_format!("{error}"),
+                    location,
+                    rendered: Vec::try_from(buffer.into_inner())?,
+                }
+            }
         };
 
         if self.params.should_panic {
-            if matches!(self.outcome, Outcome::Panic(..)) {
+            if matches!(self.outcome, Outcome::Panic { .. }) {
                 self.outcome = Outcome::Ok;
             } else {
                 self.outcome = Outcome::ExpectedPanic;
@@ -570,6 +872,75 @@ impl TestCase {
         Ok(())
     }
 
+    /// Find the source location of the instruction at `ip` in `unit`.
+    fn find_location(&self, unit: &Unit, ip: usize) -> alloc::Result<Option<report::Location>> {
+        let Some(inst) = unit.debug_info().and_then(|d| d.instruction_at(ip)) else {
+            return Ok(None);
+        };
+
+        let Some(source) = self.sources.get(inst.source_id) else {
+            return Ok(None);
+        };
+
+        let (line, column) = source.find_line_column(inst.span.start.into_usize());
+
+        let path = match source.path() {
+            Some(path) => try_format!("{}", path.display()),
+            None => source.name().try_to_owned()?,
+        };
+
+        Ok(Some(report::Location {
+            path,
+            line: line + 1,
+            column: column + 1,
+        }))
+    }
+
+    /// Construct the entry for this test in a machine-readable report.
+    fn to_report(&self) -> alloc::Result<report::Case> {
+        let name = match self.kind {
+            TestKind::Free => self.item.try_to_string()?,
+            TestKind::Protocol(protocol) => try_format!("{} {}", self.item, protocol.name),
+        };
+
+        let (status, message, location) = match &self.outcome {
+            Outcome::Ok => (report::Status::Passed, None, None),
+            Outcome::Panic {
+                message, location, ..
+            } => (
+                report::Status::Failed,
+                Some(message.try_clone()?),
+                location.try_clone()?,
+            ),
+            Outcome::ExpectedPanic => (
+                report::Status::Failed,
+                Some(String::try_from(
+                    "expected panic because of `should_panic`, but ran without issue",
+                )?),
+                None,
+            ),
+            Outcome::None => (
+                report::Status::Failed,
+                Some(String::try_from("returned none")?),
+                None,
+            ),
+            Outcome::Err(error) => (
+                report::Status::Failed,
+                Some(try_format!("err: {error}")),
+                None,
+            ),
+        };
+
+        Ok(report::Case {
+            name,
+            status,
+            duration: self.duration,
+            output: String::try_from(std::string::String::from_utf8_lossy(&self.output).as_ref())?,
+            message,
+            location,
+        })
+    }
+
     fn emit(self, io: &mut Io<'_>) -> Result<()> {
         let mut section = io.section("Test", Stream::Stdout, Color::Highlight)?;
 
@@ -586,16 +957,16 @@ impl TestCase {
         let mut emitted = None;
 
         match &self.outcome {
-            Outcome::Panic(error) => {
+            Outcome::Panic { rendered, .. } => {
                 section.error("errored")?;
-                emitted = Some(error);
+                emitted = Some(rendered);
             }
             Outcome::ExpectedPanic => {
                 section.error("expected panic because of `should_panic`, but ran without issue")?;
             }
             Outcome::Err(error) => {
                 section.error("err: ")?;
-                section.append(format_args!("{error:?}"))?;
+                section.append(error)?;
             }
             Outcome::None => {
                 section.error("returned none")?;
@@ -608,7 +979,7 @@ impl TestCase {
         section.close()?;
 
         if let Some(error) = emitted {
-            error.emit(io.stdout, &self.sources)?;
+            io.stdout.write_all(error)?;
         }
 
         if !self.outcome.is_ok() && !self.output.is_empty() {
@@ -0,0 +1,177 @@
+//! Line coverage reports of executed tests.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+
+use crate::runtime::Coverage;
+use crate::sync::Arc;
+use crate::{Sources, Unit};
+
+use super::report::Escape;
+
+/// The coverage of a single source file.
+#[derive(Default)]
+struct FileCoverage {
+    /// The contents of the file.
+    text: String,
+    /// Execution counts by zero-based line.
+    lines: BTreeMap<usize, usize>,
+}
+
+impl FileCoverage {
+    fn hit(&self) -> usize {
+        self.lines.values().filter(|&&hits| hits > 0).count()
+    }
+}
+
+/// Summary of a written coverage report.
+pub(super) struct Summary {
+    /// The number of lines with instructions.
+    pub(super) found: usize,
+    /// The number of lines which were executed.
+    pub(super) hit: usize,
+}
+
+/// Write an lcov report and an HTML summary of the given coverage to `dir`.
+///
+/// Only sources which were loaded from a path are included, which excludes
+/// doc tests. Lines of a source which is part of multiple units have their
+/// execution counts added together.
+pub(super) fn write(
+    dir: &Path,
+    coverage: &Coverage,
+    units: &[(Arc<Unit>, Arc<Sources>)],
+) -> anyhow::Result<Summary> {
+    let mut files = BTreeMap::<PathBuf, FileCoverage>::new();
+
+    for (unit, sources) in units {
+        for (location, hits) in coverage.lines(unit, sources)? {
+            let Some(source) = sources.get(location.source_id) else {
+                continue;
+            };
+
+            let Some(path) = source.path() else {
+                continue;
+            };
+
+            let file = files.entry(path.to_path_buf()).or_default();
+
+            if file.text.is_empty() {
+                file.text = source.as_str().into();
+            }
+
+            *file.lines.entry(location.line).or_default() += hits;
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+
+    let mut out = BufWriter::new(File::create(dir.join("lcov.info"))?);
+    write_lcov(&mut out, &files)?;
+    out.flush()?;
+
+    let mut out = BufWriter::new(File::create(dir.join("index.html"))?);
+    write_html(&mut out, &files)?;
+    out.flush()?;
+
+    Ok(Summary {
+        found: files.values().map(|f| f.lines.len()).sum(),
+        hit: files.values().map(FileCoverage::hit).sum(),
+    })
+}
+
+fn write_lcov(out: &mut dyn Write, files: &BTreeMap<PathBuf, FileCoverage>) -> io::Result<()> {
+    for (path, file) in files {
+        writeln!(out, "TN:")?;
+        writeln!(out, "SF:{}", path.display())?;
+
+        for (line, hits) in &file.lines {
+            writeln!(out, "DA:{},{hits}", line + 1)?;
+        }
+
+        writeln!(out, "LF:{}", file.lines.len())?;
+        writeln!(out, "LH:{}", file.hit())?;
+        writeln!(out, "end_of_record")?;
+    }
+
+    Ok(())
+}
+
+fn write_html(out: &mut dyn Write, files: &BTreeMap<PathBuf, FileCoverage>) -> io::Result<()> {
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html>")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, r#"<meta charset="utf-8">"#)?;
+    writeln!(out, "<title>Coverage</title>")?;
+    writeln!(out, "<style>")?;
+    writeln!(out, "body {{ font-family: sans-serif; }}")?;
+    writeln!(out, "table {{ border-collapse: collapse; }}")?;
+    writeln!(out, "td, th {{ padding: 0 0.5em; text-align: left; }}")?;
+    writeln!(out, "pre {{ margin: 0; }}")?;
+    writeln!(out, ".hit {{ background: #dfd; }}")?;
+    writeln!(out, ".miss {{ background: #fdd; }}")?;
+    writeln!(out, ".count {{ color: #888; text-align: right; }}")?;
+    writeln!(out, "</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>Coverage</h1>")?;
+    writeln!(out, "<table>")?;
+    writeln!(out, "<tr><th>File</th><th>Lines</th><th>Covered</th></tr>")?;
+
+    for (index, (path, file)) in files.iter().enumerate() {
+        let found = file.lines.len();
+        let hit = file.hit();
+
+        writeln!(
+            out,
+            r##"<tr><td><a href="#file-{index}">{}</a></td><td>{hit}/{found}</td><td>{:.1}%</td></tr>"##,
+            Escape(&path.display().to_string()),
+            percent(hit, found)
+        )?;
+    }
+
+    writeln!(out, "</table>")?;
+
+    for (index, (path, file)) in files.iter().enumerate() {
+        writeln!(
+            out,
+            r#"<h2 id="file-{index}">{}</h2>"#,
+            Escape(&path.display().to_string())
+        )?;
+
+        writeln!(out, "<table>")?;
+
+        for (line, text) in file.text.lines().enumerate() {
+            let (class, count) = match file.lines.get(&line) {
+                Some(0) => (" class=\"miss\"", String::from("0")),
+                Some(hits) => (" class=\"hit\"", hits.to_string()),
+                None => ("", String::new()),
+            };
+
+            writeln!(
+                out,
+                r#"<tr{class}><td class="count">{}</td><td class="count">{count}</td><td><pre>{}</pre></td></tr>"#,
+                line + 1,
+                Escape(text)
+            )?;
+        }
+
+        writeln!(out, "</table>")?;
+    }
+
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+    Ok(())
+}
+
+/// Calculate the percentage of covered lines.
+pub(super) fn percent(hit: usize, found: usize) -> f64 {
+    if found == 0 {
+        return 100.0;
+    }
+
+    hit as f64 / found as f64 * 100.0
+}
@@ -0,0 +1,221 @@
+//! Machine-readable reports of executed tests.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+
+use super::cli::Format;
+
+/// The source location a test failed at.
+#[derive(Debug, TryClone, Serialize)]
+pub(super) struct Location {
+    /// The path or name of the source.
+    pub(super) path: String,
+    /// The one-based line number.
+    pub(super) line: usize,
+    /// The one-based column number.
+    pub(super) column: usize,
+}
+
+/// The outcome of a single test in a report.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) enum Status {
+    Passed,
+    Failed,
+}
+
+/// A single test in a report.
+#[derive(Serialize)]
+pub(super) struct Case {
+    /// The item path of the test, including the protocol for protocol doc
+    /// tests.
+    pub(super) name: String,
+    pub(super) status: Status,
+    /// How long the test took to run, in seconds.
+    #[serde(serialize_with = "serialize_duration")]
+    pub(super) duration: Duration,
+    /// Output captured while running the test.
+    pub(super) output: String,
+    /// Describes why the test failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) message: Option<String>,
+    /// Where the test failed, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) location: Option<Location>,
+}
+
+/// A batch of tests in a report.
+#[derive(Serialize)]
+pub(super) struct Suite {
+    pub(super) name: String,
+    pub(super) cases: Vec<Case>,
+}
+
+impl Suite {
+    fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| matches!(case.status, Status::Failed))
+            .count()
+    }
+
+    fn duration(&self) -> Duration {
+        self.cases.iter().map(|case| case.duration).sum()
+    }
+}
+
+/// Write a report of the given suites in the specified format.
+pub(super) fn write<O>(out: O, format: Format, suites: &[Suite]) -> io::Result<()>
+where
+    O: Write,
+{
+    match format {
+        Format::Json => write_json(out, suites),
+        Format::Junit => write_junit(out, suites),
+    }
+}
+
+fn write_json<O>(mut out: O, suites: &[Suite]) -> io::Result<()>
+where
+    O: Write,
+{
+    #[derive(Serialize)]
+    struct Report<'a> {
+        tests: usize,
+        failures: usize,
+        suites: &'a [Suite],
+    }
+
+    let report = Report {
+        tests: suites.iter().map(|s| s.cases.len()).sum(),
+        failures: suites.iter().map(Suite::failures).sum(),
+        suites,
+    };
+
+    serde_json::to_writer_pretty(&mut out, &report)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_junit<O>(mut out: O, suites: &[Suite]) -> io::Result<()>
+where
+    O: Write,
+{
+    let tests = suites.iter().map(|s| s.cases.len()).sum::<usize>();
+    let failures = suites.iter().map(Suite::failures).sum::<usize>();
+    let time = suites.iter().map(Suite::duration).sum::<Duration>();
+
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="rune" tests="{tests}" failures="{failures}" time="{:.6}">"#,
+        time.as_secs_f64()
+    )?;
+
+    for suite in suites {
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.6}">"#,
+            Escape(&suite.name),
+            suite.cases.len(),
+            suite.failures(),
+            suite.duration().as_secs_f64()
+        )?;
+
+        for case in &suite.cases {
+            write!(
+                out,
+                r#"    <testcase name="{}" classname="{}" time="{:.6}""#,
+                Escape(&case.name),
+                Escape(&suite.name),
+                case.duration.as_secs_f64()
+            )?;
+
+            if let Some(location) = &case.location {
+                write!(
+                    out,
+                    r#" file="{}" line="{}""#,
+                    Escape(&location.path),
+                    location.line
+                )?;
+            }
+
+            writeln!(out, ">")?;
+
+            if let Status::Failed = case.status {
+                let message = case.message.as_deref().unwrap_or("failed");
+                write!(out, r#"      <failure message="{}">"#, Escape(message))?;
+
+                if let Some(location) = &case.location {
+                    write!(
+                        out,
+                        "{}:{}:{}: ",
+                        Escape(&location.path),
+                        location.line,
+                        location.column
+                    )?;
+                }
+
+                writeln!(out, "{}</failure>", Escape(message))?;
+            }
+
+            if !case.output.is_empty() {
+                writeln!(
+                    out,
+                    "      <system-out>{}</system-out>",
+                    Escape(&case.output)
+                )?;
+            }
+
+            writeln!(out, "    </testcase>")?;
+        }
+
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    writeln!(out, "</testsuites>")?;
+    Ok(())
+}
+
+fn serialize_duration<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Escape a string for use in XML text and attribute values.
+pub(super) struct Escape<'a>(pub(super) &'a str);
+
+impl std::fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write as _;
+
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                // Control characters other than whitespace are not permitted in
+                // XML 1.0 documents.
+                c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {
+                    write!(f, "\\u{{{:x}}}", c as u32)?
+                }
+                c => f.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::alloc::prelude::*;
+use crate::alloc::Vec;
+
+use super::{write_json, write_junit, Case, Escape, Location, Status, Suite};
+
+/// A suite with one passing test and one test which failed at a known
+/// location.
+fn suites() -> Result<[Suite; 1]> {
+    let mut cases = Vec::new();
+
+    cases.try_push(Case {
+        name: "passes".try_to_owned()?,
+        status: Status::Passed,
+        duration: Duration::from_millis(500),
+        output: "ok\n".try_to_owned()?,
+        message: None,
+        location: None,
+    })?;
+
+    cases.try_push(Case {
+        name: "fails".try_to_owned()?,
+        status: Status::Failed,
+        duration: Duration::from_millis(250),
+        output: String::new(),
+        message: Some("expected <1> & got \"2\"".try_to_owned()?),
+        location: Some(Location {
+            path: "tests/a&b.rn".try_to_owned()?,
+            line: 3,
+            column: 7,
+        }),
+    })?;
+
+    Ok([Suite {
+        name: "tests".try_to_owned()?,
+        cases,
+    }])
+}
+
+#[test]
+fn json_shape() -> Result<()> {
+    let mut out = std::vec::Vec::new();
+    write_json(&mut out, &suites()?)?;
+
+    let report = serde_json::from_slice::<Value>(&out)?;
+
+    let expected = json!({
+        "tests": 2,
+        "failures": 1,
+        "suites": [{
+            "name": "tests",
+            "cases": [
+                {
+                    "name": "passes",
+                    "status": "passed",
+                    "duration": 0.5,
+                    "output": "ok\n",
+                },
+                {
+                    "name": "fails",
+                    "status": "failed",
+                    "duration": 0.25,
+                    "output": "",
+                    "message": "expected <1> & got \"2\"",
+                    "location": {
+                        "path": "tests/a&b.rn",
+                        "line": 3,
+                        "column": 7,
+                    },
+                },
+            ],
+        }],
+    });
+
+    assert_eq!(report, expected);
+    Ok(())
+}
+
+#[test]
+fn junit_escape() {
+    assert_eq!(
+        format!("{}", Escape("a & b <c> \"d\" 'e'")),
+        "a &amp; b &lt;c&gt; &quot;d&quot; &apos;e&apos;"
+    );
+
+    assert_eq!(
+        format!("{}", Escape("bell\x07 nul\0")),
+        "bell\\u{7} nul\\u{0}"
+    );
+    assert_eq!(format!("{}", Escape("line\n\ttab\r")), "line\n\ttab\r");
+}
+
+#[test]
+fn junit_failure() -> Result<()> {
+    let mut out = std::vec::Vec::new();
+    write_junit(&mut out, &suites()?)?;
+
+    let out = std::string::String::from_utf8(out)?;
+    let lines = out.lines().collect::<std::vec::Vec<_>>();
+
+    let expected = [
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        r#"<testsuites name="rune" tests="2" failures="1" time="0.750000">"#,
+        r#"  <testsuite name="tests" tests="2" failures="1" time="0.750000">"#,
+        r#"    <testcase name="passes" classname="tests" time="0.500000">"#,
+        r#"      <system-out>ok"#,
+        r#"</system-out>"#,
+        r#"    </testcase>"#,
+        r#"    <testcase name="fails" classname="tests" time="0.250000" file="tests/a&amp;b.rn" line="3">"#,
+        r#"      <failure message="expected &lt;1&gt; &amp; got &quot;2&quot;">tests/a&amp;b.rn:3:7: expected &lt;1&gt; &amp; got &quot;2&quot;</failure>"#,
+        r#"    </testcase>"#,
+        r#"  </testsuite>"#,
+        r#"</testsuites>"#,
+    ];
+
+    assert_eq!(lines, expected);
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anyhow::Result;
+
+use crate::alloc::prelude::*;
+use crate::cli::visitor::{Attribute, FunctionVisitor};
+use crate::doc::{TestKind, TestParams};
+use crate::modules::capture_io::{self, CaptureIo};
+use crate::sync::Arc;
+use crate::{Context, Source, Sources};
+
+use super::{execute_parallel, TestCase, Worker};
+
+/// Tests which finish in the reverse order in which they are declared.
+const SOURCE: &str = r#"
+fn spin(n) {
+    let sum = 0;
+
+    for i in 0..n {
+        sum += i;
+    }
+
+    sum
+}
+
+#[test] fn t0() { spin(40000); println("t0"); }
+#[test] fn t1() { spin(30000); println("t1"); }
+#[test] fn t2() { spin(20000); println("t2"); }
+#[test] fn t3() { spin(10000); println("t3"); }
+#[test] fn t4() { println("t4"); }
+#[test] fn t5() { println("t5"); }
+"#;
+
+/// Build a context which captures the output of scripts.
+fn context(capture: &CaptureIo) -> Result<Context> {
+    let mut context = Context::with_config(false)?;
+    context.install(capture_io::module(capture)?)?;
+    Ok(context)
+}
+
+/// Compile the given source, collecting its test cases ordered by name.
+fn cases(source: &str) -> Result<Vec<TestCase>> {
+    let context = context(&CaptureIo::new())?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let mut functions = FunctionVisitor::new(Attribute::Test);
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_visitor(&mut functions)?
+        .build()?;
+
+    let unit = Arc::try_new(unit)?;
+    let sources = Arc::try_new(sources)?;
+
+    let mut cases = Vec::new();
+
+    for (hash, item) in functions.into_functions() {
+        cases.try_push(TestCase::new(
+            hash,
+            item,
+            TestKind::Free,
+            unit.clone(),
+            sources.clone(),
+            TestParams::default(),
+            false,
+        ))?;
+    }
+
+    cases.sort_by(|a, b| a.item.cmp(&b.item));
+    Ok(cases)
+}
+
+/// Construct the given number of workers.
+fn workers(jobs: usize) -> Result<std::vec::Vec<Worker>> {
+    let mut workers = std::vec::Vec::new();
+
+    for _ in 0..jobs {
+        let capture = CaptureIo::new();
+        let runtime = Arc::try_new(context(&capture)?.runtime()?)?;
+
+        workers.push(Worker {
+            runtime,
+            capture,
+            coverage: None,
+        });
+    }
+
+    Ok(workers)
+}
+
+/// Get the name and captured output of a test case.
+fn describe(case: &TestCase) -> Result<(std::string::String, std::string::String)> {
+    let name = case.item.try_to_string()?.into_std();
+    let output = std::string::String::from_utf8(case.output.to_vec())?;
+    Ok((name, output))
+}
+
+#[test]
+fn parallel_report_order() -> Result<()> {
+    let cases = cases(SOURCE)?;
+    let workers = workers(4)?;
+
+    let mut reported = std::vec::Vec::new();
+
+    execute_parallel(&workers, cases, false, |case| {
+        assert!(case.outcome.is_ok(), "{} failed", case.item);
+        reported.push(describe(&case)?);
+        Ok(true)
+    })?;
+
+    let expected = (0..6)
+        .map(|n| (format!("t{n}"), format!("t{n}\n")))
+        .collect::<std::vec::Vec<_>>();
+
+    assert_eq!(reported, expected);
+    Ok(())
+}
+
+#[test]
+fn parallel_fail_fast() -> Result<()> {
+    let cases = cases(
+        r#"
+        #[test] fn t0() { for i in 0..40000 {} println("t0"); }
+        #[test] fn t1() { println("t1"); panic("failed"); }
+        #[test] fn t2() { println("t2"); }
+        #[test] fn t3() { println("t3"); }
+        #[test] fn t4() { println("t4"); }
+        "#,
+    )?;
+
+    let workers = workers(4)?;
+
+    let mut reported = std::vec::Vec::new();
+
+    // Reporting stops after the first failure, like it does with
+    // `--fail-fast`, even if later tests have finished.
+    execute_parallel(&workers, cases, false, |case| {
+        reported.push(describe(&case)?);
+        Ok(case.outcome.is_ok())
+    })?;
+
+    let expected = [("t0", "t0\n"), ("t1", "t1\n")].map(|(name, output)| {
+        (
+            std::string::String::from(name),
+            std::string::String::from(output),
+        )
+    });
+
+    assert_eq!(reported, expected);
+    Ok(())
+}
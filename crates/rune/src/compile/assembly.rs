@@ -46,6 +46,10 @@ pub(crate) struct Assembly {
     pub(crate) instructions: Vec<(AssemblyInst, Span)>,
     /// Comments associated with instructions.
     pub(crate) comments: HashMap<usize, String>,
+    /// Variables defined right before the instruction at the given offset.
+    pub(crate) variables: HashMap<usize, Vec<(Box<str>, inst::Address)>>,
+    /// Slots freed right before the instruction at the given offset.
+    pub(crate) freed: HashMap<usize, Vec<inst::Address>>,
     /// The number of labels.
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
@@ -60,6 +64,8 @@ impl Assembly {
             labels: Default::default(),
             instructions: Default::default(),
             comments: Default::default(),
+            variables: Default::default(),
+            freed: Default::default(),
             label_count,
             required_functions: Default::default(),
         }
@@ -159,6 +165,25 @@ impl Assembly {
         Ok(())
     }
 
+    /// Record that a variable has been defined at the current instruction
+    /// offset.
+    pub(crate) fn variable(&mut self, name: &str, addr: inst::Address) -> compile::Result<()> {
+        let index = self.instructions.len();
+        let name = Box::try_from(name)?;
+        self.variables
+            .entry(index)
+            .or_try_default()?
+            .try_push((name, addr))?;
+        Ok(())
+    }
+
+    /// Record that a slot has been freed at the current instruction offset.
+    pub(crate) fn free(&mut self, addr: inst::Address) -> compile::Result<()> {
+        let index = self.instructions.len();
+        self.freed.entry(index).or_try_default()?.try_push(addr)?;
+        Ok(())
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: inst::Kind, span: &dyn Spanned) -> compile::Result<()> {
         self.inner_push(AssemblyInst::Raw { raw }, span)?;
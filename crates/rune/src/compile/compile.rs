@@ -1,21 +1,22 @@
-use crate::alloc;
 use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap};
 use crate::ast::{Span, Spanned};
 use crate::compile::v1;
 use crate::compile::{
-    self, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, Pool, Prelude,
-    SourceLoader, UnitBuilder,
+    self, Assembly, CompileVisitor, Context, ErrorKind, Location, ModId, Options, Pool, Prelude,
+    SourceLoader, UnitBuilder, Visibility,
 };
 use crate::hir;
-use crate::indexing::FunctionAst;
+use crate::indexing::{FunctionAst, Items};
 use crate::internal_macros::resolve_context;
 use crate::macros::Storage;
 use crate::parse::Resolve;
 use crate::query::{Build, BuildEntry, Query, SecondaryBuild, Used};
 use crate::runtime::unit::UnitEncoder;
+use crate::runtime::{inst, Call, Protocol};
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
-use crate::{Diagnostics, Sources};
+use crate::{Diagnostics, Hash, ItemBuf, SourceId, Sources};
 
 /// Encode the given object into a collection of asm.
 pub(crate) fn compile(
@@ -59,8 +60,17 @@ pub(crate) fn compile(
     // The worker queue.
     let mut worker = Worker::new(q);
 
+    // The root module which dependencies are mounted under.
+    let mut root_mod = None;
+    let mut dependencies = Vec::new();
+
     // Queue up the initial sources to be loaded.
     for source_id in worker.q.sources.source_ids() {
+        if worker.q.sources.dependency(source_id).is_some() {
+            dependencies.try_push(source_id)?;
+            continue;
+        }
+
         let (root_item_id, mod_item) = match worker.q.insert_root_mod(source_id, Span::empty()) {
             Ok(result) => result,
             Err(error) => {
@@ -69,6 +79,8 @@ pub(crate) fn compile(
             }
         };
 
+        root_mod = Some(mod_item);
+
         let result = worker.queue.try_push_back(Task::LoadFile {
             kind: LoadFileKind::Original,
             source_id,
@@ -84,6 +96,12 @@ pub(crate) fn compile(
         }
     }
 
+    for source_id in dependencies {
+        if let Err(error) = mount_dependency(&mut worker, &mut root_mod, source_id) {
+            worker.q.diagnostics.error(source_id, error)?;
+        }
+    }
+
     worker.index()?;
 
     if worker.q.diagnostics.has_error() {
@@ -119,6 +137,45 @@ pub(crate) fn compile(
     Ok(())
 }
 
+/// Mount the library of a dependency as a module under the root module.
+fn mount_dependency(
+    worker: &mut Worker<'_, '_>,
+    root_mod: &mut Option<ModId>,
+    source_id: SourceId,
+) -> compile::Result<()> {
+    let parent = match *root_mod {
+        Some(module) => module,
+        None => {
+            let (_, module) = worker.q.insert_root_mod(source_id, Span::empty())?;
+            *root_mod = Some(module);
+            module
+        }
+    };
+
+    let Some(name) = worker.q.sources.dependency(source_id) else {
+        return Ok(());
+    };
+
+    let items = Items::new(&ItemBuf::with_item([name])?)?;
+    let location = Location::new(source_id, Span::empty());
+
+    let (mod_item, mod_item_id) =
+        worker
+            .q
+            .insert_mod(&items, &location, parent, Visibility::Public, &[])?;
+
+    worker.queue.try_push_back(Task::LoadFile {
+        kind: LoadFileKind::Module {
+            root: Some(source_id),
+        },
+        source_id,
+        mod_item,
+        mod_item_id,
+    })?;
+
+    Ok(())
+}
+
 struct CompileBuildEntry<'a, 'arena> {
     options: &'a Options,
     q: Query<'a, 'arena>,
@@ -131,12 +188,14 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
         span: &'hir dyn Spanned,
         asm: &'a mut Assembly,
         scopes: &'a mut v1::Scopes<'hir>,
+        names: &'a HashMap<hir::Variable, hir::Name<'hir>>,
     ) -> alloc::Result<v1::Ctxt<'a, 'hir, 'arena>> {
         Ok(v1::Ctxt {
             source_id: location.source_id,
             q: self.q.borrow(),
             asm,
             scopes,
+            names,
             contexts: try_vec![span],
             breaks: self::v1::Breaks::new(),
             options: self.options,
@@ -232,18 +291,20 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
 
                         node.parse(|p| hir::lowering2::item_fn(&mut cx, p, f.impl_item.is_some()))?
                     }
-                    FunctionAst::Item(ast, _) => hir::lowering::item_fn(&mut cx, ast)?,
+                    FunctionAst::Item(ast, _) => hir::lowering::item_fn(&mut cx, ast, f.call)?,
                     FunctionAst::Empty(ast, span) => {
                         hir::lowering::empty_fn(&mut cx, ast, args, &span)?
                     }
                 };
 
+                let names = cx.take_variable_names();
                 let count = hir.args.len();
 
                 let mut scopes = self::v1::Scopes::new(location.source_id)?;
-                let mut c = self.compiler1(location, span, &mut asm, &mut scopes)?;
+                let mut c = self.compiler1(location, span, &mut asm, &mut scopes, &names)?;
                 assemble::fn_from_item_fn(&mut c, &hir, f.is_instance)?;
                 let size = c.scopes.size();
+                self::v1::optimize::assembly(&mut asm, self.options.lowering)?;
 
                 if !self.q.is_used(&item_meta) {
                     self.q
@@ -291,9 +352,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                                 format_hir_args(self.q.sources, location, true, c.hir.args.iter())?;
 
                             let mut scopes = self::v1::Scopes::new(location.source_id)?;
-                            let mut cx = self.compiler1(location, c.hir, &mut asm, &mut scopes)?;
+                            let mut cx =
+                                self.compiler1(location, c.hir, &mut asm, &mut scopes, &names)?;
                             assemble::expr_closure_secondary(&mut cx, c.hir)?;
                             let size = cx.scopes.size();
+                            self::v1::optimize::assembly(&mut asm, self.options.lowering)?;
 
                             if !self.q.is_used(&item_meta) {
                                 self.q.diagnostics.not_used(
@@ -329,9 +392,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                             tracing::trace!("async block: {}", self.q.pool.item(item_meta.item));
 
                             let mut scopes = self::v1::Scopes::new(location.source_id)?;
-                            let mut cx = self.compiler1(location, b.hir, &mut asm, &mut scopes)?;
+                            let mut cx =
+                                self.compiler1(location, b.hir, &mut asm, &mut scopes, &names)?;
                             assemble::async_block_secondary(&mut cx, b.hir)?;
                             let size = cx.scopes.size();
+                            self::v1::optimize::assembly(&mut asm, self.options.lowering)?;
 
                             if !self.q.is_used(&item_meta) {
                                 self.q.diagnostics.not_used(
@@ -437,6 +502,46 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     self.q.pool.item(import),
                 )?;
             }
+            Build::TraitImpl(trait_impl) => {
+                tracing::trace!("trait impl: {}", self.q.pool.item(item_meta.item));
+
+                for (hash, target) in trait_impl.aliases {
+                    self.q.unit.new_function_alias(location, hash, target)?;
+                }
+
+                // Iterators are converted into iterators by returning
+                // themselves, which is what makes them usable in `for` loops.
+                if trait_impl.into_iter {
+                    let mut asm = self.q.unit.new_assembly(location);
+                    asm.push(
+                        inst::Kind::Return {
+                            addr: inst::Address::ZERO,
+                        },
+                        &location.span,
+                    )?;
+
+                    let item = self.q.pool.item(item_meta.item).extended("into_iter")?;
+
+                    self.q.unit.new_function(
+                        location,
+                        &item,
+                        Some((trait_impl.type_hash, "into_iter")),
+                        1,
+                        None,
+                        asm,
+                        Call::Immediate,
+                        try_vec![Box::try_from("self")?].try_into_boxed_slice()?,
+                        unit_storage,
+                        1,
+                    )?;
+
+                    self.q.unit.new_function_alias(
+                        location,
+                        Hash::associated_function(trait_impl.type_hash, &Protocol::INTO_ITER),
+                        Hash::type_hash(&*item),
+                    )?;
+                }
+            }
         }
 
         Ok(())
@@ -14,8 +14,8 @@ use crate::item::{ComponentRef, IntoComponent};
 use crate::macros::{MacroContext, TokenStream};
 use crate::module::{
     DocFunction, Fields, Module, ModuleAssociated, ModuleAssociatedKind, ModuleFunction,
-    ModuleItem, ModuleItemCommon, ModuleReexport, ModuleTrait, ModuleTraitImpl, ModuleType,
-    TypeSpecification,
+    ModuleItem, ModuleItemCommon, ModuleParameter, ModuleReexport, ModuleTrait, ModuleTraitImpl,
+    ModuleType, TypeSpecification,
 };
 use crate::runtime::{
     Address, AnyTypeInfo, ConstConstructImpl, ConstContext, ConstValue, FunctionHandler, Memory,
@@ -196,6 +196,8 @@ impl TraitContext<'_> {
         let function = ModuleFunction {
             handler: handler.clone(),
             trait_hash: Some(self.trait_hash),
+            is_const: false,
+            parameters: Vec::new(),
             doc: DocFunction {
                 #[cfg(feature = "doc")]
                 is_async: false,
@@ -295,6 +297,8 @@ pub struct Context {
     item_to_hash: HashMap<ItemBuf, BTreeSet<Hash>>,
     /// Registered native function handlers.
     functions: hash::Map<FunctionHandler>,
+    /// Native functions which are safe to call in constant contexts.
+    const_functions: hash::Set,
     /// Registered deprecation mesages for native functions.
     deprecations: hash::Map<String>,
     /// Information on associated types.
@@ -411,10 +415,17 @@ impl Context {
     /// # Ok::<_, rune::support::Error>(())
     /// ```
     pub fn runtime(&self) -> alloc::Result<RuntimeContext> {
+        let mut types = hash::Set::default();
+
+        for hash in self.types.keys() {
+            types.try_insert(*hash)?;
+        }
+
         Ok(RuntimeContext::new(
             self.functions.try_clone()?,
             self.constants.try_clone()?,
             self.construct.try_clone()?,
+            types,
         ))
     }
 
@@ -535,8 +546,12 @@ impl Context {
         }))
     }
 
+    /// Look up the item of a type by its hash.
+    pub(crate) fn type_item(&self, hash: Hash) -> Option<&Item> {
+        Some(&self.types.get(&hash)?.item)
+    }
+
     /// Lookup meta by its hash.
-    #[cfg(any(feature = "cli", feature = "languageserver", feature = "emit"))]
     pub(crate) fn lookup_meta_by_hash(
         &self,
         hash: Hash,
@@ -565,6 +580,16 @@ impl Context {
         self.functions.get(&hash)
     }
 
+    /// Lookup the given native function handler, but only if it has been
+    /// marked as safe to call in constant contexts.
+    pub(crate) fn lookup_const_function(&self, hash: Hash) -> Option<&FunctionHandler> {
+        if !self.const_functions.contains(&hash) {
+            return None;
+        }
+
+        self.functions.get(&hash)
+    }
+
     /// Get all associated types for the given hash.
     #[cfg(all(feature = "doc", feature = "cli"))]
     pub(crate) fn associated(&self, hash: Hash) -> impl Iterator<Item = Hash> + '_ {
@@ -725,6 +750,8 @@ impl Context {
                     let constructor = match &ty.constructor {
                         Some(c) => {
                             let signature = meta::Signature {
+                                parameters: Box::default(),
+                                output: None,
                                 #[cfg(feature = "doc")]
                                 is_async: false,
                                 #[cfg(feature = "doc")]
@@ -758,6 +785,7 @@ impl Context {
                                         Ok(meta::FieldMeta {
                                             name: name.try_into()?,
                                             position,
+                                            ty: None,
                                         })
                                     })
                                     .try_collect::<alloc::Result<_>>()??,
@@ -800,6 +828,8 @@ impl Context {
 
                         let constructor = if let Some(c) = &variant.constructor {
                             let signature = meta::Signature {
+                                parameters: Box::default(),
+                                output: None,
                                 #[cfg(feature = "doc")]
                                 is_async: false,
                                 #[cfg(feature = "doc")]
@@ -843,6 +873,7 @@ impl Context {
                                                     Ok(meta::FieldMeta {
                                                         name: name.try_into()?,
                                                         position,
+                                                        ty: None,
                                                     })
                                                 })
                                                 .try_collect::<alloc::Result<_>>()??,
@@ -901,7 +932,7 @@ impl Context {
         })?;
 
         for f in &t.functions {
-            let signature = meta::Signature::from_context(&f.doc, &f.common)?;
+            let signature = meta::Signature::from_context(&f.doc, &f.common, Box::default())?;
 
             let kind = meta::Kind::Function {
                 associated: Some(f.name.kind.try_clone()?),
@@ -1044,6 +1075,40 @@ impl Context {
         Ok(())
     }
 
+    /// Install the declared parameters of a native function, storing their
+    /// default values as constants associated with the function.
+    fn install_parameters(
+        &mut self,
+        hash: Hash,
+        parameters: &[ModuleParameter],
+    ) -> Result<Box<[meta::Parameter]>, ContextError> {
+        let mut out = Vec::try_with_capacity(parameters.len())?;
+
+        for (index, p) in parameters.iter().enumerate() {
+            let default = match &p.default {
+                Some(value) => {
+                    let hash = Hash::associated_function(hash, Hash::index(index));
+                    self.constants.try_insert(hash, value.try_clone()?)?;
+                    Some(meta::ParameterDefault::Const(hash))
+                }
+                None => None,
+            };
+
+            let name = match p.name.as_ref() {
+                "self" | "_" => None,
+                name => Some(name.try_into()?),
+            };
+
+            out.try_push(meta::Parameter {
+                name,
+                default,
+                ty: None,
+            })?;
+        }
+
+        Ok(out.try_into_boxed_slice()?)
+    }
+
     /// Install a function and check for duplicates.
     fn install_item(&mut self, m: &ModuleItem) -> Result<(), ContextError> {
         self.names.insert(&m.item)?;
@@ -1059,10 +1124,15 @@ impl Context {
                     ConstValue::try_from(m.item.try_to_string()?)?,
                 )?;
 
-                let signature = meta::Signature::from_context(&f.doc, &m.common)?;
+                let parameters = self.install_parameters(m.hash, &f.parameters)?;
+                let signature = meta::Signature::from_context(&f.doc, &m.common, parameters)?;
 
                 self.insert_native_fn(&m.item, m.hash, &f.handler, m.common.deprecated.as_deref())?;
 
+                if f.is_const {
+                    self.const_functions.try_insert(m.hash)?;
+                }
+
                 meta::Kind::Function {
                     associated: None,
                     trait_hash: f.trait_hash,
@@ -1141,7 +1211,8 @@ impl Context {
                 meta::Kind::Const
             }
             ModuleAssociatedKind::Function(f) => {
-                let signature = meta::Signature::from_context(&f.doc, &assoc.common)?;
+                let parameters = self.install_parameters(hash, &f.parameters)?;
+                let signature = meta::Signature::from_context(&f.doc, &assoc.common, parameters)?;
 
                 if let Some((hash, item)) = &item {
                     self.constants.try_insert(
@@ -1155,6 +1226,10 @@ impl Context {
                         &f.handler,
                         assoc.common.deprecated.as_deref(),
                     )?;
+
+                    if f.is_const {
+                        self.const_functions.try_insert(*hash)?;
+                    }
                 }
 
                 self.insert_native_fn(
@@ -1164,6 +1239,10 @@ impl Context {
                     assoc.common.deprecated.as_deref(),
                 )?;
 
+                if f.is_const {
+                    self.const_functions.try_insert(hash)?;
+                }
+
                 meta::Kind::Function {
                     associated: Some(assoc.name.kind.try_clone()?),
                     trait_hash: f.trait_hash,
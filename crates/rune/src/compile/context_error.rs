@@ -168,6 +168,16 @@ pub enum ContextError {
         hash: Hash,
         item_hash: Hash,
     },
+    MissingArgumentName {
+        name: Box<str>,
+    },
+    UnsupportedArgumentDefault {
+        name: Box<str>,
+    },
+    InvalidArgumentDefault {
+        name: Box<str>,
+        error: Box<RuntimeError>,
+    },
 }
 
 impl From<alloc::Error> for ContextError {
@@ -415,6 +425,21 @@ impl fmt::Display for ContextError {
             } => {
                 write!(f, "Static type hash mismatch for `{type_info}`, from module is `{hash}` while from item `{item}` is `{item_hash}`. The static item might be registered in the wrong module, or that the static type hash is miscalculated.")?;
             }
+            ContextError::MissingArgumentName { name } => {
+                write!(f, "No argument named `{name}`, argument names must be declared before their default values")?;
+            }
+            ContextError::UnsupportedArgumentDefault { name } => {
+                write!(
+                    f,
+                    "Default value for argument `{name}` is not supported for this function"
+                )?;
+            }
+            ContextError::InvalidArgumentDefault { name, error } => {
+                write!(
+                    f,
+                    "Error when building default value for argument `{name}`: {error}"
+                )?;
+            }
         }
 
         Ok(())
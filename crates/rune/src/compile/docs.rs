@@ -74,6 +74,14 @@ impl Docs {
         Ok(())
     }
 
+    /// Update the label of a single argument.
+    #[cfg(feature = "doc")]
+    pub(crate) fn set_argument(&mut self, index: usize, argument: String) {
+        if let Some(slot) = self.arguments.as_mut().and_then(|a| a.get_mut(index)) {
+            *slot = argument;
+        }
+    }
+
     #[cfg(not(feature = "doc"))]
     pub(crate) fn set_arguments(
         &mut self,
@@ -298,6 +298,10 @@ pub(crate) enum ErrorKind {
         actual: TypeInfo,
         expected: TypeInfo,
     },
+    TypeMismatch {
+        expected: ItemBuf,
+        actual: ItemBuf,
+    },
     Expected {
         actual: Expectation,
         expected: Expectation,
@@ -377,6 +381,17 @@ pub(crate) enum ErrorKind {
         object: Span,
     },
     InstanceFunctionOutsideImpl,
+    MissingTraitFunction {
+        name: Box<str>,
+        trait_item: ItemBuf,
+    },
+    NotTraitFunction {
+        name: Box<str>,
+        trait_item: ItemBuf,
+    },
+    UnsupportedTraitImpl {
+        trait_item: ItemBuf,
+    },
     UnsupportedTupleIndex {
         number: ast::Number,
     },
@@ -734,6 +749,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::ExpectedType { actual, expected } => {
                 write!(f, "Expected type `{expected}` but found `{actual}`")?;
             }
+            ErrorKind::TypeMismatch { expected, actual } => {
+                write!(f, "Expected type `{expected}` but found `{actual}`")?;
+            }
             ErrorKind::Expected { actual, expected } => {
                 write!(f, "Expected {expected} but got {actual}")?;
             }
@@ -840,6 +858,24 @@ impl fmt::Display for ErrorKind {
             ErrorKind::InstanceFunctionOutsideImpl => {
                 write!(f, "Instance function declared outside of `impl` block")?;
             }
+            ErrorKind::MissingTraitFunction { name, trait_item } => {
+                write!(
+                    f,
+                    "Missing function `{name}` required by trait `{trait_item}`"
+                )?;
+            }
+            ErrorKind::NotTraitFunction { name, trait_item } => {
+                write!(
+                    f,
+                    "Function `{name}` is not a member of trait `{trait_item}`"
+                )?;
+            }
+            ErrorKind::UnsupportedTraitImpl { trait_item } => {
+                write!(
+                    f,
+                    "Trait `{trait_item}` cannot be implemented by script types"
+                )?;
+            }
             ErrorKind::UnsupportedTupleIndex { number } => {
                 write!(f, "Unsupported tuple index `{number}`")?;
             }
@@ -1446,6 +1482,13 @@ pub(crate) enum IrErrorKind {
         actual: usize,
         expected: usize,
     },
+    /// Error raised when calling a function which isn't safe to call in
+    /// constant contexts.
+    NotConstFn {
+        hash: Hash,
+    },
+    /// None of the branches in a match expression matched.
+    UnmatchedPattern,
 }
 
 impl core::error::Error for IrErrorKind {}
@@ -1483,6 +1526,15 @@ impl fmt::Display for IrErrorKind {
                     "Argument count mismatch, got {actual} but expected {expected}",
                 )?;
             }
+            IrErrorKind::NotConstFn { hash } => {
+                write!(
+                    f,
+                    "Function with hash {hash} can't be called in constant contexts"
+                )?;
+            }
+            IrErrorKind::UnmatchedPattern => {
+                write!(f, "Pattern did not match")?;
+            }
         }
 
         Ok(())
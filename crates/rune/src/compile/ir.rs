@@ -22,10 +22,11 @@ use crate::hir;
 use crate::indexing::index;
 use crate::macros::MacroContext;
 use crate::query::Used;
-use crate::runtime::{Inline, Value};
+use crate::runtime::{self, Inline, OwnedTuple, Value};
+use crate::{Hash, TypeHash};
 
 pub(crate) use self::compiler::Ctxt;
-pub(crate) use self::eval::{eval_ir, EvalOutcome};
+pub(crate) use self::eval::{const_eq, eval_ir, sequence_items, EvalOutcome};
 pub(crate) use self::interpreter::{Budget, Interpreter};
 pub(crate) use self::scopes::Scopes;
 
@@ -164,6 +165,14 @@ decl_kind! {
         Object(IrObject),
         /// A call.
         Call(IrCall),
+        /// A match over a value.
+        Match(IrMatch),
+        /// A loop over the values of an iterable.
+        For(IrFor),
+        /// An index into a value.
+        Index(IrIndex),
+        /// Constructing a range.
+        Range(IrRange),
     }
 }
 
@@ -315,6 +324,18 @@ pub(crate) enum IrCondition {
     Ir(Ir),
     /// A pattern match.
     Let(IrLet),
+    /// A chain of conditions which must all hold.
+    Chain(IrConditionChain),
+}
+
+/// A chain of conditions.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrConditionChain {
+    /// The span of the chain.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The conditions in the chain, evaluated in order.
+    pub(crate) conditions: Vec<IrCondition>,
 }
 
 /// A pattern match.
@@ -336,15 +357,51 @@ pub(crate) enum IrPat {
     Ignore,
     /// A named binding.
     Binding(hir::Variable),
+    /// A literal or constant which the value must be equal to.
+    Value(Value),
+    /// A tuple or vector pattern.
+    Sequence(IrPatSequence),
+}
+
+/// A tuple or vector pattern.
+#[derive(Debug, TryClone)]
+pub(crate) struct IrPatSequence {
+    /// The type of the sequence.
+    pub(crate) hash: Hash,
+    /// Patterns for the items in the sequence.
+    pub(crate) items: Box<[IrPat]>,
+    /// If the sequence may have more items than there are patterns.
+    pub(crate) is_open: bool,
 }
 
 impl IrPat {
-    fn compile_ast(hir: &hir::Pat<'_>) -> compile::Result<Self> {
+    fn compile_ast(hir: &hir::Pat<'_>, cx: &mut Ctxt<'_, '_>) -> compile::Result<Self> {
         match hir.kind {
             hir::PatKind::Ignore => return Ok(ir::IrPat::Ignore),
             hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => {
                 return Ok(ir::IrPat::Binding(name));
             }
+            hir::PatKind::Lit(expr) => {
+                if let IrKind::Value(value) = compiler::expr(expr, cx)?.kind {
+                    return Ok(ir::IrPat::Value(value));
+                }
+            }
+            hir::PatKind::Sequence(&hir::PatSequence {
+                kind: hir::PatSequenceKind::Sequence { hash, is_open, .. },
+                items,
+            }) if hash == runtime::Vec::HASH || hash == OwnedTuple::HASH => {
+                let mut patterns = Vec::try_with_capacity(items.len())?;
+
+                for item in items {
+                    patterns.try_push(IrPat::compile_ast(item, cx)?)?;
+                }
+
+                return Ok(ir::IrPat::Sequence(IrPatSequence {
+                    hash,
+                    items: patterns.try_into_boxed_slice()?,
+                    is_open,
+                }));
+            }
             _ => (),
         }
 
@@ -358,7 +415,7 @@ impl IrPat {
         spanned: S,
     ) -> Result<bool, ir::EvalOutcome>
     where
-        S: Spanned,
+        S: Copy + Spanned,
     {
         match self {
             IrPat::Ignore => Ok(true),
@@ -366,6 +423,33 @@ impl IrPat {
                 interp.scopes.decl(*name, value).with_span(spanned)?;
                 Ok(true)
             }
+            IrPat::Value(expected) => match ir::const_eq(&value, expected, spanned)? {
+                Some(equal) => Ok(equal),
+                None => Err(ir::EvalOutcome::not_const(spanned)),
+            },
+            IrPat::Sequence(sequence) => {
+                if value.type_hash() != sequence.hash {
+                    return Ok(false);
+                }
+
+                let Some(items) = ir::sequence_items(&value, spanned)? else {
+                    return Ok(false);
+                };
+
+                let count = sequence.items.len();
+
+                if items.len() < count || (!sequence.is_open && items.len() > count) {
+                    return Ok(false);
+                }
+
+                for (pat, value) in sequence.items.iter().zip(items) {
+                    if !pat.matches(interp, value, spanned)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
         }
     }
 }
@@ -466,7 +550,88 @@ pub(crate) struct IrCall {
     /// Arguments to the call.
     pub(crate) args: Vec<Ir>,
     /// The target of the call.
-    pub(crate) id: ItemId,
+    pub(crate) kind: IrCallKind,
+}
+
+/// The target of a call.
+#[derive(Debug, TryClone, Clone, Copy)]
+#[try_clone(copy)]
+pub(crate) enum IrCallKind {
+    /// A constant function.
+    ConstFn(ItemId),
+    /// A native function with the given hash.
+    Native(Hash),
+    /// A native instance function with the given name hash, called on the
+    /// first argument.
+    Instance(Hash),
+}
+
+/// A match expression.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrMatch {
+    /// Span of the match.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The value being matched over.
+    pub(crate) expr: Box<Ir>,
+    /// The branches of the match.
+    pub(crate) branches: Vec<IrMatchBranch>,
+}
+
+/// A single branch in a match expression.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrMatchBranch {
+    /// Span of the branch.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The pattern of the branch.
+    pub(crate) pat: IrPat,
+    /// The optional guard of the branch.
+    pub(crate) condition: Option<Ir>,
+    /// The body of the branch.
+    pub(crate) body: Ir,
+}
+
+/// A loop over the values of a range or a vector.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrFor {
+    /// Span of the loop.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The label of the loop.
+    pub(crate) label: Option<Box<str>>,
+    /// The pattern each value is bound to.
+    pub(crate) pat: IrPat,
+    /// The value being iterated over.
+    pub(crate) iter: Box<Ir>,
+    /// The body of the loop.
+    pub(crate) body: IrScope,
+}
+
+/// An index expression.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrIndex {
+    /// Span of the index expression.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The value being indexed.
+    pub(crate) target: Box<Ir>,
+    /// The index.
+    pub(crate) index: Box<Ir>,
+}
+
+/// A range expression.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrRange {
+    /// Span of the range.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The start of the range.
+    pub(crate) start: Box<Ir>,
+    /// The end of the range.
+    pub(crate) end: Box<Ir>,
+    /// If the end of the range is inclusive.
+    pub(crate) inclusive: bool,
 }
 
 /// Vector expression.
@@ -35,6 +35,17 @@ pub(crate) fn expr(hir: &hir::Expr<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result
         hir::ExprKind::Call(hir) => ir::Ir::new(span, expr_call(span, c, hir)?),
         hir::ExprKind::If(hir) => ir::Ir::new(span, expr_if(span, c, hir)?),
         hir::ExprKind::Loop(hir) => ir::Ir::new(span, expr_loop(span, c, hir)?),
+        hir::ExprKind::For(hir) => ir::Ir::new(span, expr_for(span, c, hir)?),
+        hir::ExprKind::Match(hir) => ir::Ir::new(span, expr_match(span, c, hir)?),
+        hir::ExprKind::Index(hir) => ir::Ir::new(
+            span,
+            ir::IrIndex {
+                span,
+                target: Box::try_new(expr(&hir.target, c)?)?,
+                index: Box::try_new(expr(&hir.index, c)?)?,
+            },
+        ),
+        hir::ExprKind::Range(hir) => ir::Ir::new(span, expr_range(span, c, hir)?),
         hir::ExprKind::Lit(hir) => lit(c, span, hir)?,
         hir::ExprKind::Block(hir) => ir::Ir::new(span, block(hir, c)?),
         hir::ExprKind::FieldAccess(..) => ir::Ir::new(span, ir_target(hir)?),
@@ -132,14 +143,22 @@ fn expr_call(
         args.try_push(expr(e, c)?)?;
     }
 
-    if let hir::Call::ConstFn { id, .. } = hir.call {
-        return Ok(ir::IrCall { span, id, args });
-    }
+    let kind = match hir.call {
+        hir::Call::ConstFn { id, .. } => ir::IrCallKind::ConstFn(id),
+        hir::Call::Meta { hash } => ir::IrCallKind::Native(hash),
+        hir::Call::Associated { target, hash, .. } => {
+            args.try_insert(0, expr(target, c)?)?;
+            ir::IrCallKind::Instance(hash)
+        }
+        _ => {
+            return Err(compile::Error::msg(
+                span,
+                "Call not supported in constant contexts",
+            ));
+        }
+    };
 
-    Err(compile::Error::msg(
-        span,
-        "Call not supported in constant contexts",
-    ))
+    Ok(ir::IrCall { span, args, kind })
 }
 
 #[instrument_ast]
@@ -363,6 +382,10 @@ fn builtin_template(
 fn local(hir: &hir::Local<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<ir::Ir> {
     let span = hir.span();
 
+    if hir.else_block.is_some() {
+        return Err(compile::Error::msg(span, "not supported yet"));
+    }
+
     let name = match hir.pat.pat.kind {
         hir::PatKind::Ignore => {
             return expr(&hir.expr, c);
@@ -388,7 +411,7 @@ fn condition(hir: &hir::Condition<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<
     match hir {
         hir::Condition::Expr(e) => Ok(ir::IrCondition::Ir(expr(e, c)?)),
         hir::Condition::ExprLet(hir) => {
-            let pat = ir::IrPat::compile_ast(&hir.pat.pat)?;
+            let pat = ir::IrPat::compile_ast(&hir.pat.pat, c)?;
             let ir = expr(&hir.expr, c)?;
 
             Ok(ir::IrCondition::Let(ir::IrLet {
@@ -397,6 +420,18 @@ fn condition(hir: &hir::Condition<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<
                 ir,
             }))
         }
+        hir::Condition::Chain(hir) => {
+            let mut conditions = Vec::new();
+
+            for hir in hir.conditions {
+                conditions.try_push(condition(hir, c)?)?;
+            }
+
+            Ok(ir::IrCondition::Chain(ir::IrConditionChain {
+                span: hir.span(),
+                conditions,
+            }))
+        }
     }
 }
 
@@ -442,3 +477,70 @@ fn expr_loop(
         body: block(&hir.body, c)?,
     })
 }
+
+#[instrument_ast]
+fn expr_for(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprFor<'_>,
+) -> compile::Result<ir::IrFor> {
+    Ok(ir::IrFor {
+        span,
+        label: hir.label.map(TryInto::try_into).transpose()?,
+        pat: ir::IrPat::compile_ast(&hir.binding.pat, c)?,
+        iter: Box::try_new(expr(&hir.iter, c)?)?,
+        body: block(&hir.body, c)?,
+    })
+}
+
+#[instrument_ast]
+fn expr_match(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprMatch<'_>,
+) -> compile::Result<ir::IrMatch> {
+    let mut branches = Vec::try_with_capacity(hir.branches.len())?;
+
+    for branch in hir.branches {
+        branches.try_push(ir::IrMatchBranch {
+            span: branch.span,
+            pat: ir::IrPat::compile_ast(&branch.pat.pat, c)?,
+            condition: match branch.condition {
+                Some(hir) => Some(expr(hir, c)?),
+                None => None,
+            },
+            body: expr(&branch.body, c)?,
+        })?;
+    }
+
+    Ok(ir::IrMatch {
+        span,
+        expr: Box::try_new(expr(hir.expr, c)?)?,
+        branches,
+    })
+}
+
+#[instrument_ast]
+fn expr_range(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprRange<'_>,
+) -> compile::Result<ir::IrRange> {
+    let (start, end, inclusive) = match hir {
+        hir::ExprRange::Range { start, end } => (start, end, false),
+        hir::ExprRange::RangeInclusive { start, end } => (start, end, true),
+        _ => {
+            return Err(compile::Error::msg(
+                span,
+                "Only bounded ranges are supported in constant contexts",
+            ));
+        }
+    };
+
+    Ok(ir::IrRange {
+        span,
+        start: Box::try_new(expr(start, c)?)?,
+        end: Box::try_new(expr(end, c)?)?,
+        inclusive,
+    })
+}
@@ -2,13 +2,13 @@ use core::ops::{Add, BitAnd, BitOr, BitXor, Mul, Shl, Shr, Sub};
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::alloc::{Box, String, Vec};
+use crate::alloc::{self, Box, String, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::ir::{self};
-use crate::compile::{self, WithSpan};
+use crate::compile::{self, IrErrorKind, WithSpan};
 use crate::query::Used;
-use crate::runtime::{Inline, Object, OwnedTuple, Repr, Value};
-use crate::TypeHash;
+use crate::runtime::{self, Bytes, Inline, Object, OwnedTuple, Repr, Value};
+use crate::{Hash, TypeHash};
 
 /// The outcome of a constant evaluation.
 pub enum EvalOutcome {
@@ -178,17 +178,15 @@ fn eval_ir_branches(
     used: Used,
 ) -> Result<Value, EvalOutcome> {
     for (ir_condition, branch) in &ir.branches {
-        let guard = interp.scopes.push()?;
+        let output = scoped(interp, branch, |interp| {
+            let value = eval_ir_condition(ir_condition, interp, used)?;
 
-        let value = eval_ir_condition(ir_condition, interp, used)?;
-
-        let output = if value.as_bool().with_span(ir_condition)? {
-            Some(eval_ir_scope(branch, interp, used)?)
-        } else {
-            None
-        };
-
-        interp.scopes.pop(guard).with_span(branch)?;
+            if value.as_bool().with_span(ir_condition)? {
+                Ok(Some(eval_ir_scope(branch, interp, used)?))
+            } else {
+                Ok(None)
+            }
+        })?;
 
         if let Some(output) = output {
             return Ok(output);
@@ -213,7 +211,19 @@ fn eval_ir_call(
         args.try_push(eval_ir(arg, interp, used)?)?;
     }
 
-    Ok(interp.call_const_fn(ir, ir.id, args, used)?)
+    let hash = match ir.kind {
+        ir::IrCallKind::ConstFn(id) => return Ok(interp.call_const_fn(ir, id, args, used)?),
+        ir::IrCallKind::Native(hash) => hash,
+        ir::IrCallKind::Instance(hash) => {
+            let Some(target) = args.first() else {
+                return Err(EvalOutcome::not_const(ir));
+            };
+
+            Hash::associated_function(target.type_hash(), hash)
+        }
+    };
+
+    Ok(interp.call_native_fn(ir, hash, args)?)
 }
 
 fn eval_ir_condition(
@@ -221,6 +231,14 @@ fn eval_ir_condition(
     interp: &mut ir::Interpreter<'_, '_>,
     used: Used,
 ) -> Result<Value, EvalOutcome> {
+    Ok(Value::from(eval_ir_condition_bool(ir, interp, used)?))
+}
+
+fn eval_ir_condition_bool(
+    ir: &ir::IrCondition,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<bool, EvalOutcome> {
     let value = match ir {
         ir::IrCondition::Ir(ir) => {
             let value = eval_ir(ir, interp, used)?;
@@ -230,9 +248,18 @@ fn eval_ir_condition(
             let value = eval_ir(&ir_let.ir, interp, used)?;
             ir_let.pat.matches(interp, value, ir)?
         }
+        ir::IrCondition::Chain(chain) => {
+            for ir in &chain.conditions {
+                if !eval_ir_condition_bool(ir, interp, used)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
     };
 
-    Ok(Value::from(value))
+    Ok(value)
 }
 
 fn eval_ir_decl(
@@ -254,16 +281,14 @@ fn eval_ir_loop(
     let span = ir.span();
     interp.budget.take(span)?;
 
-    let guard = interp.scopes.push()?;
-
-    let value = loop {
+    let value = scoped(interp, ir, |interp| loop {
         if let Some(condition) = &ir.condition {
             interp.scopes.clear_current().with_span(condition)?;
 
             let value = eval_ir_condition(condition, interp, used)?;
 
             if !value.as_bool().with_span(condition)? {
-                break None;
+                return Ok(None);
             }
         }
 
@@ -272,7 +297,7 @@ fn eval_ir_loop(
             Err(outcome) => match outcome {
                 EvalOutcome::Break(span, label, expr) => {
                     if label.as_deref() == ir.label.as_deref() {
-                        break expr;
+                        return Ok(expr);
                     } else {
                         return Err(EvalOutcome::Break(span, label, expr));
                     }
@@ -280,9 +305,7 @@ fn eval_ir_loop(
                 outcome => return Err(outcome),
             },
         };
-    };
-
-    interp.scopes.pop(guard).with_span(ir)?;
+    })?;
 
     if let Some(value) = value {
         if ir.condition.is_some() {
@@ -298,6 +321,191 @@ fn eval_ir_loop(
     }
 }
 
+fn eval_ir_for(
+    ir: &ir::IrFor,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    let span = ir.span();
+    interp.budget.take(span)?;
+
+    let iter = eval_ir(&ir.iter, interp, used)?;
+    let values = ForValues::new(&iter, &ir.iter)?;
+
+    scoped(interp, ir, |interp| {
+        for value in values {
+            interp.budget.take(span)?;
+            interp.scopes.clear_current().with_span(span)?;
+
+            if !ir.pat.matches(interp, value, span)? {
+                continue;
+            }
+
+            match eval_ir_scope(&ir.body, interp, used) {
+                Ok(..) => (),
+                Err(EvalOutcome::Break(span, label, expr)) => {
+                    if label.as_deref() != ir.label.as_deref() {
+                        return Err(EvalOutcome::Break(span, label, expr));
+                    }
+
+                    if expr.is_some() {
+                        return Err(EvalOutcome::from(compile::Error::msg(
+                            span,
+                            "break with value is not supported for for loops",
+                        )));
+                    }
+
+                    break;
+                }
+                Err(outcome) => return Err(outcome),
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok(Value::unit())
+}
+
+/// The values being iterated over in a constant `for` loop.
+enum ForValues {
+    /// An integer range.
+    Range(i64, i64),
+    /// Values cloned out of a sequence.
+    Items(alloc::vec::IntoIter<Value>),
+}
+
+impl ForValues {
+    fn new<S>(iter: &Value, span: S) -> Result<Self, EvalOutcome>
+    where
+        S: Copy + Spanned,
+    {
+        match iter.type_hash() {
+            runtime::Range::HASH => {
+                let range = iter.borrow_ref::<runtime::Range>().with_span(span)?;
+                let start = range.start.as_integer::<i64>().with_span(span)?;
+                let end = range.end.as_integer::<i64>().with_span(span)?;
+                Ok(Self::Range(start, end))
+            }
+            runtime::RangeInclusive::HASH => {
+                let range = iter
+                    .borrow_ref::<runtime::RangeInclusive>()
+                    .with_span(span)?;
+                let start = range.start.as_integer::<i64>().with_span(span)?;
+                let end = range.end.as_integer::<i64>().with_span(span)?;
+
+                match end.checked_add(1) {
+                    Some(end) => Ok(Self::Range(start, end)),
+                    None => Err(EvalOutcome::not_const(span)),
+                }
+            }
+            _ => match sequence_items(iter, span)? {
+                Some(items) => Ok(Self::Items(items.into_iter())),
+                None => Err(EvalOutcome::not_const(span)),
+            },
+        }
+    }
+}
+
+impl Iterator for ForValues {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        match self {
+            Self::Range(start, end) => {
+                if *start >= *end {
+                    return None;
+                }
+
+                let value = *start;
+                *start += 1;
+                Some(Value::from(value))
+            }
+            Self::Items(items) => items.next(),
+        }
+    }
+}
+
+fn eval_ir_index(
+    ir: &ir::IrIndex,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+
+    let target = eval_ir(&ir.target, interp, used)?;
+    let index = eval_ir(&ir.index, interp, used)?;
+
+    if target.type_hash() == Object::HASH {
+        let object = target.borrow_ref::<Object>().with_span(ir)?;
+        let key = index.borrow_string_ref().with_span(&ir.index)?;
+
+        let Some(value) = object.get(&*key) else {
+            return Err(EvalOutcome::from(compile::Error::new(
+                ir,
+                IrErrorKind::MissingField {
+                    field: Box::try_from(&*key)?,
+                },
+            )));
+        };
+
+        return Ok(value.clone());
+    }
+
+    let Some(items) = sequence_items(&target, ir)? else {
+        return Err(EvalOutcome::not_const(ir));
+    };
+
+    let index = index.as_integer::<usize>().with_span(&ir.index)?;
+
+    match items.get(index) {
+        Some(value) => Ok(value.clone()),
+        None => Err(EvalOutcome::from(compile::Error::new(
+            ir,
+            IrErrorKind::MissingIndex { index },
+        ))),
+    }
+}
+
+fn eval_ir_match(
+    ir: &ir::IrMatch,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+
+    let value = eval_ir(&ir.expr, interp, used)?;
+
+    for branch in &ir.branches {
+        let output = scoped(interp, branch, |interp| {
+            let mut matched = branch.pat.matches(interp, value.clone(), branch)?;
+
+            if matched {
+                if let Some(condition) = &branch.condition {
+                    matched = eval_ir(condition, interp, used)?
+                        .as_bool()
+                        .with_span(condition)?;
+                }
+            }
+
+            if matched {
+                Ok(Some(eval_ir(&branch.body, interp, used)?))
+            } else {
+                Ok(None)
+            }
+        })?;
+
+        if let Some(output) = output {
+            return Ok(output);
+        }
+    }
+
+    Err(EvalOutcome::from(compile::Error::new(
+        ir,
+        IrErrorKind::UnmatchedPattern,
+    )))
+}
+
 fn eval_ir_object(
     ir: &ir::IrObject,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -313,26 +521,59 @@ fn eval_ir_object(
     Ok(Value::try_from(object).with_span(ir)?)
 }
 
-fn eval_ir_scope(
-    ir: &ir::IrScope,
+fn eval_ir_range(
+    ir: &ir::IrRange,
     interp: &mut ir::Interpreter<'_, '_>,
     used: Used,
 ) -> Result<Value, EvalOutcome> {
     interp.budget.take(ir)?;
-    let guard = interp.scopes.push()?;
 
-    for ir in &ir.instructions {
-        let _ = eval_ir(ir, interp, used)?;
-    }
+    let start = eval_ir(&ir.start, interp, used)?;
+    let end = eval_ir(&ir.end, interp, used)?;
 
-    let value = if let Some(last) = &ir.last {
-        eval_ir(last, interp, used)?
+    let value = if ir.inclusive {
+        Value::new(runtime::RangeInclusive::new(start, end))
     } else {
-        Value::unit()
+        Value::new(runtime::Range::new(start, end))
     };
 
-    interp.scopes.pop(guard).with_span(ir)?;
-    Ok(value)
+    Ok(value.with_span(ir)?)
+}
+
+fn eval_ir_scope(
+    ir: &ir::IrScope,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+
+    scoped(interp, ir, |interp| {
+        for ir in &ir.instructions {
+            let _ = eval_ir(ir, interp, used)?;
+        }
+
+        if let Some(last) = &ir.last {
+            eval_ir(last, interp, used)
+        } else {
+            Ok(Value::unit())
+        }
+    })
+}
+
+/// Evaluate the given closure in a new scope, which is popped on every exit
+/// path including errors and breaks.
+fn scoped<S, T>(
+    interp: &mut ir::Interpreter<'_, '_>,
+    span: S,
+    f: impl FnOnce(&mut ir::Interpreter<'_, '_>) -> Result<T, EvalOutcome>,
+) -> Result<T, EvalOutcome>
+where
+    S: Spanned,
+{
+    let guard = interp.scopes.push()?;
+    let result = f(interp);
+    interp.scopes.pop(guard).with_span(span)?;
+    result
 }
 
 fn eval_ir_set(
@@ -367,6 +608,9 @@ fn eval_ir_template(
                     Repr::Inline(Inline::Signed(integer)) => {
                         write!(buf, "{integer}")?;
                     }
+                    Repr::Inline(Inline::Unsigned(integer)) => {
+                        write!(buf, "{integer}")?;
+                    }
                     Repr::Inline(Inline::Float(float)) => {
                         let mut buffer = ryu::Buffer::new();
                         buf.try_push_str(buffer.format(*float))?;
@@ -444,5 +688,67 @@ pub(crate) fn eval_ir(
         ir::IrKind::Tuple(ir) => eval_ir_tuple(ir, interp, used),
         ir::IrKind::Object(ir) => eval_ir_object(ir, interp, used),
         ir::IrKind::Call(ir) => eval_ir_call(ir, interp, used),
+        ir::IrKind::Match(ir) => eval_ir_match(ir, interp, used),
+        ir::IrKind::For(ir) => eval_ir_for(ir, interp, used),
+        ir::IrKind::Index(ir) => eval_ir_index(ir, interp, used),
+        ir::IrKind::Range(ir) => eval_ir_range(ir, interp, used),
+    }
+}
+
+/// Clone the items out of a vector or a tuple.
+///
+/// Returns `None` if the value is neither.
+pub(crate) fn sequence_items<S>(value: &Value, spanned: S) -> compile::Result<Option<Vec<Value>>>
+where
+    S: Spanned,
+{
+    let items = match value.as_ref() {
+        Repr::Inline(Inline::Unit) => Vec::new(),
+        Repr::Any(any) => match any.type_hash() {
+            runtime::Vec::HASH => {
+                let vec = any.borrow_ref::<runtime::Vec>().with_span(spanned)?;
+                Vec::try_from(vec.as_slice())?
+            }
+            OwnedTuple::HASH => {
+                let tuple = any.borrow_ref::<OwnedTuple>().with_span(spanned)?;
+                Vec::try_from(&tuple[..])?
+            }
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(items))
+}
+
+/// Test two constant values for equality.
+///
+/// Returns `None` if the values can't be compared in a constant context.
+pub(crate) fn const_eq<S>(a: &Value, b: &Value, spanned: S) -> compile::Result<Option<bool>>
+where
+    S: Copy + Spanned,
+{
+    match (a.as_ref(), b.as_ref()) {
+        (Repr::Inline(a), Repr::Inline(b)) => Ok(Some(matches!(a.partial_eq(b), Ok(true)))),
+        (Repr::Any(a), Repr::Any(b)) => {
+            if a.type_hash() != b.type_hash() {
+                return Ok(Some(false));
+            }
+
+            match a.type_hash() {
+                String::HASH => {
+                    let a = a.borrow_ref::<String>().with_span(spanned)?;
+                    let b = b.borrow_ref::<String>().with_span(spanned)?;
+                    Ok(Some(*a == *b))
+                }
+                Bytes::HASH => {
+                    let a = a.borrow_ref::<Bytes>().with_span(spanned)?;
+                    let b = b.borrow_ref::<Bytes>().with_span(spanned)?;
+                    Ok(Some(a.as_slice() == b.as_slice()))
+                }
+                _ => Ok(None),
+            }
+        }
+        _ => Ok(Some(false)),
     }
 }
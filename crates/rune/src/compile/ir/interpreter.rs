@@ -7,8 +7,8 @@ use crate::compile::meta;
 use crate::compile::{self, IrErrorKind, ItemId, ModId, WithSpan};
 use crate::hir;
 use crate::query::{Query, Used};
-use crate::runtime::{self, ConstValue, Object, OwnedTuple, Repr, Value};
-use crate::TypeHash;
+use crate::runtime::{self, ConstValue, Object, OwnedTuple, Repr, Stack, Value};
+use crate::{Hash, TypeHash};
 
 /// The interpreter that executed [Ir][crate::ir::Ir].
 pub struct Interpreter<'a, 'arena> {
@@ -176,6 +176,42 @@ impl Interpreter<'_, '_> {
         self.scopes.pop(guard).with_span(span)?;
         Ok(value)
     }
+
+    /// Call a native function which has been marked as safe to call in
+    /// constant contexts.
+    pub(crate) fn call_native_fn<S>(
+        &mut self,
+        spanned: S,
+        hash: Hash,
+        args: Vec<Value>,
+    ) -> compile::Result<Value>
+    where
+        S: Copy + Spanned,
+    {
+        let Some(handler) = self.q.context.lookup_const_function(hash) else {
+            return Err(compile::Error::new(
+                spanned,
+                IrErrorKind::NotConstFn { hash },
+            ));
+        };
+
+        let count = args.len();
+        let mut stack = Stack::with_capacity(count.max(1))?;
+        let addr = stack.addr();
+
+        for value in args {
+            stack.push(value)?;
+        }
+
+        // Ensure we have space for the return value.
+        stack.resize(count.max(1))?;
+
+        handler
+            .call(&mut stack, addr, count, addr.output())
+            .with_span(spanned)?;
+
+        Ok(stack.at(addr).clone())
+    }
 }
 
 impl ir::Scopes {
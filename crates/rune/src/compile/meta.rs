@@ -328,6 +328,9 @@ pub struct FieldMeta {
     pub(crate) name: Box<str>,
     /// The position of the field.
     pub(crate) position: usize,
+    /// The declared type of the field, if it is annotated and type checks are
+    /// enabled.
+    pub(crate) ty: Option<Hash>,
 }
 
 /// Item and the module that the item belongs to.
@@ -357,6 +360,12 @@ impl ItemMeta {
 /// A description of a function signature.
 #[derive(Debug, TryClone)]
 pub struct Signature {
+    /// Declared parameters of the function, used to resolve named and default
+    /// arguments at the call site. Empty if the parameters are not known.
+    pub(crate) parameters: Box<[Parameter]>,
+    /// The declared type of the value produced by calling the function, if it
+    /// is annotated and type checks are enabled.
+    pub(crate) output: Option<Hash>,
     /// An asynchronous function.
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
@@ -374,8 +383,11 @@ impl Signature {
     pub(crate) fn from_context(
         doc: &DocFunction,
         common: &ModuleItemCommon,
+        parameters: Box<[Parameter]>,
     ) -> alloc::Result<Self> {
         Ok(Self {
+            parameters,
+            output: None,
             #[cfg(feature = "doc")]
             is_async: doc.is_async,
             #[cfg(feature = "doc")]
@@ -388,6 +400,64 @@ impl Signature {
             return_type: doc.return_type.try_clone()?,
         })
     }
+
+    /// Get the known type of the argument at the given position.
+    ///
+    /// For native functions this relies on type information which is only
+    /// collected when the `doc` feature is enabled.
+    pub(crate) fn argument_type(&self, index: usize) -> Option<Hash> {
+        if let Some(ty) = self.parameters.get(index).and_then(|p| p.ty) {
+            return Some(ty);
+        }
+
+        #[cfg(feature = "doc")]
+        if let Some(argument) = self.arguments.as_ref().and_then(|a| a.get(index)) {
+            if argument.base != Hash::EMPTY {
+                return Some(argument.base);
+            }
+        }
+
+        None
+    }
+
+    /// Get the known type of the value produced by calling the function.
+    ///
+    /// For native functions this relies on type information which is only
+    /// collected when the `doc` feature is enabled.
+    pub(crate) fn output_type(&self) -> Option<Hash> {
+        if let Some(ty) = self.output {
+            return Some(ty);
+        }
+
+        #[cfg(feature = "doc")]
+        if !self.is_async && self.return_type.base != Hash::EMPTY {
+            return Some(self.return_type.base);
+        }
+
+        None
+    }
+}
+
+/// A declared parameter of a function.
+#[derive(Debug, TryClone)]
+pub(crate) struct Parameter {
+    /// The name of the parameter, if it is a simple binding.
+    pub(crate) name: Option<Box<str>>,
+    /// The default value of the parameter.
+    pub(crate) default: Option<ParameterDefault>,
+    /// The declared type of the parameter, if it is annotated and type checks
+    /// are enabled.
+    pub(crate) ty: Option<Hash>,
+}
+
+/// The default value of a parameter.
+#[derive(Debug, TryClone, Clone, Copy)]
+#[try_clone(copy)]
+pub(crate) enum ParameterDefault {
+    /// The default value is a constant item which is evaluated on demand.
+    Item(ItemId),
+    /// The default value is a constant with the given hash.
+    Const(Hash),
 }
 
 #[cfg(feature = "doc")]
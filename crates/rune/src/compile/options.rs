@@ -101,6 +101,8 @@ pub struct OptionMeta {
 pub struct Options {
     /// Perform link-time checks.
     pub(crate) link_checks: bool,
+    /// Check type annotations against known signatures and definitions.
+    pub(crate) type_checks: bool,
     /// Memoize the instance function in a loop.
     pub(crate) memoize_instance_fn: bool,
     /// Include debug information when compiling.
@@ -132,6 +134,7 @@ impl Options {
     /// The default options.
     pub(crate) const DEFAULT: Options = Options {
         link_checks: true,
+        type_checks: false,
         memoize_instance_fn: true,
         debug_info: true,
         macros: true,
@@ -179,6 +182,20 @@ impl Options {
                 default: "true",
                 options: BOOL,
             },
+            OptionMeta {
+                key: "type-checks",
+                unstable: true,
+                doc: &docstring! {
+                    /// Check optional type annotations on function
+                    /// parameters, return values, fields and local
+                    /// bindings against native signatures and script
+                    /// definitions.
+                    ///
+                    /// Annotations are always erased at runtime.
+                },
+                default: "false",
+                options: BOOL,
+            },
             OptionMeta {
                 key: "memoize-instance-fn",
                 unstable: false,
@@ -248,6 +265,13 @@ impl Options {
                     /// Supports a value of 0-3 with increasingly higher
                     /// levels of optimizations applied.
                     ///
+                    /// * `1` eliminates branches on constant conditions,
+                    ///   threads jumps and removes unreachable code.
+                    /// * `2` additionally folds constant arithmetic and
+                    ///   comparisons.
+                    /// * `3` additionally propagates copies and removes
+                    ///   unused stores.
+                    ///
                     /// Enabling a higher level results in better code
                     /// generation, but contributes to compilation times.
                 },
@@ -344,6 +368,9 @@ impl Options {
                 "link-checks" => {
                     self.link_checks = tail.is_none_or(|s| s == "true");
                 }
+                "type-checks" => {
+                    self.type_checks = tail.is_none_or(|s| s == "true");
+                }
                 "macros" => {
                     self.macros = tail.is_none_or(|s| s == "true");
                 }
@@ -360,6 +387,8 @@ impl Options {
                     self.lowering = match tail {
                         Some("0") | None => 0,
                         Some("1") => 1,
+                        Some("2") => 2,
+                        Some("3") => 3,
                         _ => {
                             return Err(ParseOptionError {
                                 env,
@@ -433,6 +462,14 @@ impl Options {
         self.link_checks = enabled;
     }
 
+    /// Set if type annotations should be checked or not. Defaults to `false`.
+    /// Mismatches are reported as compile errors, but annotations never affect
+    /// the runtime behavior of a program.
+    #[inline]
+    pub fn type_checks(&mut self, enabled: bool) {
+        self.type_checks = enabled;
+    }
+
     /// Set if macros are enabled or not. Defaults to `false`.
     #[inline]
     pub fn macros(&mut self, enabled: bool) {
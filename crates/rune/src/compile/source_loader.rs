@@ -78,7 +78,12 @@ impl SourceLoader for FileSourceLoader {
             ));
         }
 
-        for c in item {
+        // The library of a dependency is mounted as a module named after the
+        // dependency, so its modules are loaded relative to the first
+        // component.
+        let skip = usize::from(sources.dependency(id).is_some());
+
+        for c in item.iter().skip(skip) {
             if let ComponentRef::Str(string) = c {
                 base.push(string);
             } else {
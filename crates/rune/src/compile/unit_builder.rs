@@ -17,8 +17,8 @@ use crate::runtime::debug::{DebugArgs, DebugSignature};
 use crate::runtime::inst;
 use crate::runtime::unit::UnitEncoder;
 use crate::runtime::{
-    Address, Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, RttiKind,
-    StaticString, Unit, UnitFn,
+    Address, Call, ConstValue, DebugInfo, DebugInst, DebugVariable, Inst, Label, Protocol, Rtti,
+    RttiKind, StaticString, Unit, UnitFn,
 };
 use crate::sync::Arc;
 use crate::{Context, Diagnostics, Hash, Item, SourceId};
@@ -695,6 +695,26 @@ impl UnitBuilder {
         Ok(())
     }
 
+    /// Register a function hash which is an alias to the function identified
+    /// by `target`.
+    ///
+    /// This is used to bind the functions of trait implementations.
+    pub(crate) fn new_function_alias(
+        &mut self,
+        location: Location,
+        hash: Hash,
+        target: Hash,
+    ) -> compile::Result<()> {
+        if self.reexports.try_insert(hash, target)?.is_some() {
+            return Err(compile::Error::new(
+                location.span,
+                ErrorKind::FunctionReExportConflict { hash },
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Declare a new instance function at the current instruction pointer.
     pub(crate) fn new_function(
         &mut self,
@@ -832,6 +852,9 @@ impl UnitBuilder {
             }
         }
 
+        let mut variables = assembly.variables;
+        let mut freed = assembly.freed;
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = String::new();
 
@@ -936,10 +959,17 @@ impl UnitBuilder {
                 Some(comment.try_into()?)
             };
 
-            self.debug_mut()?.instructions.try_insert(
-                at,
-                DebugInst::new(location.source_id, span, comment, labels),
-            )?;
+            let mut debug = DebugInst::new(location.source_id, span, comment, labels);
+
+            for (name, addr) in variables.remove(&pos).into_iter().flatten() {
+                debug.variables.try_push(DebugVariable::new(name, addr))?;
+            }
+
+            if let Some(addrs) = freed.remove(&pos) {
+                debug.freed = addrs;
+            }
+
+            self.debug_mut()?.instructions.try_insert(at, debug)?;
         }
 
         Ok(())
@@ -4,8 +4,9 @@ use core::slice;
 
 use tracing::instrument_ast;
 
+use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::BTreeMap;
+use crate::alloc::{BTreeMap, HashMap};
 use crate::ast::{self, Spanned};
 use crate::compile::ir;
 use crate::compile::{self, Assembly, ErrorKind, ItemId, ModId, Options, WithSpan};
@@ -58,6 +59,8 @@ pub(crate) struct Ctxt<'a, 'hir, 'arena> {
     pub(crate) asm: &'a mut Assembly,
     /// Scopes defined in the compiler.
     pub(crate) scopes: &'a Scopes<'hir>,
+    /// Names of variables, used to emit debug information.
+    pub(crate) names: &'a HashMap<hir::Variable, hir::Name<'hir>>,
     /// Context for which to emit warnings.
     pub(crate) contexts: Vec<&'hir dyn Spanned>,
     /// The nesting of loop we are currently in.
@@ -71,6 +74,44 @@ pub(crate) struct Ctxt<'a, 'hir, 'arena> {
 }
 
 impl<'hir> Ctxt<'_, 'hir, '_> {
+    /// Define a variable, recording its name in the debug information.
+    fn define(
+        &mut self,
+        span: &'hir dyn Spanned,
+        name: hir::Variable,
+        addr: &Address<'_, 'hir>,
+    ) -> compile::Result<()> {
+        self.scopes.define(span, name, addr)?;
+
+        if self.options.debug_info {
+            let n = match self.names.get(&name) {
+                Some(hir::Name::Str(n)) => Some(*n),
+                Some(hir::Name::SelfValue) => Some("self"),
+                None => None,
+            };
+
+            if let Some(n) = n {
+                self.asm.variable(n, addr.addr())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pop the given scope, recording the variables going out of scope in the
+    /// debug information.
+    fn pop_scope(&mut self, span: &dyn Spanned, handle: ScopeHandle) -> compile::Result<()> {
+        let freed = self.scopes.pop(span, handle)?;
+
+        if self.options.debug_info {
+            for addr in freed {
+                self.asm.free(addr)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn drop_dangling(&mut self, span: &dyn Spanned) -> compile::Result<()> {
         self.scopes
             .drain_dangling_into(&mut self.drop)
@@ -227,7 +268,7 @@ pub(crate) fn fn_from_item_fn<'hir>(
                     return Err(compile::Error::new(span, ErrorKind::UnsupportedSelf));
                 }
 
-                cx.scopes.define(span, *name, needs)?;
+                cx.define(span, *name, needs)?;
             }
             hir::FnArg::Pat(pat) => {
                 let asm = pattern_panic(cx, pat, move |cx, false_label| {
@@ -265,7 +306,7 @@ pub(crate) fn async_block_secondary<'hir>(
     let linear = cx.scopes.linear(&hir.block, hir.captures.len())?;
 
     for (name, needs) in hir.captures.iter().copied().zip(&linear) {
-        cx.scopes.define(&hir.block, name, needs)?;
+        cx.define(&hir.block, name, needs)?;
     }
 
     return_(cx, &hir.block, hir.block, block_without_scope)?.ignore();
@@ -295,7 +336,7 @@ pub(crate) fn expr_closure_secondary<'hir>(
         )?;
 
         for (capture, needs) in hir.captures.iter().copied().zip(&environment) {
-            cx.scopes.define(hir, capture, needs)?;
+            cx.define(hir, capture, needs)?;
         }
     }
 
@@ -451,7 +492,7 @@ fn pat_binding_with<'a, 'hir>(
     }
 
     for (name, needs) in names.iter().copied().zip(linear.iter()) {
-        cx.scopes.define(needs.span(), name, needs)?;
+        cx.define(needs.span(), name, needs)?;
     }
 
     Ok(asm)
@@ -488,7 +529,7 @@ fn pat_binding_with_single<'a, 'hir>(
         ));
     };
 
-    cx.scopes.define(needs.span(), name, addr)?;
+    cx.define(needs.span(), name, addr)?;
     Ok(asm)
 }
 
@@ -655,7 +696,7 @@ fn condition<'a, 'hir>(
                 Asm::new(hir, (scope, Pattern::Irrefutable))
             } else {
                 addr.free()?;
-                cx.scopes.pop(hir, scope)?;
+                cx.pop_scope(hir, scope)?;
                 Asm::diverge(hir)
             };
 
@@ -684,10 +725,85 @@ fn condition<'a, 'hir>(
                 cx.asm.jump(then_label, span)?;
                 Ok(Asm::new(span, (scope, pat)))
             } else {
-                cx.scopes.pop(span, scope)?;
+                cx.pop_scope(span, scope)?;
                 Ok(Asm::diverge(span))
             }
         }
+        hir::Condition::Chain(hir) => {
+            let span = hir;
+
+            let scope = cx.scopes.child(span)?;
+            let mut pattern = Pattern::Irrefutable;
+            let mut offset = 0;
+
+            for link in hir.conditions {
+                let converging = match *link {
+                    hir::Condition::Expr(hir) => {
+                        let mut addr = cx.scopes.alloc(hir)?.with_name("chain condition");
+
+                        let converging = expr(cx, hir, &mut addr)?.converging();
+
+                        if converging {
+                            cx.asm.jump_if_not(addr.addr(), false_label, hir)?;
+                            pattern = Pattern::Refutable;
+                        }
+
+                        addr.free()?;
+                        converging
+                    }
+                    hir::Condition::ExprLet(hir) => {
+                        let count = hir.pat.names.len();
+
+                        let Some(linear) = linear.get_mut(offset..offset + count) else {
+                            return Err(compile::Error::msg(
+                                hir,
+                                "Missing bindings for condition chain",
+                            ));
+                        };
+
+                        offset += count;
+
+                        let mut load =
+                            |cx: &mut Ctxt<'a, 'hir, '_>, needs: &mut dyn Needs<'a, 'hir>| {
+                                expr(cx, &hir.expr, needs)
+                            };
+
+                        let asm = pat_binding_with(
+                            cx,
+                            &hir.pat,
+                            &hir.pat.pat,
+                            hir.pat.names,
+                            false_label,
+                            &mut load,
+                            linear,
+                        )?;
+
+                        match asm.into_converging() {
+                            Some(Pattern::Refutable) => {
+                                pattern = Pattern::Refutable;
+                                true
+                            }
+                            Some(Pattern::Irrefutable) => true,
+                            None => false,
+                        }
+                    }
+                    hir::Condition::Chain(hir) => {
+                        return Err(compile::Error::msg(
+                            hir,
+                            "Nested condition chains are not supported",
+                        ));
+                    }
+                };
+
+                if !converging {
+                    cx.pop_scope(span, scope)?;
+                    return Ok(Asm::diverge(span));
+                }
+            }
+
+            cx.asm.jump(then_label, span)?;
+            Ok(Asm::new(span, (scope, pattern)))
+        }
     }
 }
 
@@ -910,7 +1026,7 @@ fn block<'a, 'hir>(
 
     let scope = cx.scopes.child(hir)?;
     let asm = block_without_scope(cx, hir, needs)?;
-    cx.scopes.pop(hir, scope)?;
+    cx.pop_scope(hir, scope)?;
 
     cx.drop_dangling(hir)?;
 
@@ -1919,7 +2035,7 @@ fn expr_call<'a, 'hir>(
 
             linear.free_non_dangling()?;
         }
-        hir::Call::Associated { target, hash } => {
+        hir::Call::Associated { target, hash, .. } => {
             let linear = converge!(exprs_2(cx, span, slice::from_ref(target), hir.args)?);
 
             cx.asm.push(
@@ -2143,7 +2259,7 @@ fn expr_continue<'a, 'hir>(
     };
 
     cx.asm.jump(label, span)?;
-    Ok(Asm::new(span, ()))
+    Ok(Asm::diverge(span))
 }
 
 /// Assemble an expr field access, like `<value>.<field>`.
@@ -2353,7 +2469,7 @@ fn expr_for<'a, 'hir>(
 
     let asm = block(cx, &hir.body, &mut Any::ignore(span))?;
     bindings.free()?;
-    cx.scopes.pop(span, inner_loop_scope)?;
+    cx.pop_scope(span, inner_loop_scope)?;
 
     if asm.converging() {
         cx.asm.jump(&continue_label, span)?;
@@ -2481,7 +2597,7 @@ fn expr_if<'a, 'hir>(
             }
         }
 
-        cx.scopes.pop(branch, scope)?;
+        cx.pop_scope(branch, scope)?;
     }
 
     linear.free()?;
@@ -2611,7 +2727,7 @@ fn expr_match<'a, 'hir>(
                 }
 
                 cond.free()?;
-                cx.scopes.pop(span, scope)?;
+                cx.pop_scope(span, scope)?;
             } else {
                 // If there is no branch condition, and the branch is
                 // irrefutable, there is no point in assembling the additional
@@ -2627,7 +2743,7 @@ fn expr_match<'a, 'hir>(
                 // If the branch condition diverges, there is no reason to
                 // assemble the other branches if this one is irrefutable.
                 is_irrefutable = matches!(pat, Pattern::Irrefutable);
-                cx.scopes.pop(span, pattern_scope)?;
+                cx.pop_scope(span, pattern_scope)?;
             }
         }
 
@@ -2658,7 +2774,7 @@ fn expr_match<'a, 'hir>(
             cx.asm.jump(&end_label, span)?;
         }
 
-        cx.scopes.pop(span, scope)?;
+        cx.pop_scope(span, scope)?;
     }
 
     cx.asm.label(&end_label)?;
@@ -2957,7 +3073,7 @@ fn expr_select_inner<'a, 'hir>(
             cx.asm.jump(&end_label, span)?;
         }
 
-        cx.scopes.pop(&branch.body, scope)?;
+        cx.pop_scope(&branch.body, scope)?;
     }
 
     cx.select_branches = branches;
@@ -3207,7 +3323,7 @@ fn expr_loop<'a, 'hir>(
     block(cx, &hir.body, &mut Any::ignore(span))?.ignore();
 
     if let Some(scope) = condition_scope {
-        cx.scopes.pop(span, scope)?;
+        cx.pop_scope(span, scope)?;
     }
 
     cx.asm.jump(&continue_label, span)?;
@@ -3310,9 +3426,50 @@ fn local<'a, 'hir>(
     let mut load =
         |cx: &mut Ctxt<'a, 'hir, '_>, needs: &mut dyn Needs<'a, 'hir>| expr(cx, &hir.expr, needs);
 
-    converge!(pattern_panic(cx, &hir.pat, |cx, false_label| {
-        pat_binding(cx, &hir.pat, false_label, &mut load)
-    })?);
+    if let Some(else_block) = hir.else_block {
+        let false_label = cx.asm.new_label("let_else");
+
+        let pat = converge!(pat_binding(cx, &hir.pat, &false_label, &mut load)?);
+
+        if matches!(pat, Pattern::Refutable) {
+            let match_label = cx.asm.new_label("let_else_match");
+
+            cx.asm.jump(&match_label, hir)?;
+            cx.asm.label(&false_label)?;
+
+            if block(cx, else_block, &mut Any::ignore(else_block))?.converging() {
+                // Calls to functions which are known to diverge, like
+                // `panic(..)`, are not visible as diverging in the assembly.
+                // The panic below is never reached for them.
+                if !ends_with_diverging_call(else_block) {
+                    return Err(compile::Error::msg(
+                        else_block,
+                        "The else block in a `let ... else` statement must diverge",
+                    ));
+                }
+
+                cx.asm.push(
+                    inst::Kind::Panic {
+                        reason: PanicReason::UnmatchedPattern,
+                    },
+                    else_block,
+                )?;
+            }
+
+            cx.asm.label(&match_label)?;
+        } else {
+            cx.q.diagnostics.unreachable_let_else(
+                cx.source_id,
+                else_block,
+                &hir.pat,
+                cx.context(),
+            )?;
+        }
+    } else {
+        converge!(pattern_panic(cx, &hir.pat, |cx, false_label| {
+            pat_binding(cx, &hir.pat, false_label, &mut load)
+        })?);
+    }
 
     // If a value is needed for a let expression, it is evaluated as a unit.
     if let Some(out) = needs.try_alloc_output()? {
@@ -3321,3 +3478,18 @@ fn local<'a, 'hir>(
 
     Ok(Asm::new(hir, ()))
 }
+
+/// Test if a block ends with a call to a function which is known to diverge.
+fn ends_with_diverging_call(hir: &hir::Block<'_>) -> bool {
+    let last = match (hir.value, hir.statements.last()) {
+        (Some(value), _) => value,
+        (None, Some(hir::Stmt::Expr(expr))) => *expr,
+        _ => return false,
+    };
+
+    let hir::ExprKind::Call(call) = last.kind else {
+        return false;
+    };
+
+    matches!(call.call, hir::Call::Meta { hash } if hash == rune::hash!(::std::panic))
+}
@@ -22,3 +22,5 @@ use self::linear::Linear;
 
 mod display_named;
 use self::display_named::DisplayNamed;
+
+pub(crate) mod optimize;
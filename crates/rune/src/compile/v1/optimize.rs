@@ -0,0 +1,754 @@
+//! Optimizations of assembled functions.
+//!
+//! Which passes are applied is controlled by the unstable `lowering` option:
+//!
+//! * `1` eliminates branches on constant conditions, threads jumps through
+//!   other jumps and removes unreachable instructions.
+//! * `2` additionally folds arithmetic, comparisons and unary operations whose
+//!   operands are constant.
+//! * `3` additionally propagates copies, and removes stores and copies whose
+//!   values are never read.
+//!
+//! Constants and copies are tracked in a single forward pass over the
+//! instructions of a function. Any instruction which isn't understood by the
+//! optimizer forgets everything known about the stack, so everything around
+//! calls and other side effects is left as-is.
+
+use core::mem::take;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap, HashSet, Vec};
+use crate::compile::{self, Assembly, AssemblyInst};
+use crate::runtime::{inst, Address, ArithmeticOps, InstOp, InstValue, Label, Output};
+
+/// The maximum number of times the passes are repeated.
+const MAX_ROUNDS: usize = 4;
+
+/// Optimize the given assembly at the given level.
+pub(crate) fn assembly(asm: &mut Assembly, level: u8) -> compile::Result<()> {
+    if level == 0 {
+        return Ok(());
+    }
+
+    for _ in 0..MAX_ROUNDS {
+        let mut changed = propagate(asm, level)?;
+        changed |= thread_jumps(asm)?;
+        changed |= remove_dead(asm, level)?;
+
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// What is known about the stack at a given instruction.
+#[derive(Default)]
+struct State {
+    /// Slots known to hold a constant.
+    constants: HashMap<usize, InstValue>,
+    /// Slots known to hold a copy of another slot.
+    copies: HashMap<usize, usize>,
+}
+
+impl State {
+    /// Only keep what is also known in `other`.
+    fn meet(&mut self, other: &State) {
+        self.constants.retain(|slot, value| {
+            other
+                .constants
+                .get(slot)
+                .is_some_and(|other| same(value, other))
+        });
+
+        self.copies
+            .retain(|slot, src| other.copies.get(slot) == Some(src));
+    }
+
+    /// Forget everything known about the stack.
+    fn clear(&mut self) {
+        self.constants.clear();
+        self.copies.clear();
+    }
+
+    /// Forget everything known about the given slot.
+    fn clobber(&mut self, slot: usize) {
+        self.constants.remove(&slot);
+        self.copies.remove(&slot);
+        self.copies.retain(|_, src| *src != slot);
+    }
+
+    /// Record that the given output is written to.
+    fn write(&mut self, out: Output) {
+        if let Some(addr) = out.as_addr() {
+            self.clobber(addr.offset());
+        }
+    }
+
+    /// Record that the given output holds a constant.
+    fn set(&mut self, out: Output, value: InstValue) -> alloc::Result<()> {
+        if let Some(addr) = out.as_addr() {
+            self.constants.try_insert(addr.offset(), value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record that the given output holds a copy of `addr`.
+    fn copy(&mut self, addr: Address, out: Output) -> alloc::Result<()> {
+        if let Some(out) = out.as_addr() {
+            if out != addr {
+                self.copies.try_insert(out.offset(), addr.offset())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the constant held in the given address, if known.
+    fn constant(&self, addr: Address) -> Option<InstValue> {
+        self.constants.get(&addr.offset()).copied()
+    }
+
+    /// Rewrite an address to read from the slot it is a copy of.
+    fn resolve(&self, addr: &mut Address) -> bool {
+        let Some(&src) = self.copies.get(&addr.offset()) else {
+            return false;
+        };
+
+        *addr = Address::new(src);
+        true
+    }
+}
+
+impl TryClone for State {
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(Self {
+            constants: self.constants.try_clone()?,
+            copies: self.copies.try_clone()?,
+        })
+    }
+}
+
+/// Propagate constants and copies forward through the assembly, eliminating
+/// branches on constant conditions and folding operations on constants.
+fn propagate(asm: &mut Assembly, level: u8) -> compile::Result<bool> {
+    let positions = label_positions(asm)?;
+
+    // Positions which are jumped to from the same or a later instruction. What
+    // is known when entering them can't be determined in a single pass.
+    let mut loops = HashSet::new();
+
+    for (at, (inst, _)) in asm.instructions.iter().enumerate() {
+        if let Some(&to) = target(inst).and_then(|label| positions.get(&label.index)) {
+            if to <= at {
+                loops.try_insert(to)?;
+            }
+        }
+    }
+
+    let mut incoming = HashMap::<usize, State>::new();
+    let mut removed = Vec::try_with_capacity(asm.instructions.len())?;
+    let mut changed = false;
+    let mut state = Some(State::default());
+
+    for at in 0..asm.instructions.len() {
+        if asm.labels.contains_key(&at) {
+            let other = incoming.remove(&at);
+
+            state = if loops.contains(&at) {
+                Some(State::default())
+            } else {
+                match (state, other) {
+                    (Some(mut state), Some(other)) => {
+                        state.meet(&other);
+                        Some(state)
+                    }
+                    (state, other) => state.or(other),
+                }
+            };
+        }
+
+        let current = state.get_or_insert_with(State::default);
+        let (inst, _) = &mut asm.instructions[at];
+        let when = matches!(inst, AssemblyInst::JumpIf { .. });
+        let mut falls = true;
+        let mut remove = false;
+
+        match inst {
+            AssemblyInst::Jump { label } => {
+                edge(&mut incoming, &positions, label, at, current)?;
+                falls = false;
+            }
+            AssemblyInst::JumpIf { addr, label } | AssemblyInst::JumpIfNot { addr, label } => {
+                if level >= 3 {
+                    changed |= current.resolve(addr);
+                }
+
+                match current.constant(*addr) {
+                    Some(InstValue::Bool(cond)) if cond == when => {
+                        let label = label.try_clone()?;
+                        edge(&mut incoming, &positions, &label, at, current)?;
+                        *inst = AssemblyInst::Jump { label };
+                        falls = false;
+                        changed = true;
+                    }
+                    Some(InstValue::Bool(..)) => {
+                        remove = true;
+                        changed = true;
+                    }
+                    _ => {
+                        edge(&mut incoming, &positions, label, at, current)?;
+                    }
+                }
+            }
+            AssemblyInst::IterNext { label, out, .. } => {
+                current.write(*out);
+                edge(&mut incoming, &positions, label, at, current)?;
+            }
+            AssemblyInst::Raw { raw } => {
+                changed |= raw_inst(raw, current, level)?;
+                falls = !matches!(raw, inst::Kind::Return { .. } | inst::Kind::ReturnUnit);
+            }
+        }
+
+        removed.try_push(remove)?;
+
+        if !falls {
+            state = None;
+        }
+    }
+
+    changed |= compact(asm, &removed)?;
+    Ok(changed)
+}
+
+/// Record what is known when jumping to `label` from the instruction at `at`.
+fn edge(
+    incoming: &mut HashMap<usize, State>,
+    positions: &HashMap<usize, usize>,
+    label: &Label,
+    at: usize,
+    state: &State,
+) -> alloc::Result<()> {
+    let Some(&to) = positions.get(&label.index) else {
+        return Ok(());
+    };
+
+    if to <= at {
+        return Ok(());
+    }
+
+    match incoming.get_mut(&to) {
+        Some(existing) => existing.meet(state),
+        None => {
+            incoming.try_insert(to, state.try_clone()?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Update the state with the effects of a raw instruction, rewriting it if
+/// possible.
+fn raw_inst(kind: &mut inst::Kind, state: &mut State, level: u8) -> alloc::Result<bool> {
+    let fold = level >= 2;
+    let propagate = level >= 3;
+
+    let changed = match kind {
+        inst::Kind::Store { value, out } => {
+            state.write(*out);
+            state.set(*out, *value)?;
+            false
+        }
+        inst::Kind::Copy { addr, out } => {
+            let changed = propagate && state.resolve(addr);
+            let value = state.constant(*addr);
+            state.write(*out);
+
+            if let Some(value) = value {
+                state.set(*out, value)?;
+            }
+
+            if propagate {
+                state.copy(*addr, *out)?;
+            }
+
+            changed
+        }
+        inst::Kind::Move { addr, out } => {
+            let value = state.constant(*addr);
+            state.clobber(addr.offset());
+            state.write(*out);
+
+            if let Some(value) = value {
+                state.set(*out, value)?;
+            }
+
+            false
+        }
+        inst::Kind::Arithmetic { op, a, b, out } => {
+            let changed = propagate && (state.resolve(a) | state.resolve(b));
+
+            let value = match (state.constant(*a), state.constant(*b)) {
+                (Some(a), Some(b)) if fold => arithmetic(ArithmeticOps::from_op(*op), a, b),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::Op { op, a, b, out } => {
+            let changed = propagate && (state.resolve(a) | state.resolve(b));
+
+            let value = match (state.constant(*a), state.constant(*b)) {
+                (Some(a), Some(b)) if fold => operation(*op, a, b),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::Not { addr, out } => {
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(InstValue::Bool(value)) if fold => Some(InstValue::Bool(!value)),
+                Some(InstValue::Unsigned(value)) if fold => Some(InstValue::Unsigned(!value)),
+                Some(InstValue::Integer(value)) if fold => Some(InstValue::Integer(!value)),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::Neg { addr, out } => {
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(InstValue::Integer(value)) if fold => {
+                    value.checked_neg().map(InstValue::Integer)
+                }
+                Some(InstValue::Float(value)) if fold => Some(InstValue::Float(-value)),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::EqSigned { addr, value, out } => {
+            let expected = *value;
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(value) if fold => Some(InstValue::Bool(
+                    matches!(value, InstValue::Integer(value) if value == expected),
+                )),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::EqUnsigned { addr, value, out } => {
+            let expected = *value;
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(value) if fold => Some(InstValue::Bool(
+                    matches!(value, InstValue::Unsigned(value) if value == expected),
+                )),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::EqBool { addr, value, out } => {
+            let expected = *value;
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(value) if fold => Some(InstValue::Bool(
+                    matches!(value, InstValue::Bool(value) if value == expected),
+                )),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::EqChar { addr, value, out } => {
+            let expected = *value;
+            let changed = propagate && state.resolve(addr);
+
+            let value = match state.constant(*addr) {
+                Some(value) if fold => Some(InstValue::Bool(
+                    matches!(value, InstValue::Char(value) if value == expected),
+                )),
+                _ => None,
+            };
+
+            let out = *out;
+            changed | store(kind, state, out, value)?
+        }
+        inst::Kind::Return { addr } => propagate && state.resolve(addr),
+        inst::Kind::ReturnUnit => false,
+        _ => {
+            state.clear();
+            false
+        }
+    };
+
+    Ok(changed)
+}
+
+/// Record that an instruction writes to `out`, replacing it with a store if
+/// the value it produces is known.
+fn store(
+    kind: &mut inst::Kind,
+    state: &mut State,
+    out: Output,
+    value: Option<InstValue>,
+) -> alloc::Result<bool> {
+    state.write(out);
+
+    let Some(value) = value else {
+        return Ok(false);
+    };
+
+    state.set(out, value)?;
+    *kind = inst::Kind::Store { value, out };
+    Ok(true)
+}
+
+/// Evaluate an arithmetic operation the same way the virtual machine does.
+///
+/// Operations which would raise an error at runtime are not evaluated.
+fn arithmetic(ops: &ArithmeticOps, a: InstValue, b: InstValue) -> Option<InstValue> {
+    match (a, b) {
+        (InstValue::Integer(a), InstValue::Integer(b)) => {
+            Some(InstValue::Integer((ops.i64)(a, b)?))
+        }
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => {
+            Some(InstValue::Unsigned((ops.u64)(a, b)?))
+        }
+        (InstValue::Float(a), InstValue::Float(b)) => Some(InstValue::Float((ops.f64)(a, b))),
+        _ => None,
+    }
+}
+
+/// Evaluate a comparison or boolean operation the same way the virtual machine
+/// does.
+fn operation(op: InstOp, a: InstValue, b: InstValue) -> Option<InstValue> {
+    let ordering = match (a, b) {
+        (InstValue::Integer(a), InstValue::Integer(b)) => a.cmp(&b),
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => a.cmp(&b),
+        (InstValue::Char(a), InstValue::Char(b)) => a.cmp(&b),
+        (InstValue::Bool(a), InstValue::Bool(b)) => {
+            return match op {
+                InstOp::Eq => Some(InstValue::Bool(a == b)),
+                InstOp::Neq => Some(InstValue::Bool(a != b)),
+                InstOp::And => Some(InstValue::Bool(a && b)),
+                InstOp::Or => Some(InstValue::Bool(a || b)),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+
+    let value = match op {
+        InstOp::Lt => ordering.is_lt(),
+        InstOp::Le => ordering.is_le(),
+        InstOp::Gt => ordering.is_gt(),
+        InstOp::Ge => ordering.is_ge(),
+        InstOp::Eq => ordering.is_eq(),
+        InstOp::Neq => ordering.is_ne(),
+        _ => return None,
+    };
+
+    Some(InstValue::Bool(value))
+}
+
+/// Test if two constants are known to be the same.
+fn same(a: &InstValue, b: &InstValue) -> bool {
+    match (a, b) {
+        (InstValue::Unit, InstValue::Unit) => true,
+        (InstValue::Bool(a), InstValue::Bool(b)) => a == b,
+        (InstValue::Char(a), InstValue::Char(b)) => a == b,
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => a == b,
+        (InstValue::Integer(a), InstValue::Integer(b)) => a == b,
+        (InstValue::Float(a), InstValue::Float(b)) => a.to_bits() == b.to_bits(),
+        _ => false,
+    }
+}
+
+/// Retarget jumps whose target is another unconditional jump.
+fn thread_jumps(asm: &mut Assembly) -> compile::Result<bool> {
+    let positions = label_positions(asm)?;
+    let mut changed = false;
+
+    for at in 0..asm.instructions.len() {
+        let Some(label) = target(&asm.instructions[at].0) else {
+            continue;
+        };
+
+        let mut current = label.try_clone()?;
+
+        // Limit the number of hops, since a chain of jumps might loop.
+        for _ in 0..asm.instructions.len() {
+            let Some(&to) = positions.get(&current.index) else {
+                break;
+            };
+
+            let Some((AssemblyInst::Jump { label: next }, _)) = asm.instructions.get(to) else {
+                break;
+            };
+
+            if next.index == current.index {
+                break;
+            }
+
+            current = next.try_clone()?;
+        }
+
+        if let Some(label) = target_mut(&mut asm.instructions[at].0) {
+            if label.index != current.index {
+                *label = current;
+                changed = true;
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Remove unreachable instructions, jumps to the next instruction and, at
+/// level 3 and above, stores and copies whose values are never read.
+fn remove_dead(asm: &mut Assembly, level: u8) -> compile::Result<bool> {
+    let positions = label_positions(asm)?;
+    let mut referenced = HashSet::new();
+
+    for (inst, _) in &asm.instructions {
+        if let Some(&to) = target(inst).and_then(|label| positions.get(&label.index)) {
+            referenced.try_insert(to)?;
+        }
+    }
+
+    let len = asm.instructions.len();
+    let mut removed = Vec::try_with_capacity(len)?;
+    let mut reachable = true;
+
+    for (at, (inst, _)) in asm.instructions.iter().enumerate() {
+        reachable |= referenced.contains(&at);
+        removed.try_push(!reachable)?;
+
+        if terminates(inst) {
+            reachable = false;
+        }
+    }
+
+    for at in 0..len {
+        if removed[at] {
+            continue;
+        }
+
+        let AssemblyInst::Jump { label } = &asm.instructions[at].0 else {
+            continue;
+        };
+
+        let next = (at + 1..len).find(|&n| !removed[n]).unwrap_or(len);
+
+        if positions.get(&label.index) == Some(&next) {
+            removed[at] = true;
+        }
+    }
+
+    if level >= 3 {
+        for at in 0..len {
+            if removed[at] {
+                continue;
+            }
+
+            let out = match asm.instructions[at].0 {
+                AssemblyInst::Raw {
+                    raw: inst::Kind::Store { out, .. } | inst::Kind::Copy { out, .. },
+                } => out,
+                _ => continue,
+            };
+
+            removed[at] = match out.as_addr() {
+                Some(addr) => is_dead(asm, &removed, at, addr.offset()),
+                None => true,
+            };
+        }
+    }
+
+    Ok(compact(asm, &removed)?)
+}
+
+/// Test if the value written to `slot` by the instruction at `at` is
+/// overwritten or discarded before it is read.
+fn is_dead(asm: &Assembly, removed: &[bool], at: usize, slot: usize) -> bool {
+    for (n, &is_removed) in removed.iter().enumerate().skip(at + 1) {
+        if asm.labels.contains_key(&n) {
+            return false;
+        }
+
+        if is_removed {
+            continue;
+        }
+
+        let AssemblyInst::Raw { raw } = &asm.instructions[n].0 else {
+            return false;
+        };
+
+        let (reads, out) = match *raw {
+            inst::Kind::Return { addr } => return addr.offset() != slot,
+            inst::Kind::ReturnUnit => return true,
+            inst::Kind::Store { out, .. } => ([None, None], out),
+            inst::Kind::Copy { addr, out }
+            | inst::Kind::Move { addr, out }
+            | inst::Kind::Not { addr, out }
+            | inst::Kind::Neg { addr, out }
+            | inst::Kind::EqSigned { addr, out, .. }
+            | inst::Kind::EqUnsigned { addr, out, .. }
+            | inst::Kind::EqBool { addr, out, .. }
+            | inst::Kind::EqChar { addr, out, .. } => ([Some(addr), None], out),
+            inst::Kind::Arithmetic { a, b, out, .. } | inst::Kind::Op { a, b, out, .. } => {
+                ([Some(a), Some(b)], out)
+            }
+            _ => return false,
+        };
+
+        if reads.iter().flatten().any(|addr| addr.offset() == slot) {
+            return false;
+        }
+
+        if out.as_addr().is_some_and(|addr| addr.offset() == slot) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Remove the instructions marked as removed, moving labels, variables, freed
+/// slots and comments along with the instructions that remain.
+fn compact(asm: &mut Assembly, removed: &[bool]) -> alloc::Result<bool> {
+    if !removed.contains(&true) {
+        return Ok(false);
+    }
+
+    // Maps old positions to new ones, where removed instructions map to the
+    // next instruction which remains.
+    let mut map = Vec::try_with_capacity(removed.len().saturating_add(1))?;
+    let mut n = 0;
+
+    for &removed in removed {
+        map.try_push(n)?;
+
+        if !removed {
+            n += 1;
+        }
+    }
+
+    map.try_push(n)?;
+
+    let instructions = take(&mut asm.instructions);
+
+    for (inst, &removed) in instructions.into_iter().zip(removed) {
+        if !removed {
+            asm.instructions.try_push(inst)?;
+        }
+    }
+
+    let mut labels = Vec::new();
+
+    for (at, (_, list)) in take(&mut asm.labels) {
+        labels.try_push((map[at], list))?;
+    }
+
+    labels.sort_by_key(|(at, _)| *at);
+
+    // Labels are renumbered, since each label slot has to be unique.
+    for (at, list) in labels {
+        let slot = asm.labels.len();
+        let (slot, existing) = asm.labels.entry(at).or_try_insert((slot, Vec::new()))?;
+
+        for label in &list {
+            label.set_jump(*slot);
+        }
+
+        existing.try_extend(list)?;
+    }
+
+    for (at, list) in take(&mut asm.variables) {
+        asm.variables
+            .entry(map[at])
+            .or_try_default()?
+            .try_extend(list)?;
+    }
+
+    for (at, list) in take(&mut asm.freed) {
+        asm.freed
+            .entry(map[at])
+            .or_try_default()?
+            .try_extend(list)?;
+    }
+
+    for (at, comment) in take(&mut asm.comments) {
+        if !removed.get(at).copied().unwrap_or_default() {
+            asm.comments.try_insert(map[at], comment)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Map each label to the position of the instruction it labels.
+fn label_positions(asm: &Assembly) -> alloc::Result<HashMap<usize, usize>> {
+    let mut positions = HashMap::new();
+
+    for (&at, (_, labels)) in &asm.labels {
+        for label in labels {
+            positions.try_insert(label.index, at)?;
+        }
+    }
+
+    Ok(positions)
+}
+
+/// Get the label an instruction might jump to.
+fn target(inst: &AssemblyInst) -> Option<&Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// Get a mutable reference to the label an instruction might jump to.
+fn target_mut(inst: &mut AssemblyInst) -> Option<&mut Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// Test if execution never continues past the given instruction.
+fn terminates(inst: &AssemblyInst) -> bool {
+    matches!(
+        inst,
+        AssemblyInst::Jump { .. }
+            | AssemblyInst::Raw {
+                raw: inst::Kind::Return { .. } | inst::Kind::ReturnUnit
+            }
+    )
+}
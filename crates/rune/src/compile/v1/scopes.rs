@@ -473,8 +473,14 @@ impl<'hir> Scopes<'hir> {
         Ok(())
     }
 
+    /// Pop the given scope, returning the addresses of the slots which were
+    /// freed by it.
     #[tracing::instrument(skip(self, span, handle), fields(id = ?handle.id))]
-    pub(super) fn pop(&self, span: &dyn Spanned, handle: ScopeHandle) -> compile::Result<()> {
+    pub(super) fn pop(
+        &self,
+        span: &dyn Spanned,
+        handle: ScopeHandle,
+    ) -> compile::Result<Vec<inst::Address>> {
         let ScopeHandle { id } = handle;
 
         let Some(mut scope) = self.scopes.borrow_mut().try_remove(id.index) else {
@@ -498,6 +504,7 @@ impl<'hir> Scopes<'hir> {
 
         let mut slots = self.slots.borrow_mut();
         let mut dangling = self.dangling.borrow_mut();
+        let mut freed = Vec::new();
 
         // Free any locally defined variables associated with the scope.
         for addr in scope.locals.addresses() {
@@ -512,11 +519,12 @@ impl<'hir> Scopes<'hir> {
             }
 
             dangling.insert(addr).with_span(span)?;
+            freed.try_push(addr)?;
         }
 
         scope.locals.clear();
         self.top.set(scope.parent);
-        Ok(())
+        Ok(freed)
     }
 
     /// Pop the last of the scope.
@@ -443,8 +443,21 @@ where
                 writeln!(note, "    // ..")?;
                 writeln!(note, "}}")?;
                 notes.push(note.into_std());
+
+                let mut note = String::new();
+                writeln!(note, "Hint: Or handle the mismatch with:")?;
+                writeln!(note, "let {binding} = .. else {{")?;
+                writeln!(note, "    // ..")?;
+                writeln!(note, "}};")?;
+                notes.push(note.into_std());
             }
         }
+        WarningDiagnosticKind::UnreachableLetElse { pattern, .. } => {
+            labels.push(
+                d::Label::secondary(this.source_id(), pattern.range())
+                    .with_message("This pattern always matches"),
+            );
+        }
         WarningDiagnosticKind::RemoveTupleCallParams { variant, .. } => {
             if let Some(variant) = sources.source(this.source_id(), *variant) {
                 let mut note = String::new();
@@ -253,6 +253,27 @@ impl Diagnostics {
         )
     }
 
+    /// Indicate that the else block of a let statement is never executed since
+    /// its pattern always matches.
+    ///
+    /// Like `let (a, b) = value else { .. }`.
+    pub(crate) fn unreachable_let_else(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        pattern: &dyn Spanned,
+        context: Option<&dyn Spanned>,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnreachableLetElse {
+                span: span.span(),
+                pattern: pattern.span(),
+                context: context.map(Spanned::span),
+            },
+        )
+    }
+
     /// Indicate that we encountered a template string without any expansion
     /// groups.
     ///
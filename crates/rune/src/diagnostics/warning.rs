@@ -37,6 +37,7 @@ impl WarningDiagnostic {
     pub(crate) fn context(&self) -> Option<Span> {
         match &self.kind {
             WarningDiagnosticKind::LetPatternMightPanic { context, .. }
+            | WarningDiagnosticKind::UnreachableLetElse { context, .. }
             | WarningDiagnosticKind::RemoveTupleCallParams { context, .. }
             | WarningDiagnosticKind::NotUsed { context, .. }
             | WarningDiagnosticKind::UsedDeprecated { context, .. }
@@ -53,6 +54,7 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::NotUsed { span, .. } => *span,
             WarningDiagnosticKind::Unreachable { span, .. } => *span,
             WarningDiagnosticKind::LetPatternMightPanic { span, .. } => *span,
+            WarningDiagnosticKind::UnreachableLetElse { span, .. } => *span,
             WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
@@ -105,6 +107,18 @@ pub(crate) enum WarningDiagnosticKind {
         #[cfg_attr(not(feature = "emit"), allow(dead_code))]
         context: Option<Span>,
     },
+    /// Warning that the else block of a let statement is never executed since
+    /// the pattern always matches.
+    UnreachableLetElse {
+        /// The span of the else block.
+        span: Span,
+        /// The span of the pattern.
+        #[cfg_attr(not(feature = "emit"), allow(dead_code))]
+        pattern: Span,
+        /// The context in which it is used.
+        #[cfg_attr(not(feature = "emit"), allow(dead_code))]
+        context: Option<Span>,
+    },
     /// Encountered a template string without an expansion.
     TemplateWithoutExpansions {
         /// Span that caused the error.
@@ -148,6 +162,9 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::LetPatternMightPanic { .. } => {
                 write!(f, "Pattern might panic")
             }
+            WarningDiagnosticKind::UnreachableLetElse { .. } => {
+                write!(f, "Else block is never executed")
+            }
             WarningDiagnosticKind::TemplateWithoutExpansions { .. } => write!(
                 f,
                 "Using a template string without expansions, like `Hello World`"
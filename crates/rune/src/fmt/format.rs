@@ -183,6 +183,14 @@ fn item<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
                     item_impl(fmt, p)?;
                 }
             }
+            ItemTrait => {
+                if attrs.skip {
+                    p.write_remaining(fmt)?;
+                } else {
+                    modifiers(fmt, p)?;
+                    item_trait(fmt, p)?;
+                }
+            }
             ItemMod | ItemFileMod => {
                 if attrs.skip {
                     p.write_remaining(fmt)?;
@@ -227,10 +235,19 @@ fn local<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.expect(K![let])?.fmt(fmt)?;
     fmt.ws()?;
     p.expect(Pat)?.parse(|p| pat(fmt, p))?;
+    type_annotation(fmt, p)?;
     fmt.ws()?;
     p.one(K![=]).fmt(fmt)?;
     fmt.ws()?;
     p.expect(Expr)?.parse(|p| expr(fmt, p))?;
+
+    if let MaybeNode::Some(else_token) = p.eat(K![else]) {
+        fmt.ws()?;
+        else_token.fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Block)?.parse(|p| block(fmt, p))?;
+    }
+
     Ok(())
 }
 
@@ -293,6 +310,47 @@ fn path<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     Ok(())
 }
 
+/// Format an optional type annotation, like `: i64`.
+fn type_annotation<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    if let MaybeNode::Some(colon) = p.eat(K![:]) {
+        colon.fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Type)?.parse(|p| ty(fmt, p))?;
+    }
+
+    Ok(())
+}
+
+fn ty<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    match p.peek() {
+        Path => {
+            p.pump()?.parse(|p| path(fmt, p))?;
+        }
+        K!['('] => {
+            p.expect(K!['('])?.fmt(fmt)?;
+
+            let mut comma = Remaining::default();
+
+            while let MaybeNode::Some(node) = p.eat(Type) {
+                if comma.fmt(fmt)? {
+                    fmt.ws()?;
+                }
+
+                node.parse(|p| ty(fmt, p))?;
+                comma = p.remaining(fmt, K![,])?;
+            }
+
+            comma.ignore(fmt)?;
+            p.one(K![')']).fmt(fmt)?;
+        }
+        _ => {
+            p.pump()?.fmt(fmt)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn path_generics<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.expect(K![<])?.fmt(fmt)?;
 
@@ -789,7 +847,7 @@ fn exprs<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>, open: Kind, close: Kin
             break;
         }
 
-        count += usize::from(matches!(node.kind(), Expr));
+        count += usize::from(matches!(node.kind(), Expr | NamedArgument));
         expanded |= matches!(node.kind(), Kind::Comment) || count >= 6;
     }
 
@@ -809,9 +867,9 @@ fn exprs_loose<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     fmt.nl(1)?;
     fmt.indent(1)?;
 
-    while let MaybeNode::Some(node) = p.eat(Expr) {
+    while let MaybeNode::Some(node) = p.eat_matching(|k| matches!(k, Expr | NamedArgument)) {
         fmt.comments(Line)?;
-        node.parse(|p| expr(fmt, p))?;
+        expr_or_named_argument(fmt, node)?;
         p.remaining(fmt, K![,])?.fmt(fmt)?;
         fmt.nl(1)?;
     }
@@ -825,14 +883,14 @@ fn exprs_loose<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
 fn exprs_compact<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     let mut comma = Remaining::default();
 
-    while let MaybeNode::Some(node) = p.eat(Expr) {
+    while let MaybeNode::Some(node) = p.eat_matching(|k| matches!(k, Expr | NamedArgument)) {
         fmt.comments(Prefix)?;
 
         if comma.fmt(fmt)? {
             fmt.ws()?;
         }
 
-        node.parse(|p| expr(fmt, p))?;
+        expr_or_named_argument(fmt, node)?;
         comma = p.remaining(fmt, K![,])?;
         fmt.comments(Suffix)?;
     }
@@ -844,6 +902,21 @@ fn exprs_compact<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()>
     Ok(())
 }
 
+fn expr_or_named_argument<'a>(fmt: &mut Formatter<'a>, node: Node<'a>) -> Result<()> {
+    if node.kind() != NamedArgument {
+        node.parse(|p| expr(fmt, p))?;
+        return Ok(());
+    }
+
+    node.parse(|p| {
+        p.pump()?.fmt(fmt)?;
+        p.one(K![:]).fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Expr)?.parse(|p| expr(fmt, p))?;
+        Ok(())
+    })
+}
+
 fn expr_binary<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.pump()?.parse(|p| inner_expr(fmt, p))?;
 
@@ -1284,6 +1357,13 @@ fn expr_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
 }
 
 fn condition_or_expr<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    if p.eat(ConditionChain)
+        .parse(|p| condition_chain(fmt, p))?
+        .is_some()
+    {
+        return Ok(());
+    }
+
     if p.eat(Condition).parse(|p| condition(fmt, p))?.is_none() {
         p.expect(Expr)?.parse(|p| expr(fmt, p))?;
     }
@@ -1291,6 +1371,19 @@ fn condition_or_expr<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<
     Ok(())
 }
 
+fn condition_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    p.expect(Condition)?.parse(|p| condition(fmt, p))?;
+
+    while let MaybeNode::Some(and) = p.eat(K![&&]) {
+        fmt.ws()?;
+        and.fmt(fmt)?;
+        fmt.ws()?;
+        condition_or_expr(fmt, p)?;
+    }
+
+    Ok(())
+}
+
 fn condition<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.expect(K![let])?.fmt(fmt)?;
     fmt.ws()?;
@@ -1400,7 +1493,10 @@ fn struct_body<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     while let MaybeNode::Some(field) = p.eat(Field) {
         fmt.nl(1)?;
         fmt.comments(Line)?;
-        field.parse(|p| p.pump()?.fmt(fmt))?;
+        field.parse(|p| {
+            p.pump()?.fmt(fmt)?;
+            type_annotation(fmt, p)
+        })?;
         p.remaining(fmt, K![,])?.fmt(fmt)?;
         empty = false;
     }
@@ -1424,7 +1520,10 @@ fn tuple_body<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
             fmt.ws()?;
         }
 
-        node.parse(|p| p.pump()?.fmt(fmt))?;
+        node.parse(|p| {
+            p.pump()?.fmt(fmt)?;
+            type_annotation(fmt, p)
+        })?;
         comma = p.remaining(fmt, K![,])?;
         fmt.comments(Suffix)?;
     }
@@ -1450,14 +1549,20 @@ fn item_fn<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.lit("()")?;
     }
 
-    fmt.ws()?;
-
-    if p.eat(Block).parse(|p| block(fmt, p))?.is_none() {
-        fmt.lit("{")?;
-        fmt.nl(1)?;
-        fmt.lit("}")?;
+    if let MaybeNode::Some(arrow) = p.eat(K![->]) {
+        fmt.ws()?;
+        arrow.fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Type)?.parse(|p| ty(fmt, p))?;
     }
 
+    // Functions declared in traits might not have a body, in which case the
+    // trailing semi-colon is emitted as part of the statement.
+    p.eat(Block).parse(|p| {
+        fmt.ws()?;
+        block(fmt, p)
+    })?;
+
     Ok(())
 }
 
@@ -1546,6 +1651,23 @@ fn item_impl<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     fmt.ws()?;
     p.expect(Path)?.parse(|p| path(fmt, p))?;
     fmt.ws()?;
+
+    if let MaybeNode::Some(for_) = p.eat(K![for]) {
+        for_.fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Path)?.parse(|p| path(fmt, p))?;
+        fmt.ws()?;
+    }
+
+    p.expect(Block)?.parse(|p| block(fmt, p))?;
+    Ok(())
+}
+
+fn item_trait<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    p.expect(K![trait])?.fmt(fmt)?;
+    fmt.ws()?;
+    p.pump()?.fmt(fmt)?;
+    fmt.ws()?;
     p.expect(Block)?.parse(|p| block(fmt, p))?;
     Ok(())
 }
@@ -1578,14 +1700,37 @@ fn fn_args<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
 
     let mut comma = Remaining::default();
 
-    while let MaybeNode::Some(node) = p.eat(Pat) {
+    while let MaybeNode::Some(node) =
+        p.eat_matching(|k| matches!(k, Pat | FnArgTyped | FnArgDefault))
+    {
         fmt.comments(Prefix)?;
 
         if comma.fmt(fmt)? {
             fmt.ws()?;
         }
 
-        node.parse(|p| pat(fmt, p))?;
+        match node.kind() {
+            FnArgTyped => {
+                node.parse(|p| {
+                    p.expect(Pat)?.parse(|p| pat(fmt, p))?;
+                    type_annotation(fmt, p)
+                })?;
+            }
+            FnArgDefault => {
+                node.parse(|p| {
+                    p.expect(Pat)?.parse(|p| pat(fmt, p))?;
+                    type_annotation(fmt, p)?;
+                    fmt.ws()?;
+                    p.one(K![=]).fmt(fmt)?;
+                    fmt.ws()?;
+                    p.expect(Expr)?.parse(|p| expr(fmt, p))
+                })?;
+            }
+            _ => {
+                node.parse(|p| pat(fmt, p))?;
+            }
+        }
+
         comma = p.remaining(fmt, K![,])?;
         fmt.comments(Suffix)?;
     }
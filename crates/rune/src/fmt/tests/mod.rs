@@ -1357,3 +1357,67 @@ fn test_expanded_chain() {
         "#
     );
 }
+
+#[test]
+fn fmt_let_else_and_chains() {
+    assert_format!(
+        "let Some(a)=b else{return;};",
+        r#"
+        let Some(a) = b else {
+            return;
+        };
+        "#
+    );
+
+    assert_format!(
+        "if let Some(a)=b&&let [c, ..]=a&&c>3{c}",
+        r#"
+        if let Some(a) = b && let [c, ..] = a && c > 3 {
+            c
+        }
+        "#
+    );
+}
+
+#[test]
+fn fmt_default_and_named_arguments() {
+    assert_format!(
+        "fn connect(host,port=80){host}",
+        r#"
+        fn connect(host, port = 80) {
+            host
+        }
+        "#
+    );
+
+    assert_format!(
+        "connect(host,port:8080)",
+        r#"
+        connect(host, port: 8080)
+        "#
+    );
+}
+
+#[test]
+fn fmt_type_annotations() {
+    assert_format!(
+        "struct Point{x:i64,y:i64}",
+        r#"
+        struct Point {
+            x: i64,
+            y: i64,
+        }
+        "#
+    );
+
+    assert_format!(
+        "fn add(a:i64,b:(i64,i64)=(1,2))->i64{let c:i64=a;c}",
+        r#"
+        fn add(a: i64, b: (i64, i64) = (1, 2)) -> i64 {
+            let c: i64 = a;
+
+            c
+        }
+        "#
+    );
+}
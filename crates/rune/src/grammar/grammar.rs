@@ -34,9 +34,15 @@ enum Brace {
 #[derive(Debug, Clone, Copy)]
 enum Binary {
     Yes,
+    /// Only binary operators which bind tighter than `&&`, as used in the links
+    /// of a condition chain.
+    Chain,
     No,
 }
 
+/// The lowest precedence of a binary operator in a condition chain link.
+const CHAIN_PRECEDENCE: usize = 5;
+
 #[derive(Debug, Clone, Copy)]
 enum Range {
     Yes,
@@ -127,6 +133,7 @@ fn stmt(p: &mut Parser<'_>) -> Result<()> {
             K![enum] => false,
             K![fn] => false,
             K![impl] => false,
+            K![trait] => false,
             K![mod] => false,
             K![ident] => false,
             K![pub] => false,
@@ -189,6 +196,11 @@ fn stmt(p: &mut Parser<'_>) -> Result<()> {
             p.close_at(&inner_c, ItemImpl)?;
             Item
         }
+        K![trait] => {
+            item_trait(p)?;
+            p.close_at(&inner_c, ItemTrait)?;
+            Item
+        }
         K![mod] => {
             if item_mod(p)? {
                 p.close_at(&inner_c, ItemMod)?;
@@ -339,8 +351,18 @@ fn modifiers(p: &mut Parser<'_>) -> Result<Modifiers> {
 fn local(p: &mut Parser<'_>, cx: &dyn ExprCx) -> Result<()> {
     p.bump()?;
     pat(p)?;
+
+    if p.bump_if(K![:])? {
+        ty(p)?;
+    }
+
     p.bump_if(K![=])?;
     expr_with(p, Brace::Yes, Range::Yes, Binary::Yes, cx)?;
+
+    if p.bump_if(K![else])? {
+        block(p)?;
+    }
+
     Ok(())
 }
 
@@ -453,6 +475,11 @@ fn struct_body(p: &mut Parser<'_>) -> Result<()> {
     while matches!(p.peek()?, K![ident]) {
         let c = p.checkpoint()?;
         p.bump()?;
+
+        if p.bump_if(K![:])? {
+            ty(p)?;
+        }
+
         p.close_at(&c, Field)?;
         p.bump_while(K![,])?;
     }
@@ -471,6 +498,11 @@ fn tuple_body(p: &mut Parser<'_>) -> Result<()> {
     while matches!(p.peek()?, K![ident]) {
         let c = p.checkpoint()?;
         p.bump()?;
+
+        if p.bump_if(K![:])? {
+            ty(p)?;
+        }
+
         p.close_at(&c, Field)?;
         p.bump_while(K![,])?;
     }
@@ -495,7 +527,23 @@ fn item_fn(p: &mut Parser<'_>) -> Result<()> {
         p.bump_while(K![,])?;
 
         while is_pat(p)? {
+            let c = p.checkpoint()?;
             pat(p)?;
+
+            let typed = p.bump_if(K![:])?;
+
+            if typed {
+                ty(p)?;
+            }
+
+            if p.peek()? == K![=] {
+                p.bump()?;
+                expr(p)?;
+                p.close_at(&c, FnArgDefault)?;
+            } else if typed {
+                p.close_at(&c, FnArgTyped)?;
+            }
+
             p.bump_while(K![,])?;
         }
 
@@ -503,6 +551,10 @@ fn item_fn(p: &mut Parser<'_>) -> Result<()> {
         p.close_at(&c, FnArgs)?;
     }
 
+    if p.bump_if(K![->])? {
+        ty(p)?;
+    }
+
     if p.peek()? == K!['{'] {
         block(p)?;
     }
@@ -518,6 +570,22 @@ fn item_impl(p: &mut Parser<'_>) -> Result<()> {
         path(p)?;
     }
 
+    if p.bump_if(K![for])? && matches!(p.peek()?, path_component!()) {
+        path(p)?;
+    }
+
+    block(p)?;
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+fn item_trait(p: &mut Parser<'_>) -> Result<()> {
+    p.bump()?;
+
+    if matches!(p.peek()?, K![ident]) {
+        p.bump()?;
+    }
+
     block(p)?;
     Ok(())
 }
@@ -538,6 +606,37 @@ fn item_mod(p: &mut Parser<'_>) -> Result<bool> {
     }
 }
 
+#[tracing::instrument(skip_all)]
+fn ty(p: &mut Parser<'_>) -> Result<()> {
+    let c = p.checkpoint()?;
+
+    match p.peek()? {
+        K![!] => {
+            p.bump()?;
+        }
+        K!['('] => {
+            p.bump()?;
+
+            while matches!(p.peek()?, K![!] | K!['('] | path_component!()) {
+                ty(p)?;
+                p.bump_while(K![,])?;
+            }
+
+            p.bump_if(K![')'])?;
+        }
+        path_component!() => {
+            path(p)?;
+        }
+        _ => {
+            let c = p.checkpoint()?;
+            p.close_at(&c, Error)?;
+        }
+    }
+
+    p.close_at(&c, Type)?;
+    Ok(())
+}
+
 #[tracing::instrument(skip_all)]
 fn is_pat(p: &mut Parser<'_>) -> Result<bool> {
     Ok(match p.peek()? {
@@ -724,11 +823,17 @@ fn outer_expr_with(
         return Ok(ExprAssign);
     }
 
-    if matches!(binary, Binary::Yes) {
+    let min_precedence = match binary {
+        Binary::Yes => Some(0),
+        Binary::Chain => Some(CHAIN_PRECEDENCE),
+        Binary::No => None,
+    };
+
+    if let Some(min_precedence) = min_precedence {
         let slice = p.array::<2>()?;
         let lookahead = ast::BinOp::from_slice(&slice);
 
-        kind = if expr_binary(p, lookahead, 0, brace, cx)? {
+        kind = if expr_binary(p, lookahead, min_precedence, brace, cx)? {
             p.close_at(&c, ExprBinary)?;
             ExprBinary
         } else {
@@ -947,7 +1052,7 @@ fn expr_chain(p: &mut Parser<'_>, c: &Checkpoint, mut kind: Kind) -> Result<Kind
             }
             // Chained function call.
             K!['('] if is_callable => {
-                parenthesized(p, is_expr, expr, K![')'])?;
+                parenthesized(p, is_expr, call_arg, K![')'])?;
                 ExprCall
             }
             K![?] => {
@@ -1196,23 +1301,45 @@ fn expr_select(p: &mut Parser<'_>) -> Result<()> {
 
 #[tracing::instrument(skip_all)]
 fn condition(p: &mut Parser<'_>) -> Result<()> {
-    if p.peek()? == K![let] {
-        let c = p.checkpoint()?;
-        p.bump()?;
-        pat(p)?;
+    let cx = ErrorCx;
 
-        if p.peek()? == K![=] {
-            p.bump()?;
-            let cx = ErrorCx;
-            expr_with(p, Brace::No, Range::Yes, Binary::Yes, &cx)?;
+    if p.peek()? != K![let] {
+        expr_with(p, Brace::No, Range::Yes, Binary::Yes, &cx)?;
+        return Ok(());
+    }
+
+    let c = p.checkpoint()?;
+    condition_let(p)?;
+
+    if p.peek()? != K![&&] {
+        return Ok(());
+    }
+
+    while p.bump_if(K![&&])? {
+        if p.peek()? == K![let] {
+            condition_let(p)?;
+        } else {
+            expr_with(p, Brace::No, Range::Yes, Binary::Chain, &cx)?;
         }
+    }
 
-        p.close_at(&c, Condition)?;
-    } else {
+    p.close_at(&c, ConditionChain)?;
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+fn condition_let(p: &mut Parser<'_>) -> Result<()> {
+    let c = p.checkpoint()?;
+    p.bump()?;
+    pat(p)?;
+
+    if p.peek()? == K![=] {
+        p.bump()?;
         let cx = ErrorCx;
-        expr_with(p, Brace::No, Range::Yes, Binary::Yes, &cx)?;
+        expr_with(p, Brace::No, Range::Yes, Binary::Chain, &cx)?;
     }
 
+    p.close_at(&c, Condition)?;
     Ok(())
 }
 
@@ -1382,6 +1509,20 @@ fn pat_parens(p: &mut Parser, end: Kind) -> Result<()> {
     Ok(())
 }
 
+#[tracing::instrument(skip_all)]
+fn call_arg(p: &mut Parser<'_>) -> Result<()> {
+    if matches!(p.peek()?, K![ident]) && p.nth(1)? == K![:] {
+        let c = p.checkpoint()?;
+        p.bump()?;
+        p.bump()?;
+        expr(p)?;
+        p.close_at(&c, NamedArgument)?;
+        return Ok(());
+    }
+
+    expr(p)
+}
+
 #[tracing::instrument(skip(p, is, parser))]
 fn parenthesized(
     p: &mut Parser,
@@ -1,6 +1,6 @@
 //! Utilities for working with hashes.
 
-use crate::alloc::HashMap;
+use crate::alloc::{HashMap, HashSet};
 
 use core::hash::{BuildHasher, Hasher};
 
@@ -14,6 +14,9 @@ pub use rune_core::hash::{
 /// A hash map suitable for storing values with hash keys.
 pub(crate) type Map<T> = HashMap<Hash, T, HashBuildHasher>;
 
+/// A hash set suitable for storing hashes.
+pub(crate) type Set = HashSet<Hash, HashBuildHasher>;
+
 #[derive(Default, Clone, Copy)]
 pub(crate) struct HashBuildHasher;
 
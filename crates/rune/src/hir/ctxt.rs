@@ -2,12 +2,13 @@ use tracing::instrument_ast;
 
 use crate::alloc;
 use crate::alloc::prelude::*;
+use crate::alloc::HashMap;
 use crate::ast::{self, Spanned};
 use crate::compile::{meta, DynLocation, Error, ItemId, Result};
 use crate::grammar::{Ignore, Node};
 use crate::hir;
 use crate::query::{GenericsParameters, Query, SecondaryBuildEntry};
-use crate::SourceId;
+use crate::{Hash, SourceId};
 
 #[derive(Default, Clone, Copy)]
 pub(super) enum Needs {
@@ -31,6 +32,11 @@ pub(crate) struct Ctxt<'hir, 'a, 'arena> {
     pub(super) statements: Vec<hir::Stmt<'hir>>,
     pub(super) pattern_bindings: Vec<hir::Variable>,
     pub(super) label: Option<ast::Label>,
+    /// Declared types of variables, only populated when type checks are
+    /// enabled.
+    pub(super) variable_types: HashMap<hir::Variable, Hash>,
+    /// Declared type of the value returned by the function being lowered.
+    pub(super) return_type: Option<Hash>,
 }
 
 impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
@@ -78,9 +84,18 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
             statements: Vec::new(),
             pattern_bindings: Vec::new(),
             label: None,
+            variable_types: HashMap::new(),
+            return_type: None,
         })
     }
 
+    /// Take the names of all variables defined while lowering.
+    pub(crate) fn take_variable_names(
+        &mut self,
+    ) -> crate::alloc::HashMap<hir::Variable, hir::Name<'hir>> {
+        self.scopes.take_names()
+    }
+
     #[instrument_ast(span = ast)]
     pub(super) fn try_lookup_meta(
         &mut self,
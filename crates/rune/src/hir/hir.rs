@@ -409,6 +409,8 @@ pub(crate) enum Call<'hir> {
         target: &'hir Expr<'hir>,
         /// Hash of the fn being called.
         hash: Hash,
+        /// The name of the fn being called, if it's a plain identifier.
+        name: Option<&'hir str>,
     },
     Meta {
         /// Hash being called.
@@ -661,6 +663,8 @@ pub(crate) enum Condition<'hir> {
     Expr(&'hir Expr<'hir>),
     /// A pattern match.
     ExprLet(&'hir ExprLet<'hir>),
+    /// A chain of conditions separated by `&&`.
+    Chain(&'hir ConditionChain<'hir>),
 }
 
 impl Condition<'_> {
@@ -669,10 +673,27 @@ impl Condition<'_> {
         match self {
             Condition::Expr(_) => None,
             Condition::ExprLet(hir) => Some(hir.pat.names.len()),
+            Condition::Chain(hir) => hir
+                .conditions
+                .iter()
+                .flat_map(|c| c.count())
+                .reduce(|a, b| a + b),
         }
     }
 }
 
+/// A chain of conditions, like `let Some(x) = a && x > 3`.
+#[derive(Debug, TryClone, Clone, Copy, Spanned)]
+#[try_clone(copy)]
+#[non_exhaustive]
+pub(crate) struct ConditionChain<'hir> {
+    /// The span of the chain.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The conditions in the chain, which are tested in order.
+    pub(crate) conditions: &'hir [Condition<'hir>],
+}
+
 #[derive(Debug, TryClone, Clone, Copy, Spanned)]
 #[try_clone(copy)]
 #[non_exhaustive]
@@ -747,4 +768,6 @@ pub(crate) struct Local<'hir> {
     pub(crate) pat: PatBinding<'hir>,
     /// The expression the binding is assigned to.
     pub(crate) expr: Expr<'hir>,
+    /// The block to evaluate if the pattern doesn't match.
+    pub(crate) else_block: Option<&'hir Block<'hir>>,
 }
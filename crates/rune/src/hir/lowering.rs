@@ -19,7 +19,7 @@ use crate::query::{self, GenericsParameters, Named, SecondaryBuild};
 use crate::runtime::{self, ConstInstance, ConstValue, ConstValueKind, Inline, Type, TypeHash};
 use crate::{Hash, Item};
 
-use super::{Ctxt, Needs};
+use super::{typeck, Ctxt, Needs};
 
 /// Lower an empty function.
 #[instrument_ast(span = span)]
@@ -65,13 +65,30 @@ fn named_arg<'hir>(
 pub(crate) fn item_fn<'hir>(
     cx: &mut Ctxt<'hir, '_, '_>,
     ast: &ast::ItemFn,
+    call: runtime::Call,
 ) -> compile::Result<hir::ItemFn<'hir>> {
     alloc_with!(cx, ast);
 
+    let args = iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?);
+
+    // Only immediate calls produce the value of the function body, other
+    // calls produce a future, generator or stream.
+    cx.return_type = match (call, &ast.output) {
+        (runtime::Call::Immediate, Some((_, ty))) => typeck::annotation(cx, Some(ty))?,
+        _ => None,
+    };
+
+    let body = block(cx, None, &ast.body)?;
+
+    if let Some(value) = body.value {
+        let return_type = cx.return_type;
+        typeck::check(cx, return_type, value)?;
+    }
+
     Ok(hir::ItemFn {
         span: ast.span(),
-        args: iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?),
-        body: block(cx, None, &ast.body)?,
+        args,
+        body,
     })
 }
 
@@ -108,8 +125,10 @@ fn expr_call_closure<'hir>(
 
     cx.scopes.push_captures()?;
 
+    let return_type = cx.return_type.take();
     let args = iter!(ast.args.as_slice(), |(arg, _)| fn_arg(cx, arg)?);
     let body = alloc!(expr(cx, &ast.body)?);
+    cx.return_type = return_type;
 
     let layer = cx.scopes.pop().with_span(&ast.body)?;
 
@@ -176,7 +195,11 @@ fn statements<'hir>(
     for ast in statements {
         let last = match ast {
             ast::Stmt::Local(ast) => {
-                let depacked = if ast.attributes.is_empty() && cx.q.options.lowering > 0 {
+                let depacked = if ast.attributes.is_empty()
+                    && ast.ty.is_none()
+                    && ast.else_branch.is_none()
+                    && cx.q.options.lowering > 0
+                {
                     unpack_locals(cx, &ast.pat, &ast.expr)?
                 } else {
                     false
@@ -380,6 +403,7 @@ fn expr_object<'hir>(
                     ..
                 } => {
                     check_object_fields(&st.fields, item)?;
+                    typeck::check_fields(cx, &st.fields, assignments)?;
 
                     match constructor {
                         Some(_) => hir::ExprObjectKind::ExternalType {
@@ -420,10 +444,12 @@ pub(crate) fn expr<'hir>(
 
     let kind = match ast {
         ast::Expr::Path(ast) => expr_path(cx, ast, in_path)?,
-        ast::Expr::Assign(ast) => hir::ExprKind::Assign(alloc!(hir::ExprAssign {
-            lhs: expr(cx, &ast.lhs)?,
-            rhs: expr(cx, &ast.rhs)?,
-        })),
+        ast::Expr::Assign(ast) => {
+            let lhs = expr(cx, &ast.lhs)?;
+            let rhs = expr(cx, &ast.rhs)?;
+            typeck::check_assign(cx, &lhs, &rhs)?;
+            hir::ExprKind::Assign(alloc!(hir::ExprAssign { lhs, rhs }))
+        }
         // TODO: lower all of these loop constructs to the same loop-like
         // representation. We only do different ones here right now since it's
         // easier when refactoring.
@@ -511,7 +537,7 @@ pub(crate) fn expr<'hir>(
                 }
             }),
         })),
-        ast::Expr::Call(ast) => hir::ExprKind::Call(alloc!(expr_call(cx, ast)?)),
+        ast::Expr::Call(ast) => expr_call(cx, ast)?,
         ast::Expr::FieldAccess(ast) => {
             hir::ExprKind::FieldAccess(alloc!(expr_field_access(cx, ast)?))
         }
@@ -547,7 +573,16 @@ pub(crate) fn expr<'hir>(
         ast::Expr::Break(ast) => hir::ExprKind::Break(alloc!(expr_break(cx, ast)?)),
         ast::Expr::Continue(ast) => hir::ExprKind::Continue(alloc!(expr_continue(cx, ast)?)),
         ast::Expr::Yield(ast) => hir::ExprKind::Yield(option!(&ast.expr, |ast| expr(cx, ast)?)),
-        ast::Expr::Return(ast) => hir::ExprKind::Return(option!(&ast.expr, |ast| expr(cx, ast)?)),
+        ast::Expr::Return(ast) => {
+            let value = option!(&ast.expr, |ast| expr(cx, ast)?);
+
+            if let Some(value) = value {
+                let return_type = cx.return_type;
+                typeck::check(cx, return_type, value)?;
+            }
+
+            hir::ExprKind::Return(value)
+        }
         ast::Expr::Await(ast) => hir::ExprKind::Await(alloc!(expr(cx, &ast.expr)?)),
         ast::Expr::Try(ast) => hir::ExprKind::Try(alloc!(expr(cx, &ast.expr)?)),
         ast::Expr::Select(ast) => {
@@ -1047,7 +1082,9 @@ fn expr_block<'hir>(
             };
 
             cx.scopes.push_captures()?;
+            let return_type = cx.return_type.take();
             let block = alloc!(block(cx, None, &ast.block)?);
+            cx.return_type = return_type;
             let layer = cx.scopes.pop().with_span(&ast.block)?;
 
             cx.q.set_used(&meta.item_meta)?;
@@ -1168,20 +1205,48 @@ fn fn_arg<'hir>(
             hir::FnArg::SelfValue(ast.span(), id)
         }
         ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(pat_binding(cx, ast)?)),
+        ast::FnArg::Typed(ast) => {
+            let pat = pat_binding(cx, &ast.pat)?;
+            let ty = typeck::annotation(cx, Some(&ast.ty))?;
+            typeck::bind(cx, &pat, ty)?;
+            hir::FnArg::Pat(alloc!(pat))
+        }
+        // Note: default values are resolved at the call site.
+        ast::FnArg::Default(ast) => {
+            let pat = pat_binding(cx, &ast.pat)?;
+            let ty = typeck::annotation(cx, ast.ty.as_ref().map(|(_, ty)| ty))?;
+            typeck::bind(cx, &pat, ty)?;
+            hir::FnArg::Pat(alloc!(pat))
+        }
     })
 }
 
 /// Lower an assignment.
 fn local<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Local) -> compile::Result<hir::Local<'hir>> {
+    alloc_with!(cx, ast);
+
     // Note: expression needs to be assembled before pattern, otherwise the
     // expression will see declarations in the pattern.
     let expr = expr(cx, &ast.expr)?;
+
+    // Note: the else block is lowered before the pattern, since it must not be
+    // able to see any of the bindings introduced by it.
+    let else_block = match &ast.else_branch {
+        Some((_, block_ast)) => Some(&*alloc!(block(cx, None, block_ast)?)),
+        None => None,
+    };
+
     let pat = pat_binding(cx, &ast.pat)?;
 
+    let ty = typeck::annotation(cx, ast.ty.as_ref().map(|(_, ty)| ty))?;
+    typeck::check(cx, ty, &expr)?;
+    typeck::bind(cx, &pat, ty)?;
+
     Ok(hir::Local {
         span: ast.span(),
         pat,
         expr,
+        else_block,
     })
 }
 
@@ -1204,6 +1269,7 @@ fn unpack_locals(cx: &mut Ctxt<'_, '_, '_>, p: &ast::Pat, e: &ast::Expr) -> comp
                     span: p.span().join(e.span()),
                     pat: p,
                     expr: e,
+                    else_block: None,
                 })))?;
 
             return Ok(true);
@@ -1233,6 +1299,7 @@ fn unpack_locals(cx: &mut Ctxt<'_, '_, '_>, p: &ast::Pat, e: &ast::Expr) -> comp
                         span: p.span().join(e.span()),
                         pat: p,
                         expr: e,
+                        else_block: None,
                     })))?;
             }
 
@@ -1610,7 +1677,18 @@ fn expr_path_meta<'hir>(
                 fields: meta::Fields::Unnamed(..),
                 ..
             } => Ok(hir::ExprKind::Fn(meta.hash)),
-            meta::Kind::Function { .. } => Ok(hir::ExprKind::Fn(meta.hash)),
+            meta::Kind::Function { signature, .. } => {
+                // Note: default values are filled in at the call site, which
+                // isn't possible when the function is called through a value.
+                if signature.parameters.iter().any(|p| p.default.is_some()) {
+                    return Err(compile::Error::msg(
+                        span,
+                        "Functions with default values for arguments can only be called by their path",
+                    ));
+                }
+
+                Ok(hir::ExprKind::Fn(meta.hash))
+            }
             meta::Kind::Const => Ok(hir::ExprKind::Const(meta.hash)),
             meta::Kind::Struct { .. } | meta::Kind::Type { .. } | meta::Kind::Enum { .. } => {
                 Ok(hir::ExprKind::Type(Type::new(meta.hash)))
@@ -1646,6 +1724,27 @@ fn condition<'hir>(
             pat: pat_binding(cx, &ast.pat)?,
             expr: expr(cx, &ast.expr)?,
         })),
+        ast::Condition::Chain(ast) => {
+            let mut links = Vec::new();
+            links.try_push(hir::Condition::ExprLet(alloc!(hir::ExprLet {
+                pat: pat_binding(cx, &ast.first.pat)?,
+                expr: expr(cx, &ast.first.expr)?,
+            })))?;
+
+            for (_, ast) in &ast.rest {
+                match condition(cx, ast)? {
+                    hir::Condition::Chain(chain) => {
+                        links.try_extend(chain.conditions.iter().copied())?
+                    }
+                    link => links.try_push(link)?,
+                }
+            }
+
+            hir::Condition::Chain(alloc!(hir::ConditionChain {
+                span: ast.span(),
+                conditions: iter!(links),
+            }))
+        }
     })
 }
 
@@ -1799,7 +1898,7 @@ fn generics_parameters(
 fn expr_call<'hir>(
     cx: &mut Ctxt<'hir, '_, '_>,
     ast: &ast::ExprCall,
-) -> compile::Result<hir::ExprCall<'hir>> {
+) -> compile::Result<hir::ExprKind<'hir>> {
     fn find_path(ast: &ast::Expr) -> Option<&ast::Path> {
         let mut current = ast;
 
@@ -1875,7 +1974,7 @@ fn expr_call<'hir>(
                             )?;
                         }
                     }
-                    meta::Kind::Function { .. } => {
+                    meta::Kind::Function { signature, .. } => {
                         if let Some(message) = cx.q.lookup_deprecation(meta.hash) {
                             cx.q.diagnostics.used_deprecated(
                                 cx.source_id,
@@ -1884,6 +1983,16 @@ fn expr_call<'hir>(
                                 message.try_into()?,
                             )?;
                         };
+
+                        let (args, order) = call_args(cx, ast, &signature.parameters)?;
+                        typeck::check_args(cx, signature, args, meta.context)?;
+
+                        let call = hir::Call::Meta { hash: meta.hash };
+
+                        return match order {
+                            Some(order) => call_in_order(cx, ast, call, args, &order),
+                            None => Ok(hir::ExprKind::Call(alloc!(hir::ExprCall { call, args }))),
+                        };
                     }
                     meta::Kind::ConstFn => {
                         let from =
@@ -1911,22 +2020,34 @@ fn expr_call<'hir>(
                 expr_field,
                 expr: target,
             }) => {
-                let hash = match expr_field {
-                    hir::ExprField::Index(index) => Hash::index(index),
+                let (hash, name) = match expr_field {
+                    hir::ExprField::Index(index) => (Hash::index(index), None),
                     hir::ExprField::Ident(ident) => {
                         cx.q.unit.insert_debug_ident(ident)?;
-                        Hash::ident(ident)
+                        (Hash::ident(ident), Some(ident))
                     }
                     hir::ExprField::IdentGenerics(ident, hash) => {
                         cx.q.unit.insert_debug_ident(ident)?;
-                        Hash::ident(ident).with_function_parameters(hash)
+                        let hash = Hash::ident(ident).with_function_parameters(hash);
+                        (hash, None)
                     }
                 };
 
-                break 'ok hir::Call::Associated {
-                    target: alloc!(target),
-                    hash,
-                };
+                named_args_unsupported(ast)?;
+                let args = iter!(&ast.args, |(ast, _)| self::expr(cx, &ast.expr)?);
+
+                if let Some(name) = name {
+                    typeck::check_associated(cx, &target, name, args)?;
+                }
+
+                return Ok(hir::ExprKind::Call(alloc!(hir::ExprCall {
+                    call: hir::Call::Associated {
+                        target: alloc!(target),
+                        hash,
+                        name,
+                    },
+                    args,
+                })));
             }
             _ => {}
         }
@@ -1934,10 +2055,226 @@ fn expr_call<'hir>(
         break 'ok hir::Call::Expr { expr: alloc!(expr) };
     };
 
-    Ok(hir::ExprCall {
+    named_args_unsupported(ast)?;
+
+    Ok(hir::ExprKind::Call(alloc!(hir::ExprCall {
         call,
-        args: iter!(&ast.args, |(ast, _)| self::expr(cx, ast)?),
-    })
+        args: iter!(&ast.args, |(ast, _)| self::expr(cx, &ast.expr)?),
+    })))
+}
+
+/// Named arguments can only be resolved when calling a function whose
+/// parameters are known at compile time.
+fn named_args_unsupported(ast: &ast::ExprCall) -> compile::Result<()> {
+    if let Some((name, _)) = ast.args.iter().find_map(|(arg, _)| arg.name.as_ref()) {
+        return Err(compile::Error::msg(
+            name,
+            "Named arguments are only supported when calling a function by its path",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lower the arguments of a call to a function with the given parameters.
+///
+/// Named arguments are mapped to the parameter with the same name, and
+/// parameters which are not provided are filled in with their default value.
+/// Calls which don't make use of either are lowered as-is.
+fn call_args<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &ast::ExprCall,
+    parameters: &[meta::Parameter],
+) -> compile::Result<(&'hir [hir::Expr<'hir>], Option<Vec<usize>>)> {
+    alloc_with!(cx, ast);
+
+    let has_named = ast.args.iter().any(|(arg, _)| arg.name.is_some());
+
+    let has_defaults = parameters
+        .get(ast.args.len()..)
+        .is_some_and(|rest| rest.iter().any(|p| p.default.is_some()));
+
+    if !has_named && !has_defaults {
+        return Ok((iter!(&ast.args, |(ast, _)| expr(cx, &ast.expr)?), None));
+    }
+
+    if has_named && parameters.is_empty() {
+        return Err(compile::Error::msg(
+            &ast.args,
+            "Named arguments are not supported since the function doesn't declare the names of its arguments",
+        ));
+    }
+
+    let mut slots = Vec::new();
+
+    for _ in parameters {
+        slots.try_push(None)?;
+    }
+
+    // The parameter index of each argument in the order they are written.
+    let mut order = Vec::try_with_capacity(ast.args.len())?;
+    let mut positional = 0;
+    let mut seen_named = false;
+
+    for (arg, _) in &ast.args {
+        let index = match &arg.name {
+            None => {
+                if seen_named {
+                    return Err(compile::Error::msg(
+                        arg,
+                        "Positional arguments must come before named arguments",
+                    ));
+                }
+
+                if positional >= parameters.len() {
+                    return Err(compile::Error::new(
+                        &ast.args,
+                        ErrorKind::BadArgumentCount {
+                            expected: parameters.len(),
+                            actual: ast.args.len(),
+                        },
+                    ));
+                }
+
+                let index = positional;
+                positional += 1;
+                index
+            }
+            Some((ident, _)) => {
+                seen_named = true;
+
+                let name = ident.resolve(resolve_context!(cx.q))?;
+
+                let Some(index) = parameters
+                    .iter()
+                    .position(|p| p.name.as_deref() == Some(name))
+                else {
+                    return Err(compile::Error::msg(
+                        ident,
+                        try_format!("Function has no argument named `{name}`"),
+                    ));
+                };
+
+                if slots[index].is_some() {
+                    return Err(compile::Error::msg(
+                        arg,
+                        try_format!("Argument `{name}` is provided more than once"),
+                    ));
+                }
+
+                index
+            }
+        };
+
+        slots[index] = Some(expr(cx, &arg.expr)?);
+        order.try_push(index)?;
+    }
+
+    let mut args = Vec::try_with_capacity(slots.len())?;
+
+    for (slot, p) in slots.into_iter().zip(parameters) {
+        let value = match (slot, p.default) {
+            (Some(value), _) => value,
+            (None, Some(meta::ParameterDefault::Const(hash))) => hir::Expr {
+                span: ast.args.span(),
+                kind: hir::ExprKind::Const(hash),
+            },
+            (None, Some(meta::ParameterDefault::Item(id))) => {
+                let Some(meta) = cx.q.query_meta(&ast.args, id, Default::default())? else {
+                    return Err(compile::Error::new(
+                        &ast.args,
+                        ErrorKind::MissingItem {
+                            item: cx.q.pool.item(id).try_to_owned()?,
+                        },
+                    ));
+                };
+
+                hir::Expr {
+                    span: ast.args.span(),
+                    kind: hir::ExprKind::Const(meta.hash),
+                }
+            }
+            (None, None) => {
+                return Err(match &p.name {
+                    Some(name) => {
+                        compile::Error::msg(&ast.args, try_format!("Missing argument `{name}`"))
+                    }
+                    None => compile::Error::new(
+                        &ast.args,
+                        ErrorKind::BadArgumentCount {
+                            expected: parameters.len(),
+                            actual: ast.args.len(),
+                        },
+                    ),
+                });
+            }
+        };
+
+        args.try_push(value)?;
+    }
+
+    let in_order = order.windows(2).all(|w| w[0] < w[1]);
+    Ok((iter!(args), (!in_order).then_some(order)))
+}
+
+/// Construct a call whose arguments are written in a different order than the
+/// parameters they are passed to.
+///
+/// The arguments are evaluated into temporaries in the order they are written,
+/// so that their side effects happen in that order.
+fn call_in_order<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &ast::ExprCall,
+    call: hir::Call<'hir>,
+    args: &'hir [hir::Expr<'hir>],
+    order: &[usize],
+) -> compile::Result<hir::ExprKind<'hir>> {
+    alloc_with!(cx, ast);
+
+    let mut statements = Vec::try_with_capacity(order.len())?;
+    let mut temporaries = Vec::try_with_capacity(order.len())?;
+    let mut values = Vec::try_from(args)?;
+
+    for &index in order {
+        let arg = args[index];
+        let variable = cx.scopes.anonymous();
+
+        statements.try_push(hir::Stmt::Local(alloc!(hir::Local {
+            span: arg.span,
+            pat: hir::PatBinding {
+                pat: hir::Pat {
+                    span: arg.span,
+                    kind: hir::PatKind::Path(alloc!(hir::PatPathKind::Ident(variable))),
+                },
+                names: iter!([variable]),
+            },
+            expr: arg,
+            else_block: None,
+        })))?;
+
+        temporaries.try_push(variable)?;
+
+        values[index] = hir::Expr {
+            span: arg.span,
+            kind: hir::ExprKind::Variable(variable),
+        };
+    }
+
+    let value = hir::Expr {
+        span: ast.span(),
+        kind: hir::ExprKind::Call(alloc!(hir::ExprCall {
+            call,
+            args: iter!(values),
+        })),
+    };
+
+    Ok(hir::ExprKind::Block(alloc!(hir::Block {
+        span: ast.span(),
+        label: None,
+        statements: iter!(statements),
+        value: Some(alloc!(value)),
+        drop: iter!(temporaries.into_iter().rev()),
+    })))
 }
 
 #[instrument_ast(span = ast)]
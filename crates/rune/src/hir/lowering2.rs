@@ -87,9 +87,29 @@ pub(crate) fn item_fn<'hir>(
 
         let mut comma = Remaining::default();
 
-        while let MaybeNode::Some(pat) = p.eat(Pat) {
+        while let MaybeNode::Some(pat) =
+            p.eat_matching(|k| matches!(k, Pat | FnArgTyped | FnArgDefault))
+        {
             comma.exactly_one(cx)?;
-            let pat = pat.parse(|p| self::pat_binding_with(cx, p, is_instance))?;
+
+            let pat = match pat.kind() {
+                FnArgDefault => {
+                    return Err(Error::msg(
+                        pat,
+                        "Default values for arguments are not supported by the v2 compiler",
+                    ));
+                }
+                // Note: type annotations are erased.
+                FnArgTyped => pat.parse(|p| {
+                    let pat = p
+                        .expect(Pat)?
+                        .parse(|p| self::pat_binding_with(cx, p, is_instance))?;
+                    p.ignore();
+                    Ok(pat)
+                })?,
+                _ => pat.parse(|p| self::pat_binding_with(cx, p, is_instance))?,
+            };
+
             args.try_push(hir::FnArg::Pat(alloc!(pat)))?;
             comma = p.one(K![,]);
         }
@@ -99,6 +119,10 @@ pub(crate) fn item_fn<'hir>(
         Ok(())
     })?;
 
+    if p.eat(K![->]).is_some() {
+        p.expect(Kind::Type)?;
+    }
+
     let body = p.expect(Block)?.parse(|p| block(cx, None, p))?;
 
     Ok(hir::ItemFn {
@@ -248,18 +272,37 @@ pub(crate) fn local<'hir>(
     // Note: expression needs to be assembled before pattern, otherwise the
     // expression will see declarations in the pattern.
 
+    alloc_with!(cx, p);
+
     p.expect(K![let])?;
     let pat = p.expect(Pat)?;
+
+    // Note: type annotations are erased.
+    if p.eat(K![:]).is_some() {
+        p.expect(Kind::Type)?;
+    }
+
     p.expect(K![=])?;
     let expr = p.expect(Expr)?;
 
     let expr = expr.parse(|p| self::expr(cx, p))?;
+
+    // Note: the else block is lowered before the pattern, since it must not be
+    // able to see any of the bindings introduced by it.
+    let else_block = if p.eat(K![else]).is_some() {
+        let block = p.expect(Block)?.parse(|p| self::block(cx, None, p))?;
+        Some(&*alloc!(block))
+    } else {
+        None
+    };
+
     let pat = pat.parse(|p| self::pat_binding(cx, p))?;
 
     Ok(hir::Local {
         span: p.span(),
         pat,
         expr,
+        else_block,
     })
 }
 
@@ -371,7 +414,16 @@ impl<'hir> ExprInner<'hir, '_> {
                             )?;
                         }
                     }
-                    meta::Kind::Function { .. } => {
+                    meta::Kind::Function { signature, .. } => {
+                        let omitted = signature.parameters.get(args..).unwrap_or_default();
+
+                        if omitted.iter().any(|p| p.default.is_some()) {
+                            return Err(Error::msg(
+                                self.span,
+                                "Omitting arguments with default values is not supported by the v2 compiler",
+                            ));
+                        }
+
                         if let Some(message) = cx.q.lookup_deprecation(meta.hash) {
                             cx.q.diagnostics.used_deprecated(
                                 cx.source_id,
@@ -412,21 +464,23 @@ impl<'hir> ExprInner<'hir, '_> {
                         expr_field,
                         expr: target,
                     }) => {
-                        let hash = match expr_field {
-                            hir::ExprField::Index(index) => Hash::index(index),
+                        let (hash, name) = match expr_field {
+                            hir::ExprField::Index(index) => (Hash::index(index), None),
                             hir::ExprField::Ident(ident) => {
                                 cx.q.unit.insert_debug_ident(ident)?;
-                                Hash::ident(ident)
+                                (Hash::ident(ident), Some(ident))
                             }
                             hir::ExprField::IdentGenerics(ident, hash) => {
                                 cx.q.unit.insert_debug_ident(ident)?;
-                                Hash::ident(ident).with_function_parameters(hash)
+                                let hash = Hash::ident(ident).with_function_parameters(hash);
+                                (hash, None)
                             }
                         };
 
                         Ok(hir::Call::Associated {
                             target: alloc!(target),
                             hash,
+                            name,
                         })
                     }
                     kind => Ok(hir::Call::Expr {
@@ -1838,6 +1892,20 @@ fn condition<'hir>(
 
     match p.kind() {
         Condition => Ok(hir::Condition::ExprLet(alloc!(expr_let(cx, p)?))),
+        ConditionChain => {
+            let mut conditions = Vec::new();
+
+            conditions.try_push(p.expect(Condition)?.parse(|p| condition(cx, p))?)?;
+
+            while p.eat(K![&&]).is_some() {
+                conditions.try_push(p.pump()?.parse(|p| condition(cx, p))?)?;
+            }
+
+            Ok(hir::Condition::Chain(alloc!(hir::ConditionChain {
+                span: p.span(),
+                conditions: iter!(conditions),
+            })))
+        }
         Expr => Ok(hir::Condition::Expr(alloc!(expr(cx, p)?))),
         _ => Err(p.expected(Condition)),
     }
@@ -1959,8 +2027,16 @@ fn expr_call<'hir>(
     let mut comma = Remaining::default();
     let mut args = Vec::new();
 
-    while let MaybeNode::Some(node) = p.eat(Expr) {
+    while let MaybeNode::Some(node) = p.eat_matching(|k| matches!(k, Expr | NamedArgument)) {
         comma.exactly_one(cx)?;
+
+        if node.kind() == NamedArgument {
+            return Err(Error::msg(
+                node,
+                "Named arguments are not supported by the v2 compiler",
+            ));
+        }
+
         let expr = node.parse(|p| expr(cx, p))?;
         args.try_push(expr)?;
         comma = p.one(K![,]);
@@ -2117,7 +2193,18 @@ fn expr_path_meta<'hir>(
                 fields: meta::Fields::Unnamed(..),
                 ..
             } => Ok(hir::ExprKind::Fn(meta.hash)),
-            meta::Kind::Function { .. } => Ok(hir::ExprKind::Fn(meta.hash)),
+            meta::Kind::Function { signature, .. } => {
+                // Note: default values are filled in at the call site, which
+                // isn't possible when the function is called through a value.
+                if signature.parameters.iter().any(|p| p.default.is_some()) {
+                    return Err(Error::msg(
+                        span,
+                        "Functions with default values for arguments can only be called by their path",
+                    ));
+                }
+
+                Ok(hir::ExprKind::Fn(meta.hash))
+            }
             meta::Kind::Const => Ok(hir::ExprKind::Const(meta.hash)),
             meta::Kind::Struct { .. } | meta::Kind::Type { .. } | meta::Kind::Enum { .. } => {
                 Ok(hir::ExprKind::Type(Type::new(meta.hash)))
@@ -66,6 +66,8 @@ impl<'hir> Layer<'hir> {
 pub(crate) struct Scopes<'hir, 'a> {
     scope: Scope,
     scopes: Vec<Layer<'hir>>,
+    /// Names of every variable which has been defined.
+    names: HashMap<hir::Variable, hir::Name<'hir>>,
     gen: &'a Gen,
 }
 
@@ -81,6 +83,7 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         Ok(Self {
             scope: Scopes::ROOT,
             scopes,
+            names: HashMap::new(),
             gen,
         })
     }
@@ -155,6 +158,7 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
 
         layer.variables.try_insert(name, id)?;
         layer.order.try_push(id)?;
+        self.names.try_insert(id, name)?;
         Ok(id)
     }
 
@@ -174,9 +178,20 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         let id = hir::Variable(self.gen.next());
         layer.variables.try_insert(name, id)?;
         layer.order.try_push(id)?;
+        self.names.try_insert(id, name)?;
         Ok(id)
     }
 
+    /// Allocate a variable which can't be referred to by name.
+    pub(crate) fn anonymous(&mut self) -> hir::Variable {
+        hir::Variable(self.gen.next())
+    }
+
+    /// Take the names of all variables which have been defined.
+    pub(crate) fn take_names(&mut self) -> HashMap<hir::Variable, hir::Name<'hir>> {
+        core::mem::take(&mut self.names)
+    }
+
     /// Try to lookup the given variable.
     #[tracing::instrument(skip_all, fields(?self.scope, ?name))]
     pub(crate) fn get(
@@ -0,0 +1,401 @@
+//! Checking of optional type annotations.
+//!
+//! Annotations are only checked if the `type-checks` option is enabled and
+//! never affect the code which is generated. The checker is gradual, a value
+//! whose type can't be determined is compatible with any annotation.
+
+use crate::alloc::prelude::*;
+use crate::ast::{self, Span};
+use crate::compile::{self, meta, ErrorKind};
+use crate::hir;
+use crate::runtime::{self, Bytes, OwnedTuple, TypeHash};
+use crate::{Hash, ItemBuf};
+
+use super::Ctxt;
+
+/// Resolve an optional type annotation.
+pub(super) fn annotation(
+    cx: &mut Ctxt<'_, '_, '_>,
+    ty: Option<&ast::Type>,
+) -> compile::Result<Option<Hash>> {
+    cx.q.resolve_annotation(cx.source_id, ty)
+}
+
+/// Record the declared type of a pattern binding.
+///
+/// Types are only tracked for bindings to plain identifiers.
+pub(super) fn bind(
+    cx: &mut Ctxt<'_, '_, '_>,
+    pat: &hir::PatBinding<'_>,
+    ty: Option<Hash>,
+) -> compile::Result<()> {
+    let Some(ty) = ty else {
+        return Ok(());
+    };
+
+    if let hir::PatKind::Path(&hir::PatPathKind::Ident(variable)) = pat.pat.kind {
+        cx.variable_types.try_insert(variable, ty)?;
+    }
+
+    Ok(())
+}
+
+/// Check that an expression produces a value of the expected type.
+///
+/// Mismatches are reported as diagnostics so that checking can proceed.
+pub(super) fn check(
+    cx: &mut Ctxt<'_, '_, '_>,
+    expected: Option<Hash>,
+    hir: &hir::Expr<'_>,
+) -> compile::Result<()> {
+    check_with(cx, expected, hir, false)
+}
+
+/// Check an expression against the expected type, where `native` indicates
+/// that the value is passed into a native function.
+fn check_with(
+    cx: &mut Ctxt<'_, '_, '_>,
+    expected: Option<Hash>,
+    hir: &hir::Expr<'_>,
+    native: bool,
+) -> compile::Result<()> {
+    if !cx.q.options.type_checks {
+        return Ok(());
+    }
+
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+
+    let Some(actual) = type_of(cx, hir)? else {
+        return Ok(());
+    };
+
+    if is_compatible(expected, actual, native) {
+        return Ok(());
+    }
+
+    let (Some(expected), Some(actual)) = (type_name(cx, expected)?, type_name(cx, actual)?) else {
+        return Ok(());
+    };
+
+    cx.q.diagnostics.error(
+        cx.source_id,
+        compile::Error::new(hir.span, ErrorKind::TypeMismatch { expected, actual }),
+    )?;
+
+    Ok(())
+}
+
+/// Check that the value assigned to a place matches its declared type.
+pub(super) fn check_assign(
+    cx: &mut Ctxt<'_, '_, '_>,
+    lhs: &hir::Expr<'_>,
+    rhs: &hir::Expr<'_>,
+) -> compile::Result<()> {
+    if !cx.q.options.type_checks {
+        return Ok(());
+    }
+
+    let expected = type_of(cx, lhs)?;
+    check(cx, expected, rhs)
+}
+
+/// Check the arguments of a call against the signature being called.
+///
+/// The `native` flag indicates that the signature belongs to a native
+/// function.
+pub(super) fn check_args(
+    cx: &mut Ctxt<'_, '_, '_>,
+    signature: &meta::Signature,
+    args: &[hir::Expr<'_>],
+    native: bool,
+) -> compile::Result<()> {
+    if !cx.q.options.type_checks {
+        return Ok(());
+    }
+
+    for (index, arg) in args.iter().enumerate() {
+        check_with(cx, signature.argument_type(index), arg, native)?;
+    }
+
+    Ok(())
+}
+
+/// Check the arguments of an instance function call against the signature of
+/// the associated function being called.
+pub(super) fn check_associated(
+    cx: &mut Ctxt<'_, '_, '_>,
+    target: &hir::Expr<'_>,
+    name: &str,
+    args: &[hir::Expr<'_>],
+) -> compile::Result<()> {
+    if !cx.q.options.type_checks {
+        return Ok(());
+    }
+
+    let Some((signature, native)) = associated_signature(cx, target, name)? else {
+        return Ok(());
+    };
+
+    // The first argument of an instance function is the target itself.
+    for (index, arg) in args.iter().enumerate() {
+        check_with(cx, signature.argument_type(index + 1), arg, native)?;
+    }
+
+    Ok(())
+}
+
+/// Check the fields assigned in an object expression against their declared
+/// types.
+pub(super) fn check_fields(
+    cx: &mut Ctxt<'_, '_, '_>,
+    fields: &[meta::FieldMeta],
+    assignments: &[hir::FieldAssign<'_>],
+) -> compile::Result<()> {
+    if !cx.q.options.type_checks {
+        return Ok(());
+    }
+
+    for assign in assignments {
+        let Some(field) = assign.position.and_then(|p| fields.get(p)) else {
+            continue;
+        };
+
+        check(cx, field.ty, &assign.assign)?;
+    }
+
+    Ok(())
+}
+
+/// Integers convert freely between each other when passed into native
+/// functions, so they are only considered compatible at that boundary.
+fn is_compatible(expected: Hash, actual: Hash, native: bool) -> bool {
+    if expected == actual {
+        return true;
+    }
+
+    let is_integer = |hash| hash == i64::HASH || hash == u64::HASH;
+    native && is_integer(expected) && is_integer(actual)
+}
+
+/// Get the name of a type for diagnostics.
+fn type_name(cx: &mut Ctxt<'_, '_, '_>, hash: Hash) -> compile::Result<Option<ItemBuf>> {
+    if let Some(item) = cx.q.pool.item_by_type_hash(hash) {
+        return Ok(Some(cx.q.pool.item(item).try_to_owned()?));
+    }
+
+    match cx.q.context.type_item(hash) {
+        Some(item) => Ok(Some(item.try_to_owned()?)),
+        None => Ok(None),
+    }
+}
+
+/// Look up meta by its type hash.
+fn lookup(
+    cx: &mut Ctxt<'_, '_, '_>,
+    span: Span,
+    hash: Hash,
+) -> compile::Result<Option<meta::Meta>> {
+    let Some(item) = cx.q.pool.item_by_type_hash(hash) else {
+        return Ok(None);
+    };
+
+    cx.try_lookup_meta(&span, item, &Default::default())
+}
+
+/// Get the type of a value constructed or produced by calling the given hash.
+fn call_type(cx: &mut Ctxt<'_, '_, '_>, span: Span, hash: Hash) -> compile::Result<Option<Hash>> {
+    let Some(meta) = lookup(cx, span, hash)? else {
+        return Ok(None);
+    };
+
+    Ok(match &meta.kind {
+        meta::Kind::Struct { enum_hash, .. } if *enum_hash != Hash::EMPTY => Some(*enum_hash),
+        meta::Kind::Struct { .. } => Some(meta.hash),
+        meta::Kind::Function { signature, .. } => signature.output_type(),
+        _ => None,
+    })
+}
+
+/// Resolve the signature of the instance function `name` called on the
+/// given target, and whether it's a native function.
+fn associated_signature(
+    cx: &mut Ctxt<'_, '_, '_>,
+    target: &hir::Expr<'_>,
+    name: &str,
+) -> compile::Result<Option<(meta::Signature, bool)>> {
+    let Some(ty) = type_of(cx, target)? else {
+        return Ok(None);
+    };
+
+    let hash = Hash::associated_function(ty, name);
+
+    if let Some(meta) = cx.q.context.lookup_meta_by_hash(hash).next() {
+        return Ok(match &meta.kind {
+            meta::Kind::Function { signature, .. } => Some((signature.try_clone()?, true)),
+            _ => None,
+        });
+    }
+
+    let Some(item) = cx.q.pool.item_by_type_hash(ty) else {
+        return Ok(None);
+    };
+
+    let item = cx.q.pool.item(item).extended(name)?;
+
+    let Some(item) = cx.q.pool.item_by_type_hash(Hash::type_hash(&item)) else {
+        return Ok(None);
+    };
+
+    let Some(meta) = cx.try_lookup_meta(&target.span, item, &Default::default())? else {
+        return Ok(None);
+    };
+
+    Ok(match meta.kind {
+        meta::Kind::Function {
+            associated: Some(meta::AssociatedKind::Instance(..)),
+            signature,
+            ..
+        } => Some((signature, false)),
+        _ => None,
+    })
+}
+
+/// Get the declared type of a named field.
+fn field_type(
+    cx: &mut Ctxt<'_, '_, '_>,
+    span: Span,
+    hash: Hash,
+    name: &str,
+) -> compile::Result<Option<Hash>> {
+    let Some(meta) = lookup(cx, span, hash)? else {
+        return Ok(None);
+    };
+
+    let meta::Kind::Struct {
+        fields: meta::Fields::Named(named),
+        ..
+    } = &meta.kind
+    else {
+        return Ok(None);
+    };
+
+    Ok(named
+        .fields
+        .iter()
+        .find(|f| f.name.as_ref() == name)
+        .and_then(|f| f.ty))
+}
+
+/// Infer the type of an expression, if possible.
+fn type_of(cx: &mut Ctxt<'_, '_, '_>, hir: &hir::Expr<'_>) -> compile::Result<Option<Hash>> {
+    let ty = match hir.kind {
+        hir::ExprKind::Lit(lit) => match lit {
+            hir::Lit::Bool(..) => Some(bool::HASH),
+            hir::Lit::Unsigned(..) => Some(u64::HASH),
+            hir::Lit::Signed(..) => Some(i64::HASH),
+            hir::Lit::Float(..) => Some(f64::HASH),
+            hir::Lit::Char(..) => Some(char::HASH),
+            hir::Lit::Str(..) => Some(String::HASH),
+            hir::Lit::ByteStr(..) => Some(Bytes::HASH),
+        },
+        hir::ExprKind::Variable(variable) => cx.variable_types.get(&variable).copied(),
+        hir::ExprKind::Group(hir) => type_of(cx, hir)?,
+        hir::ExprKind::Block(block) => match block.value {
+            Some(hir) => type_of(cx, hir)?,
+            None => None,
+        },
+        hir::ExprKind::Tuple(..) => Some(OwnedTuple::HASH),
+        hir::ExprKind::Vec(..) => Some(runtime::Vec::HASH),
+        hir::ExprKind::Template(..) => Some(String::HASH),
+        hir::ExprKind::Object(object) => match object.kind {
+            hir::ExprObjectKind::Struct { hash } => call_type(cx, hir.span, hash)?,
+            hir::ExprObjectKind::ExternalType { hash, .. } => Some(hash),
+            hir::ExprObjectKind::Anonymous => Some(runtime::Object::HASH),
+        },
+        hir::ExprKind::Call(call) => match call.call {
+            hir::Call::Meta { hash } => call_type(cx, hir.span, hash)?,
+            hir::Call::Associated {
+                target,
+                name: Some(name),
+                ..
+            } => match associated_signature(cx, target, name)? {
+                Some((signature, _)) => signature.output_type(),
+                None => None,
+            },
+            _ => None,
+        },
+        hir::ExprKind::FieldAccess(access) => {
+            let hir::ExprField::Ident(name) = access.expr_field else {
+                return Ok(None);
+            };
+
+            match type_of(cx, &access.expr)? {
+                Some(hash) => field_type(cx, hir.span, hash, name)?,
+                None => None,
+            }
+        }
+        hir::ExprKind::Binary(binary) => match binary.op {
+            ast::BinOp::Eq(..)
+            | ast::BinOp::Neq(..)
+            | ast::BinOp::Gt(..)
+            | ast::BinOp::Lt(..)
+            | ast::BinOp::Gte(..)
+            | ast::BinOp::Lte(..)
+            | ast::BinOp::Is(..)
+            | ast::BinOp::IsNot(..)
+            | ast::BinOp::And(..)
+            | ast::BinOp::Or(..) => Some(bool::HASH),
+            ast::BinOp::Add(..)
+            | ast::BinOp::Sub(..)
+            | ast::BinOp::Mul(..)
+            | ast::BinOp::Div(..)
+            | ast::BinOp::Rem(..) => {
+                let lhs = type_of(cx, &binary.lhs)?;
+                let rhs = type_of(cx, &binary.rhs)?;
+
+                match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) if lhs == rhs && is_number(lhs) => Some(lhs),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        hir::ExprKind::Unary(unary) => match unary.op {
+            ast::UnOp::Not(..) | ast::UnOp::Neg(..) => type_of(cx, &unary.expr)?,
+            _ => None,
+        },
+        hir::ExprKind::If(conditional) => {
+            let Some(fallback) = conditional.fallback else {
+                return Ok(None);
+            };
+
+            let Some(ty) = block_type(cx, fallback)? else {
+                return Ok(None);
+            };
+
+            for branch in conditional.branches {
+                if block_type(cx, &branch.block)? != Some(ty) {
+                    return Ok(None);
+                }
+            }
+
+            Some(ty)
+        }
+        _ => None,
+    };
+
+    Ok(ty)
+}
+
+fn block_type(cx: &mut Ctxt<'_, '_, '_>, block: &hir::Block<'_>) -> compile::Result<Option<Hash>> {
+    match block.value {
+        Some(hir) => type_of(cx, hir),
+        None => Ok(None),
+    }
+}
+
+fn is_number(hash: Hash) -> bool {
+    hash == i64::HASH || hash == u64::HASH || hash == f64::HASH
+}
@@ -1,4 +1,4 @@
-use core::mem::take;
+use core::mem::{replace, take};
 
 use tracing::instrument_ast;
 
@@ -15,7 +15,7 @@ use crate::query::{DeferEntry, ImplItem, ImplItemKind};
 use crate::runtime::Call;
 use crate::worker::{Import, ImportKind, ImportState};
 
-use super::{ast_to_visibility, validate_call, Indexer};
+use super::{ast_to_visibility, validate_call, IndexItem, Indexer};
 
 /// Macros are only allowed to expand recursively into other macros 64 times.
 const MAX_MACRO_RECURSION: usize = 64;
@@ -222,12 +222,51 @@ pub(crate) fn empty_block_fn(
             is_bench: false,
             impl_item: None,
             args: Vec::new(),
+            parameters: Vec::new(),
         }),
     })?;
 
     Ok(())
 }
 
+/// Get the name of a parameter which can be passed by name, which is only the
+/// case for parameters which are bound to a plain identifier.
+fn parameter_name(idx: &mut Indexer<'_, '_>, ast: &ast::Pat) -> compile::Result<Option<Box<str>>> {
+    let ast::Pat::Path(p) = ast else {
+        return Ok(None);
+    };
+
+    let Some(ident) = p.path.try_as_ident() else {
+        return Ok(None);
+    };
+
+    Ok(Some(ident.resolve(resolve_context!(idx.q))?.try_into()?))
+}
+
+/// Index the default value of a function argument as an anonymous constant.
+#[instrument_ast(span = ast)]
+fn fn_arg_default(idx: &mut Indexer<'_, '_>, ast: &mut ast::FnArgDefault) -> compile::Result<()> {
+    let guard = idx.push_id()?;
+
+    // Note: the constant is only used by calls which omit the argument, so it
+    // is marked as public to avoid it being reported as unused.
+    let item_meta = idx.insert_new_item(&ast.default, Visibility::Public, &[])?;
+    let idx_item = idx.item.replace(item_meta.item);
+
+    ast.id = item_meta.item;
+
+    expr(idx, &mut ast.default)?;
+
+    idx.q.index_const_expr(
+        item_meta,
+        indexing::ConstExpr::Ast(Box::try_new(ast.default.try_clone()?)?),
+    )?;
+
+    idx.item = idx_item;
+    idx.items.pop(guard).with_span(&*ast)?;
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<()> {
     let name = ast.name.resolve(resolve_context!(idx.q))?;
@@ -242,10 +281,52 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
     let item_meta = idx.insert_new_item(&ast, visibility, &docs)?;
     let idx_item = idx.item.replace(item_meta.item);
 
+    let mut parameters = Vec::new();
+    let is_instance = matches!(ast.args.first(), Some((ast::FnArg::SelfValue(..), _)));
+
     for (arg, _) in &mut ast.args {
-        if let ast::FnArg::Pat(p) = arg {
-            pat(idx, p)?;
-        }
+        let (name, default) = match arg {
+            ast::FnArg::SelfValue(..) => (None, None),
+            ast::FnArg::Pat(p) => {
+                pat(idx, p)?;
+                (parameter_name(idx, p)?, None)
+            }
+            ast::FnArg::Typed(arg) => {
+                pat(idx, &mut arg.pat)?;
+                ty(idx, &mut arg.ty)?;
+                (parameter_name(idx, &arg.pat)?, None)
+            }
+            ast::FnArg::Default(arg) => {
+                // Note: instance functions are called as methods, where the
+                // function being called is only known at runtime.
+                if is_instance {
+                    return Err(compile::Error::msg(
+                        &**arg,
+                        "Default values for arguments are not supported in instance functions",
+                    ));
+                }
+
+                pat(idx, &mut arg.pat)?;
+
+                if let Some((_, ty)) = &mut arg.ty {
+                    self::ty(idx, ty)?;
+                }
+
+                let name = parameter_name(idx, &arg.pat)?;
+                fn_arg_default(idx, arg)?;
+                (name, Some(meta::ParameterDefault::Item(arg.id)))
+            }
+        };
+
+        parameters.try_push(meta::Parameter {
+            name,
+            default,
+            ty: None,
+        })?;
+    }
+
+    if let Some((_, output)) = &mut ast.output {
+        ty(idx, output)?;
     }
 
     idx.scopes.push()?;
@@ -318,13 +399,6 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
         ));
     }
 
-    if ast.output.is_some() {
-        return Err(compile::Error::msg(
-            &ast,
-            "Adding a return type in functions is not supported",
-        ));
-    }
-
     let is_instance = ast.is_instance();
 
     if is_instance {
@@ -363,6 +437,7 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
             is_bench,
             impl_item: idx.item.impl_item,
             args,
+            parameters,
         }),
     };
 
@@ -537,7 +612,17 @@ fn local(idx: &mut Indexer<'_, '_>, ast: &mut ast::Local) -> compile::Result<()>
     // We index the rhs expression first so that it doesn't see it's own
     // declaration and use that instead of capturing from the outside.
     expr(idx, &mut ast.expr)?;
+
+    if let Some((_, block)) = &mut ast.else_branch {
+        self::block(idx, block)?;
+    }
+
     pat(idx, &mut ast.pat)?;
+
+    if let Some((_, ty)) = &mut ast.ty {
+        self::ty(idx, ty)?;
+    }
+
     Ok(())
 }
 
@@ -831,6 +916,13 @@ fn condition(idx: &mut Indexer<'_, '_>, ast: &mut ast::Condition) -> compile::Re
         ast::Condition::ExprLet(e) => {
             expr_let(idx, e)?;
         }
+        ast::Condition::Chain(chain) => {
+            expr_let(idx, &mut chain.first)?;
+
+            for (_, c) in &mut chain.rest {
+                condition(idx, c)?;
+            }
+        }
     }
 
     Ok(())
@@ -908,6 +1000,8 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
             }
         }
 
+        let types = field_types(idx, &mut variant.body)?;
+
         idx.item = idx_item;
         idx.items.pop(guard).with_span(&variant)?;
 
@@ -916,6 +1010,7 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
             indexing::Variant {
                 enum_id: enum_item.item,
                 fields: convert_fields(resolve_context!(idx.q), variant.body)?,
+                types,
             },
         )?;
     }
@@ -959,13 +1054,6 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
             ));
         }
 
-        if field.ty.is_some() {
-            return Err(compile::Error::msg(
-                field,
-                "Static typing on fields is not supported",
-            ));
-        }
-
         let name = field.name.resolve(cx)?;
 
         for doc in docs {
@@ -989,11 +1077,14 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
         }
     }
 
+    let types = field_types(idx, &mut ast.body)?;
+
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
 
     let fields = convert_fields(resolve_context!(idx.q), ast.body)?;
-    idx.q.index_struct(item_meta, indexing::Struct { fields })?;
+    idx.q
+        .index_struct(item_meta, indexing::Struct { fields, types })?;
     Ok(())
 }
 
@@ -1006,6 +1097,10 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
         ));
     }
 
+    if let Some((trait_path, _)) = &mut ast.trait_ {
+        path(idx, trait_path)?;
+    }
+
     path(idx, &mut ast.path)?;
 
     let location = Location::new(idx.source_id, ast.path.span());
@@ -1016,6 +1111,10 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
         .try_push_back(DeferEntry::ImplItem(ImplItem {
             kind: ImplItemKind::Ast {
                 path: Box::try_new(ast.path)?,
+                trait_: match ast.trait_ {
+                    Some((trait_path, _)) => Some(Box::try_new(trait_path)?),
+                    None => None,
+                },
                 functions: take(&mut ast.functions),
             },
             location,
@@ -1027,6 +1126,116 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
     Ok(())
 }
 
+/// Trait functions are always called as methods, so their arguments can't be
+/// given default values.
+fn trait_fn_args(args: &ast::Parenthesized<ast::FnArg, T![,]>) -> compile::Result<()> {
+    for (arg, _) in args {
+        if let ast::FnArg::Default(arg) = arg {
+            return Err(compile::Error::msg(
+                arg,
+                "Default values for arguments are not supported in trait functions",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument_ast(span = ast)]
+fn item_trait(idx: &mut Indexer<'_, '_>, ast: ast::ItemTrait) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on traits are not supported",
+        ));
+    }
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref())?;
+
+    let visibility = ast_to_visibility(&ast.visibility)?;
+    let item_meta = idx.insert_new_item(&ast.name, visibility, &docs)?;
+
+    // Default functions are indexed as instance functions of the trait
+    // itself, which implementing types then refer to.
+    let module = idx.item.module;
+
+    let idx_item = replace(
+        &mut idx.item,
+        IndexItem::with_impl_item(module, item_meta.item, item_meta.item),
+    );
+
+    let mut functions = Vec::<indexing::TraitFunction>::new();
+
+    for item in ast.items {
+        let name = item.name().resolve(resolve_context!(idx.q))?;
+
+        if functions.iter().any(|f| *f.name == *name) {
+            return Err(compile::Error::msg(
+                item.name(),
+                try_format!("Function `{name}` is already declared in this trait"),
+            ));
+        }
+
+        let function = indexing::TraitFunction {
+            name: name.try_into()?,
+            span: item.span(),
+            is_default: matches!(item, ast::TraitItem::Fn(..)),
+        };
+
+        match item {
+            ast::TraitItem::Fn(f) => {
+                if !f.is_instance() {
+                    return Err(compile::Error::msg(
+                        f.descriptive_span(),
+                        "Functions in traits must receive `self`",
+                    ));
+                }
+
+                trait_fn_args(&f.args)?;
+                item_fn(idx, f)?;
+            }
+            ast::TraitItem::Required(f) => {
+                if let Some(first) = f.attributes.first() {
+                    return Err(compile::Error::msg(
+                        first,
+                        "Attributes on trait functions are not supported",
+                    ));
+                }
+
+                if !matches!(f.args.first(), Some((ast::FnArg::SelfValue(..), _))) {
+                    return Err(compile::Error::msg(
+                        &f,
+                        "Functions in traits must receive `self`",
+                    ));
+                }
+
+                if f.output.is_some() {
+                    return Err(compile::Error::msg(
+                        &f,
+                        "Adding a return type in functions is not supported",
+                    ));
+                }
+
+                trait_fn_args(&f.args)?;
+            }
+        }
+
+        functions.try_push(function)?;
+    }
+
+    idx.item = idx_item;
+    idx.items.pop(guard).with_span(&ast.name)?;
+
+    idx.q
+        .index_trait(item_meta, indexing::Trait { functions })?;
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn item_mod(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemMod) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes)?;
@@ -1123,6 +1332,9 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         ast::Item::Impl(item) => {
             item_impl(idx, item)?;
         }
+        ast::Item::Trait(item) => {
+            item_trait(idx, item)?;
+        }
         ast::Item::Mod(item) => {
             item_mod(idx, item)?;
         }
@@ -1203,6 +1415,23 @@ fn path(idx: &mut Indexer<'_, '_>, ast: &mut ast::Path) -> compile::Result<()> {
     Ok(())
 }
 
+#[instrument_ast(span = ast)]
+fn ty(idx: &mut Indexer<'_, '_>, ast: &mut ast::Type) -> compile::Result<()> {
+    match ast {
+        ast::Type::Path(p) => {
+            path(idx, p)?;
+        }
+        ast::Type::Bang(..) => {}
+        ast::Type::Tuple(tuple) => {
+            for (ty, _) in tuple.iter_mut() {
+                self::ty(idx, ty)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn path_segment(idx: &mut Indexer<'_, '_>, ast: &mut ast::PathSegment) -> compile::Result<()> {
     if let ast::PathSegment::Generics(generics) = ast {
@@ -1254,6 +1483,16 @@ fn expr_closure(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprClosure) -> compil
             ast::FnArg::Pat(p) => {
                 pat(idx, p)?;
             }
+            ast::FnArg::Typed(arg) => {
+                pat(idx, &mut arg.pat)?;
+                ty(idx, &mut arg.ty)?;
+            }
+            ast::FnArg::Default(arg) => {
+                return Err(compile::Error::msg(
+                    arg,
+                    "Default values for arguments are not supported in closures",
+                ));
+            }
         }
     }
 
@@ -1320,8 +1559,8 @@ fn expr_select(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprSelect) -> compile:
 fn expr_call(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprCall) -> compile::Result<()> {
     ast.id = idx.item.id;
 
-    for (e, _) in &mut ast.args {
-        expr(idx, e)?;
+    for (arg, _) in &mut ast.args {
+        expr(idx, &mut arg.expr)?;
     }
 
     expr(idx, &mut ast.expr)?;
@@ -1344,6 +1583,31 @@ fn expr_object(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprObject) -> compile:
     Ok(())
 }
 
+/// Index the type annotations of fields, collecting the ones which belong to
+/// named fields by position so that they can be resolved once the item is
+/// built.
+fn field_types(
+    idx: &mut Indexer<'_, '_>,
+    body: &mut ast::Fields,
+) -> compile::Result<Vec<(usize, ast::Type)>> {
+    let named = matches!(body, ast::Fields::Named(..));
+    let mut types = Vec::new();
+
+    for (position, (field, _)) in body.fields_mut().enumerate() {
+        let Some((_, ty)) = &mut field.ty else {
+            continue;
+        };
+
+        self::ty(idx, ty)?;
+
+        if named {
+            types.try_push((position, ty.try_clone()?))?;
+        }
+    }
+
+    Ok(types)
+}
+
 /// Convert AST fields into meta fields.
 fn convert_fields(cx: ResolveContext<'_, '_>, body: ast::Fields) -> compile::Result<meta::Fields> {
     let fields = match body {
@@ -1357,6 +1621,7 @@ fn convert_fields(cx: ResolveContext<'_, '_>, body: ast::Fields) -> compile::Res
                 fields.try_push(meta::FieldMeta {
                     name: name.try_into()?,
                     position,
+                    ty: None,
                 })?;
             }
 
@@ -62,9 +62,15 @@ fn is_instance(node: &Node<'_>) -> alloc::Result<(bool, Vec<Span>)> {
     };
 
     for node in node.children() {
-        if matches!(node.kind(), Pat) {
-            args.try_push(node.span())?;
-            is_instance |= is_self(node);
+        match node.kind() {
+            Pat => {
+                args.try_push(node.span())?;
+                is_instance |= is_self(node);
+            }
+            FnArgTyped => {
+                args.try_push(node.span())?;
+            }
+            _ => {}
         }
     }
 
@@ -443,6 +449,7 @@ impl<'a> Processor<'a> {
                 is_bench: false,
                 impl_item: None,
                 args: Vec::new(),
+                parameters: Vec::new(),
             }),
         })?;
 
@@ -557,6 +564,7 @@ impl<'a> Processor<'a> {
                 is_bench,
                 impl_item: idx.item.impl_item,
                 args,
+                parameters: Vec::new(),
             }),
         };
 
@@ -593,6 +601,10 @@ impl<'a> Processor<'a> {
             ItemImpl => {
                 item_impl(idx, p, mods, attrs)?;
             }
+            ItemTrait => {
+                idx.error(Error::msg(&*p, "Traits are not supported"))?;
+                p.ignore();
+            }
             ItemStruct => {
                 item_struct(idx, p, mods, attrs)?;
             }
@@ -833,6 +845,12 @@ fn item_impl(
 
     node.replace(IndexedPath(idx.item.id));
 
+    if let MaybeNode::Some(for_) = p.eat(K![for]) {
+        idx.error(Error::msg(&for_, "Trait implementations are not supported"))?;
+        p.ignore();
+        return Ok(());
+    }
+
     let mut functions = Vec::new();
 
     p.eat(Block).parse(|p| {
@@ -909,7 +927,13 @@ fn item_struct(
 
     let fields = p.pump()?.parse(|p| fields(idx, p))?;
 
-    idx.q.index_struct(item_meta, indexing::Struct { fields })?;
+    idx.q.index_struct(
+        item_meta,
+        indexing::Struct {
+            fields,
+            types: Vec::new(),
+        },
+    )?;
 
     idx.items.pop(guard).with_span(&*p)?;
 
@@ -956,6 +980,7 @@ fn item_enum(
         let variant = indexing::Variant {
             enum_id: enum_item_meta.item,
             fields,
+            types: Vec::new(),
         };
 
         idx.q.index_variant(item_meta, variant)?;
@@ -1167,12 +1192,18 @@ fn fields(idx: &mut Indexer<'_, '_>, p: &mut Stream<'_>) -> Result<meta::Fields>
 
             while let MaybeNode::Some(field) = p.eat(Field) {
                 comma.exactly_one(idx)?;
-                let name = field.parse(|p| p.ast::<ast::Ident>())?;
+                // Note: type annotations are erased.
+                let name = field.parse(|p| {
+                    let name = p.ast::<ast::Ident>()?;
+                    p.ignore();
+                    Ok(name)
+                })?;
                 let name = name.resolve(resolve_context!(idx.q))?;
                 let position = fields.len();
                 fields.try_push(meta::FieldMeta {
                     name: name.try_into()?,
                     position,
+                    ty: None,
                 })?;
                 comma = p.remaining(idx, K![,])?;
             }
@@ -58,6 +58,8 @@ pub(crate) enum Indexed {
     Import(Import),
     /// An indexed module.
     Module,
+    /// A trait.
+    Trait,
 }
 
 /// The ast of a function.
@@ -89,6 +91,8 @@ pub(crate) struct Function {
     pub(crate) impl_item: Option<ItemId>,
     /// Spans of the arguments to the function for diagnostics.
     pub(crate) args: Vec<Span>,
+    /// Declared parameters of the function.
+    pub(crate) parameters: Vec<meta::Parameter>,
 }
 
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -106,6 +110,24 @@ pub(crate) struct Import {
 pub(crate) struct Struct {
     /// The fields of the struct.
     pub(crate) fields: meta::Fields,
+    /// Type annotations of named fields by position.
+    pub(crate) types: Vec<(usize, ast::Type)>,
+}
+
+#[derive(Debug, TryClone)]
+pub(crate) struct Trait {
+    /// The functions declared in the trait.
+    pub(crate) functions: Vec<TraitFunction>,
+}
+
+#[derive(Debug, TryClone)]
+pub(crate) struct TraitFunction {
+    /// The name of the function.
+    pub(crate) name: Box<str>,
+    /// The span of the function declaration.
+    pub(crate) span: Span,
+    /// If the function has a default implementation.
+    pub(crate) is_default: bool,
 }
 
 #[derive(Debug, TryClone)]
@@ -114,6 +136,8 @@ pub(crate) struct Variant {
     pub(crate) enum_id: ItemId,
     /// The fields of the variant.
     pub(crate) fields: meta::Fields,
+    /// Type annotations of named fields by position.
+    pub(crate) types: Vec<(usize, ast::Type)>,
 }
 
 #[derive(Debug, TryClone)]
@@ -8,6 +8,9 @@ use lsp::CompletionTextEdit;
 use lsp::Documentation;
 use lsp::MarkupContent;
 use lsp::MarkupKind;
+use lsp::ParameterInformation;
+use lsp::ParameterLabel;
+use lsp::SignatureInformation;
 use lsp::TextEdit;
 
 use crate::alloc::fmt::TryWrite;
@@ -16,6 +19,7 @@ use crate::alloc::{String, Vec};
 use crate::compile::meta;
 use crate::runtime::debug::DebugArgs;
 use crate::Context;
+use crate::Item;
 use crate::Unit;
 
 use super::state::ServerSource;
@@ -42,26 +46,7 @@ pub(super) fn complete_for_unit(
             continue;
         };
 
-        let args = match &function.args {
-            DebugArgs::EmptyArgs => None,
-            DebugArgs::TupleArgs(n) => Some({
-                let mut o = String::new();
-
-                let mut it = 0..*n;
-                let last = it.next_back();
-
-                for n in it {
-                    write!(o, "_{n}, ")?;
-                }
-
-                if let Some(n) = last {
-                    write!(o, "_{n}")?;
-                }
-
-                o
-            }),
-            DebugArgs::Named(names) => Some(names.iter().map(|s| s.as_ref()).try_join(", ")?),
-        };
+        let args = format_debug_args(&function.args)?;
 
         let docs = workspace_source
             .get_docs_by_hash(*hash)
@@ -120,12 +105,7 @@ pub(super) fn complete_native_instance_data(
         };
 
         if n.starts_with(symbol) {
-            let return_type = signature
-                .return_type
-                .base
-                .as_non_empty()
-                .and_then(|hash| context.lookup_meta_by_hash(hash.get()).next())
-                .and_then(|r| r.item.as_deref());
+            let return_type = native_return_type(context, signature);
 
             let docs = meta.docs.lines().join("\n");
             let args = meta.docs.args().join(", ");
@@ -185,12 +165,7 @@ pub(super) fn complete_native_loose_data(
             .try_to_owned()?;
 
         if func_name.starts_with(symbol) {
-            let return_type = signature
-                .return_type
-                .base
-                .as_non_empty()
-                .and_then(|hash| context.lookup_meta_by_hash(hash.get()).next())
-                .and_then(|r| r.item.as_deref());
+            let return_type = native_return_type(context, signature);
 
             let docs = meta.docs.lines().join("\n");
             let args = meta.docs.args().join(", ");
@@ -227,3 +202,168 @@ pub(super) fn complete_native_loose_data(
 
     Ok(())
 }
+
+/// Format the arguments of a script function as they were recorded in debug
+/// info.
+pub(super) fn format_debug_args(args: &DebugArgs) -> Result<Option<String>> {
+    let args = match args {
+        DebugArgs::EmptyArgs => None,
+        DebugArgs::TupleArgs(n) => Some({
+            let mut o = String::new();
+
+            let mut it = 0..*n;
+            let last = it.next_back();
+
+            for n in it {
+                write!(o, "_{n}, ")?;
+            }
+
+            if let Some(n) = last {
+                write!(o, "_{n}")?;
+            }
+
+            o
+        }),
+        DebugArgs::Named(names) => Some(names.iter().map(|s| s.as_ref()).try_join(", ")?),
+    };
+
+    Ok(args)
+}
+
+/// Resolve the item of the return type of a native function signature.
+pub(super) fn native_return_type<'a>(
+    context: &'a Context,
+    signature: &meta::Signature,
+) -> Option<&'a Item> {
+    signature
+        .return_type
+        .base
+        .as_non_empty()
+        .and_then(|hash| context.lookup_meta_by_hash(hash.get()).next())
+        .and_then(|r| r.item.as_deref())
+}
+
+/// Collect signatures of script functions matching the given call path.
+///
+/// If `instance` is set, `path` is the name of an instance function.
+pub(super) fn signatures_for_unit(
+    workspace_source: &ServerSource,
+    unit: &Unit,
+    path: &str,
+    instance: bool,
+    results: &mut Vec<SignatureInformation>,
+) -> Result<()> {
+    let Some(debug_info) = unit.debug_info() else {
+        return Ok(());
+    };
+
+    for (hash, function) in debug_info.functions.iter() {
+        let args = format_debug_args(&function.args)?.unwrap_or_default();
+        let names = args
+            .split(", ")
+            .filter(|s| !s.is_empty())
+            .try_collect::<Vec<_>>()?;
+
+        let is_instance = names.first() == Some(&"self");
+
+        let matches = if instance {
+            is_instance && function.path.base_name() == Some(path)
+        } else {
+            path_matches(&function.path.try_to_string()?, path)
+        };
+
+        if !matches {
+            continue;
+        }
+
+        let docs = workspace_source
+            .get_docs_by_hash(*hash)
+            .map(|docs| docs.docs.join("\n"));
+
+        let label = format!("fn {}({args})", function.path);
+        results.try_push(signature_information(label, &names, docs))?;
+    }
+
+    Ok(())
+}
+
+/// Collect signatures of native functions matching the given call path.
+///
+/// If `instance` is set, `path` is the name of an instance function.
+pub(super) fn signatures_for_native(
+    context: &Context,
+    path: &str,
+    instance: bool,
+    results: &mut Vec<SignatureInformation>,
+) -> Result<()> {
+    for (meta, signature) in context.iter_functions() {
+        let Some(item) = &meta.item else {
+            continue;
+        };
+
+        let matches = match &meta.kind {
+            meta::Kind::Function {
+                associated: Some(meta::AssociatedKind::Instance(name)),
+                ..
+            } if instance => **name == *path,
+            meta::Kind::Function { .. } if !instance => path_matches(&item.try_to_string()?, path),
+            _ => false,
+        };
+
+        if !matches {
+            continue;
+        }
+
+        let args = meta.docs.args();
+        let joined = args.join(", ");
+
+        let label = match native_return_type(context, signature) {
+            Some(r) => format!("fn {item}({joined}) -> {r}"),
+            None => format!("fn {item}({joined})"),
+        };
+
+        let docs = meta.docs.lines().join("\n");
+        results.try_push(signature_information(label, args, Some(docs)))?;
+    }
+
+    Ok(())
+}
+
+fn signature_information<S>(
+    label: std::string::String,
+    args: &[S],
+    docs: Option<std::string::String>,
+) -> SignatureInformation
+where
+    S: AsRef<str>,
+{
+    let parameters = args
+        .iter()
+        .map(|arg| ParameterInformation {
+            label: ParameterLabel::Simple(arg.as_ref().to_owned()),
+            documentation: None,
+        })
+        .collect();
+
+    SignatureInformation {
+        label,
+        documentation: docs.filter(|d| !d.trim().is_empty()).map(|d| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: d,
+            })
+        }),
+        parameters: Some(parameters),
+        active_parameter: None,
+    }
+}
+
+/// Test if an item matches the path used at a call site.
+fn path_matches(item: &str, path: &str) -> bool {
+    let item = item.trim_start_matches("::");
+
+    match item.strip_suffix(path) {
+        Some(prefix) => prefix.is_empty() || prefix.ends_with("::"),
+        None => false,
+    }
+}
@@ -261,6 +261,13 @@ where
                         req(lsp::request::Shutdown, shutdown),
                         req(lsp::request::GotoDefinition, goto_definition),
                         req(lsp::request::Completion, completion),
+                        req(lsp::request::HoverRequest, hover),
+                        req(lsp::request::SignatureHelpRequest, signature_help),
+                        req(lsp::request::References, references),
+                        req(lsp::request::PrepareRenameRequest, prepare_rename),
+                        req(lsp::request::Rename, rename),
+                        req(lsp::request::DocumentSymbolRequest, document_symbol),
+                        req(lsp::request::WorkspaceSymbolRequest, workspace_symbol),
                         req(lsp::request::Formatting, formatting),
                         req(lsp::request::RangeFormatting, range_formatting),
                         notif(lsp::notification::DidOpenTextDocument, did_open_text_document),
@@ -340,6 +347,23 @@ fn initialize(s: &mut State<'_>, params: lsp::InitializeParams) -> Result<lsp::I
                 label_details_support: Some(true),
             }),
         }),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
         document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
         ..Default::default()
@@ -419,6 +443,82 @@ fn completion(
     Ok(Some(lsp::CompletionResponse::Array(results.into_std())))
 }
 
+/// Handle hover request.
+fn hover(state: &mut State<'_>, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    state.hover(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle signature help request.
+fn signature_help(
+    state: &mut State<'_>,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    state.signature_help(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle references request.
+fn references(
+    state: &mut State<'_>,
+    params: lsp::ReferenceParams,
+) -> Result<Option<rust_alloc::vec::Vec<lsp::Location>>> {
+    let Some(locations) = state.references(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        params.context.include_declaration,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(locations.into_std()))
+}
+
+/// Handle prepare rename request.
+fn prepare_rename(
+    state: &mut State<'_>,
+    params: lsp::TextDocumentPositionParams,
+) -> Result<Option<lsp::PrepareRenameResponse>> {
+    state.prepare_rename(&params.text_document.uri, params.position)
+}
+
+/// Handle rename request.
+fn rename(state: &mut State<'_>, params: lsp::RenameParams) -> Result<Option<lsp::WorkspaceEdit>> {
+    state.rename(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        &params.new_name,
+    )
+}
+
+/// Handle document symbol request.
+fn document_symbol(
+    state: &mut State<'_>,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    let Some(symbols) = state.document_symbols(&params.text_document.uri)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(lsp::DocumentSymbolResponse::Nested(
+        symbols.into_std(),
+    )))
+}
+
+/// Handle workspace symbol request.
+fn workspace_symbol(
+    state: &mut State<'_>,
+    params: lsp::WorkspaceSymbolParams,
+) -> Result<Option<lsp::WorkspaceSymbolResponse>> {
+    let symbols = state.workspace_symbols(&params.query)?;
+    Ok(Some(lsp::WorkspaceSymbolResponse::Flat(symbols.into_std())))
+}
+
 /// Handle formatting request.
 fn formatting(
     state: &mut State<'_>,
@@ -8,9 +8,10 @@ use lsp::Url;
 use ropey::Rope;
 use tokio::sync::Notify;
 
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, HashMap, String, Vec};
-use crate::ast::{Span, Spanned};
+use crate::ast::{self, Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{
     self, CompileVisitor, LinkerError, Located, Location, MetaError, MetaRef, SourceMeta, WithSpan,
@@ -20,9 +21,10 @@ use crate::doc::VisitorData;
 use crate::item::ComponentRef;
 use crate::languageserver::connection::Outbound;
 use crate::languageserver::Language;
+use crate::parse;
 use crate::workspace::{self, FileSourceLoader, Manifest, WorkspaceError, MANIFEST_FILE};
 use crate::{self as rune, Diagnostics};
-use crate::{BuildError, Context, Hash, Item, Options, Source, SourceId, Sources, Unit};
+use crate::{BuildError, Context, Hash, Item, ItemBuf, Options, Source, SourceId, Sources, Unit};
 
 #[derive(Default)]
 struct Reporter {
@@ -246,16 +248,16 @@ impl<'a> State<'a> {
             return Ok(None);
         };
 
+        let Some(source_id) = def.source.source_id() else {
+            return Ok(None);
+        };
+
         let url = match def.source.path() {
             Some(path) => crate::languageserver::url::from_file_path(path)?,
             None => uri.clone(),
         };
 
-        let Some(source) = source
-            .build_sources
-            .as_ref()
-            .and_then(|s| s.get(def.source.source_id()))
-        else {
+        let Some(source) = source.build_sources.as_ref().and_then(|s| s.get(source_id)) else {
             return Ok(None);
         };
 
@@ -267,6 +269,588 @@ impl<'a> State<'a> {
         Ok(Some(location))
     }
 
+    /// Find hover information at the given uri and LSP position.
+    pub(super) fn hover(&self, uri: &Url, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+
+        let Some(def) = source.find_definition_at(Span::point(offset)) else {
+            return Ok(None);
+        };
+
+        let Some(item) = &def.item else {
+            return Ok(None);
+        };
+
+        let mut signature = String::new();
+        let mut docs = None;
+
+        match &def.source {
+            DefinitionSource::Context => {
+                let meta = self.context.lookup_meta_by_hash(def.hash).next();
+
+                match meta.and_then(|meta| Some((meta, meta.kind.as_signature()?))) {
+                    Some((meta, sig)) => {
+                        let args = meta.docs.args().join(", ");
+                        write!(signature, "fn {item}({args})")?;
+
+                        if let Some(ty) = super::completion::native_return_type(&self.context, sig)
+                        {
+                            write!(signature, " -> {ty}")?;
+                        }
+                    }
+                    None => {
+                        write!(signature, "{}{item}", def.kind.keyword())?;
+                    }
+                }
+
+                if let Some(meta) = meta {
+                    docs = Some(meta.docs.lines().join("\n"));
+                }
+            }
+            _ => {
+                let function = source
+                    .unit
+                    .as_ref()
+                    .and_then(|unit| unit.debug_info())
+                    .and_then(|debug| debug.functions.get(&def.hash));
+
+                match function {
+                    Some(function) => {
+                        let args = super::completion::format_debug_args(&function.args)?;
+                        let args = args.as_deref().unwrap_or_default();
+                        write!(signature, "fn {item}({args})")?;
+                    }
+                    None => {
+                        write!(signature, "{}{item}", def.kind.keyword())?;
+                    }
+                }
+
+                if let Some(data) = source.get_docs_by_hash(def.hash) {
+                    docs = Some(data.docs.join("\n"));
+                }
+            }
+        }
+
+        let mut value = String::new();
+        writeln!(value, "```rune")?;
+        writeln!(value, "{signature}")?;
+        write!(value, "```")?;
+
+        if let Some(docs) = docs.filter(|docs| !docs.trim().is_empty()) {
+            write!(value, "\n\n{docs}")?;
+        }
+
+        Ok(Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: value.into_std(),
+            }),
+            range: None,
+        }))
+    }
+
+    /// Find all references to the symbol at the given uri and LSP position.
+    pub(super) fn references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Result<Option<Vec<lsp::Location>>> {
+        let Some((symbol, name)) = self.symbol_at(uri, position)? else {
+            return Ok(None);
+        };
+
+        let mut locations = Vec::new();
+
+        for (uri, range) in self.find_references(uri, &symbol, &name, include_declaration)? {
+            locations.try_push(lsp::Location { uri, range })?;
+        }
+
+        Ok(Some(locations))
+    }
+
+    /// Test if the symbol at the given uri and LSP position can be renamed,
+    /// returning the range of the name being renamed.
+    pub(super) fn prepare_rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::PrepareRenameResponse>> {
+        let Some((symbol, name)) = self.symbol_at(uri, position)? else {
+            return Ok(None);
+        };
+
+        if matches!(symbol, Symbol::Native(..)) {
+            return Ok(None);
+        }
+
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+
+        for (found, range) in self.find_references(uri, &symbol, &name, true)? {
+            if found != *uri {
+                continue;
+            }
+
+            let start = self.encoding.rope_position(&source.content, range.start)?;
+            let end = self.encoding.rope_position(&source.content, range.end)?;
+
+            if (start..=end).contains(&offset) {
+                return Ok(Some(lsp::PrepareRenameResponse::RangeWithPlaceholder {
+                    range,
+                    placeholder: name.into_std(),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Rename the symbol at the given uri and LSP position.
+    ///
+    /// Items which are provided by native modules and names which are not
+    /// valid identifiers are refused.
+    pub(super) fn rename(
+        &mut self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        let Some((symbol, name)) = self.symbol_at(uri, position)? else {
+            return Ok(None);
+        };
+
+        if !parse::is_ident(new_name) || ast::Kind::from_keyword(new_name).is_some() {
+            self.out
+                .notification::<lsp::notification::ShowMessage>(lsp::ShowMessageParams {
+                    typ: lsp::MessageType::WARNING,
+                    message: format!(
+                        "Cannot rename to `{new_name}` since it's not a valid identifier"
+                    ),
+                })?;
+
+            return Ok(None);
+        }
+
+        if let Symbol::Native(item) = &symbol {
+            self.out
+                .notification::<lsp::notification::ShowMessage>(lsp::ShowMessageParams {
+                    typ: lsp::MessageType::WARNING,
+                    message: format!(
+                        "Cannot rename `{item}` since it's provided by a native module"
+                    ),
+                })?;
+
+            return Ok(None);
+        }
+
+        let mut changes = std::collections::HashMap::<Url, std::vec::Vec<lsp::TextEdit>>::new();
+
+        for (uri, range) in self.find_references(uri, &symbol, &name, true)? {
+            changes.entry(uri).or_default().push(lsp::TextEdit {
+                range,
+                new_text: new_name.try_to_owned()?.into_std(),
+            });
+        }
+
+        Ok(Some(lsp::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+    /// Resolve the symbol at the given uri and LSP position, either by it being
+    /// used or declared at that position.
+    fn symbol_at(&self, uri: &Url, position: lsp::Position) -> Result<Option<(Symbol, String)>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+
+        if let Some(def) = source.find_definition_at(Span::point(offset)) {
+            return self.symbol(uri, source, def);
+        }
+
+        for (origin_uri, origin) in &self.workspace.sources {
+            for def in origin.index.definitions.values() {
+                let Some((decl_uri, _, span)) = self.declaration(origin_uri, origin, def)? else {
+                    continue;
+                };
+
+                if decl_uri != *uri || !span.range().contains(&offset) {
+                    continue;
+                }
+
+                if let Some(symbol) = self.symbol(origin_uri, origin, def)? {
+                    return Ok(Some(symbol));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the symbol and name of the given definition found in `origin`.
+    fn symbol(
+        &self,
+        origin_uri: &Url,
+        origin: &ServerSource,
+        def: &Definition,
+    ) -> Result<Option<(Symbol, String)>> {
+        if let Some(item) = &def.item {
+            let Some(name) = item.base_name() else {
+                return Ok(None);
+            };
+
+            let symbol = match def.source {
+                DefinitionSource::Context => Symbol::Native(item.try_clone()?),
+                _ => Symbol::Item(def.hash),
+            };
+
+            return Ok(Some((symbol, name.try_to_owned()?)));
+        }
+
+        let DefinitionSource::Location(location) = &def.source else {
+            return Ok(None);
+        };
+
+        let Some((_, source, span)) = self.declaration(origin_uri, origin, def)? else {
+            return Ok(None);
+        };
+
+        let Some(name) = source.get(span.range()) else {
+            return Ok(None);
+        };
+
+        let symbol = Symbol::Local(origin_uri.clone(), *location);
+        Ok(Some((symbol, name.try_to_owned()?)))
+    }
+
+    /// Resolve the location of the name in the declaration of the given
+    /// definition found in `origin`.
+    fn declaration<'s>(
+        &self,
+        origin_uri: &Url,
+        origin: &'s ServerSource,
+        def: &Definition,
+    ) -> Result<Option<(Url, &'s Source, Span)>> {
+        let Some(sources) = &origin.build_sources else {
+            return Ok(None);
+        };
+
+        match &def.source {
+            DefinitionSource::Location(location) => {
+                let Some(source) = sources.get(location.source_id) else {
+                    return Ok(None);
+                };
+
+                let Some(text) = source.get(location.span.range()) else {
+                    return Ok(None);
+                };
+
+                let Some(name) = text.split_whitespace().next_back() else {
+                    return Ok(None);
+                };
+
+                let Some(span) = find_word(source, location.span, name, true) else {
+                    return Ok(None);
+                };
+
+                Ok(Some((origin_uri.clone(), source, span)))
+            }
+            DefinitionSource::SourceMeta(meta) => {
+                let Some(name) = def.item.as_deref().and_then(|item| item.base_name()) else {
+                    return Ok(None);
+                };
+
+                let Some(source) = sources.get(meta.location.source_id) else {
+                    return Ok(None);
+                };
+
+                let Some(span) = find_word(source, meta.location.span, name, false) else {
+                    return Ok(None);
+                };
+
+                let uri = match &meta.path {
+                    Some(path) => crate::languageserver::url::from_file_path(path)?,
+                    None => origin_uri.clone(),
+                };
+
+                Ok(Some((uri, source, span)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Find all references to the given symbol across workspace sources.
+    fn find_references(
+        &self,
+        uri: &Url,
+        symbol: &Symbol,
+        name: &str,
+        include_declaration: bool,
+    ) -> Result<Vec<(Url, lsp::Range)>> {
+        let mut references = Vec::new();
+
+        for (origin_uri, origin) in &self.workspace.sources {
+            // Locals can only be referenced from the source they're declared
+            // in.
+            if matches!(symbol, Symbol::Local(..)) && origin_uri != uri {
+                continue;
+            }
+
+            let (Some(sources), Some(source_id)) = (&origin.build_sources, origin.source_id) else {
+                continue;
+            };
+
+            let Some(source) = sources.get(source_id) else {
+                continue;
+            };
+
+            for (span, def) in &origin.index.definitions {
+                if !def.is_symbol(origin_uri, symbol) {
+                    continue;
+                }
+
+                if let Some(span) = find_word(source, *span, name, true) {
+                    let range = self.encoding.source_range(source, span)?;
+                    push_reference(&mut references, origin_uri, range)?;
+                }
+
+                if !include_declaration {
+                    continue;
+                }
+
+                if let Some((uri, source, span)) = self.declaration(origin_uri, origin, def)? {
+                    let range = self.encoding.source_range(source, span)?;
+                    push_reference(&mut references, &uri, range)?;
+                }
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Build a nested outline of the items declared in the given uri.
+    pub(super) fn document_symbols(&self, uri: &Url) -> Result<Option<Vec<lsp::DocumentSymbol>>> {
+        let Some(origin) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let (Some(sources), Some(source_id)) = (&origin.build_sources, origin.source_id) else {
+            return Ok(None);
+        };
+
+        let Some(source) = sources.get(source_id) else {
+            return Ok(None);
+        };
+
+        let items = &origin.index.items;
+
+        // Items are nested under the closest item in the same source which
+        // is a prefix of it.
+        let mut children = HashMap::<Option<usize>, Vec<usize>>::new();
+
+        for (n, item) in items.iter().enumerate() {
+            let mut parent = item.item.parent();
+
+            let parent = loop {
+                let Some(p) = parent else {
+                    break None;
+                };
+
+                if let Some(index) = items.iter().position(|i| *i.item == *p) {
+                    break Some(index);
+                }
+
+                parent = p.parent();
+            };
+
+            children.entry(parent).or_try_default()?.try_push(n)?;
+        }
+
+        self.build_document_symbols(source, items, &children, None)
+            .map(Some)
+    }
+
+    fn build_document_symbols(
+        &self,
+        source: &Source,
+        items: &[IndexItem],
+        children: &HashMap<Option<usize>, Vec<usize>>,
+        parent: Option<usize>,
+    ) -> Result<Vec<lsp::DocumentSymbol>> {
+        let mut symbols = Vec::new();
+
+        let Some(indexes) = children.get(&parent) else {
+            return Ok(symbols);
+        };
+
+        for &n in indexes {
+            let item = &items[n];
+
+            let Some(name) = item.item.base_name() else {
+                continue;
+            };
+
+            let range = self.encoding.source_range(source, item.span)?;
+
+            let selection_range = match find_word(source, item.span, name, false) {
+                Some(span) => self.encoding.source_range(source, span)?,
+                None => range,
+            };
+
+            let nested = self.build_document_symbols(source, items, children, Some(n))?;
+
+            #[allow(deprecated)]
+            symbols.try_push(lsp::DocumentSymbol {
+                name: name.try_to_owned()?.into_std(),
+                detail: Some(item.item.try_to_string()?.into_std()),
+                kind: item.kind,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range,
+                children: (!nested.is_empty()).then(|| nested.into_std()),
+            })?;
+        }
+
+        Ok(symbols)
+    }
+
+    /// Fuzzy search for items declared across all workspace sources.
+    pub(super) fn workspace_symbols(&self, query: &str) -> Result<Vec<lsp::SymbolInformation>> {
+        let mut symbols = Vec::new();
+
+        for (uri, origin) in &self.workspace.sources {
+            let (Some(sources), Some(source_id)) = (&origin.build_sources, origin.source_id) else {
+                continue;
+            };
+
+            let Some(source) = sources.get(source_id) else {
+                continue;
+            };
+
+            for item in &origin.index.items {
+                let Some(name) = item.item.base_name() else {
+                    continue;
+                };
+
+                let path = item.item.try_to_string()?;
+
+                if !fuzzy_match(&path, query) {
+                    continue;
+                }
+
+                let span = find_word(source, item.span, name, false).unwrap_or(item.span);
+
+                #[allow(deprecated)]
+                symbols.try_push(lsp::SymbolInformation {
+                    name: name.try_to_owned()?.into_std(),
+                    kind: item.kind,
+                    tags: None,
+                    deprecated: None,
+                    location: lsp::Location {
+                        uri: uri.clone(),
+                        range: self.encoding.source_range(source, span)?,
+                    },
+                    container_name: match item.item.parent() {
+                        Some(parent) if !parent.is_empty() => {
+                            Some(parent.try_to_string()?.into_std())
+                        }
+                        _ => None,
+                    },
+                })?;
+            }
+        }
+
+        Ok(symbols)
+    }
+
+    /// Find signatures for the call surrounding the given uri and LSP
+    /// position.
+    pub(super) fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::SignatureHelp>> {
+        let Some(workspace_source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self
+            .encoding
+            .rope_position(&workspace_source.content, position)?;
+
+        let slice = workspace_source
+            .content
+            .get_slice(offset.saturating_sub(SIGNATURE_LOOK_BACK)..offset);
+
+        let Some(slice) = slice else {
+            return Ok(None);
+        };
+
+        let mut text = String::new();
+
+        for chunk in slice.chunks() {
+            text.try_push_str(chunk)?;
+        }
+
+        let Some((callee, mut active)) = find_call(&text) else {
+            return Ok(None);
+        };
+
+        let start = callee
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .map(|n| n + 1)
+            .unwrap_or_default();
+
+        let path = callee[start..].trim_start_matches("::");
+
+        if path.is_empty() {
+            return Ok(None);
+        }
+
+        let instance = callee[..start].ends_with('.');
+
+        // Instance functions take the receiver as their first argument.
+        if instance {
+            active += 1;
+        }
+
+        let mut signatures = Vec::new();
+
+        if let Some(unit) = workspace_source.unit.as_ref() {
+            super::completion::signatures_for_unit(
+                workspace_source,
+                unit,
+                path,
+                instance,
+                &mut signatures,
+            )?;
+        }
+
+        super::completion::signatures_for_native(&self.context, path, instance, &mut signatures)?;
+
+        if signatures.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(lsp::SignatureHelp {
+            signatures: signatures.into_std(),
+            active_signature: Some(0),
+            active_parameter: Some(u32::try_from(active)?),
+        }))
+    }
+
     /// Find definition at the given uri and LSP position.
     #[tracing::instrument(skip_all)]
     pub(super) fn complete(
@@ -550,6 +1134,7 @@ impl<'a> State<'a> {
 
                 source.index = value;
                 source.build_sources = Some(sources.clone());
+                source.source_id = Some(source_id);
 
                 if let Ok(unit) = &unit {
                     source.unit = Some(unit.try_clone()?);
@@ -819,6 +1404,7 @@ impl Workspace {
             content: Rope::from_str(text.as_str()),
             index: Default::default(),
             build_sources: None,
+            source_id: None,
             language,
             unit: None,
             docs: None,
@@ -856,6 +1442,8 @@ pub(super) struct ServerSource {
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
     build_sources: Option<Arc<Sources>>,
+    /// The identifier of this source in `build_sources`.
+    source_id: Option<SourceId>,
     /// The language of the source.
     language: Language,
     /// The compiled unit
@@ -1001,10 +1589,155 @@ where
     })
 }
 
+/// Push a reference unless it has already been recorded.
+fn push_reference(
+    references: &mut Vec<(Url, lsp::Range)>,
+    uri: &Url,
+    range: lsp::Range,
+) -> alloc::Result<()> {
+    if !references.iter().any(|(u, r)| u == uri && *r == range) {
+        references.try_push((uri.clone(), range))?;
+    }
+
+    Ok(())
+}
+
+/// Find the first or last occurence of `name` as a whole word inside of the
+/// given span of the source.
+fn find_word(source: &Source, span: Span, name: &str, last: bool) -> Option<Span> {
+    let text = source.get(span.range())?;
+
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    let is_word = |&(n, _): &(usize, &str)| {
+        let before = text[..n].chars().next_back();
+        let after = text[n + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    };
+
+    let (n, _) = if last {
+        text.rmatch_indices(name).find(is_word)?
+    } else {
+        text.match_indices(name).find(is_word)?
+    };
+    let start = span.start.into_usize() + n;
+    Some(Span::new(start, start + name.len()))
+}
+
+/// The number of characters to look back for the start of a call when
+/// providing signature help.
+const SIGNATURE_LOOK_BACK: usize = 4096;
+
+/// Find the call which is open at the end of `text`, returning the text
+/// preceding its opening parenthesis and the index of the active argument.
+///
+/// Delimiters inside of string literals, character literals and comments are
+/// ignored. If `text` ends inside of one of them, no call is open.
+fn find_call(text: &str) -> Option<(&str, usize)> {
+    // Open delimiters, their offset and the number of arguments preceding the
+    // active one.
+    let mut open = Vec::new();
+    let mut skip = 0;
+
+    for (n, c) in text.char_indices() {
+        if n < skip {
+            continue;
+        }
+
+        let rest = &text[n + c.len_utf8()..];
+
+        match c {
+            '"' | '`' => {
+                skip = n + 1 + string_end(rest, c)?;
+            }
+            '\'' => {
+                let mut chars = rest.chars();
+
+                // Labels like `'outer` are not character literals.
+                skip = match (chars.next(), chars.next()) {
+                    (Some('\\'), _) => n + 1 + string_end(rest, '\'')?,
+                    (Some(c), Some('\'')) => n + 2 + c.len_utf8(),
+                    _ => continue,
+                };
+            }
+            '/' if rest.starts_with('/') => {
+                skip = n + 1 + rest.find('\n')?;
+            }
+            '/' if rest.starts_with('*') => {
+                skip = n + 2 + rest[1..].find("*/")? + 2;
+            }
+            '(' | '[' | '{' => {
+                open.try_push((c, n, 0)).ok()?;
+            }
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            ',' => {
+                if let Some((_, _, active)) = open.last_mut() {
+                    *active += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let &(c, n, active) = open.last()?;
+    (c == '(').then(|| (text[..n].trim_end(), active))
+}
+
+/// Find the end of a string literal delimited by `quote`, returning the offset
+/// following its closing quote.
+fn string_end(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+
+    for (n, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(n + c.len_utf8()),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Test if the characters of `query` appear in order in `name`, ignoring
+/// case.
+fn fuzzy_match(name: &str, query: &str) -> bool {
+    let mut chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
+/// A symbol which can be referenced.
+enum Symbol {
+    /// An item declared in a script.
+    Item(Hash),
+    /// An item provided by a native module.
+    Native(ItemBuf),
+    /// A local variable declared in the given source.
+    Local(Url, Location),
+}
+
 #[derive(Default)]
 pub(super) struct Index {
     /// Spans mapping to their corresponding definitions.
     definitions: BTreeMap<Span, Definition>,
+    /// Items declared in the source.
+    items: Vec<IndexItem>,
+}
+
+/// An item declared in a source.
+pub(super) struct IndexItem {
+    /// The item being declared.
+    item: ItemBuf,
+    /// The kind of the symbol.
+    kind: lsp::SymbolKind,
+    /// The span of the declaration.
+    span: Span,
 }
 
 /// A definition source.
@@ -1016,22 +1749,25 @@ pub(super) enum DefinitionSource {
     Location(Location),
     /// A complete compile source.
     SourceMeta(SourceMeta),
+    /// A native item provided by the context, which has no source.
+    Context,
 }
 
 impl DefinitionSource {
     fn span(&self) -> Span {
         match self {
-            Self::Source(..) => Span::empty(),
+            Self::Source(..) | Self::Context => Span::empty(),
             Self::Location(location) => location.span,
             Self::SourceMeta(compile_source) => compile_source.location.span,
         }
     }
 
-    fn source_id(&self) -> SourceId {
+    fn source_id(&self) -> Option<SourceId> {
         match self {
-            Self::Source(source_id) => *source_id,
-            Self::Location(location) => location.source_id,
-            Self::SourceMeta(compile_source) => compile_source.location.source_id,
+            Self::Source(source_id) => Some(*source_id),
+            Self::Location(location) => Some(location.source_id),
+            Self::SourceMeta(compile_source) => Some(compile_source.location.source_id),
+            Self::Context => None,
         }
     }
 
@@ -1049,6 +1785,10 @@ pub(super) struct Definition {
     pub(super) kind: DefinitionKind,
     /// The id of the source id the definition corresponds to.
     pub(super) source: DefinitionSource,
+    /// The item the definition refers to, if any.
+    pub(super) item: Option<ItemBuf>,
+    /// The hash of the item the definition refers to.
+    pub(super) hash: Hash,
 }
 
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -1076,6 +1816,40 @@ pub(super) enum DefinitionKind {
     Local,
     /// A module that can be jumped to.
     Module,
+    /// A native type.
+    Type,
+}
+
+impl Definition {
+    /// Test if the definition found in `origin_uri` refers to the given symbol.
+    fn is_symbol(&self, origin_uri: &Url, symbol: &Symbol) -> bool {
+        match (symbol, &self.source) {
+            (Symbol::Item(hash), _) => self.item.is_some() && self.hash == *hash,
+            (Symbol::Native(item), DefinitionSource::Context) => self.item.as_ref() == Some(item),
+            (Symbol::Local(uri, location), DefinitionSource::Location(l)) => {
+                self.item.is_none()
+                    && uri == origin_uri
+                    && location.source_id == l.source_id
+                    && location.span == l.span
+            }
+            _ => false,
+        }
+    }
+}
+
+impl DefinitionKind {
+    /// The keyword used when displaying a definition of this kind.
+    fn keyword(self) -> &'static str {
+        match self {
+            Self::EmptyStruct | Self::TupleStruct | Self::Struct => "struct ",
+            Self::Enum => "enum ",
+            Self::Function | Self::AssociatedFunction => "fn ",
+            Self::Local => "let ",
+            Self::Module => "mod ",
+            Self::Type => "type ",
+            Self::UnitVariant | Self::TupleVariant | Self::StructVariant => "",
+        }
+    }
 }
 
 #[derive(Default)]
@@ -1091,11 +1865,57 @@ impl Visitor {
 }
 
 impl CompileVisitor for Visitor {
-    fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
+    fn register_meta(&mut self, meta: MetaRef<'_>) -> Result<(), MetaError> {
         let Some(source) = meta.source else {
             return Ok(());
         };
 
+        if meta.item.is_empty() {
+            return Ok(());
+        }
+
+        let kind = match meta.kind {
+            meta::Kind::Module => lsp::SymbolKind::MODULE,
+            meta::Kind::Struct {
+                enum_hash: Hash::EMPTY,
+                ..
+            } => lsp::SymbolKind::STRUCT,
+            meta::Kind::Struct { .. } => lsp::SymbolKind::ENUM_MEMBER,
+            meta::Kind::Enum { .. } => lsp::SymbolKind::ENUM,
+            meta::Kind::Function {
+                associated: None, ..
+            } => lsp::SymbolKind::FUNCTION,
+            meta::Kind::Function {
+                associated: Some(..),
+                ..
+            } => lsp::SymbolKind::METHOD,
+            meta::Kind::ConstFn => lsp::SymbolKind::FUNCTION,
+            meta::Kind::Const => lsp::SymbolKind::CONSTANT,
+            meta::Kind::Trait => lsp::SymbolKind::INTERFACE,
+            _ => return Ok(()),
+        };
+
+        let index = self
+            .indexes
+            .entry(source.location.source_id)
+            .or_try_default()?;
+
+        index.items.try_push(IndexItem {
+            item: meta.item.try_to_owned()?,
+            kind,
+            span: source.location.span,
+        })?;
+
+        Ok(())
+    }
+
+    fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        let source = match meta.source {
+            Some(source) => DefinitionSource::SourceMeta(source.try_clone()?),
+            None if meta.context => DefinitionSource::Context,
+            None => return Ok(()),
+        };
+
         let kind = match &meta.kind {
             meta::Kind::Struct {
                 fields: meta::Fields::Empty,
@@ -1132,12 +1952,15 @@ impl CompileVisitor for Visitor {
                 associated: Some(..),
                 ..
             } => DefinitionKind::AssociatedFunction,
+            meta::Kind::Type { .. } => DefinitionKind::Type,
             _ => return Ok(()),
         };
 
         let definition = Definition {
             kind,
-            source: DefinitionSource::SourceMeta(source.try_clone()?),
+            source,
+            item: Some(meta.item.try_to_owned()?),
+            hash: meta.hash,
         };
 
         let location = location.location();
@@ -1160,6 +1983,8 @@ impl CompileVisitor for Visitor {
         let definition = Definition {
             kind: DefinitionKind::Local,
             source: DefinitionSource::Location(Location::new(source_id, var_span.span())),
+            item: None,
+            hash: Hash::EMPTY,
         };
 
         let index = self.indexes.entry(source_id).or_try_default()?;
@@ -1177,6 +2002,8 @@ impl CompileVisitor for Visitor {
         let definition = Definition {
             kind: DefinitionKind::Module,
             source: DefinitionSource::Source(location.source_id),
+            item: None,
+            hash: Hash::EMPTY,
         };
 
         let index = self.indexes.entry(location.source_id).or_try_default()?;
@@ -1,4 +1,15 @@
-use super::Code;
+use std::string::String;
+
+use anyhow::Result;
+use lsp::Url;
+use tokio::sync::Notify;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::{Context, Module, Options};
+
+use super::state::State;
+use super::{Code, Language};
 
 #[test]
 fn test_code() {
@@ -10,3 +21,347 @@ fn test_code() {
     assert_eq!(code, Code::MethodNotFound);
     assert_eq!(serde_json::to_string(&code).unwrap(), "-32601");
 }
+
+/// Build a state with a single source containing the given text.
+fn build<'a>(notify: &'a Notify, text: &str) -> Result<(State<'a>, Url)> {
+    build_with(notify, Context::with_default_modules()?, text)
+}
+
+/// Build a state with the given context and a single source containing the
+/// given text.
+fn build_with<'a>(notify: &'a Notify, context: Context, text: &str) -> Result<(State<'a>, Url)> {
+    let mut state = State::new(notify, context, Options::default());
+
+    let url = Url::parse("file:///languageserver/main.rn")?;
+
+    state
+        .workspace
+        .insert_source(url.clone(), text.try_to_owned()?, Language::Rune)?;
+
+    state.rebuild()?;
+
+    // Skip over published diagnostics.
+    let n = state.out.readable().len();
+    state.out.advance(n);
+    Ok((state, url))
+}
+
+fn position(line: u32, character: u32) -> lsp::Position {
+    lsp::Position { line, character }
+}
+
+fn range(line: u32, start: u32, end: u32) -> lsp::Range {
+    lsp::Range {
+        start: position(line, start),
+        end: position(line, end),
+    }
+}
+
+/// Collect the ranges of the edits in the given workspace edit.
+fn edits(edit: lsp::WorkspaceEdit, url: &Url, new_text: &str) -> std::vec::Vec<lsp::Range> {
+    let mut changes = edit.changes.expect("missing changes");
+    let mut edits = changes.remove(url).expect("missing edits");
+    assert!(changes.is_empty(), "edits in other sources: {changes:?}");
+    assert!(edits.iter().all(|e| e.new_text == new_text));
+    edits.sort_by_key(|e| (e.range.start.line, e.range.start.character));
+    edits.into_iter().map(|e| e.range).collect()
+}
+
+const RENAME: &str = r#"fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let value = add(1, 2);
+    value + add(value, 3)
+}
+"#;
+
+#[test]
+fn test_rename() -> Result<()> {
+    let notify = Notify::new();
+    let (mut state, url) = build(&notify, RENAME)?;
+
+    let edit = state.rename(&url, position(6, 6), "total")?;
+    let edit = edit.expect("expected rename of local");
+
+    assert_eq!(
+        edits(edit, &url, "total"),
+        [range(5, 8, 13), range(6, 4, 9), range(6, 16, 21)]
+    );
+
+    let edit = state.rename(&url, position(0, 4), "sum")?;
+    let edit = edit.expect("expected rename of function");
+
+    assert_eq!(
+        edits(edit, &url, "sum"),
+        [range(0, 3, 6), range(5, 16, 19), range(6, 12, 15)]
+    );
+
+    assert!(state.out.is_empty());
+    Ok(())
+}
+
+/// Collect the sorted ranges of the given locations.
+fn ranges(locations: std::vec::Vec<lsp::Location>, url: &Url) -> std::vec::Vec<lsp::Range> {
+    assert!(locations.iter().all(|l| l.uri == *url));
+    let mut ranges = locations
+        .into_iter()
+        .map(|l| l.range)
+        .collect::<std::vec::Vec<_>>();
+    ranges.sort_by_key(|r| (r.start.line, r.start.character));
+    ranges
+}
+
+#[test]
+fn test_references() -> Result<()> {
+    let notify = Notify::new();
+    let (state, url) = build(&notify, RENAME)?;
+
+    let locations = state.references(&url, position(6, 18), true)?;
+    let locations = locations.expect("expected references of local");
+
+    assert_eq!(
+        ranges(locations.into_std(), &url),
+        [range(5, 8, 13), range(6, 4, 9), range(6, 16, 21)]
+    );
+
+    let locations = state.references(&url, position(5, 17), false)?;
+    let locations = locations.expect("expected references of function");
+
+    assert_eq!(
+        ranges(locations.into_std(), &url),
+        [range(5, 16, 19), range(6, 12, 15)]
+    );
+
+    assert!(state.references(&url, position(4, 0), true)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_rename_invalid_identifier() -> Result<()> {
+    let notify = Notify::new();
+    let (mut state, url) = build(&notify, RENAME)?;
+
+    for new_name in ["", "1value", "two words", "fn"] {
+        assert!(state.rename(&url, position(6, 6), new_name)?.is_none());
+
+        let message = std::str::from_utf8(state.out.readable())?;
+        assert!(
+            message.contains("not a valid identifier"),
+            "{new_name:?}: {message}"
+        );
+
+        let n = state.out.readable().len();
+        state.out.advance(n);
+    }
+
+    Ok(())
+}
+
+const SYMBOLS: &str = r#"struct Point {
+    x,
+    y,
+}
+
+impl Point {
+    fn new(x, y) {
+        Point { x, y }
+    }
+
+    fn norm(self) {
+        self.x + self.y
+    }
+}
+
+enum Shape {
+    Circle(radius),
+}
+
+pub fn main() {
+    Point::new(1, 2)
+}
+"#;
+
+/// Flatten document symbols into their names and kinds, sorted by name.
+fn outline(symbols: &[lsp::DocumentSymbol]) -> std::vec::Vec<(String, lsp::SymbolKind, usize)> {
+    fn walk(
+        symbols: &[lsp::DocumentSymbol],
+        depth: usize,
+        out: &mut std::vec::Vec<(String, lsp::SymbolKind, usize)>,
+    ) {
+        for symbol in symbols {
+            out.push((symbol.name.clone(), symbol.kind, depth));
+
+            if let Some(children) = &symbol.children {
+                walk(children, depth + 1, out);
+            }
+        }
+    }
+
+    let mut out = std::vec::Vec::new();
+    walk(symbols, 0, &mut out);
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+#[test]
+fn test_document_symbols() -> Result<()> {
+    let notify = Notify::new();
+    let (state, url) = build(&notify, SYMBOLS)?;
+
+    let symbols = state.document_symbols(&url)?.expect("missing symbols");
+
+    let expected = [
+        ("Circle", lsp::SymbolKind::ENUM_MEMBER, 1),
+        ("Point", lsp::SymbolKind::STRUCT, 0),
+        ("Shape", lsp::SymbolKind::ENUM, 0),
+        ("main", lsp::SymbolKind::FUNCTION, 0),
+        ("new", lsp::SymbolKind::FUNCTION, 1),
+        ("norm", lsp::SymbolKind::METHOD, 1),
+    ];
+
+    let expected = expected
+        .into_iter()
+        .map(|(name, kind, depth)| (String::from(name), kind, depth))
+        .collect::<std::vec::Vec<_>>();
+
+    assert_eq!(outline(&symbols), expected);
+
+    let point = symbols.iter().find(|s| s.name == "Point").expect("Point");
+    assert_eq!(point.selection_range, range(0, 7, 12));
+    assert_eq!(point.detail.as_deref(), Some("Point"));
+    Ok(())
+}
+
+#[test]
+fn test_workspace_symbols() -> Result<()> {
+    let notify = Notify::new();
+    let (state, url) = build(&notify, SYMBOLS)?;
+
+    let symbols = state.workspace_symbols("pnew")?;
+    assert_eq!(symbols.len(), 1);
+
+    let symbol = &symbols[0];
+    assert_eq!(symbol.name, "new");
+    assert_eq!(symbol.kind, lsp::SymbolKind::FUNCTION);
+    assert_eq!(symbol.container_name.as_deref(), Some("Point"));
+    assert_eq!(symbol.location.uri, url);
+    assert_eq!(symbol.location.range, range(6, 7, 10));
+
+    let mut names = state
+        .workspace_symbols("")?
+        .into_iter()
+        .map(|s| s.name)
+        .collect::<std::vec::Vec<_>>();
+
+    names.sort();
+    assert_eq!(names, ["Circle", "Point", "Shape", "main", "new", "norm"]);
+    Ok(())
+}
+
+const SIGNATURES: &str = r#"fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    // add(
+    let s = "add(1, ";
+    add(1, /* ) */ add(2, 3))
+}
+"#;
+
+/// Get the label and active parameter of the signature help at the given
+/// position.
+fn signature(
+    state: &State<'_>,
+    url: &Url,
+    line: u32,
+    character: u32,
+) -> Result<Option<(String, u32)>> {
+    let Some(help) = state.signature_help(url, position(line, character))? else {
+        return Ok(None);
+    };
+
+    let signature = help.signatures.first().expect("missing signature");
+    let active = help.active_parameter.expect("missing active parameter");
+    Ok(Some((signature.label.clone(), active)))
+}
+
+#[test]
+fn test_signature_help() -> Result<()> {
+    let notify = Notify::new();
+    let (state, url) = build(&notify, SIGNATURES)?;
+
+    let label = String::from("fn add(a, b)");
+
+    assert_eq!(signature(&state, &url, 7, 8)?, Some((label.clone(), 0)));
+    assert_eq!(signature(&state, &url, 7, 19)?, Some((label.clone(), 1)));
+    assert_eq!(signature(&state, &url, 7, 23)?, Some((label.clone(), 0)));
+    assert_eq!(signature(&state, &url, 7, 26)?, Some((label, 1)));
+
+    // Calls inside of string literals and comments are ignored.
+    assert_eq!(signature(&state, &url, 6, 19)?, None);
+    assert_eq!(signature(&state, &url, 5, 11)?, None);
+    assert_eq!(signature(&state, &url, 6, 4)?, None);
+    Ok(())
+}
+
+const HOVER: &str = r#"/// Add two numbers together.
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    add(1, 2) + math::scale(3, 4)
+}
+"#;
+
+/// Get the markdown of the hover at the given position.
+fn hover(state: &State<'_>, url: &Url, line: u32, character: u32) -> Result<Option<String>> {
+    let Some(hover) = state.hover(url, position(line, character))? else {
+        return Ok(None);
+    };
+
+    let lsp::HoverContents::Markup(content) = hover.contents else {
+        panic!("expected markup, got {:?}", hover.contents);
+    };
+
+    assert_eq!(content.kind, lsp::MarkupKind::Markdown);
+    Ok(Some(String::from(content.value.as_str())))
+}
+
+#[test]
+fn test_hover() -> Result<()> {
+    /// Scale a value by the given factor.
+    #[rune::function]
+    fn scale(value: i64, factor: i64) -> i64 {
+        value * factor
+    }
+
+    let mut module = Module::with_crate("math")?;
+    module.function_meta(scale)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let notify = Notify::new();
+    let (state, url) = build_with(&notify, context, HOVER)?;
+
+    let value = hover(&state, &url, 6, 5)?.expect("missing hover for script function");
+
+    assert_eq!(
+        value,
+        "```rune\nfn add(a, b)\n```\n\n Add two numbers together."
+    );
+
+    let value = hover(&state, &url, 6, 21)?.expect("missing hover for native function");
+
+    assert_eq!(
+        value,
+        "```rune\nfn ::math::scale(value, factor) -> ::std::i64\n```\n\n Scale a value by the given factor."
+    );
+
+    assert!(hover(&state, &url, 4, 0)?.is_none());
+    Ok(())
+}
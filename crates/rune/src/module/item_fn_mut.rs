@@ -1,12 +1,15 @@
 use core::fmt;
 
-#[cfg(feature = "doc")]
-use crate::alloc::Box;
+use crate::alloc::prelude::*;
 #[cfg(feature = "doc")]
 use crate::compile::meta;
 use crate::compile::{ContextError, Docs};
 use crate::function_meta::FunctionArgs;
-use crate::runtime::MaybeTypeOf;
+#[cfg(feature = "doc")]
+use crate::runtime::{ConstValue, ConstValueKind};
+use crate::runtime::{MaybeTypeOf, ToConstValue};
+
+use super::ModuleParameter;
 
 /// Handle to a an item inserted into a module which allows for mutation of item
 /// metadata.
@@ -29,6 +32,8 @@ use crate::runtime::MaybeTypeOf;
 /// [`Module::function_meta`]: super::Module::function_meta
 pub struct ItemFnMut<'a> {
     pub(super) docs: &'a mut Docs,
+    pub(super) is_const: Option<&'a mut bool>,
+    pub(super) parameters: Option<&'a mut Vec<ModuleParameter>>,
     #[cfg(feature = "doc")]
     pub(super) deprecated: &'a mut Option<Box<str>>,
     #[cfg(feature = "doc")]
@@ -60,6 +65,21 @@ impl ItemFnMut<'_> {
         self
     }
 
+    /// Mark the function as safe to call in constant contexts.
+    ///
+    /// This allows the function to be called from `const` items and `const fn`
+    /// during compilation. Only mark functions which are pure, that is their
+    /// output depends only on their arguments and they have no side effects.
+    ///
+    /// This has no effect on trait functions.
+    pub fn const_safe(mut self) -> Self {
+        if let Some(is_const) = &mut self.is_const {
+            **is_const = true;
+        }
+
+        self
+    }
+
     /// Mark the given item as deprecated.
     pub fn deprecated(
         self,
@@ -111,19 +131,115 @@ impl ItemFnMut<'_> {
     }
 
     /// Set argument names.
+    ///
+    /// Naming the arguments of a function allows scripts to pass them by
+    /// name, like `connect(host, port: 8080)`. This replaces any previously
+    /// declared default values.
     pub fn argument_names(
-        self,
-        #[cfg_attr(not(feature = "doc"), allow(unused))] names: impl IntoIterator<Item: AsRef<str>>,
+        mut self,
+        names: impl IntoIterator<Item: AsRef<str>>,
+    ) -> Result<Self, ContextError> {
+        let mut parameters = Vec::new();
+
+        for name in names {
+            parameters.try_push(ModuleParameter {
+                name: name.as_ref().try_into()?,
+                default: None,
+            })?;
+        }
+
+        self.docs
+            .set_arguments(parameters.iter().map(|p| p.name.as_ref()))?;
+
+        if let Some(existing) = &mut self.parameters {
+            **existing = parameters;
+        }
+
+        Ok(self)
+    }
+
+    /// Set the default value of an argument.
+    ///
+    /// The argument must have been named first, either through
+    /// [`ItemFnMut::argument_names`] or by registering the function with
+    /// [`Module::function_meta`]. Calls by path which omit the argument will
+    /// pass the default value in its place.
+    ///
+    /// This is not supported for trait functions or instance functions, since
+    /// they are called as methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Module;
+    ///
+    /// fn connect(host: String, port: i64) -> String {
+    ///     format!("{host}:{port}")
+    /// }
+    ///
+    /// let mut m = Module::new();
+    ///
+    /// m.function("connect", connect)
+    ///     .build()?
+    ///     .argument_names(["host", "port"])?
+    ///     .argument_default("port", 80i64)?;
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    ///
+    /// [`Module::function_meta`]: super::Module::function_meta
+    pub fn argument_default(
+        mut self,
+        name: &str,
+        value: impl ToConstValue,
     ) -> Result<Self, ContextError> {
+        let Some(parameters) = self
+            .parameters
+            .as_mut()
+            .filter(|p| p.first().is_none_or(|p| *p.name != *"self"))
+        else {
+            return Err(ContextError::UnsupportedArgumentDefault {
+                name: name.try_into()?,
+            });
+        };
+
+        let Some(index) = parameters.iter().position(|p| *p.name == *name) else {
+            return Err(ContextError::MissingArgumentName {
+                name: name.try_into()?,
+            });
+        };
+
+        let value = match value.to_const_value() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(ContextError::InvalidArgumentDefault {
+                    name: name.try_into()?,
+                    error: Box::try_new(error)?,
+                });
+            }
+        };
+
         #[cfg(feature = "doc")]
         {
-            self.docs.set_arguments(names)?;
+            self.docs.set_argument(index, default_label(name, &value)?);
         }
 
+        parameters[index].default = Some(value);
         Ok(self)
     }
 }
 
+/// Format the documentation label for an argument with a default value.
+#[cfg(feature = "doc")]
+fn default_label(name: &str, value: &ConstValue) -> Result<String, ContextError> {
+    let label = match value.as_kind() {
+        ConstValueKind::Inline(value) => try_format!("{name} = {value:?}"),
+        ConstValueKind::String(value) => try_format!("{name} = {value:?}"),
+        ConstValueKind::Bytes(..) | ConstValueKind::Instance(..) => try_format!("{name} = .."),
+    };
+
+    Ok(label)
+}
+
 impl fmt::Debug for ItemFnMut<'_> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -749,15 +749,16 @@ impl Module {
 
         let mut docs = Docs::EMPTY;
         docs.set_docs(meta.statics.docs)?;
-        docs.set_arguments(meta.statics.arguments)?;
         let deprecated = meta.statics.deprecated.map(TryInto::try_into).transpose()?;
 
-        match meta.kind {
-            FunctionMetaKind::Function(data) => self.function_inner(data, docs, deprecated),
+        let item = match meta.kind {
+            FunctionMetaKind::Function(data) => self.function_inner(data, docs, deprecated)?,
             FunctionMetaKind::AssociatedFunction(data) => {
-                self.insert_associated_function(data, docs, deprecated)
+                self.insert_associated_function(data, docs, deprecated)?
             }
-        }
+        };
+
+        item.argument_names(meta.statics.arguments)
     }
 
     pub(super) fn function_from_meta_kind(
@@ -1139,6 +1140,8 @@ impl Module {
             kind: ModuleItemKind::Function(ModuleFunction {
                 handler: data.handler,
                 trait_hash: None,
+                is_const: false,
+                parameters: Vec::new(),
                 doc: DocFunction {
                     #[cfg(feature = "doc")]
                     is_async: data.is_async,
@@ -1154,7 +1157,6 @@ impl Module {
 
         let last = self.items.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleItemKind::Function(f) => f,
             _ => unreachable!(),
@@ -1162,6 +1164,8 @@ impl Module {
 
         Ok(ItemFnMut {
             docs: &mut last.common.docs,
+            is_const: Some(&mut last_fn.is_const),
+            parameters: Some(&mut last_fn.parameters),
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
@@ -1192,6 +1196,8 @@ impl Module {
             kind: ModuleAssociatedKind::Function(ModuleFunction {
                 handler: data.handler,
                 trait_hash: None,
+                is_const: false,
+                parameters: Vec::new(),
                 doc: DocFunction {
                     #[cfg(feature = "doc")]
                     is_async: data.is_async,
@@ -1207,7 +1213,6 @@ impl Module {
 
         let last = self.associated.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleAssociatedKind::Function(f) => f,
             _ => unreachable!(),
@@ -1215,6 +1220,8 @@ impl Module {
 
         Ok(ItemFnMut {
             docs: &mut last.common.docs,
+            is_const: Some(&mut last_fn.is_const),
+            parameters: Some(&mut last_fn.parameters),
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
@@ -227,10 +227,23 @@ pub(crate) struct ModuleFunction {
     pub(crate) handler: FunctionHandler,
     /// If the function is associated with a trait, this is the hash of that trait.
     pub(crate) trait_hash: Option<Hash>,
+    /// If the function is safe to call in constant contexts.
+    pub(crate) is_const: bool,
+    /// Declared parameters of the function.
+    pub(crate) parameters: Vec<ModuleParameter>,
     /// Documentation related to the function.
     pub(crate) doc: DocFunction,
 }
 
+/// A declared parameter of a native function.
+#[derive(TryClone)]
+pub(crate) struct ModuleParameter {
+    /// The name of the parameter.
+    pub(crate) name: Box<str>,
+    /// The default value of the parameter.
+    pub(crate) default: Option<ConstValue>,
+}
+
 #[derive(TryClone)]
 pub(crate) enum ModuleAssociatedKind {
     Constant(ConstValue),
@@ -81,6 +81,8 @@ impl TraitMut<'_> {
 
         Ok(ItemFnMut {
             docs: &mut f.common.docs,
+            is_const: None,
+            parameters: None,
             #[cfg(feature = "doc")]
             deprecated: &mut f.common.deprecated,
             #[cfg(feature = "doc")]
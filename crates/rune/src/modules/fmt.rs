@@ -9,8 +9,8 @@ use crate::alloc::prelude::*;
 use crate::compile;
 use crate::macros::{FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
-use crate::runtime::{EnvProtocolCaller, Format, Formatter, VmError};
-use crate::{ContextError, Module};
+use crate::runtime::{EnvProtocolCaller, Format, Formatter, Protocol, VmError};
+use crate::{docstring, ContextError, Module};
 
 /// Formatting text.
 ///
@@ -28,6 +28,7 @@ pub fn module() -> Result<Module, ContextError> {
     let mut m = Module::from_meta(self::module__meta)?.with_unique("std::fmt");
 
     m.ty::<Formatter>()?;
+    m.function_meta(formatter_write_str)?;
     m.ty::<fmt::Error>()?;
     m.function_meta(fmt_error_display_fmt)?;
     m.macro_meta(format)?;
@@ -38,9 +39,62 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(format_clone__meta)?;
     m.implement_trait::<Format>(rune::item!(::std::clone::Clone))?;
 
+    let mut t = m.define_trait(["Display"])?;
+
+    t.docs(docstring! {
+        /// Format trait for an empty format, `{}`.
+        ///
+        /// Implementing this trait for a type allows it to be used in template
+        /// strings and with the `format!` macro.
+        ///
+        /// # Examples
+        ///
+        /// ```rune
+        /// use std::fmt::Display;
+        ///
+        /// struct Point { x, y }
+        ///
+        /// impl Display for Point {
+        ///     fn fmt(self, f) {
+        ///         f.write_str(`(${self.x}, ${self.y})`)
+        ///     }
+        /// }
+        ///
+        /// let p = Point { x: 1, y: 2 };
+        /// assert_eq!(`${p}`, "(1, 2)");
+        /// ```
+    })?;
+
+    t.handler(|cx| {
+        _ = cx.find(&Protocol::DISPLAY_FMT)?;
+        Ok(())
+    })?;
+
     Ok(m)
 }
 
+/// Write a string to the formatter.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::fmt::Display;
+///
+/// struct Name { name }
+///
+/// impl Display for Name {
+///     fn fmt(self, f) {
+///         f.write_str(self.name)
+///     }
+/// }
+///
+/// assert_eq!(`${Name { name: "Jane" }}`, "Jane");
+/// ```
+#[rune::function(instance, path = write_str)]
+fn formatter_write_str(f: &mut Formatter, s: &str) -> alloc::Result<()> {
+    f.try_write_str(s)
+}
+
 #[rune::function(instance, protocol = DISPLAY_FMT)]
 fn fmt_error_display_fmt(error: &fmt::Error, f: &mut Formatter) -> alloc::Result<()> {
     write!(f, "{error}")
@@ -1,5 +1,7 @@
 //! Iterators.
 
+use core::cmp::Ordering;
+
 use crate as rune;
 use crate::alloc;
 use crate::alloc::prelude::*;
@@ -103,6 +105,56 @@ pub fn module() -> Result<Module, ContextError> {
     m.implement_trait::<Take>(rune::item!(::std::iter::DoubleEndedIterator))?;
     m.implement_trait::<Take>(rune::item!(::std::iter::ExactSizeIterator))?;
 
+    m.ty::<Zip>()?;
+    m.function_meta(Zip::next__meta)?;
+    m.function_meta(Zip::size_hint__meta)?;
+    m.function_meta(Zip::len__meta)?;
+    m.implement_trait::<Zip>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Zip>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<StepBy>()?;
+    m.function_meta(StepBy::next__meta)?;
+    m.function_meta(StepBy::size_hint__meta)?;
+    m.function_meta(StepBy::len__meta)?;
+    m.implement_trait::<StepBy>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<StepBy>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<TakeWhile>()?;
+    m.function_meta(TakeWhile::next__meta)?;
+    m.function_meta(TakeWhile::size_hint__meta)?;
+    m.implement_trait::<TakeWhile>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<SkipWhile>()?;
+    m.function_meta(SkipWhile::next__meta)?;
+    m.function_meta(SkipWhile::size_hint__meta)?;
+    m.implement_trait::<SkipWhile>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Scan>()?;
+    m.function_meta(Scan::next__meta)?;
+    m.function_meta(Scan::size_hint__meta)?;
+    m.implement_trait::<Scan>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Cycle>()?;
+    m.function_meta(Cycle::next__meta)?;
+    m.function_meta(Cycle::size_hint__meta)?;
+    m.implement_trait::<Cycle>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Inspect>()?;
+    m.function_meta(Inspect::next__meta)?;
+    m.function_meta(Inspect::next_back__meta)?;
+    m.function_meta(Inspect::size_hint__meta)?;
+    m.function_meta(Inspect::len__meta)?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Flatten>()?;
+    m.function_meta(Flatten::next__meta)?;
+    m.function_meta(Flatten::next_back__meta)?;
+    m.function_meta(Flatten::size_hint__meta)?;
+    m.implement_trait::<Flatten>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Flatten>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
     {
         let mut t = m.define_trait(["ExactSizeIterator"])?;
 
@@ -364,6 +416,192 @@ pub fn module() -> Result<Module, ContextError> {
                 })?;
                 cx.function("skip", move |iter: Value, n: usize| Skip { iter, n })?;
                 cx.function("take", move |iter: Value, n: usize| Take { iter, n })?;
+                cx.function("zip", |a: Value, b: Value| -> Result<Zip, VmError> {
+                    let b = b.protocol_into_iter()?;
+                    Ok(Zip { a, b })
+                })?;
+                cx.function(
+                    "step_by",
+                    move |iter: Value, step: usize| -> Result<StepBy, VmError> {
+                        if step == 0 {
+                            return Err(VmError::panic("assertion failed: step != 0"));
+                        }
+
+                        Ok(StepBy {
+                            iter,
+                            step: step - 1,
+                            first_take: true,
+                        })
+                    },
+                )?;
+                cx.function("take_while", move |iter: Value, f: Function| TakeWhile {
+                    iter,
+                    f,
+                    flag: false,
+                })?;
+                cx.function("skip_while", move |iter: Value, f: Function| SkipWhile {
+                    iter,
+                    f,
+                    flag: false,
+                })?;
+                cx.function("scan", move |iter: Value, state: Value, f: Function| Scan {
+                    iter: Some(iter),
+                    state,
+                    f,
+                })?;
+                cx.function("cycle", move |iter: Value| Cycle {
+                    iter: Some(iter),
+                    buffer: alloc::Vec::new(),
+                    index: 0,
+                })?;
+                cx.function("inspect", move |iter: Value, f: Function| Inspect {
+                    iter,
+                    f,
+                })?;
+                cx.function("flatten", move |iter: Value| Flatten {
+                    iter: Some(iter),
+                    frontiter: None,
+                    backiter: None,
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "max_by",
+                    move |iter: Value, f: Function| -> Result<Option<Value>, VmError> {
+                        let Some(mut acc) = next.call((iter.clone(),))? else {
+                            return Ok(None);
+                        };
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            if f.call::<Ordering>((acc.clone(), value.clone()))?
+                                != Ordering::Greater
+                            {
+                                acc = value;
+                            }
+                        }
+
+                        Ok(Some(acc))
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "min_by",
+                    move |iter: Value, f: Function| -> Result<Option<Value>, VmError> {
+                        let Some(mut acc) = next.call((iter.clone(),))? else {
+                            return Ok(None);
+                        };
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            if f.call::<Ordering>((acc.clone(), value.clone()))?
+                                == Ordering::Greater
+                            {
+                                acc = value;
+                            }
+                        }
+
+                        Ok(Some(acc))
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "max_by_key",
+                    move |iter: Value, f: Function| -> Result<Option<Value>, VmError> {
+                        let Some(mut acc) = next.call((iter.clone(),))? else {
+                            return Ok(None);
+                        };
+
+                        let mut acc_key = f.call::<Value>((acc.clone(),))?;
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            let key = f.call::<Value>((value.clone(),))?;
+
+                            if Value::cmp(&acc_key, &key)? != Ordering::Greater {
+                                acc = value;
+                                acc_key = key;
+                            }
+                        }
+
+                        Ok(Some(acc))
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "min_by_key",
+                    move |iter: Value, f: Function| -> Result<Option<Value>, VmError> {
+                        let Some(mut acc) = next.call((iter.clone(),))? else {
+                            return Ok(None);
+                        };
+
+                        let mut acc_key = f.call::<Value>((acc.clone(),))?;
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            let key = f.call::<Value>((value.clone(),))?;
+
+                            if Value::cmp(&acc_key, &key)? == Ordering::Greater {
+                                acc = value;
+                                acc_key = key;
+                            }
+                        }
+
+                        Ok(Some(acc))
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "partition",
+                    move |iter: Value, f: Function| -> Result<(Vec, Vec), VmError> {
+                        let mut left = Vec::new();
+                        let mut right = Vec::new();
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            if f.call::<bool>((value.clone(),))? {
+                                left.push(value)?;
+                            } else {
+                                right.push(value)?;
+                            }
+                        }
+
+                        Ok((left, right))
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                // The size hint of an iterator is not guaranteed to be correct, so
+                // the collections are grown as values are produced.
+                cx.function("unzip", move |iter: Value| -> Result<(Vec, Vec), VmError> {
+                    let mut left = Vec::new();
+                    let mut right = Vec::new();
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        let (a, b) = <(Value, Value)>::from_value(value)?;
+                        left.push(a)?;
+                        right.push(b)?;
+                    }
+
+                    Ok((left, right))
+                })?;
             }
 
             {
@@ -1397,206 +1635,514 @@ pub fn module() -> Result<Module, ContextError> {
                 /// ```
             })?;
 
-        macro_rules! sum_ops {
-            ($ty:ty) => {
-                t.function(Params::new("sum", [<$ty>::HASH]))?
-                    .argument_types::<(Value,)>()?
-                    .argument_names(["self"])?
-                    .return_type::<$ty>()?
-                    .docs(docstring! {
-                        /// Sums the elements of an iterator.
-                        ///
-                        /// Takes each element, adds them together, and returns
-                        /// the result.
-                        ///
-                        /// An empty iterator returns the zero value of the
-                        /// type.
-                        ///
-                        /// `sum()` can be used to sum numerical built-in types,
-                        /// such as `i64`, `float` and `u64`. The first element
-                        /// returned by the iterator determines the type being
-                        /// summed.
-                        ///
-                        /// # Panics
-                        ///
-                        /// When calling `sum()` and a primitive integer type is
-                        /// being returned, this method will panic if the
-                        /// computation overflows.
-                        ///
-                        /// # Examples
-                        ///
-                        /// Basic usage:
-                        ///
-                        /// ```rune
-                        #[doc = concat!(" let a = [1", stringify!($ty), ", 2", stringify!($ty), ", 3", stringify!($ty), "];")]
-                        #[doc = concat!(" let sum = a.iter().sum::<", stringify!($ty), ">();")]
-                        ///
-                        #[doc = concat!(" assert_eq!(sum, 6", stringify!($ty), ");")]
-                        /// ```
-                    })?;
-            };
-        }
-
-        sum_ops!(u64);
-        sum_ops!(i64);
-        sum_ops!(f64);
-
-        macro_rules! integer_product_ops {
-            ($ty:ty) => {
-                t.function(Params::new("product", [<$ty>::HASH]))?
-                    .argument_types::<(Value,)>()?
-                    .argument_names(["self"])?
-                    .return_type::<$ty>()?
-                    .docs(docstring! {
-                        /// Iterates over the entire iterator, multiplying all
-                        /// the elements
-                        ///
-                        /// An empty iterator returns the one value of the type.
-                        ///
-                        /// `sum()` can be used to sum numerical built-in types,
-                        /// such as `i64`, `f64` and `u64`. The first element
-                        /// returned by the iterator determines the type being
-                        /// multiplied.
-                        ///
-                        /// # Panics
-                        ///
-                        /// When calling `product()` and a primitive integer
-                        /// type is being returned, method will panic if the
-                        /// computation overflows.
-                        ///
-                        /// # Examples
-                        ///
-                        /// ```rune
-                        /// fn factorial(n) {
-                        #[doc = concat!("     (1", stringify!($ty), "..=n).iter().product::<", stringify!($ty), ">()")]
-                        /// }
-                        ///
-                        #[doc = concat!(" assert_eq!(factorial(0", stringify!($ty), "), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(1", stringify!($ty), "), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(5", stringify!($ty), "), 120", stringify!($ty), ");")]
-                        /// ```
-                    })?;
-            };
-        }
-
-        t.function(Params::new("collect", [Vec::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<Vec>()?
+        t.function("zip")?
+            .argument_types::<(Value, Value)>()?
+            .argument_names(["self", "other"])?
+            .return_type::<Zip>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`Vec`].
+                /// 'Zips up' two iterators into a single iterator of pairs.
+                ///
+                /// `zip()` returns a new iterator that will iterate over two
+                /// other iterators, returning a tuple where the first element
+                /// comes from the first iterator, and the second element comes
+                /// from the second iterator.
+                ///
+                /// If either iterator returns [`None`], [`next`] from the
+                /// zipped iterator will return [`None`]. If the zipped
+                /// iterator has no more elements to return then each further
+                /// attempt to advance it will first try to advance the first
+                /// iterator at most one time and if it still yielded an item
+                /// try to advance the second iterator at most one time.
+                ///
+                /// Since the argument to `zip()` uses [`into_iter`], we can
+                /// pass anything that can be converted into an iterator, not
+                /// just an iterator itself.
+                ///
+                /// [`next`]: Iterator::next
+                /// [`into_iter`]: Iterator::into_iter
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::iter::range;
+                /// let a1 = [1, 2, 3];
+                /// let a2 = [4, 5, 6];
                 ///
-                /// assert_eq!((0..3).iter().collect::<Vec>(), [0, 1, 2]);
+                /// let iter = a1.iter().zip(a2);
+                ///
+                /// assert_eq!(iter.next(), Some((1, 4)));
+                /// assert_eq!(iter.next(), Some((2, 5)));
+                /// assert_eq!(iter.next(), Some((3, 6)));
+                /// assert_eq!(iter.next(), None);
                 /// ```
-            })?;
-
-        t.function(Params::new("collect", [VecDeque::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<VecDeque>()?
-            .docs(docstring! {
-                /// Collect the iterator as a [`VecDeque`].
                 ///
-                /// # Examples
+                /// The zipped iterator is as long as the shortest of the two:
                 ///
                 /// ```rune
-                /// use std::collections::VecDeque;
+                /// let iter = (0..).iter().zip(['a', 'b']);
                 ///
-                /// assert_eq!((0..3).iter().collect::<VecDeque>(), VecDeque::from::<Vec>([0, 1, 2]));
+                /// assert_eq!(iter.size_hint(), (2, Some(2)));
+                /// assert_eq!(iter.collect::<Vec>(), [(0, 'a'), (1, 'b')]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [HashSet::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<HashSet>()?
+        t.function("step_by")?
+            .argument_types::<(Value, usize)>()?
+            .argument_names(["self", "step"])?
+            .return_type::<StepBy>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`HashSet`].
+                /// Creates an iterator starting at the same point, but
+                /// stepping by the given amount at each iteration.
+                ///
+                /// Note 1: The first element of the iterator will always be
+                /// returned, regardless of the step given.
+                ///
+                /// Note 2: The iterator uses the `nth` method of the
+                /// underlying iterator to skip elements, so any side effects of
+                /// advancing it will still be observed.
+                ///
+                /// # Panics
+                ///
+                /// The method will panic if the given step is `0`.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::collections::HashSet;
+                /// let a = [0, 1, 2, 3, 4, 5];
+                /// let iter = a.iter().step_by(2);
                 ///
-                /// let a = (0..3).iter().collect::<HashSet>();
-                /// let b = HashSet::from_iter([0, 1, 2]);
+                /// assert_eq!(iter.next(), Some(0));
+                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.next(), Some(4));
+                /// assert_eq!(iter.next(), None);
+                /// ```
                 ///
-                /// assert_eq!(a, b);
+                /// ```rune,should_panic
+                /// let iter = (0..10).iter().step_by(0);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [HashMap::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<HashMap>()?
+        t.function("take_while")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<TakeWhile>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`HashMap`].
+                /// Creates an iterator that yields elements based on a
+                /// predicate.
+                ///
+                /// `take_while()` takes a closure as an argument. It will call
+                /// this closure on each element of the iterator, and yield
+                /// elements while it returns `true`.
+                ///
+                /// After `false` is returned, `take_while()`'s job is over, and
+                /// the rest of the elements are ignored.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::collections::HashMap;
+                /// let a = [-1, 0, 1];
                 ///
-                /// let actual = (0..3).iter().map(|n| (n, n.to_string())).collect::<HashMap>();
+                /// let iter = a.iter().take_while(|x| x < 0);
                 ///
-                /// let expected = HashMap::from_iter([
-                ///     (0, "0"),
-                ///     (1, "1"),
-                ///     (2, "2"),
-                /// ]);
+                /// assert_eq!(iter.next(), Some(-1));
+                /// assert_eq!(iter.next(), None);
+                /// ```
                 ///
-                /// assert_eq!(actual, expected);
+                /// Stopping after an initial `false`:
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3, 4];
+                ///
+                /// let iter = a.iter().take_while(|n| n != 3);
+                ///
+                /// // we have more elements that are less than three, but
+                /// // `take_while()` has stopped at the first `false`.
+                /// assert_eq!(iter.collect::<Vec>(), [1, 2]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [Object::HASH]))?
+        t.function("skip_while")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<SkipWhile>()?
+            .docs(docstring! {
+                /// Creates an iterator that [`skip`]s elements based on a
+                /// predicate.
+                ///
+                /// `skip_while()` takes a closure as an argument. It will call
+                /// this closure on each element of the iterator, and ignore
+                /// elements until it returns `false`.
+                ///
+                /// After `false` is returned, `skip_while()`'s job is over, and
+                /// the rest of the elements are yielded.
+                ///
+                /// [`skip`]: Iterator::skip
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                /// let a = [-1, 0, 1];
+                ///
+                /// let iter = a.iter().skip_while(|x| x < 0);
+                ///
+                /// assert_eq!(iter.next(), Some(0));
+                /// assert_eq!(iter.next(), Some(1));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// After the first `false`, the predicate is no longer
+                /// consulted:
+                ///
+                /// ```rune
+                /// let a = [-1, 0, 1, -2];
+                ///
+                /// let iter = a.iter().skip_while(|x| x < 0);
+                ///
+                /// assert_eq!(iter.collect::<Vec>(), [0, 1, -2]);
+                /// ```
+            })?;
+
+        t.function("scan")?
+            .argument_types::<(Value, Value, Function)>()?
+            .argument_names(["self", "initial_state", "f"])?
+            .return_type::<Scan>()?
+            .docs(docstring! {
+                /// An iterator adapter which, like [`fold`], holds internal
+                /// state, but unlike [`fold`], produces a new iterator.
+                ///
+                /// `scan()` takes two arguments: an initial value which seeds
+                /// the internal state, and a closure with two arguments, the
+                /// first being the current internal state and the second an
+                /// iterator element.
+                ///
+                /// Since values such as integers can't be modified in place,
+                /// the closure returns `Some((state, value))` where `state`
+                /// replaces the internal state and `value` is yielded from the
+                /// iterator. Returning `None` ends the iteration.
+                ///
+                /// [`fold`]: Iterator::fold
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3, 4];
+                ///
+                /// let iter = a.iter().scan(1, |state, x| {
+                ///     let state = state * x;
+                ///
+                ///     if state > 6 {
+                ///         return None;
+                ///     }
+                ///
+                ///     Some((state, -state))
+                /// });
+                ///
+                /// assert_eq!(iter.next(), Some(-1));
+                /// assert_eq!(iter.next(), Some(-2));
+                /// assert_eq!(iter.next(), Some(-6));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        t.function("cycle")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<HashMap>()?
+            .return_type::<Cycle>()?
             .docs(docstring! {
-                /// Collect the iterator as an [`Object`].
+                /// Repeats an iterator endlessly.
+                ///
+                /// Instead of stopping at [`None`], the iterator will instead
+                /// start again, from the beginning. After iterating again, it
+                /// will start at the beginning again. And again. And again.
+                /// Forever. Note that in case the original iterator is empty,
+                /// the resulting iterator will also be empty.
+                ///
+                /// The values produced during the first pass are remembered
+                /// and replayed on subsequent passes, so the underlying
+                /// iterator is only advanced once.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// assert_eq!([("first", 1), ("second", 2)].iter().collect::<Object>(), #{first: 1, second: 2});
+                /// let a = [1, 2, 3];
+                ///
+                /// let it = a.iter().cycle();
+                ///
+                /// assert_eq!(it.next(), Some(1));
+                /// assert_eq!(it.next(), Some(2));
+                /// assert_eq!(it.next(), Some(3));
+                /// assert_eq!(it.next(), Some(1));
+                /// assert_eq!(it.next(), Some(2));
+                /// assert_eq!(it.next(), Some(3));
+                /// assert_eq!(it.next(), Some(1));
+                /// ```
+                ///
+                /// An empty iterator stays empty:
+                ///
+                /// ```rune
+                /// let it = [].iter().cycle();
+                /// assert_eq!(it.next(), None);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [OwnedTuple::HASH]))?
+        t.function("inspect")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Inspect>()?
+            .docs(docstring! {
+                /// Does something with each element of an iterator, passing
+                /// the value on.
+                ///
+                /// When using iterators, you'll often chain several of them
+                /// together. While working on such code, you might want to
+                /// check out what's happening at various parts in the pipeline.
+                /// To do that, insert a call to `inspect()`.
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                /// let seen = [];
+                ///
+                /// let sum = [1, 4, 2, 3]
+                ///     .iter()
+                ///     .inspect(|x| seen.push(x))
+                ///     .filter(|x| x % 2 == 0)
+                ///     .sum::<i64>();
+                ///
+                /// assert_eq!(sum, 6);
+                /// assert_eq!(seen, [1, 4, 2, 3]);
+                /// ```
+            })?;
+
+        t.function("flatten")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<OwnedTuple>()?
+            .return_type::<Flatten>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`Tuple`].
+                /// Creates an iterator that flattens nested structure.
+                ///
+                /// This is useful when you have an iterator of iterators or an
+                /// iterator of things that can be turned into iterators and you
+                /// want to remove one level of indirection.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// assert_eq!((0..3).iter().collect::<Tuple>(), (0, 1, 2));
+                /// let data = [[1, 2, 3, 4], [5, 6]];
+                /// let flattened = data.iter().flatten().collect::<Vec>();
+                /// assert_eq!(flattened, [1, 2, 3, 4, 5, 6]);
+                /// ```
+                ///
+                /// Mapping and then flattening:
+                ///
+                /// ```rune
+                /// let words = ["alpha", "beta", "gamma"];
+                ///
+                /// // chars() returns an iterator
+                /// let merged = words.iter().map(|s| s.chars()).flatten().collect::<String>();
+                /// assert_eq!(merged, "alphabetagamma");
+                /// ```
+                ///
+                /// Flattening only removes one level of nesting at a time:
+                ///
+                /// ```rune
+                /// let d3 = [[[1, 2], [3, 4]], [[5, 6], [7, 8]]];
+                ///
+                /// let d2 = d3.iter().flatten().collect::<Vec>();
+                /// assert_eq!(d2, [[1, 2], [3, 4], [5, 6], [7, 8]]);
+                ///
+                /// let d1 = d3.iter().flatten().flatten().collect::<Vec>();
+                /// assert_eq!(d1, [1, 2, 3, 4, 5, 6, 7, 8]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [String::HASH]))?
+        t.function("max_by")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "compare"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the maximum value with
+                /// respect to the specified comparison function.
+                ///
+                /// If several elements are equally maximum, the last element is
+                /// returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().max_by(|x, y| x.cmp(y)), Some(5));
+                /// assert_eq!([].iter().max_by(|x, y| x.cmp(y)), None);
+                /// ```
+            })?;
+
+        t.function("min_by")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "compare"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the minimum value with
+                /// respect to the specified comparison function.
+                ///
+                /// If several elements are equally minimum, the first element
+                /// is returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().min_by(|x, y| x.cmp(y)), Some(-10));
+                /// assert_eq!([].iter().min_by(|x, y| x.cmp(y)), None);
+                /// ```
+            })?;
+
+        t.function("max_by_key")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the maximum value from the
+                /// specified function.
+                ///
+                /// If several elements are equally maximum, the last element is
+                /// returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().max_by_key(|x| x.abs()), Some(-10));
+                /// ```
+            })?;
+
+        t.function("min_by_key")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the minimum value from the
+                /// specified function.
+                ///
+                /// If several elements are equally minimum, the first element
+                /// is returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().min_by_key(|x| x.abs()), Some(0));
+                /// ```
+            })?;
+
+        t.function("partition")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<(Vec, Vec)>()?
+            .docs(docstring! {
+                /// Consumes an iterator, creating two collections from it.
+                ///
+                /// The predicate passed to `partition()` can return `true`, or
+                /// `false`. `partition()` returns a pair, all of the elements
+                /// for which it returned `true`, and all of the elements for
+                /// which it returned `false`.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3];
+                ///
+                /// let (even, odd) = a.iter().partition(|n| n % 2 == 0);
+                ///
+                /// assert_eq!(even, [2]);
+                /// assert_eq!(odd, [1, 3]);
+                /// ```
+            })?;
+
+        t.function("unzip")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<String>()?
+            .return_type::<(Vec, Vec)>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`String`].
+                /// Converts an iterator of pairs into a pair of vectors.
+                ///
+                /// `unzip()` consumes an entire iterator of pairs, producing
+                /// two collections: one from the left elements of the pairs,
+                /// and one from the right elements.
+                ///
+                /// This function is, in some sense, the opposite of [`zip`].
+                ///
+                /// [`zip`]: Iterator::zip
                 ///
                 /// # Examples
                 ///
                 /// ```rune
-                /// assert_eq!(["first", "second"].iter().collect::<String>(), "firstsecond");
+                /// let a = [(1, 2), (3, 4), (5, 6)];
+                ///
+                /// let (left, right) = a.iter().unzip();
+                ///
+                /// assert_eq!(left, [1, 3, 5]);
+                /// assert_eq!(right, [2, 4, 6]);
                 /// ```
             })?;
 
-        macro_rules! float_product_ops {
+        macro_rules! sum_ops {
+            ($ty:ty) => {
+                t.function(Params::new("sum", [<$ty>::HASH]))?
+                    .argument_types::<(Value,)>()?
+                    .argument_names(["self"])?
+                    .return_type::<$ty>()?
+                    .docs(docstring! {
+                        /// Sums the elements of an iterator.
+                        ///
+                        /// Takes each element, adds them together, and returns
+                        /// the result.
+                        ///
+                        /// An empty iterator returns the zero value of the
+                        /// type.
+                        ///
+                        /// `sum()` can be used to sum numerical built-in types,
+                        /// such as `i64`, `float` and `u64`. The first element
+                        /// returned by the iterator determines the type being
+                        /// summed.
+                        ///
+                        /// # Panics
+                        ///
+                        /// When calling `sum()` and a primitive integer type is
+                        /// being returned, this method will panic if the
+                        /// computation overflows.
+                        ///
+                        /// # Examples
+                        ///
+                        /// Basic usage:
+                        ///
+                        /// ```rune
+                        #[doc = concat!(" let a = [1", stringify!($ty), ", 2", stringify!($ty), ", 3", stringify!($ty), "];")]
+                        #[doc = concat!(" let sum = a.iter().sum::<", stringify!($ty), ">();")]
+                        ///
+                        #[doc = concat!(" assert_eq!(sum, 6", stringify!($ty), ");")]
+                        /// ```
+                    })?;
+            };
+        }
+
+        sum_ops!(u64);
+        sum_ops!(i64);
+        sum_ops!(f64);
+
+        macro_rules! integer_product_ops {
             ($ty:ty) => {
                 t.function(Params::new("product", [<$ty>::HASH]))?
                     .argument_types::<(Value,)>()?
@@ -1623,35 +2169,190 @@ pub fn module() -> Result<Module, ContextError> {
                         ///
                         /// ```rune
                         /// fn factorial(n) {
-                        #[doc = concat!("     (1..=n).iter().map(|n| n as ", stringify!($ty), ").product::<", stringify!($ty), ">()")]
+                        #[doc = concat!("     (1", stringify!($ty), "..=n).iter().product::<", stringify!($ty), ">()")]
                         /// }
                         ///
-                        #[doc = concat!(" assert_eq!(factorial(0), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(1), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(5), 120", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(0", stringify!($ty), "), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(1", stringify!($ty), "), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(5", stringify!($ty), "), 120", stringify!($ty), ");")]
                         /// ```
                     })?;
             };
         }
 
-        integer_product_ops!(u64);
-        integer_product_ops!(i64);
-        float_product_ops!(f64);
-    }
-
-    {
-        let mut t = m.define_trait(["DoubleEndedIterator"])?;
-
-        t.docs(docstring! {
-            /// An iterator able to yield elements from both ends.
-            ///
-            /// Something that implements `DoubleEndedIterator` has one extra
-            /// capability over something that implements [`Iterator`]: the
-            /// ability to also take `Item`s from the back, as well as the
-            /// front.
-            ///
-            /// It is important to note that both back and forth work on the
-            /// same range, and do not cross: iteration is over when they meet
+        t.function(Params::new("collect", [Vec::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<Vec>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`Vec`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::iter::range;
+                ///
+                /// assert_eq!((0..3).iter().collect::<Vec>(), [0, 1, 2]);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [VecDeque::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<VecDeque>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`VecDeque`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::VecDeque;
+                ///
+                /// assert_eq!((0..3).iter().collect::<VecDeque>(), VecDeque::from::<Vec>([0, 1, 2]));
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [HashSet::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashSet>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`HashSet`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::HashSet;
+                ///
+                /// let a = (0..3).iter().collect::<HashSet>();
+                /// let b = HashSet::from_iter([0, 1, 2]);
+                ///
+                /// assert_eq!(a, b);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [HashMap::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`HashMap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::HashMap;
+                ///
+                /// let actual = (0..3).iter().map(|n| (n, n.to_string())).collect::<HashMap>();
+                ///
+                /// let expected = HashMap::from_iter([
+                ///     (0, "0"),
+                ///     (1, "1"),
+                ///     (2, "2"),
+                /// ]);
+                ///
+                /// assert_eq!(actual, expected);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [Object::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as an [`Object`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!([("first", 1), ("second", 2)].iter().collect::<Object>(), #{first: 1, second: 2});
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [OwnedTuple::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<OwnedTuple>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`Tuple`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!((0..3).iter().collect::<Tuple>(), (0, 1, 2));
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [String::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<String>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`String`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!(["first", "second"].iter().collect::<String>(), "firstsecond");
+                /// ```
+            })?;
+
+        macro_rules! float_product_ops {
+            ($ty:ty) => {
+                t.function(Params::new("product", [<$ty>::HASH]))?
+                    .argument_types::<(Value,)>()?
+                    .argument_names(["self"])?
+                    .return_type::<$ty>()?
+                    .docs(docstring! {
+                        /// Iterates over the entire iterator, multiplying all
+                        /// the elements
+                        ///
+                        /// An empty iterator returns the one value of the type.
+                        ///
+                        /// `sum()` can be used to sum numerical built-in types,
+                        /// such as `i64`, `f64` and `u64`. The first element
+                        /// returned by the iterator determines the type being
+                        /// multiplied.
+                        ///
+                        /// # Panics
+                        ///
+                        /// When calling `product()` and a primitive integer
+                        /// type is being returned, method will panic if the
+                        /// computation overflows.
+                        ///
+                        /// # Examples
+                        ///
+                        /// ```rune
+                        /// fn factorial(n) {
+                        #[doc = concat!("     (1..=n).iter().map(|n| n as ", stringify!($ty), ").product::<", stringify!($ty), ">()")]
+                        /// }
+                        ///
+                        #[doc = concat!(" assert_eq!(factorial(0), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(1), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(5), 120", stringify!($ty), ");")]
+                        /// ```
+                    })?;
+            };
+        }
+
+        integer_product_ops!(u64);
+        integer_product_ops!(i64);
+        float_product_ops!(f64);
+    }
+
+    {
+        let mut t = m.define_trait(["DoubleEndedIterator"])?;
+
+        t.docs(docstring! {
+            /// An iterator able to yield elements from both ends.
+            ///
+            /// Something that implements `DoubleEndedIterator` has one extra
+            /// capability over something that implements [`Iterator`]: the
+            /// ability to also take `Item`s from the back, as well as the
+            /// front.
+            ///
+            /// It is important to note that both back and forth work on the
+            /// same range, and do not cross: iteration is over when they meet
             /// in the middle.
             ///
             /// In a similar fashion to the [`Iterator`] protocol, once a
@@ -2058,499 +2759,886 @@ impl Chain {
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Enumerate {
+struct Enumerate {
+    iter: Value,
+    count: usize,
+}
+
+impl Enumerate {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<(usize, Value)>, VmError> {
+        if let Some(value) = self.iter.protocol_next()? {
+            let i = self.count;
+            self.count += 1;
+            return Ok(Some((i, value)));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<(usize, Value)>, VmError> {
+        if let Some(value) = self.iter.protocol_next_back()? {
+            let len = self.iter.protocol_len()?;
+            return Ok(Some((self.count + len, value)));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        self.iter.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        self.iter.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Filter {
+    iter: Value,
+    f: Function,
+}
+
+impl Filter {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = self.iter.protocol_next()? {
+            if self.f.call::<bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = self.iter.protocol_next_back()? {
+            if self.f.call::<bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (_, hi) = self.iter.protocol_size_hint()?;
+        Ok((0, hi))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Map {
+    iter: Option<Value>,
+    f: Function,
+}
+
+impl Map {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = fuse!(self.iter.protocol_next()) {
+            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = fuse!(self.iter.protocol_next_back()) {
+            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let Some(iter) = &self.iter else {
+            return Ok((0, Some(0)));
+        };
+
+        iter.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let Some(iter) = &self.iter else {
+            return Ok(0);
+        };
+
+        iter.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct FilterMap {
+    iter: Option<Value>,
+    f: Function,
+}
+
+impl FilterMap {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = fuse!(self.iter.protocol_next()) {
+            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = fuse!(self.iter.protocol_next_back()) {
+            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct FlatMap {
+    map: Map,
+    frontiter: Option<Value>,
+    backiter: Option<Value>,
+}
+
+impl FlatMap {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            if let Some(iter) = &mut self.frontiter {
+                match iter.protocol_next()? {
+                    None => self.frontiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = self.map.next()? else {
+                return Ok(match &mut self.backiter {
+                    Some(backiter) => backiter.protocol_next()?,
+                    None => None,
+                });
+            };
+
+            self.frontiter = Some(value.protocol_into_iter()?)
+        }
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            if let Some(ref mut iter) = self.backiter {
+                match iter.protocol_next_back()? {
+                    None => self.backiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = self.map.next_back()? else {
+                return Ok(match &mut self.frontiter {
+                    Some(frontiter) => frontiter.protocol_next_back()?,
+                    None => None,
+                });
+            };
+
+            self.backiter = Some(value.protocol_into_iter()?);
+        }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (flo, fhi) = match &self.frontiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        let (blo, bhi) = match &self.backiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        let lo = flo.saturating_add(blo);
+
+        Ok(match (self.map.size_hint()?, fhi, bhi) {
+            ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(b)),
+            _ => (lo, None),
+        })
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Peekable {
+    iter: Value,
+    peeked: Option<Option<Value>>,
+}
+
+impl Peekable {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match self.peeked.take() {
+            Some(v) => v,
+            None => self.iter.protocol_next()?,
+        })
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match self.peeked.as_mut() {
+            Some(v @ Some(_)) => self.iter.protocol_next_back()?.or_else(|| v.take()),
+            Some(None) => None,
+            None => self.iter.protocol_next_back()?,
+        })
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let peek_len = match &self.peeked {
+            Some(None) => return Ok((0, Some(0))),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+
+        let (lo, hi) = self.iter.protocol_size_hint()?;
+        let lo = lo.saturating_add(peek_len);
+
+        let hi = match hi {
+            Some(x) => x.checked_add(peek_len),
+            None => None,
+        };
+
+        Ok((lo, hi))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let peek_len = match &self.peeked {
+            Some(None) => return Ok(0),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+
+        let len = self.iter.protocol_len()?;
+        Ok(len.saturating_add(peek_len))
+    }
+
+    /// Returns a reference to the `next()` value without advancing the iterator.
+    ///
+    /// Like [`next`], if there is a value, it is wrapped in a `Some(T)`. But if the
+    /// iteration is over, `None` is returned.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// Because `peek()` returns a reference, and many iterators iterate over
+    /// references, there can be a possibly confusing situation where the return
+    /// value is a double reference. You can see this effect in the examples below.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rune
+    /// let xs = [1, 2, 3];
+    ///
+    /// let iter = xs.iter().peekable();
+    ///
+    /// // peek() lets us see into the future
+    /// assert_eq!(iter.peek(), Some(1));
+    /// assert_eq!(iter.next(), Some(1));
+    ///
+    /// assert_eq!(iter.next(), Some(2));
+    ///
+    /// // The iterator does not advance even if we `peek` multiple times
+    /// assert_eq!(iter.peek(), Some(3));
+    /// assert_eq!(iter.peek(), Some(3));
+    ///
+    /// assert_eq!(iter.next(), Some(3));
+    ///
+    /// // After the iterator is finished, so is `peek()`
+    /// assert_eq!(iter.peek(), None);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[rune::function(keep)]
+    #[inline]
+    fn peek(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(v) = &self.peeked {
+            return Ok(v.clone());
+        }
+
+        let value = self.iter.protocol_next()?;
+        self.peeked = Some(value.clone());
+        Ok(value)
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Skip {
     iter: Value,
-    count: usize,
+    n: usize,
 }
 
-impl Enumerate {
+impl Skip {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
-    fn next(&mut self) -> Result<Option<(usize, Value)>, VmError> {
-        if let Some(value) = self.iter.protocol_next()? {
-            let i = self.count;
-            self.count += 1;
-            return Ok(Some((i, value)));
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.n > 0 {
+            let old_n = self.n;
+            self.n = 0;
+
+            for _ in 0..old_n {
+                match self.iter.protocol_next()? {
+                    Some(..) => (),
+                    None => return Ok(None),
+                }
+            }
         }
 
-        Ok(None)
+        self.iter.protocol_next()
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
-    fn next_back(&mut self) -> Result<Option<(usize, Value)>, VmError> {
-        if let Some(value) = self.iter.protocol_next_back()? {
-            let len = self.iter.protocol_len()?;
-            return Ok(Some((self.count + len, value)));
-        }
-
-        Ok(None)
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(if self.len()? > 0 {
+            self.iter.protocol_next_back()?
+        } else {
+            None
+        })
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        self.iter.protocol_size_hint()
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+        let lower = lower.saturating_sub(self.n);
+        let upper = upper.map(|x| x.saturating_sub(self.n));
+        Ok((lower, upper))
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        self.iter.protocol_len()
+        let len = self.iter.protocol_len()?;
+        Ok(len.saturating_sub(self.n))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Filter {
+struct Take {
     iter: Value,
-    f: Function,
+    n: usize,
 }
 
-impl Filter {
+impl Take {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = self.iter.protocol_next()? {
-            if self.f.call::<bool>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+        if self.n == 0 {
+            return Ok(None);
         }
 
-        Ok(None)
+        self.n -= 1;
+        self.iter.protocol_next()
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = self.iter.protocol_next_back()? {
-            if self.f.call::<bool>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+        if self.n == 0 {
+            Ok(None)
+        } else {
+            let n = self.n;
+            self.n -= 1;
+            let len = self.iter.protocol_len()?;
+            self.iter.protocol_nth_back(len.saturating_sub(n))
         }
-
-        Ok(None)
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let (_, hi) = self.iter.protocol_size_hint()?;
-        Ok((0, hi))
+        if self.n == 0 {
+            return Ok((0, Some(0)));
+        }
+
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+
+        let lower = lower.min(self.n);
+
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+
+        Ok((lower, upper))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        if self.n == 0 {
+            return Ok(0);
+        }
+
+        let len = self.iter.protocol_len()?;
+        Ok(len.min(self.n))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Map {
-    iter: Option<Value>,
-    f: Function,
+struct Rev {
+    value: Value,
 }
 
-impl Map {
+impl Rev {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(value) = fuse!(self.iter.protocol_next()) {
-            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
-        }
-
-        Ok(None)
+        self.value.protocol_next_back()
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(value) = fuse!(self.iter.protocol_next_back()) {
-            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
-        }
+        self.value.protocol_next()
+    }
 
-        Ok(None)
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        self.value.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        self.value.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Zip {
+    a: Value,
+    b: Value,
+}
+
+impl Zip {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<(Value, Value)>, VmError> {
+        let Some(a) = self.a.protocol_next()? else {
+            return Ok(None);
+        };
+
+        let Some(b) = self.b.protocol_next()? else {
+            return Ok(None);
+        };
+
+        Ok(Some((a, b)))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let Some(iter) = &self.iter else {
-            return Ok((0, Some(0)));
+        let (a_lower, a_upper) = self.a.protocol_size_hint()?;
+        let (b_lower, b_upper) = self.b.protocol_size_hint()?;
+
+        let lower = a_lower.min(b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (Some(x), None) => Some(x),
+            (None, Some(y)) => Some(y),
+            (None, None) => None,
         };
 
-        iter.protocol_size_hint()
+        Ok((lower, upper))
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        let Some(iter) = &self.iter else {
-            return Ok(0);
-        };
-
-        iter.protocol_len()
+        let a_len = self.a.protocol_len()?;
+        let b_len = self.b.protocol_len()?;
+        Ok(a_len.min(b_len))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct FilterMap {
-    iter: Option<Value>,
-    f: Function,
+struct StepBy {
+    iter: Value,
+    /// The step minus one, which is the number of elements skipped between
+    /// each yielded element.
+    step: usize,
+    first_take: bool,
 }
 
-impl FilterMap {
+impl StepBy {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = fuse!(self.iter.protocol_next()) {
-            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+        if self.first_take {
+            self.first_take = false;
+            return self.iter.protocol_next();
         }
 
-        Ok(None)
+        self.iter.protocol_nth(self.step)
     }
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = fuse!(self.iter.protocol_next_back()) {
-            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
-                return Ok(Some(value));
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+        let step = self.step;
+
+        let size = |n: usize| {
+            if !self.first_take {
+                n / (step + 1)
+            } else if n == 0 {
+                0
+            } else {
+                1 + (n - 1) / (step + 1)
             }
-        }
+        };
 
-        Ok(None)
+        Ok((size(lower), upper.map(size)))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let (len, _) = self.size_hint()?;
+        Ok(len)
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct FlatMap {
-    map: Map,
-    frontiter: Option<Value>,
-    backiter: Option<Value>,
+struct TakeWhile {
+    iter: Value,
+    f: Function,
+    flag: bool,
 }
 
-impl FlatMap {
+impl TakeWhile {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        loop {
-            if let Some(iter) = &mut self.frontiter {
-                match iter.protocol_next()? {
-                    None => self.frontiter = None,
-                    item @ Some(_) => return Ok(item),
-                }
-            }
-
-            let Some(value) = self.map.next()? else {
-                return Ok(match &mut self.backiter {
-                    Some(backiter) => backiter.protocol_next()?,
-                    None => None,
-                });
-            };
-
-            self.frontiter = Some(value.protocol_into_iter()?)
+        if self.flag {
+            return Ok(None);
         }
-    }
-
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        loop {
-            if let Some(ref mut iter) = self.backiter {
-                match iter.protocol_next_back()? {
-                    None => self.backiter = None,
-                    item @ Some(_) => return Ok(item),
-                }
-            }
 
-            let Some(value) = self.map.next_back()? else {
-                return Ok(match &mut self.frontiter {
-                    Some(frontiter) => frontiter.protocol_next_back()?,
-                    None => None,
-                });
-            };
+        let Some(value) = self.iter.protocol_next()? else {
+            return Ok(None);
+        };
 
-            self.backiter = Some(value.protocol_into_iter()?);
+        if self.f.call::<bool>((value.clone(),))? {
+            return Ok(Some(value));
         }
+
+        self.flag = true;
+        Ok(None)
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let (flo, fhi) = match &self.frontiter {
-            Some(iter) => iter.protocol_size_hint()?,
-            None => (0, Some(0)),
-        };
-
-        let (blo, bhi) = match &self.backiter {
-            Some(iter) => iter.protocol_size_hint()?,
-            None => (0, Some(0)),
-        };
-
-        let lo = flo.saturating_add(blo);
+        if self.flag {
+            return Ok((0, Some(0)));
+        }
 
-        Ok(match (self.map.size_hint()?, fhi, bhi) {
-            ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(b)),
-            _ => (lo, None),
-        })
+        let (_, upper) = self.iter.protocol_size_hint()?;
+        Ok((0, upper))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Peekable {
+struct SkipWhile {
     iter: Value,
-    peeked: Option<Option<Value>>,
+    f: Function,
+    flag: bool,
 }
 
-impl Peekable {
+impl SkipWhile {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match self.peeked.take() {
-            Some(v) => v,
-            None => self.iter.protocol_next()?,
-        })
-    }
+        while !self.flag {
+            let Some(value) = self.iter.protocol_next()? else {
+                return Ok(None);
+            };
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match self.peeked.as_mut() {
-            Some(v @ Some(_)) => self.iter.protocol_next_back()?.or_else(|| v.take()),
-            Some(None) => None,
-            None => self.iter.protocol_next_back()?,
-        })
+            if !self.f.call::<bool>((value.clone(),))? {
+                self.flag = true;
+                return Ok(Some(value));
+            }
+        }
+
+        self.iter.protocol_next()
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let peek_len = match &self.peeked {
-            Some(None) => return Ok((0, Some(0))),
-            Some(Some(_)) => 1,
-            None => 0,
-        };
-
-        let (lo, hi) = self.iter.protocol_size_hint()?;
-        let lo = lo.saturating_add(peek_len);
+        let (lower, upper) = self.iter.protocol_size_hint()?;
 
-        let hi = match hi {
-            Some(x) => x.checked_add(peek_len),
-            None => None,
-        };
+        if self.flag {
+            return Ok((lower, upper));
+        }
 
-        Ok((lo, hi))
+        Ok((0, upper))
     }
+}
 
-    #[rune::function(keep, protocol = LEN)]
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Scan {
+    iter: Option<Value>,
+    state: Value,
+    f: Function,
+}
+
+impl Scan {
+    #[rune::function(keep, protocol = NEXT)]
     #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        let peek_len = match &self.peeked {
-            Some(None) => return Ok(0),
-            Some(Some(_)) => 1,
-            None => 0,
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let Some(value) = fuse!(self.iter.protocol_next()) else {
+            return Ok(None);
         };
 
-        let len = self.iter.protocol_len()?;
-        Ok(len.saturating_add(peek_len))
+        let state = self.state.clone();
+
+        match self.f.call::<Option<(Value, Value)>>((state, value))? {
+            Some((state, value)) => {
+                self.state = state;
+                Ok(Some(value))
+            }
+            None => {
+                self.iter = None;
+                Ok(None)
+            }
+        }
     }
 
-    /// Returns a reference to the `next()` value without advancing the iterator.
-    ///
-    /// Like [`next`], if there is a value, it is wrapped in a `Some(T)`. But if the
-    /// iteration is over, `None` is returned.
-    ///
-    /// [`next`]: Iterator::next
-    ///
-    /// Because `peek()` returns a reference, and many iterators iterate over
-    /// references, there can be a possibly confusing situation where the return
-    /// value is a double reference. You can see this effect in the examples below.
-    ///
-    /// # Examples
-    ///
-    /// Basic usage:
-    ///
-    /// ```rune
-    /// let xs = [1, 2, 3];
-    ///
-    /// let iter = xs.iter().peekable();
-    ///
-    /// // peek() lets us see into the future
-    /// assert_eq!(iter.peek(), Some(1));
-    /// assert_eq!(iter.next(), Some(1));
-    ///
-    /// assert_eq!(iter.next(), Some(2));
-    ///
-    /// // The iterator does not advance even if we `peek` multiple times
-    /// assert_eq!(iter.peek(), Some(3));
-    /// assert_eq!(iter.peek(), Some(3));
-    ///
-    /// assert_eq!(iter.next(), Some(3));
-    ///
-    /// // After the iterator is finished, so is `peek()`
-    /// assert_eq!(iter.peek(), None);
-    /// assert_eq!(iter.next(), None);
-    /// ```
-    #[rune::function(keep)]
+    #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
-    fn peek(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(v) = &self.peeked {
-            return Ok(v.clone());
-        }
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let Some(iter) = &self.iter else {
+            return Ok((0, Some(0)));
+        };
 
-        let value = self.iter.protocol_next()?;
-        self.peeked = Some(value.clone());
-        Ok(value)
+        let (_, upper) = iter.protocol_size_hint()?;
+        Ok((0, upper))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Skip {
-    iter: Value,
-    n: usize,
+struct Cycle {
+    iter: Option<Value>,
+    buffer: alloc::Vec<Value>,
+    index: usize,
 }
 
-impl Skip {
+impl Cycle {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n > 0 {
-            let old_n = self.n;
-            self.n = 0;
-
-            for _ in 0..old_n {
-                match self.iter.protocol_next()? {
-                    Some(..) => (),
-                    None => return Ok(None),
-                }
-            }
+        if let Some(value) = fuse!(self.iter.protocol_next()) {
+            self.buffer.try_push(value.clone())?;
+            return Ok(Some(value));
         }
 
-        self.iter.protocol_next()
-    }
+        let Some(value) = self.buffer.get(self.index) else {
+            return Ok(None);
+        };
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(if self.len()? > 0 {
-            self.iter.protocol_next_back()?
-        } else {
-            None
-        })
+        let value = value.clone();
+        self.index = (self.index + 1) % self.buffer.len();
+        Ok(Some(value))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let (lower, upper) = self.iter.protocol_size_hint()?;
-        let lower = lower.saturating_sub(self.n);
-        let upper = upper.map(|x| x.saturating_sub(self.n));
-        Ok((lower, upper))
-    }
+        if !self.buffer.is_empty() {
+            return Ok((usize::MAX, None));
+        }
 
-    #[rune::function(keep, protocol = LEN)]
-    #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        let len = self.iter.protocol_len()?;
-        Ok(len.saturating_sub(self.n))
+        let Some(iter) = &self.iter else {
+            return Ok((0, Some(0)));
+        };
+
+        Ok(match iter.protocol_size_hint()? {
+            (0, Some(0)) => (0, Some(0)),
+            (0, _) => (0, None),
+            _ => (usize::MAX, None),
+        })
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Take {
+struct Inspect {
     iter: Value,
-    n: usize,
+    f: Function,
 }
 
-impl Take {
+impl Inspect {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n == 0 {
+        let Some(value) = self.iter.protocol_next()? else {
             return Ok(None);
-        }
+        };
 
-        self.n -= 1;
-        self.iter.protocol_next()
+        self.f.call::<Value>((value.clone(),))?;
+        Ok(Some(value))
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n == 0 {
-            Ok(None)
-        } else {
-            let n = self.n;
-            self.n -= 1;
-            let len = self.iter.protocol_len()?;
-            self.iter.protocol_nth_back(len.saturating_sub(n))
-        }
+        let Some(value) = self.iter.protocol_next_back()? else {
+            return Ok(None);
+        };
+
+        self.f.call::<Value>((value.clone(),))?;
+        Ok(Some(value))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        if self.n == 0 {
-            return Ok((0, Some(0)));
-        }
-
-        let (lower, upper) = self.iter.protocol_size_hint()?;
-
-        let lower = lower.min(self.n);
-
-        let upper = match upper {
-            Some(x) if x < self.n => Some(x),
-            _ => Some(self.n),
-        };
-
-        Ok((lower, upper))
+        self.iter.protocol_size_hint()
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        if self.n == 0 {
-            return Ok(0);
-        }
-
-        let len = self.iter.protocol_len()?;
-        Ok(len.min(self.n))
+        self.iter.protocol_len()
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Rev {
-    value: Value,
+struct Flatten {
+    iter: Option<Value>,
+    frontiter: Option<Value>,
+    backiter: Option<Value>,
 }
 
-impl Rev {
+impl Flatten {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        self.value.protocol_next_back()
+        loop {
+            if let Some(iter) = &mut self.frontiter {
+                match iter.protocol_next()? {
+                    None => self.frontiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = fuse!(self.iter.protocol_next()) else {
+                return Ok(match &mut self.backiter {
+                    Some(backiter) => backiter.protocol_next()?,
+                    None => None,
+                });
+            };
+
+            self.frontiter = Some(value.protocol_into_iter()?)
+        }
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        self.value.protocol_next()
+        loop {
+            if let Some(ref mut iter) = self.backiter {
+                match iter.protocol_next_back()? {
+                    None => self.backiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = fuse!(self.iter.protocol_next_back()) else {
+                return Ok(match &mut self.frontiter {
+                    Some(frontiter) => frontiter.protocol_next_back()?,
+                    None => None,
+                });
+            };
+
+            self.backiter = Some(value.protocol_into_iter()?);
+        }
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        self.value.protocol_size_hint()
-    }
+        let (flo, fhi) = match &self.frontiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
 
-    #[rune::function(keep, protocol = LEN)]
-    #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        self.value.protocol_len()
+        let (blo, bhi) = match &self.backiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        let lo = flo.saturating_add(blo);
+
+        let inner = match &self.iter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        Ok(match (inner, fhi, bhi) {
+            ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(b)),
+            _ => (lo, None),
+        })
     }
 }
 
@@ -13,8 +13,8 @@ use crate::runtime::range::RangeIter;
 use crate::runtime::range_from::RangeFromIter;
 use crate::runtime::range_inclusive::RangeInclusiveIter;
 use crate::runtime::{
-    ControlFlow, EnvProtocolCaller, Function, Hasher, Range, RangeFrom, RangeFull, RangeInclusive,
-    RangeTo, RangeToInclusive, Value, VmError,
+    ControlFlow, EnvProtocolCaller, Function, Hasher, Protocol, Range, RangeFrom, RangeFull,
+    RangeInclusive, RangeTo, RangeToInclusive, Value, VmError,
 };
 use crate::{ContextError, Module};
 
@@ -205,6 +205,46 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(cmp__meta)?;
     m.function_meta(hash__meta)?;
 
+    macro_rules! operator {
+        ($($name:literal => $protocol:ident, $doc:literal;)*) => {
+            $(
+                let mut t = m.define_trait([$name])?;
+                t.static_docs(&[$doc])?;
+
+                t.handler(|cx| {
+                    _ = cx.find(&Protocol::$protocol)?;
+                    Ok(())
+                })?;
+            )*
+        };
+    }
+
+    operator! {
+        "Neg" => NEG, " The unary negation operator `-`.";
+        "Add" => ADD, " The addition operator `+`.";
+        "Sub" => SUB, " The subtraction operator `-`.";
+        "Mul" => MUL, " The multiplication operator `*`.";
+        "Div" => DIV, " The division operator `/`.";
+        "Rem" => REM, " The remainder operator `%`.";
+        "BitAnd" => BIT_AND, " The bitwise AND operator `&`.";
+        "BitOr" => BIT_OR, " The bitwise OR operator `|`.";
+        "BitXor" => BIT_XOR, " The bitwise XOR operator `^`.";
+        "Shl" => SHL, " The left shift operator `<<`.";
+        "Shr" => SHR, " The right shift operator `>>`.";
+        "AddAssign" => ADD_ASSIGN, " The addition assignment operator `+=`.";
+        "SubAssign" => SUB_ASSIGN, " The subtraction assignment operator `-=`.";
+        "MulAssign" => MUL_ASSIGN, " The multiplication assignment operator `*=`.";
+        "DivAssign" => DIV_ASSIGN, " The division assignment operator `/=`.";
+        "RemAssign" => REM_ASSIGN, " The remainder assignment operator `%=`.";
+        "BitAndAssign" => BIT_AND_ASSIGN, " The bitwise AND assignment operator `&=`.";
+        "BitOrAssign" => BIT_OR_ASSIGN, " The bitwise OR assignment operator `|=`.";
+        "BitXorAssign" => BIT_XOR_ASSIGN, " The bitwise XOR assignment operator `^=`.";
+        "ShlAssign" => SHL_ASSIGN, " The left shift assignment operator `<<=`.";
+        "ShrAssign" => SHR_ASSIGN, " The right shift assignment operator `>>=`.";
+        "Index" => INDEX_GET, " Indexing operations like `value[index]`.";
+        "IndexMut" => INDEX_SET, " Index assignments like `value[index] = input`.";
+    }
+
     m.reexport(["Generator"], item!(::std::ops::generator::Generator))?;
     m.reexport(
         ["GeneratorState"],
@@ -39,12 +39,12 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(string_from_str)?;
     m.function_meta(string_new)?;
     m.function_meta(string_with_capacity)?;
-    m.function_meta(len)?;
-    m.function_meta(starts_with)?;
-    m.function_meta(ends_with)?;
+    m.function_meta(len)?.const_safe();
+    m.function_meta(starts_with)?.const_safe();
+    m.function_meta(ends_with)?.const_safe();
     m.function_meta(capacity)?;
     m.function_meta(clear)?;
-    m.function_meta(contains)?;
+    m.function_meta(contains)?.const_safe();
     m.function_meta(push)?;
     m.function_meta(push_str)?;
     m.function_meta(reserve)?;
@@ -53,21 +53,21 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(as_bytes)?;
     m.function_meta(into_bytes)?;
     m.function_meta(shrink_to_fit)?;
-    m.function_meta(char_at)?;
+    m.function_meta(char_at)?.const_safe();
     m.function_meta(split)?;
     m.function_meta(split_once)?;
     m.associated_function("split_str", __rune_fn__split)?;
-    m.function_meta(trim)?;
-    m.function_meta(trim_end)?;
-    m.function_meta(replace)?;
-    m.function_meta(is_empty)?;
+    m.function_meta(trim)?.const_safe();
+    m.function_meta(trim_end)?.const_safe();
+    m.function_meta(replace)?.const_safe();
+    m.function_meta(is_empty)?.const_safe();
     m.function_meta(chars)?;
     m.function_meta(get__meta)?;
     m.function_meta(parse_int)?;
     m.function_meta(parse_float)?;
     m.function_meta(parse_char)?;
-    m.function_meta(to_lowercase)?;
-    m.function_meta(to_uppercase)?;
+    m.function_meta(to_lowercase)?.const_safe();
+    m.function_meta(to_uppercase)?.const_safe();
 
     m.function_meta(add)?;
     m.function_meta(add_assign)?;
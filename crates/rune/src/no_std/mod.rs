@@ -9,6 +9,9 @@ pub struct RawEnv {
     pub(crate) context: Option<NonNull<()>>,
     pub(crate) unit: Option<NonNull<()>>,
     pub(crate) diagnostics: Option<NonNull<()>>,
+    pub(crate) call_depth: usize,
+    pub(crate) max_call_depth: usize,
+    pub(crate) limited: bool,
 }
 
 impl RawEnv {
@@ -18,6 +21,9 @@ impl RawEnv {
             context: None,
             unit: None,
             diagnostics: None,
+            call_depth: 0,
+            max_call_depth: 0,
+            limited: false,
         }
     }
 }
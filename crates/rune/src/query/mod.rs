@@ -198,6 +198,20 @@ pub(crate) enum Build {
     ReExport,
     /// A build which simply queries for the item.
     Query,
+    /// Bind the functions of a trait implementation.
+    TraitImpl(TraitImpl),
+}
+
+/// The functions bound by an `impl Trait for Type` block.
+#[derive(Debug, TryClone)]
+pub(crate) struct TraitImpl {
+    /// The type hash of the implementing type.
+    pub(crate) type_hash: Hash,
+    /// Function hashes which are aliases to other functions.
+    pub(crate) aliases: Vec<(Hash, Hash)>,
+    /// Whether an identity `into_iter` function should be generated, which is
+    /// the case for iterators.
+    pub(crate) into_iter: bool,
 }
 
 /// An entry in the build queue.
@@ -214,6 +228,8 @@ pub(crate) enum ImplItemKind {
     Ast {
         /// Non-expanded ast of the path.
         path: Box<ast::Path>,
+        /// Non-expanded ast of the trait being implemented, if any.
+        trait_: Option<Box<ast::Path>>,
         /// Functions in the impl block.
         functions: Vec<ast::ItemFn>,
     },
@@ -26,9 +26,7 @@ use crate::item::ComponentRef;
 use crate::item::IntoComponent;
 use crate::macros::Storage;
 use crate::parse::{NonZeroId, Resolve};
-#[cfg(feature = "doc")]
-use crate::runtime::Call;
-use crate::runtime::ConstValue;
+use crate::runtime::{Call, ConstValue, OwnedTuple, TypeHash};
 use crate::shared::{Consts, Gen};
 use crate::{Context, Diagnostics, Hash, Item, ItemBuf, Options, SourceId, Sources};
 
@@ -77,6 +75,8 @@ pub(crate) struct QueryInner<'arena> {
     names: Names,
     /// Queue of impl items to process.
     pub(crate) defer_queue: VecDeque<DeferEntry>,
+    /// Functions declared by indexed traits.
+    traits: HashMap<ItemId, indexing::Trait>,
 }
 
 impl QueryInner<'_> {
@@ -458,6 +458,95 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Err(compile::Error::new(location.as_spanned(), kind))
     }
 
+    /// Resolve a type annotation into the hash of the type it refers to.
+    ///
+    /// Returns `None` if the annotation doesn't constrain the type of a
+    /// value, like the never type `!`.
+    pub(crate) fn resolve_type(
+        &mut self,
+        source_id: SourceId,
+        ty: &ast::Type,
+    ) -> compile::Result<Option<Hash>> {
+        let path = match ty {
+            ast::Type::Bang(..) => return Ok(None),
+            ast::Type::Tuple(..) => return Ok(Some(OwnedTuple::HASH)),
+            ast::Type::Path(path) => path,
+        };
+
+        let named = self.convert_path(path)?;
+
+        if let Some((spanned, _)) = named.parameters.iter().flatten().next() {
+            return Err(compile::Error::new(
+                spanned.span(),
+                ErrorKind::UnsupportedGenerics,
+            ));
+        }
+
+        let location = DynLocation::new(source_id, path);
+
+        let Some(meta) = self.try_lookup_meta(&location, named.item, &Default::default())? else {
+            // The item is currently being built, which happens when a type
+            // refers to itself through its fields.
+            if self.inner.items.contains_key(&named.item) {
+                return Ok(Some(self.pool.item_type_hash(named.item)));
+            }
+
+            return Err(compile::Error::new(
+                path,
+                ErrorKind::MissingItem {
+                    item: self.pool.item(named.item).try_to_owned()?,
+                },
+            ));
+        };
+
+        match &meta.kind {
+            meta::Kind::Type { .. } | meta::Kind::Enum { .. } => Ok(Some(meta.hash)),
+            meta::Kind::Struct { enum_hash, .. } if *enum_hash == Hash::EMPTY => {
+                Ok(Some(meta.hash))
+            }
+            _ => Err(compile::Error::expected_meta(
+                path,
+                meta.info(self.pool)?,
+                "a type",
+            )),
+        }
+    }
+
+    /// Resolve an optional type annotation, but only if type checks are
+    /// enabled since annotations are otherwise erased.
+    pub(crate) fn resolve_annotation(
+        &mut self,
+        source_id: SourceId,
+        ty: Option<&ast::Type>,
+    ) -> compile::Result<Option<Hash>> {
+        match ty {
+            Some(ty) if self.options.type_checks => self.resolve_type(source_id, ty),
+            _ => Ok(None),
+        }
+    }
+
+    /// Resolve the type annotations of named fields.
+    fn resolve_field_types(
+        &mut self,
+        source_id: SourceId,
+        fields: &mut meta::Fields,
+        types: &[(usize, ast::Type)],
+    ) -> compile::Result<()> {
+        let meta::Fields::Named(named) = fields else {
+            return Ok(());
+        };
+
+        for (position, ty) in types {
+            let ty = self.resolve_annotation(source_id, Some(ty))?;
+
+            if let Some(field) = named.fields.get_mut(*position) {
+                field.ty = ty;
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn lookup_deprecation(&self, hash: Hash) -> Option<&str> {
         self.context.lookup_deprecation(hash)
     }
@@ -787,6 +876,30 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Add a new trait item that can be queried.
+    #[tracing::instrument(skip_all)]
+    pub(crate) fn index_trait(
+        &mut self,
+        item_meta: ItemMeta,
+        trait_: indexing::Trait,
+    ) -> compile::Result<()> {
+        tracing::trace!(item = ?self.pool.item(item_meta.item));
+
+        self.inner.traits.try_insert(item_meta.item, trait_)?;
+
+        self.index(indexing::Entry {
+            item_meta,
+            indexed: Indexed::Trait,
+        })?;
+
+        Ok(())
+    }
+
+    /// Get the functions declared by an indexed trait.
+    pub(crate) fn get_trait(&self, item: ItemId) -> Option<&indexing::Trait> {
+        self.inner.traits.get(&item)
+    }
+
     /// Add a new variant item that can be queried.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_variant(
@@ -1624,20 +1737,50 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     ));
                 };
 
+                let mut fields = variant.fields;
+                self.resolve_field_types(
+                    item_meta.location.source_id,
+                    &mut fields,
+                    &variant.types,
+                )?;
+
                 meta::Kind::Struct {
-                    fields: variant.fields,
+                    fields,
                     constructor: None,
                     parameters: Hash::EMPTY,
                     enum_hash: enum_meta.hash,
                 }
             }
-            Indexed::Struct(st) => meta::Kind::Struct {
-                fields: st.fields,
-                constructor: None,
-                parameters: Hash::EMPTY,
-                enum_hash: Hash::EMPTY,
-            },
+            Indexed::Struct(st) => {
+                let mut fields = st.fields;
+                self.resolve_field_types(item_meta.location.source_id, &mut fields, &st.types)?;
+
+                meta::Kind::Struct {
+                    fields,
+                    constructor: None,
+                    parameters: Hash::EMPTY,
+                    enum_hash: Hash::EMPTY,
+                }
+            }
             Indexed::Function(f) => {
+                let mut parameters = f.parameters.try_clone()?;
+                let mut output = None;
+
+                if let FunctionAst::Item(ast, _) = &f.ast {
+                    let source_id = item_meta.location.source_id;
+
+                    for ((arg, _), parameter) in ast.args.iter().zip(parameters.iter_mut()) {
+                        parameter.ty = self.resolve_annotation(source_id, arg.ty())?;
+                    }
+
+                    // Only immediate calls produce the value of the function
+                    // body, other calls produce a future, generator or stream.
+                    if let Call::Immediate = f.call {
+                        let ty = ast.output.as_ref().map(|(_, ty)| ty);
+                        output = self.resolve_annotation(source_id, ty)?;
+                    }
+                }
+
                 let kind = meta::Kind::Function {
                     associated: match (f.is_instance, &f.ast) {
                         (true, FunctionAst::Item(_, name)) => {
@@ -1656,6 +1799,8 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     is_test: f.is_test,
                     is_bench: f.is_bench,
                     signature: meta::Signature {
+                        parameters: parameters.try_into_boxed_slice()?,
+                        output,
                         #[cfg(feature = "doc")]
                         is_async: matches!(f.call, Call::Async | Call::Stream),
                         #[cfg(feature = "doc")]
@@ -1797,7 +1942,9 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     )?;
 
                     let hir = match &c {
-                        indexing::ConstFn::Ast(ast) => crate::hir::lowering::item_fn(&mut cx, ast)?,
+                        indexing::ConstFn::Ast(ast) => {
+                            crate::hir::lowering::item_fn(&mut cx, ast, Call::Immediate)?
+                        }
                         indexing::ConstFn::Node(node) => {
                             node.parse(|p| crate::hir::lowering2::item_fn(&mut cx, p, false))?
                         }
@@ -1839,6 +1986,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 meta::Kind::Import(import.entry)
             }
             Indexed::Module => meta::Kind::Module,
+            Indexed::Trait => meta::Kind::Trait,
         };
 
         let source = SourceMeta {
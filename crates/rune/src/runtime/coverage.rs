@@ -0,0 +1,201 @@
+//! Coverage of executed instructions.
+
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::alloc::{self, BTreeMap, Box, Vec};
+use crate::sync::Arc;
+use crate::Sources;
+
+use super::{DebugLocation, Unit, UnitStorage};
+
+/// Records how many times each instruction of a collection of units has been
+/// executed.
+///
+/// Units have to be registered with [`Coverage::track`] before the coverage is
+/// installed in a virtual machine with [`Vm::set_coverage`]. Instructions
+/// executed in units which are not tracked are not recorded.
+///
+/// Since the counters are atomic, the same coverage can be shared by virtual
+/// machines running on different threads.
+///
+/// [`Vm::set_coverage`]: super::Vm::set_coverage
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Source, Sources, Vm};
+/// use rune::runtime::Coverage;
+/// use rune::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = Sources::new();
+///
+/// let source_id = sources.insert(Source::memory(r#"
+/// pub fn main(n) {
+///     if n > 10 {
+///         n * 2
+///     } else {
+///         n
+///     }
+/// }
+/// "#)?)?;
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut coverage = Coverage::new();
+/// coverage.track(unit.clone())?;
+/// let coverage = Arc::try_new(coverage)?;
+///
+/// let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit.clone());
+/// vm.set_coverage(Some(coverage.clone()));
+/// vm.call(["main"], (1i64,))?;
+///
+/// let lines = coverage.lines(&unit, &sources)?;
+///
+/// // The `n * 2` branch on line 3 is never taken.
+/// let line = lines.iter().find(|(l, _)| l.source_id == source_id && l.line == 3);
+/// assert!(matches!(line, Some((_, 0))));
+///
+/// // While the condition on line 2 is evaluated once.
+/// let line = lines.iter().find(|(l, _)| l.source_id == source_id && l.line == 2);
+/// assert!(matches!(line, Some((_, 1))));
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Coverage {
+    units: Vec<UnitCoverage>,
+}
+
+struct UnitCoverage {
+    unit: Arc<Unit>,
+    /// Execution counters indexed by instruction pointer.
+    counters: Box<[AtomicUsize]>,
+}
+
+impl Coverage {
+    /// Construct a new empty coverage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking coverage for the given unit.
+    ///
+    /// Tracking a unit which is already tracked does nothing.
+    pub fn track(&mut self, unit: Arc<Unit>) -> alloc::Result<()> {
+        if self.units.iter().any(|u| Arc::ptr_eq(&u.unit, &unit)) {
+            return Ok(());
+        }
+
+        let len = unit.instructions().end();
+        let mut counters = Vec::try_with_capacity(len)?;
+
+        for _ in 0..len {
+            counters.try_push(AtomicUsize::new(0))?;
+        }
+
+        self.units.try_push(UnitCoverage {
+            unit,
+            counters: counters.try_into_boxed_slice()?,
+        })?;
+
+        Ok(())
+    }
+
+    /// Iterate over all tracked units.
+    pub fn units(&self) -> impl Iterator<Item = &Arc<Unit>> {
+        self.units.iter().map(|u| &u.unit)
+    }
+
+    /// Get the number of times the instruction at `ip` in the given unit has
+    /// been executed.
+    pub fn hits(&self, unit: &Arc<Unit>, ip: usize) -> usize {
+        let Some(counters) = self.counters(unit) else {
+            return 0;
+        };
+
+        match counters.get(ip) {
+            Some(counter) => counter.load(Ordering::Relaxed),
+            None => 0,
+        }
+    }
+
+    /// Map the recorded coverage of the given unit to lines in the sources it
+    /// was built from.
+    ///
+    /// Every line which has instructions associated with it through the
+    /// [`DebugInfo`] of the unit is included, and the number of times a line
+    /// has been executed is the largest number of times any of its
+    /// instructions have been executed.
+    ///
+    /// [`DebugInfo`]: super::DebugInfo
+    pub fn lines(
+        &self,
+        unit: &Arc<Unit>,
+        sources: &Sources,
+    ) -> alloc::Result<Vec<(DebugLocation, usize)>> {
+        let mut lines = BTreeMap::new();
+
+        let Some(debug) = unit.debug_info() else {
+            return Ok(Vec::new());
+        };
+
+        for (&ip, inst) in &debug.instructions {
+            let Some(source) = sources.get(inst.source_id) else {
+                continue;
+            };
+
+            let (line, _) = source.find_line_column(inst.span.start.into_usize());
+
+            let location = DebugLocation {
+                source_id: inst.source_id,
+                line,
+            };
+
+            let hits = self.hits(unit, ip);
+
+            match lines.get_mut(&location) {
+                Some(existing) => {
+                    *existing = usize::max(*existing, hits);
+                }
+                None => {
+                    lines.try_insert(location, hits)?;
+                }
+            }
+        }
+
+        let mut output = Vec::try_with_capacity(lines.len())?;
+
+        for (location, hits) in lines {
+            output.try_push((location, hits))?;
+        }
+
+        Ok(output)
+    }
+
+    /// Get the counters associated with the given unit.
+    #[inline]
+    pub(crate) fn counters(&self, unit: &Arc<Unit>) -> Option<&[AtomicUsize]> {
+        let unit = self.units.iter().find(|u| Arc::ptr_eq(&u.unit, unit))?;
+        Some(&unit.counters)
+    }
+}
+
+impl fmt::Debug for Coverage {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coverage")
+            .field("units", &self.units.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Record that the instruction at `ip` has been executed.
+#[inline]
+pub(crate) fn record(counters: &[AtomicUsize], ip: usize) {
+    if let Some(counter) = counters.get(ip) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -11,7 +11,7 @@ use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{Box, HashMap, Vec};
 use crate::ast::Span;
-use crate::runtime::DebugLabel;
+use crate::runtime::{Address, DebugLabel};
 use crate::{Hash, ItemBuf, SourceId};
 
 /// Debug information about a unit.
@@ -63,6 +63,11 @@ pub struct DebugInst {
     pub comment: Option<Box<str>>,
     /// Label associated with the location.
     pub labels: Vec<DebugLabel>,
+    /// Variables which are defined right before this instruction.
+    pub variables: Vec<DebugVariable>,
+    /// Addresses of variables which go out of scope right before this
+    /// instruction, after the ones in `variables` have been defined.
+    pub freed: Vec<Address>,
 }
 
 impl DebugInst {
@@ -78,10 +83,32 @@ impl DebugInst {
             span,
             comment,
             labels,
+            variables: Vec::new(),
+            freed: Vec::new(),
         }
     }
 }
 
+/// Debug information on a variable defined in a function.
+#[derive(Debug, TryClone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "musli", derive(Decode, Encode), musli(crate = musli_core))]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The address of the variable relative to the top of its call frame.
+    pub addr: Address,
+}
+
+impl DebugVariable {
+    /// Construct a new debug variable.
+    #[inline]
+    pub fn new(name: Box<str>, addr: Address) -> Self {
+        Self { name, addr }
+    }
+}
+
 /// Debug information on function arguments.
 #[derive(Debug, TryClone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -0,0 +1,324 @@
+//! A step debugger driving a [`VmExecution`].
+
+use crate::alloc::{self, HashMap, Vec};
+use crate::{SourceId, Sources};
+
+use super::{budget, Unit, Value, Vm, VmError, VmExecution, VmOutcome};
+
+/// A location in the source, as a zero-based line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct DebugLocation {
+    /// The source the location belongs to.
+    pub source_id: SourceId,
+    /// The zero-based line of the location.
+    pub line: usize,
+}
+
+/// A breakpoint registered with a [`Debugger`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Breakpoint {
+    /// The identifier of the breakpoint.
+    pub id: usize,
+    /// The location the breakpoint was resolved to.
+    ///
+    /// This might differ from the requested line, in which case it is the
+    /// closest line following it which has any instructions associated with
+    /// it.
+    pub location: DebugLocation,
+    /// The instructions the breakpoint triggers on.
+    ips: Vec<usize>,
+}
+
+/// How execution should proceed when resumed through
+/// [`VmExecution::debug`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DebugStep {
+    /// Run until a breakpoint is hit or execution completes.
+    Continue,
+    /// Run until a new line is reached, entering any called functions.
+    Into,
+    /// Run until a new line is reached in the current function or one of its
+    /// callers.
+    Over,
+    /// Run until the current function returns.
+    Out,
+}
+
+/// The outcome of resuming an execution through [`VmExecution::debug`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DebugOutcome {
+    /// Execution paused at the breakpoint with the given identifier.
+    Breakpoint(usize),
+    /// Execution paused since the requested step completed.
+    Step,
+    /// The execution yielded a value.
+    Yielded(Value),
+    /// The execution completed with a value.
+    Complete(Value),
+    /// Execution paused since the budget of the host was exhausted.
+    ///
+    /// Every executed instruction consumes one unit of the budget the debugged
+    /// execution is running under, see [`budget`].
+    ///
+    /// [`budget`]: super::budget
+    Limited,
+}
+
+/// A step debugger.
+///
+/// Instructions are mapped to lines through the [`DebugInfo`] of a unit, so
+/// the unit must have been compiled with debug information enabled.
+///
+/// [`DebugInfo`]: super::DebugInfo
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Source, Sources, Vm};
+/// use rune::runtime::{DebugOutcome, DebugStep, Debugger};
+/// use rune::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = Sources::new();
+///
+/// let source_id = sources.insert(Source::memory(r#"
+/// pub fn main() {
+///     let a = 1;
+///     let b = a + 2;
+///     b
+/// }
+/// "#)?)?;
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut debugger = Debugger::new(&unit, &sources)?;
+/// let breakpoint = debugger.add_breakpoint(source_id, 3)?.expect("no line to break on");
+///
+/// let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+/// let mut execution = vm.execute(["main"], ())?;
+///
+/// let outcome = futures_executor::block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+/// assert!(matches!(outcome, DebugOutcome::Breakpoint(id) if id == breakpoint));
+///
+/// {
+///     let locals = execution.vm().locals()?;
+///     assert_eq!(locals.len(), 1);
+///     assert_eq!(locals[0].0, "a");
+/// }
+///
+/// let outcome = futures_executor::block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+///
+/// let DebugOutcome::Complete(value) = outcome else {
+///     panic!("expected execution to complete");
+/// };
+///
+/// assert_eq!(value.as_integer::<i64>()?, 3);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+pub struct Debugger {
+    /// Location of every instruction with debug information.
+    locations: HashMap<usize, DebugLocation>,
+    /// Sorted instructions with debug information.
+    ips: Vec<usize>,
+    /// Registered breakpoints.
+    breakpoints: Vec<Breakpoint>,
+    /// Instructions with breakpoints mapped to the breakpoint identifier.
+    breakpoint_ips: HashMap<usize, usize>,
+    /// The identifier of the next breakpoint.
+    next_id: usize,
+}
+
+impl Debugger {
+    /// Construct a new debugger for the given unit, using the sources it was
+    /// built from to resolve lines.
+    pub fn new(unit: &Unit, sources: &Sources) -> alloc::Result<Self> {
+        let mut locations = HashMap::new();
+        let mut ips = Vec::new();
+
+        if let Some(debug) = unit.debug_info() {
+            for (ip, inst) in &debug.instructions {
+                let Some(source) = sources.get(inst.source_id) else {
+                    continue;
+                };
+
+                let (line, _) = source.find_line_column(inst.span.start.into_usize());
+
+                ips.try_push(*ip)?;
+
+                locations.try_insert(
+                    *ip,
+                    DebugLocation {
+                        source_id: inst.source_id,
+                        line,
+                    },
+                )?;
+            }
+        }
+
+        ips.sort();
+
+        Ok(Self {
+            locations,
+            ips,
+            breakpoints: Vec::new(),
+            breakpoint_ips: HashMap::new(),
+            next_id: 0,
+        })
+    }
+
+    /// Get the location of the given instruction pointer.
+    pub fn location(&self, ip: usize) -> Option<DebugLocation> {
+        self.locations.get(&ip).copied()
+    }
+
+    /// Add a breakpoint at the given zero-based line.
+    ///
+    /// If the line has no instructions associated with it, the breakpoint is
+    /// placed on the closest following line which has. Returns the identifier
+    /// of the breakpoint, or `None` if no line could be found.
+    pub fn add_breakpoint(
+        &mut self,
+        source_id: SourceId,
+        line: usize,
+    ) -> alloc::Result<Option<usize>> {
+        let resolved = self
+            .locations
+            .values()
+            .filter(|l| l.source_id == source_id && l.line >= line)
+            .map(|l| l.line)
+            .min();
+
+        let Some(line) = resolved else {
+            return Ok(None);
+        };
+
+        let location = DebugLocation { source_id, line };
+        let mut ips = Vec::new();
+
+        for (ip, l) in &self.locations {
+            if *l == location {
+                ips.try_push(*ip)?;
+            }
+        }
+
+        ips.sort();
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        for ip in &ips {
+            self.breakpoint_ips.try_insert(*ip, id)?;
+        }
+
+        self.breakpoints
+            .try_push(Breakpoint { id, location, ips })?;
+        Ok(Some(id))
+    }
+
+    /// Remove the breakpoint with the given identifier.
+    ///
+    /// Returns `true` if the breakpoint existed.
+    pub fn remove_breakpoint(&mut self, id: usize) -> bool {
+        let Some(index) = self.breakpoints.iter().position(|b| b.id == id) else {
+            return false;
+        };
+
+        let breakpoint = self.breakpoints.remove(index);
+
+        for ip in &breakpoint.ips {
+            if self.breakpoint_ips.get(ip) == Some(&id) {
+                self.breakpoint_ips.remove(ip);
+            }
+        }
+
+        true
+    }
+
+    /// Iterate over all registered breakpoints.
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    /// Resume the given execution until the requested step completes.
+    pub(crate) async fn resume<T>(
+        &mut self,
+        execution: &mut VmExecution<T>,
+        step: DebugStep,
+    ) -> Result<DebugOutcome, VmError>
+    where
+        T: AsRef<Vm> + AsMut<Vm>,
+    {
+        let (start, depth) = self.current(execution.vm());
+        let mut previous = (start, depth);
+
+        loop {
+            // Each instruction is executed under a budget of one, so charge
+            // the budget of the host separately to honor it.
+            if !budget::acquire().take() {
+                return Ok(DebugOutcome::Limited);
+            }
+
+            match execution.resume().with_budget(1).await? {
+                VmOutcome::Complete(value) => return Ok(DebugOutcome::Complete(value)),
+                VmOutcome::Yielded(value) => return Ok(DebugOutcome::Yielded(value)),
+                VmOutcome::Limited => {}
+            }
+
+            let vm = execution.vm();
+            let (location, current) = self.current(vm);
+
+            // Instructions without debug information do not have a location
+            // we can meaningfully pause at.
+            if location.is_none() {
+                continue;
+            }
+
+            // Returning into the middle of the line of the call does not count
+            // as entering it, but returning to a following line does.
+            let entered = if current < previous.1 {
+                location != self.preceding(vm.ip())
+            } else {
+                location != previous.0 || current > previous.1
+            };
+
+            previous = (location, current);
+
+            if entered {
+                if let Some(id) = self.breakpoint_ips.get(&vm.ip()) {
+                    return Ok(DebugOutcome::Breakpoint(*id));
+                }
+            }
+
+            let moved = location != start || current != depth;
+
+            let done = match step {
+                DebugStep::Continue => false,
+                DebugStep::Into => moved,
+                DebugStep::Over => moved && current <= depth,
+                DebugStep::Out => current < depth,
+            };
+
+            if done {
+                return Ok(DebugOutcome::Step);
+            }
+        }
+    }
+
+    /// Get the location of the closest instruction preceding the given one,
+    /// which for a return address is the call it returns from.
+    fn preceding(&self, ip: usize) -> Option<DebugLocation> {
+        let index = self.ips.partition_point(|&i| i < ip);
+        let ip = self.ips.get(index.checked_sub(1)?)?;
+        self.location(*ip)
+    }
+
+    fn current(&self, vm: &Vm) -> (Option<DebugLocation>, usize) {
+        (self.location(vm.ip()), vm.call_frames().len())
+    }
+}
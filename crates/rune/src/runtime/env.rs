@@ -86,6 +86,29 @@ where
     c(&context, &unit, diagnostics)
 }
 
+/// The call depth of a virtual machine which is calling a native function.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CallDepth {
+    /// The number of active calls.
+    pub(crate) depth: usize,
+    /// The maximum number of active calls permitted.
+    pub(crate) max: usize,
+}
+
+/// Get the call depth registered by the virtual machine calling the current
+/// native function, if it has a call depth limit.
+pub(crate) fn call_depth() -> Option<CallDepth> {
+    self::no_std::rune_env_get().call_depth
+}
+
+/// Register the call depth of a virtual machine which is about to call a
+/// native function.
+pub(crate) fn set_call_depth(call_depth: Option<CallDepth>) {
+    let mut env = self::no_std::rune_env_get();
+    env.call_depth = call_depth;
+    self::no_std::rune_env_replace(env);
+}
+
 pub(crate) struct Guard {
     env: Env,
 }
@@ -110,6 +133,7 @@ impl Guard {
                 context: Some(NonNull::new_unchecked(context.cast_mut())),
                 unit: Some(NonNull::new_unchecked(unit.cast_mut())),
                 diagnostics,
+                call_depth: None,
             })
         };
 
@@ -139,6 +163,7 @@ struct Env {
     context: Option<NonNull<RuntimeContext>>,
     unit: Option<NonNull<Unit>>,
     diagnostics: Option<NonNull<VmDiagnosticsObj>>,
+    call_depth: Option<CallDepth>,
 }
 
 impl Env {
@@ -147,6 +172,7 @@ impl Env {
             context: None,
             unit: None,
             diagnostics: None,
+            call_depth: None,
         }
     }
 }
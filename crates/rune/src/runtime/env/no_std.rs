@@ -1,4 +1,4 @@
-use super::Env;
+use super::{CallDepth, Env};
 
 use crate::no_std::RawEnv;
 
@@ -33,6 +33,9 @@ unsafe fn from_env(env: Env) -> RawEnv {
         context: env.context.map(|ptr| ptr.cast()),
         unit: env.unit.map(|ptr| ptr.cast()),
         diagnostics: env.diagnostics.map(|ptr| ptr.cast()),
+        call_depth: env.call_depth.map_or(0, |c| c.depth),
+        max_call_depth: env.call_depth.map_or(0, |c| c.max),
+        limited: env.call_depth.is_some(),
     }
 }
 
@@ -41,5 +44,9 @@ unsafe fn from_raw_env(env: RawEnv) -> Env {
         context: env.context.map(|ptr| ptr.cast()),
         unit: env.unit.map(|ptr| ptr.cast()),
         diagnostics: env.diagnostics.map(|ptr| ptr.cast()),
+        call_depth: env.limited.then_some(CallDepth {
+            depth: env.call_depth,
+            max: env.max_call_depth,
+        }),
     }
 }
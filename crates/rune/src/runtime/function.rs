@@ -861,6 +861,7 @@ impl FnOffset {
         let mut vm = Vm::new(self.context.clone(), self.unit.clone());
 
         vm.set_ip(self.offset);
+        vm.inherit_env_call()?;
         let _guard = unsafe { args.guarded_into_stack(vm.stack_mut())? };
         extra.into_stack(vm.stack_mut())?;
 
@@ -59,8 +59,19 @@ pub(crate) use self::const_value::{
     ConstContext, ConstInstance, ConstValueKind, EmptyConstContext,
 };
 
+mod coverage;
+pub use self::coverage::Coverage;
+
+#[cfg(feature = "std")]
+mod profiler;
+#[cfg(feature = "std")]
+pub use self::profiler::{Profile, ProfileFrame, ProfileStack, Profiler};
+
 pub mod debug;
-pub use self::debug::{DebugInfo, DebugInst};
+pub use self::debug::{DebugInfo, DebugInst, DebugVariable};
+
+mod debugger;
+pub use self::debugger::{Breakpoint, DebugLocation, DebugOutcome, DebugStep, Debugger};
 
 mod env;
 
@@ -190,6 +201,7 @@ mod vec_tuple;
 pub use self::vec_tuple::VecTuple;
 
 mod vm;
+pub(crate) use self::vm::ArithmeticOps;
 use self::vm::CallResultOnly;
 pub use self::vm::{CallFrame, Isolated, Vm};
 
@@ -210,7 +222,7 @@ pub(crate) use self::vm_error::{VmErrorKind, VmIntegerRepr};
 
 mod vm_execution;
 pub(crate) use self::vm_execution::ExecutionState;
-pub use self::vm_execution::{VmExecution, VmOutcome, VmResume, VmSendExecution};
+pub use self::vm_execution::{VmDeadline, VmExecution, VmOutcome, VmResume, VmSendExecution};
 
 mod vm_halt;
 pub(crate) use self::vm_halt::{VmHalt, VmHaltInfo};
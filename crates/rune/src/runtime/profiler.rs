@@ -0,0 +1,380 @@
+//! An instrumenting profiler for virtual machines.
+
+use core::fmt;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap, Vec};
+use crate::sync::Arc;
+use crate::{Hash, ItemBuf};
+
+use super::{CallFrame, Unit};
+
+/// A single frame in a profiled stack.
+#[derive(Debug, TryClone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProfileFrame {
+    /// A function in a unit, identified by its path.
+    Function(ItemBuf),
+    /// A native function, identified by its hash.
+    Native(Hash),
+    /// Instructions which could not be attributed to a function, since the
+    /// unit they belong to lacks debug information.
+    Unknown,
+}
+
+/// The instruction count and time attributed to a single stack.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ProfileStack {
+    /// Indexes into [`Profile::frames`], starting with the outermost frame.
+    pub frames: Vec<usize>,
+    /// The number of instructions executed with this stack.
+    pub instructions: u64,
+    /// The time spent with this stack, excluding time spent in any callees.
+    pub time: Duration,
+}
+
+/// A snapshot of the data collected by a [`Profiler`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Profile {
+    /// Every frame which is referenced by a stack.
+    pub frames: Vec<ProfileFrame>,
+    /// Every stack which has been observed.
+    pub stacks: Vec<ProfileStack>,
+}
+
+/// An instrumenting profiler, attributing instruction counts and time to the
+/// stack of functions they were executed in.
+///
+/// Script functions are resolved through the [`DebugInfo`] of the unit being
+/// executed, and native functions called from scripts are attributed by their
+/// hash.
+///
+/// The profiler is installed in a virtual machine with [`Vm::set_profiler`],
+/// and is shared with any virtual machines constructed to run async
+/// functions, generators and streams. Time is attributed by observing the
+/// clock between instructions, so a profiler should only be shared by virtual
+/// machines running on the same thread. Time spent while an execution is
+/// suspended is attributed to the stack which suspended it.
+///
+/// [`DebugInfo`]: super::DebugInfo
+/// [`Vm::set_profiler`]: super::Vm::set_profiler
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::{ProfileFrame, Profiler};
+/// use rune::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn fib(n) {
+///             if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+///         }
+///
+///         pub fn main() {
+///             fib(10)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let profiler = Arc::try_new(Profiler::new())?;
+///
+/// let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+/// vm.set_profiler(Some(profiler.clone()));
+/// vm.call(["main"], ())?;
+///
+/// let profile = profiler.profile()?;
+///
+/// let fib = profile
+///     .frames
+///     .iter()
+///     .position(|f| matches!(f, ProfileFrame::Function(item) if *item == *rune::item!(fib)))
+///     .expect("missing fib");
+///
+/// let instructions = profile
+///     .stacks
+///     .iter()
+///     .filter(|s| s.frames.last() == Some(&fib))
+///     .map(|s| s.instructions)
+///     .sum::<u64>();
+///
+/// assert!(instructions > 0);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+pub struct Profiler {
+    state: Mutex<State>,
+}
+
+impl Profiler {
+    /// Construct a new profiler.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                frames: Vec::new(),
+                units: Vec::new(),
+                natives: HashMap::new(),
+                children: HashMap::new(),
+                nodes: Vec::new(),
+                current: None,
+                last: Instant::now(),
+                native: Vec::new(),
+            }),
+        }
+    }
+
+    /// Take a snapshot of the data collected by the profiler.
+    pub fn profile(&self) -> alloc::Result<Profile> {
+        let state = self.lock();
+
+        let mut stacks = Vec::try_with_capacity(state.nodes.len())?;
+
+        for node in &state.nodes {
+            let mut frames = Vec::new();
+            let mut current = Some(node);
+
+            while let Some(node) = current {
+                frames.try_push(node.frame)?;
+                current = node.parent.map(|parent| &state.nodes[parent]);
+            }
+
+            frames.reverse();
+
+            stacks.try_push(ProfileStack {
+                frames,
+                instructions: node.instructions,
+                time: node.time,
+            })?;
+        }
+
+        Ok(Profile {
+            frames: state.frames.try_clone()?,
+            stacks,
+        })
+    }
+
+    /// Resolve the stack of nodes for the given call frames, where `ip` is the
+    /// instruction pointer of the innermost frame.
+    ///
+    /// This is only needed when a virtual machine starts executing or has been
+    /// modified externally, after which the stack is maintained with
+    /// [`Profiler::call`].
+    pub(crate) fn stack(
+        &self,
+        unit: &Arc<Unit>,
+        ip: usize,
+        call_frames: &[CallFrame],
+        out: &mut Vec<usize>,
+    ) -> alloc::Result<()> {
+        let mut state = self.lock();
+        out.clear();
+
+        let mut parent = None;
+
+        for ip in call_frames.iter().map(|f| f.ip).chain([ip]) {
+            let frame = state.function(unit, ip)?;
+            let node = state.child(parent, frame)?;
+            out.try_push(node)?;
+            parent = Some(node);
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the node of a call to the function at `ip` in the given unit
+    /// from the given node.
+    pub(crate) fn call(&self, parent: usize, unit: &Arc<Unit>, ip: usize) -> alloc::Result<usize> {
+        let mut state = self.lock();
+        let frame = state.function(unit, ip)?;
+        state.child(Some(parent), frame)
+    }
+
+    /// Record that an instruction is about to be executed in the given node.
+    pub(crate) fn instruction(&self, node: usize) {
+        let mut state = self.lock();
+        state.switch(Some(node));
+        state.nodes[node].instructions += 1;
+    }
+
+    /// Record that a native function with the given hash is about to be
+    /// called from the current stack.
+    pub(crate) fn enter_native(&self, hash: Hash) -> alloc::Result<()> {
+        let mut state = self.lock();
+
+        let frame = match state.natives.get(&hash) {
+            Some(frame) => *frame,
+            None => {
+                let frame = state.frame(ProfileFrame::Native(hash))?;
+                state.natives.try_insert(hash, frame)?;
+                frame
+            }
+        };
+
+        let previous = state.current;
+        let node = state.child(previous, frame)?;
+        state.native.try_push(previous)?;
+        state.switch(Some(node));
+        Ok(())
+    }
+
+    /// Record that the last native function entered has returned.
+    pub(crate) fn exit_native(&self) {
+        let mut state = self.lock();
+
+        if let Some(previous) = state.native.pop() {
+            state.switch(previous);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for Profiler {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler").finish_non_exhaustive()
+    }
+}
+
+impl Default for Profiler {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A node in the call tree, identifying a unique stack by its parent.
+struct Node {
+    parent: Option<usize>,
+    frame: usize,
+    instructions: u64,
+    time: Duration,
+}
+
+/// Functions in a unit sorted by their entry offset.
+struct UnitFunctions {
+    unit: Arc<Unit>,
+    functions: Vec<(usize, usize)>,
+}
+
+struct State {
+    /// Interned frames.
+    frames: Vec<ProfileFrame>,
+    /// Functions of every unit which has been seen.
+    units: Vec<UnitFunctions>,
+    /// Frames for native functions.
+    natives: HashMap<Hash, usize>,
+    /// Nodes in the call tree indexed by their parent and frame.
+    children: HashMap<(Option<usize>, usize), usize>,
+    /// Nodes in the call tree.
+    nodes: Vec<Node>,
+    /// The node time is currently being attributed to.
+    current: Option<usize>,
+    /// When the current stack was entered.
+    last: Instant,
+    /// Stacks to restore when exiting native functions.
+    native: Vec<Option<usize>>,
+}
+
+impl State {
+    /// Get or insert the node for calling `frame` from `parent`.
+    fn child(&mut self, parent: Option<usize>, frame: usize) -> alloc::Result<usize> {
+        if let Some(node) = self.children.get(&(parent, frame)) {
+            return Ok(*node);
+        }
+
+        let node = self.nodes.len();
+
+        self.nodes.try_push(Node {
+            parent,
+            frame,
+            instructions: 0,
+            time: Duration::ZERO,
+        })?;
+
+        self.children.try_insert((parent, frame), node)?;
+        Ok(node)
+    }
+
+    /// Switch the node time is attributed to, attributing the time elapsed
+    /// since the last switch to the previous node.
+    fn switch(&mut self, id: Option<usize>) {
+        let now = Instant::now();
+
+        if let Some(current) = self.current {
+            let elapsed = now.saturating_duration_since(self.last);
+            self.nodes[current].time += elapsed;
+        }
+
+        self.current = id;
+        self.last = now;
+    }
+
+    /// Resolve the frame of the function containing `ip` in the given unit.
+    fn function(&mut self, unit: &Arc<Unit>, ip: usize) -> alloc::Result<usize> {
+        let index = match self.units.iter().position(|u| Arc::ptr_eq(&u.unit, unit)) {
+            Some(index) => index,
+            None => {
+                let functions = self.functions(unit)?;
+
+                self.units.try_push(UnitFunctions {
+                    unit: unit.clone(),
+                    functions,
+                })?;
+
+                self.units.len() - 1
+            }
+        };
+
+        let functions = &self.units[index].functions;
+        let n = functions.partition_point(|&(entry, _)| entry <= ip);
+
+        match n.checked_sub(1).and_then(|n| functions.get(n)) {
+            Some(&(_, frame)) => Ok(frame),
+            None => self.frame(ProfileFrame::Unknown),
+        }
+    }
+
+    /// Collect the functions of a unit sorted by entry offset.
+    fn functions(&mut self, unit: &Unit) -> alloc::Result<Vec<(usize, usize)>> {
+        let mut functions = Vec::new();
+
+        let Some(debug) = unit.debug_info() else {
+            return Ok(functions);
+        };
+
+        for (&entry, hash) in &debug.functions_rev {
+            let Some(signature) = debug.functions.get(hash) else {
+                continue;
+            };
+
+            let frame = self.frame(ProfileFrame::Function(signature.path.try_clone()?))?;
+            functions.try_push((entry, frame))?;
+        }
+
+        functions.sort_by_key(|&(entry, _)| entry);
+        Ok(functions)
+    }
+
+    /// Intern a frame.
+    fn frame(&mut self, frame: ProfileFrame) -> alloc::Result<usize> {
+        if let Some(index) = self.frames.iter().position(|f| *f == frame) {
+            return Ok(index);
+        }
+
+        self.frames.try_push(frame)?;
+        Ok(self.frames.len() - 1)
+    }
+}
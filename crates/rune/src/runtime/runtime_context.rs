@@ -22,6 +22,8 @@ pub struct RuntimeContext {
     constants: hash::Map<ConstValue>,
     /// Constant constructors.
     construct: hash::Map<ConstConstructImpl>,
+    /// Hashes of registered types.
+    types: hash::Set,
 }
 
 assert_impl!(RuntimeContext: Send + Sync);
@@ -31,11 +33,13 @@ impl RuntimeContext {
         functions: hash::Map<FunctionHandler>,
         constants: hash::Map<ConstValue>,
         construct: hash::Map<ConstConstructImpl>,
+        types: hash::Set,
     ) -> Self {
         Self {
             functions,
             constants,
             construct,
+            types,
         }
     }
 
@@ -56,6 +60,12 @@ impl RuntimeContext {
     pub(crate) fn construct(&self, hash: &Hash) -> Option<&ConstConstructImpl> {
         self.construct.get(hash)
     }
+
+    /// Iterate over the hashes of all registered types.
+    #[inline]
+    pub(crate) fn types(&self) -> impl Iterator<Item = Hash> + '_ {
+        self.types.iter().copied()
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
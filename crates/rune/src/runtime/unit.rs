@@ -3,8 +3,11 @@
 //! A unit consists of a sequence of instructions, and lookaside tables for
 //! metadata like function locations.
 
+#[cfg(feature = "byte-code")]
+mod artifact;
 #[cfg(feature = "byte-code")]
 mod byte_code;
+mod link;
 mod storage;
 
 use core::fmt;
@@ -26,9 +29,12 @@ use crate::runtime::{Address, Call, ConstValue, DebugInfo, Inst, Rtti, StaticStr
 use crate::sync::Arc;
 use crate::Hash;
 
+pub use self::link::{LinkError, Unresolved, UnresolvedKind};
 pub use self::storage::{ArrayUnit, EncodeError, UnitEncoder, UnitStorage};
 pub(crate) use self::storage::{BadInstruction, BadJump};
 
+#[cfg(feature = "byte-code")]
+pub use self::artifact::ArtifactError;
 #[cfg(feature = "byte-code")]
 pub use self::byte_code::ByteCodeUnit;
 
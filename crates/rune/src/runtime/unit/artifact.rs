@@ -0,0 +1,325 @@
+//! Ahead-of-time compiled unit artifacts.
+//!
+//! An artifact starts with a fixed header, followed by the unit encoded with
+//! `musli` storage. All integers in the header are little endian:
+//!
+//! ```text
+//! magic:      b"RUNEUNIT"
+//! format:     u32
+//! flags:      u32
+//! version:    u16 length followed by the version of rune which wrote it
+//! checksum:   u64 FNV-1a hash of the payload
+//! length:     u64 length of the payload
+//! payload:    [u8; length]
+//! ```
+
+use core::fmt;
+
+use musli::storage;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, String, Vec};
+use crate::runtime::RuntimeContext;
+use crate::Hash;
+
+use super::{Unit, UnresolvedKind};
+
+/// Magic bytes identifying a unit artifact.
+const MAGIC: [u8; 8] = *b"RUNEUNIT";
+/// The current version of the artifact format.
+const FORMAT: u32 = 1;
+/// The version of rune which is writing the artifact.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The artifact was written without debug information.
+const FLAG_STRIPPED: u32 = 1 << 0;
+/// The artifact was written with byte code storage.
+const FLAG_BYTE_CODE: u32 = 1 << 1;
+
+#[cfg(not(rune_byte_code))]
+const FLAG_STORAGE: u32 = 0;
+#[cfg(rune_byte_code)]
+const FLAG_STORAGE: u32 = FLAG_BYTE_CODE;
+
+impl Unit {
+    /// Encode the unit into a versioned and checksummed artifact, which can
+    /// later be loaded with [`Unit::decode_artifact`].
+    ///
+    /// If `strip_debug` is set, debug information is left out of the artifact.
+    /// This makes it smaller, but errors raised while executing it can't be
+    /// mapped back to the sources the unit was built from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Unit, Vm};
+    /// use rune::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = context.runtime()?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let values = [1, 2, 3];
+    ///             values[0] + values[1] + values[2]
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let bytes = unit.encode_artifact(true)?;
+    ///
+    /// let unit = Unit::decode_artifact(&bytes, &runtime)?;
+    /// assert!(unit.debug_info().is_none());
+    ///
+    /// let mut vm = Vm::new(Arc::try_new(runtime)?, Arc::try_new(unit)?);
+    /// let output: i64 = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, 6);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn encode_artifact(&self, strip_debug: bool) -> Result<Vec<u8>, ArtifactError> {
+        let mut payload = Vec::new();
+
+        if strip_debug {
+            let unit = Unit::from_parts(self.logic.try_clone()?, None)?;
+            storage::to_writer(&mut payload, &unit)?;
+        } else {
+            storage::to_writer(&mut payload, self)?;
+        }
+
+        let mut flags = FLAG_STORAGE;
+
+        if strip_debug {
+            flags |= FLAG_STRIPPED;
+        }
+
+        let mut out = Vec::try_with_capacity(payload.len().saturating_add(64))?;
+        out.try_extend_from_slice(&MAGIC)?;
+        out.try_extend_from_slice(&FORMAT.to_le_bytes())?;
+        out.try_extend_from_slice(&flags.to_le_bytes())?;
+        out.try_extend_from_slice(&(VERSION.len() as u16).to_le_bytes())?;
+        out.try_extend_from_slice(VERSION.as_bytes())?;
+        out.try_extend_from_slice(&checksum(&payload).to_le_bytes())?;
+        out.try_extend_from_slice(&(payload.len() as u64).to_le_bytes())?;
+        out.try_extend_from_slice(&payload)?;
+        Ok(out)
+    }
+
+    /// Decode a unit from an artifact written by [`Unit::encode_artifact`],
+    /// and link it against the given runtime context.
+    ///
+    /// The artifact must have been written by the same version of rune, and
+    /// every native function the unit calls must be present in `context`. See
+    /// [`Unit::link`] for a more thorough check.
+    pub fn decode_artifact(bytes: &[u8], context: &RuntimeContext) -> Result<Unit, ArtifactError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ArtifactError::new(ArtifactErrorKind::BadMagic));
+        }
+
+        let format = reader.u32()?;
+
+        if format != FORMAT {
+            return Err(ArtifactError::new(ArtifactErrorKind::UnsupportedFormat {
+                format,
+            }));
+        }
+
+        let flags = reader.u32()?;
+
+        let version_len = usize::from(reader.u16()?);
+        let version = reader.take(version_len)?;
+
+        if version != VERSION.as_bytes() {
+            let version = core::str::from_utf8(version).unwrap_or("?");
+
+            return Err(ArtifactError::new(ArtifactErrorKind::VersionMismatch {
+                version: version.try_to_owned()?,
+            }));
+        }
+
+        if flags & FLAG_BYTE_CODE != FLAG_STORAGE {
+            return Err(ArtifactError::new(ArtifactErrorKind::StorageMismatch));
+        }
+
+        let expected = reader.u64()?;
+
+        let Ok(len) = usize::try_from(reader.u64()?) else {
+            return Err(ArtifactError::new(ArtifactErrorKind::Truncated));
+        };
+
+        let payload = reader.take(len)?;
+
+        if checksum(payload) != expected {
+            return Err(ArtifactError::new(ArtifactErrorKind::ChecksumMismatch));
+        }
+
+        let unit = storage::from_slice::<Unit>(payload)?;
+
+        let mut missing = Vec::new();
+
+        for unresolved in unit.unresolved(context)? {
+            let UnresolvedKind::Function { hash } = unresolved.kind else {
+                continue;
+            };
+
+            if !missing.contains(&hash) {
+                missing.try_push(hash)?;
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(ArtifactError::new(ArtifactErrorKind::MissingFunctions {
+                hashes: missing,
+            }));
+        }
+
+        Ok(unit)
+    }
+}
+
+/// Calculate the FNV-1a hash of the given bytes.
+fn checksum(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET;
+
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(PRIME);
+    }
+
+    hash
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ArtifactError> {
+        if self.bytes.len() < n {
+            return Err(ArtifactError::new(ArtifactErrorKind::Truncated));
+        }
+
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ArtifactError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u16(&mut self) -> Result<u16, ArtifactError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, ArtifactError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ArtifactError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+}
+
+/// Error raised when encoding or decoding a unit artifact.
+#[derive(Debug)]
+pub struct ArtifactError {
+    kind: ArtifactErrorKind,
+}
+
+impl ArtifactError {
+    #[inline]
+    fn new(kind: ArtifactErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// The hashes of native functions referenced by the unit which are missing
+    /// from the runtime context it was loaded against.
+    pub fn missing_functions(&self) -> &[Hash] {
+        match &self.kind {
+            ArtifactErrorKind::MissingFunctions { hashes } => hashes,
+            _ => &[],
+        }
+    }
+}
+
+impl From<storage::Error> for ArtifactError {
+    #[inline]
+    fn from(error: storage::Error) -> Self {
+        Self::new(ArtifactErrorKind::StorageError { error })
+    }
+}
+
+impl From<alloc::Error> for ArtifactError {
+    #[inline]
+    fn from(error: alloc::Error) -> Self {
+        Self::new(ArtifactErrorKind::AllocError { error })
+    }
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ArtifactErrorKind::BadMagic => write!(f, "Not a unit artifact"),
+            ArtifactErrorKind::UnsupportedFormat { format } => {
+                write!(f, "Unsupported artifact format {format}, expected {FORMAT}")
+            }
+            ArtifactErrorKind::VersionMismatch { version } => {
+                write!(
+                    f,
+                    "Artifact was built with rune {version}, but this is rune {VERSION}"
+                )
+            }
+            ArtifactErrorKind::StorageMismatch => {
+                write!(f, "Artifact was built with a different unit storage")
+            }
+            ArtifactErrorKind::Truncated => write!(f, "Artifact is truncated"),
+            ArtifactErrorKind::ChecksumMismatch => {
+                write!(f, "Artifact checksum does not match its contents")
+            }
+            ArtifactErrorKind::MissingFunctions { hashes } => {
+                write!(f, "Missing native functions:")?;
+
+                for hash in hashes {
+                    write!(f, " {hash}")?;
+                }
+
+                Ok(())
+            }
+            ArtifactErrorKind::StorageError { error } => error.fmt(f),
+            ArtifactErrorKind::AllocError { error } => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ArtifactError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            ArtifactErrorKind::StorageError { error } => Some(error),
+            ArtifactErrorKind::AllocError { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ArtifactErrorKind {
+    BadMagic,
+    UnsupportedFormat { format: u32 },
+    VersionMismatch { version: String },
+    StorageMismatch,
+    Truncated,
+    ChecksumMismatch,
+    MissingFunctions { hashes: Vec<Hash> },
+    StorageError { error: storage::Error },
+    AllocError { error: alloc::Error },
+}
@@ -0,0 +1,266 @@
+//! Validation of a unit against a runtime context.
+
+use core::fmt;
+
+use crate::alloc::{self, Vec};
+use crate::ast::Span;
+use crate::hash;
+use crate::runtime::{inst, Protocol, RuntimeContext};
+use crate::{Hash, SourceId};
+
+use super::{Unit, UnitStorage};
+
+impl<S> Unit<S>
+where
+    S: UnitStorage,
+{
+    /// Check that everything the unit refers to can be resolved in the given
+    /// runtime context, without executing it.
+    ///
+    /// This reports every call, function load and constructor in the unit
+    /// which would fail at runtime because the function or type it refers to
+    /// is missing, as well as every instance function or protocol call for
+    /// which no type in the unit or context provides an implementation. Which
+    /// type an instance function is called on is only known at runtime, so
+    /// those can only be reported if they are missing from every type.
+    ///
+    /// If the unit has debug information, each unresolved reference is mapped
+    /// to the span it originates from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module};
+    ///
+    /// let mut module = Module::new();
+    /// module.function("native", || 42i64).build()?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(module)?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             native()
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// assert!(unit.link(&context.runtime()?).is_ok());
+    ///
+    /// // A context which lacks the module the unit was compiled against.
+    /// let runtime = Context::with_default_modules()?.runtime()?;
+    /// let error = unit.link(&runtime).unwrap_err();
+    /// assert_eq!(error.unresolved().len(), 1);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn link(&self, context: &RuntimeContext) -> Result<(), LinkError> {
+        let unresolved = self.unresolved(context)?;
+
+        if unresolved.is_empty() {
+            return Ok(());
+        }
+
+        Err(LinkError::new(LinkErrorKind::Unresolved { unresolved }))
+    }
+
+    /// Collect every reference in the unit which can't be resolved in the
+    /// given runtime context.
+    pub(crate) fn unresolved(&self, context: &RuntimeContext) -> alloc::Result<Vec<Unresolved>> {
+        let mut unresolved = Vec::new();
+        let mut instance = hash::Map::<bool>::default();
+
+        for (ip, inst) in self.logic.storage.iter() {
+            let kind = match inst.kind {
+                inst::Kind::Call { hash, .. } | inst::Kind::LoadFn { hash, .. } => {
+                    if self.function(&hash).is_some() || context.function(&hash).is_some() {
+                        continue;
+                    }
+
+                    UnresolvedKind::Function { hash }
+                }
+                inst::Kind::Closure { hash, .. } => {
+                    if self.function(&hash).is_some() {
+                        continue;
+                    }
+
+                    UnresolvedKind::Function { hash }
+                }
+                inst::Kind::CallAssociated { hash, .. }
+                | inst::Kind::LoadInstanceFn { hash, .. } => {
+                    let resolved = match instance.get(&hash) {
+                        Some(resolved) => *resolved,
+                        None => {
+                            let resolved = self.has_instance_function(context, hash);
+                            instance.try_insert(hash, resolved)?;
+                            resolved
+                        }
+                    };
+
+                    if resolved {
+                        continue;
+                    }
+
+                    match Protocol::from_hash(hash) {
+                        Some(protocol) => UnresolvedKind::Protocol { protocol },
+                        None => UnresolvedKind::InstanceFunction { hash },
+                    }
+                }
+                inst::Kind::Struct { hash, .. } => {
+                    if self.lookup_rtti(&hash).is_some() {
+                        continue;
+                    }
+
+                    UnresolvedKind::Type { hash }
+                }
+                inst::Kind::ConstConstruct { hash, .. } => {
+                    if context.construct(&hash).is_some() {
+                        continue;
+                    }
+
+                    UnresolvedKind::Type { hash }
+                }
+                _ => continue,
+            };
+
+            let location = self
+                .debug_info()
+                .and_then(|debug| debug.instruction_at(ip))
+                .map(|inst| (inst.source_id, inst.span));
+
+            unresolved.try_push(Unresolved { ip, kind, location })?;
+        }
+
+        Ok(unresolved)
+    }
+
+    /// Test if any type known to the unit or the context has an instance
+    /// function with the given name hash.
+    fn has_instance_function(&self, context: &RuntimeContext, name: Hash) -> bool {
+        let mut types = self
+            .logic
+            .rtti
+            .values()
+            .map(|rtti| rtti.hash)
+            .chain(context.types());
+
+        types.any(|ty| {
+            let hash = Hash::associated_function(ty, name);
+            self.function(&hash).is_some() || context.function(&hash).is_some()
+        })
+    }
+}
+
+/// A reference in a unit which could not be resolved.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Unresolved {
+    /// The instruction pointer of the instruction holding the reference.
+    pub ip: usize,
+    /// What could not be resolved.
+    pub kind: UnresolvedKind,
+    /// The source and span the instruction originates from, if the unit has
+    /// debug information.
+    pub location: Option<(SourceId, Span)>,
+}
+
+/// The kind of an [`Unresolved`] reference.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnresolvedKind {
+    /// A function which is neither in the unit nor in the context.
+    Function {
+        /// The hash of the function.
+        hash: Hash,
+    },
+    /// An instance function which no known type implements.
+    InstanceFunction {
+        /// The name hash of the instance function.
+        hash: Hash,
+    },
+    /// A protocol which no known type implements.
+    Protocol {
+        /// The protocol.
+        protocol: Protocol,
+    },
+    /// A type which can't be constructed.
+    Type {
+        /// The hash of the type.
+        hash: Hash,
+    },
+}
+
+impl fmt::Display for UnresolvedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnresolvedKind::Function { hash } => write!(f, "Missing function with hash {hash}"),
+            UnresolvedKind::InstanceFunction { hash } => {
+                write!(f, "Missing instance function with hash {hash}")
+            }
+            UnresolvedKind::Protocol { protocol } => write!(f, "Missing protocol {protocol}"),
+            UnresolvedKind::Type { hash } => write!(f, "Missing type with hash {hash}"),
+        }
+    }
+}
+
+/// Error raised by [`Unit::link`].
+#[derive(Debug)]
+pub struct LinkError {
+    kind: LinkErrorKind,
+}
+
+impl LinkError {
+    #[inline]
+    fn new(kind: LinkErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Every reference which could not be resolved, in instruction order.
+    pub fn unresolved(&self) -> &[Unresolved] {
+        match &self.kind {
+            LinkErrorKind::Unresolved { unresolved } => unresolved,
+            _ => &[],
+        }
+    }
+}
+
+impl From<alloc::Error> for LinkError {
+    #[inline]
+    fn from(error: alloc::Error) -> Self {
+        Self::new(LinkErrorKind::AllocError { error })
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LinkErrorKind::Unresolved { unresolved } => {
+                write!(f, "{} unresolved references in unit", unresolved.len())?;
+
+                for u in unresolved {
+                    write!(f, "\n  {} at instruction {}", u.kind, u.ip)?;
+                }
+
+                Ok(())
+            }
+            LinkErrorKind::AllocError { error } => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for LinkError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            LinkErrorKind::AllocError { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum LinkErrorKind {
+    Unresolved { unresolved: Vec<Unresolved> },
+    AllocError { error: alloc::Error },
+}
@@ -1344,6 +1344,14 @@ impl Value {
                 }));
             }
             (Repr::Dynamic(lhs), Repr::Dynamic(rhs)) => {
+                // Script types implementing the protocol through a trait take
+                // precedence over structural comparison.
+                if let CallResultOnly::Ok(value) =
+                    caller.try_call_protocol_fn(protocol, self.clone(), &mut Some((b.clone(),)))?
+                {
+                    return Ok(T::from_value(value)?);
+                }
+
                 let lhs_rtti = lhs.rtti();
                 let rhs_rtti = rhs.rtti();
 
@@ -1504,6 +1512,13 @@ impl Value {
         Ok(FromValue::from_value(value)?)
     }
 
+    pub(crate) fn protocol_nth(&self, n: usize) -> Result<Option<Value>, VmError> {
+        let value =
+            EnvProtocolCaller.call_protocol_fn(&Protocol::NTH, self.clone(), &mut Some((n,)))?;
+
+        Ok(FromValue::from_value(value)?)
+    }
+
     pub(crate) fn protocol_nth_back(&self, n: usize) -> Result<Option<Value>, VmError> {
         let value = EnvProtocolCaller.call_protocol_fn(
             &Protocol::NTH_BACK,
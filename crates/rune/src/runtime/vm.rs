@@ -11,19 +11,24 @@ use crate::runtime;
 use crate::sync::Arc;
 
 mod ops;
+pub(crate) use self::ops::ArithmeticOps;
 use self::ops::*;
 
 use super::{
-    budget, inst, Address, AnySequence, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow,
-    DynArgs, DynGuardedArgs, Format, FormatSpec, Formatter, FromValue, Function, Future, Generator,
-    GeneratorState, GuardedArgs, Inline, InstArithmeticOp, InstBitwiseOp, InstOp, InstRange,
-    InstShiftOp, InstTarget, InstValue, Object, Output, OwnedTuple, Pair, Panic, Protocol,
-    ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Repr,
-    RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type, TypeHash, TypeInfo,
-    TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics, VmDiagnosticsObj, VmError,
-    VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmOutcome, VmSendExecution,
+    budget, coverage, inst, Address, AnySequence, Args, Awaited, BorrowMut, Bytes, Call,
+    ControlFlow, Coverage, DynArgs, DynGuardedArgs, Format, FormatSpec, Formatter, FromValue,
+    Function, Future, Generator, GeneratorState, GuardedArgs, Inline, InstArithmeticOp,
+    InstBitwiseOp, InstOp, InstRange, InstShiftOp, InstTarget, InstValue, Object, Output,
+    OwnedTuple, Pair, Panic, Protocol, ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive,
+    RangeTo, RangeToInclusive, Repr, RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream,
+    Type, TypeHash, TypeInfo, TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics,
+    VmDiagnosticsObj, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmOutcome,
+    VmSendExecution,
 };
 
+#[cfg(feature = "std")]
+use super::Profiler;
+
 /// Helper to take a value, replacing the old one with empty.
 #[inline(always)]
 fn take(value: &mut Value) -> Value {
@@ -113,6 +118,20 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: alloc::Vec<CallFrame>,
+    /// The maximum number of call frames permitted.
+    max_call_depth: Option<usize>,
+    /// The number of calls active in the virtual machines this one was called
+    /// from, which count towards the maximum call depth.
+    depth: usize,
+    /// Coverage to record executed instructions into.
+    coverage: Option<Arc<Coverage>>,
+    /// Profiler to record executed instructions into.
+    #[cfg(feature = "std")]
+    profiler: Option<Arc<Profiler>>,
+    /// Profiler nodes corresponding to each call frame, followed by the node
+    /// of the current function. Empty if it needs to be rebuilt.
+    #[cfg(feature = "std")]
+    profile_stack: alloc::Vec<usize>,
 }
 
 impl Vm {
@@ -138,6 +157,13 @@ impl Vm {
             last_ip_len: 0,
             stack,
             call_frames: alloc::Vec::new(),
+            max_call_depth: None,
+            depth: 0,
+            coverage: None,
+            #[cfg(feature = "std")]
+            profiler: None,
+            #[cfg(feature = "std")]
+            profile_stack: alloc::Vec::new(),
         }
     }
 
@@ -167,6 +193,195 @@ impl Vm {
     #[inline]
     pub fn set_ip(&mut self, ip: usize) {
         self.ip = ip;
+        self.reset_profile_stack();
+    }
+
+    /// Set the maximum call depth of the virtual machine.
+    ///
+    /// Calling a function which would cause the number of call frames to
+    /// exceed this limit results in a stack overflow error. Passing `None`
+    /// removes the limit, which is the default.
+    ///
+    /// The limit is inherited by virtual machines running functions called
+    /// from this one, such as async functions and generators, and the calls
+    /// active in this virtual machine count towards their limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn recurse(n) { recurse(n + 1) }
+    ///         pub fn main() { recurse(0) }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    /// vm.set_max_call_depth(Some(64));
+    ///
+    /// let error = vm.call(["main"], ()).unwrap_err();
+    /// assert!(error.to_string().contains("Stack overflow"));
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_max_call_depth(&mut self, max_call_depth: Option<usize>) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    /// Get the maximum call depth of the virtual machine.
+    #[inline]
+    pub fn max_call_depth(&self) -> Option<usize> {
+        self.max_call_depth
+    }
+
+    /// Set the coverage executed instructions are recorded into.
+    ///
+    /// Only instructions in units tracked by the coverage are recorded. The
+    /// coverage is shared with any virtual machines constructed to run async
+    /// functions, generators and streams called from this one. See
+    /// [`Coverage`] for an example.
+    pub fn set_coverage(&mut self, coverage: Option<Arc<Coverage>>) {
+        self.coverage = coverage;
+    }
+
+    /// Get the coverage executed instructions are recorded into, if any.
+    #[inline]
+    pub fn coverage(&self) -> Option<&Arc<Coverage>> {
+        self.coverage.as_ref()
+    }
+
+    /// Set the profiler executed instructions and native function calls are
+    /// recorded into.
+    ///
+    /// Like coverage, the profiler is shared with any virtual machines
+    /// constructed to run async functions, generators and streams called from
+    /// this one. See [`Profiler`] for an example.
+    #[cfg(feature = "std")]
+    pub fn set_profiler(&mut self, profiler: Option<Arc<Profiler>>) {
+        self.profiler = profiler;
+        self.profile_stack.clear();
+    }
+
+    /// Get the profiler executed instructions are recorded into, if any.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn profiler(&self) -> Option<&Arc<Profiler>> {
+        self.profiler.as_ref()
+    }
+
+    /// Share the call depth limit, coverage and profiler of `parent` with this
+    /// virtual machine.
+    pub(crate) fn inherit(&mut self, parent: &Vm) {
+        self.max_call_depth = parent.max_call_depth;
+        self.depth = parent.depth;
+        self.coverage = parent.coverage.clone();
+
+        #[cfg(feature = "std")]
+        {
+            self.profiler = parent.profiler.clone();
+        }
+    }
+
+    /// Inherit from `parent` for a virtual machine which executes a function
+    /// called from it, in which case the call counts towards the call depth.
+    pub(crate) fn inherit_call(&mut self, parent: &Vm) {
+        self.inherit(parent);
+        self.depth = parent.call_depth().wrapping_add(1);
+    }
+
+    /// Inherit the call depth limit of the virtual machine calling the current
+    /// native function, if any, for a virtual machine which executes a
+    /// function called from it.
+    pub(crate) fn inherit_env_call(&mut self) -> Result<(), VmErrorKind> {
+        let Some(call_depth) = runtime::env::call_depth() else {
+            return Ok(());
+        };
+
+        self.max_call_depth = Some(call_depth.max);
+        self.depth = call_depth.depth;
+        self.check_call_depth(self.ip)?;
+        self.depth = call_depth.depth.wrapping_add(1);
+        Ok(())
+    }
+
+    /// The number of active calls, including the ones in the virtual machines
+    /// this one was called from.
+    #[inline]
+    fn call_depth(&self) -> usize {
+        self.depth.wrapping_add(self.call_frames.len())
+    }
+
+    /// Check that calling the function at `ip` does not exceed the maximum
+    /// call depth.
+    fn check_call_depth(&self, ip: usize) -> Result<(), VmErrorKind> {
+        let Some(max_call_depth) = self.max_call_depth else {
+            return Ok(());
+        };
+
+        if self.call_depth() < max_call_depth {
+            return Ok(());
+        }
+
+        let item = match self.unit.debug_info().and_then(|d| d.function_at(ip)) {
+            Some((_, signature)) => Some(signature.path.try_clone()?),
+            None => None,
+        };
+
+        Err(VmErrorKind::StackOverflow {
+            max_call_depth,
+            item,
+        })
+    }
+
+    /// Mark the stack of profiler nodes as needing to be rebuilt.
+    #[inline(always)]
+    fn reset_profile_stack(&mut self) {
+        #[cfg(feature = "std")]
+        self.profile_stack.clear();
+    }
+
+    /// Notify the profiler, if any, that a native function is being called.
+    ///
+    /// This also registers the call depth in the environment, so that any
+    /// functions called by the native function are subject to the same limit.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn enter_native(&self, hash: Hash) -> alloc::Result<()> {
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &self.profiler {
+            profiler.enter_native(hash)?;
+        }
+
+        if let Some(max) = self.max_call_depth {
+            runtime::env::set_call_depth(Some(runtime::env::CallDepth {
+                depth: self.call_depth(),
+                max,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Notify the profiler, if any, that a native function has returned.
+    #[inline(always)]
+    fn exit_native(&self) {
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &self.profiler {
+            profiler.exit_native();
+        }
+
+        if self.max_call_depth.is_some() {
+            runtime::env::set_call_depth(None);
+        }
     }
 
     /// Get the stack.
@@ -225,6 +440,7 @@ impl Vm {
     /// [`new`]: Vm::new
     #[inline]
     pub fn unit_mut(&mut self) -> &mut Arc<Unit> {
+        self.reset_profile_stack();
         &mut self.unit
     }
 
@@ -240,6 +456,56 @@ impl Vm {
         self.ip
     }
 
+    /// Access the local variables of the current call frame by name.
+    ///
+    /// Variables are resolved through the debug information of the unit, so
+    /// nothing is returned if it's not available. Shadowed variables and
+    /// variables whose scope has ended are omitted.
+    pub fn locals(&self) -> alloc::Result<alloc::Vec<(&str, &Value)>> {
+        let mut locals = alloc::Vec::new();
+
+        let Some(debug) = self.unit.debug_info() else {
+            return Ok(locals);
+        };
+
+        let start = debug
+            .functions_rev
+            .keys()
+            .copied()
+            .filter(|&ip| ip <= self.ip)
+            .max()
+            .unwrap_or_default();
+
+        let mut instructions = alloc::Vec::new();
+
+        for (&ip, inst) in &debug.instructions {
+            if (start..=self.ip).contains(&ip)
+                && (!inst.variables.is_empty() || !inst.freed.is_empty())
+            {
+                instructions.try_push((ip, inst))?;
+            }
+        }
+
+        instructions.sort_by_key(|&(ip, _)| ip);
+
+        let mut variables = alloc::Vec::<(&str, Address)>::new();
+
+        for (_, inst) in instructions {
+            for var in &inst.variables {
+                variables.retain(|&(name, addr)| name != &*var.name && addr != var.addr);
+                variables.try_push((&var.name, var.addr))?;
+            }
+
+            variables.retain(|(_, addr)| !inst.freed.contains(addr));
+        }
+
+        for (name, addr) in variables {
+            locals.try_push((name, self.stack.at(addr)))?;
+        }
+
+        Ok(locals)
+    }
+
     /// Access the last instruction that was executed.
     #[inline]
     pub fn last_ip(&self) -> usize {
@@ -251,6 +517,7 @@ impl Vm {
         self.ip = 0;
         self.stack.clear();
         self.call_frames.clear();
+        self.reset_profile_stack();
     }
 
     /// Look up a function in the virtual machine by its name.
@@ -535,6 +802,7 @@ impl Vm {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
+        self.reset_profile_stack();
         Ok(())
     }
 
@@ -611,7 +879,9 @@ impl Vm {
             self.stack.push(target)?;
             args.push_to_stack(&mut self.stack)?;
 
+            self.enter_native(hash)?;
             let result = handler.call(&mut self.stack, addr, count, out);
+            self.exit_native();
             self.stack.truncate(addr);
             result?;
             return Ok(CallResult::Ok(()));
@@ -688,6 +958,8 @@ impl Vm {
     ) -> Result<(), VmErrorKind> {
         tracing::trace!("pushing call frame");
 
+        self.check_call_depth(ip)?;
+
         let top = self.stack.swap_top(addr, args)?;
         let ip = replace(&mut self.ip, ip);
 
@@ -699,6 +971,13 @@ impl Vm {
         };
 
         self.call_frames.try_push(frame)?;
+
+        #[cfg(feature = "std")]
+        if let (Some(profiler), Some(&parent)) = (&self.profiler, self.profile_stack.last()) {
+            let node = profiler.call(parent, &self.unit, self.ip)?;
+            self.profile_stack.try_push(node)?;
+        }
+
         Ok(())
     }
 
@@ -710,6 +989,10 @@ impl Vm {
         tracing::trace!("popping call frame from call");
         let frame = self.call_frames.pop()?;
         tracing::trace!(?frame);
+
+        #[cfg(feature = "std")]
+        self.profile_stack.pop();
+
         self.stack.pop_stack_top(frame.top);
         Some(replace(&mut self.ip, frame.ip))
     }
@@ -721,10 +1004,15 @@ impl Vm {
 
         let Some(frame) = self.call_frames.pop() else {
             self.stack.pop_stack_top(0);
+            self.reset_profile_stack();
             return (Isolated::Isolated, None);
         };
 
         tracing::trace!(?frame);
+
+        #[cfg(feature = "std")]
+        self.profile_stack.pop();
+
         self.stack.pop_stack_top(frame.top);
         self.ip = frame.ip;
         (frame.isolated, Some(frame.out))
@@ -775,7 +1063,7 @@ impl Vm {
                     None => Err(data.type_info()),
                 }
             }
-            Repr::Dynamic(data) => Err(data.type_info()),
+            Repr::Dynamic(..) => return Ok(None),
             Repr::Any(target) => match target.type_hash() {
                 Result::<Value, Value>::HASH => {
                     match (index, &*target.borrow_ref::<Result<Value, Value>>()?) {
@@ -1027,12 +1315,15 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmErrorKind> {
+        self.check_call_depth(offset)?;
+
         let values = self.stack.slice_at_mut(addr, args)?;
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            vm.inherit_call(self);
             *self.stack.at_mut(at)? = Value::try_from(Generator::new(vm))?;
         } else {
             values.iter_mut().for_each(consume);
@@ -1049,12 +1340,15 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmErrorKind> {
+        self.check_call_depth(offset)?;
+
         let values = self.stack.slice_at_mut(addr, args)?;
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            vm.inherit_call(self);
             *self.stack.at_mut(at)? = Value::try_from(Stream::new(vm))?;
         } else {
             values.iter_mut().for_each(consume);
@@ -1071,12 +1365,15 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmErrorKind> {
+        self.check_call_depth(offset)?;
+
         let values = self.stack.slice_at_mut(addr, args)?;
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            vm.inherit_call(self);
             let mut execution = vm.into_execution();
             let future = Future::new(async move { execution.resume().await?.into_complete() })?;
             *self.stack.at_mut(at)? = Value::try_from(future)?;
@@ -1392,8 +1689,7 @@ impl Vm {
         'fallback: {
             let store = match operand.as_ref() {
                 Repr::Inline(inline) => op(inline),
-                Repr::Any(..) => break 'fallback,
-                _ => None,
+                Repr::Dynamic(..) | Repr::Any(..) => break 'fallback,
             };
 
             let Some(store) = store else {
@@ -1541,7 +1837,7 @@ impl Vm {
                         }));
                     }
                 },
-                (Repr::Any(..), ..) => {
+                (Repr::Dynamic(..) | Repr::Any(..), ..) => {
                     break 'fallback;
                 }
                 (lhs, rhs) => {
@@ -1604,7 +1900,7 @@ impl Vm {
                     let value = (ops.bool)(*lhs, *rhs);
                     Inline::Bool(value)
                 }
-                (Repr::Any(_), _) => {
+                (Repr::Dynamic(..) | Repr::Any(..), _) => {
                     break 'fallback;
                 }
                 (lhs, rhs) => {
@@ -1662,7 +1958,9 @@ impl Vm {
                             let value = (ops.i64)(*value, shift).ok_or_else(ops.error)?;
                             Inline::Signed(value)
                         }
-                        Repr::Any(..) => break 'fallback (value.clone(), value.clone()),
+                        Repr::Dynamic(..) | Repr::Any(..) => {
+                            break 'fallback (value.clone(), value.clone())
+                        }
                         value => {
                             return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                                 op: ops.protocol.name,
@@ -1682,7 +1980,7 @@ impl Vm {
                             let value = (ops.i64)(*lhs, rhs).ok_or_else(ops.error)?;
                             Inline::Signed(value)
                         }
-                        (Repr::Any(..), _) => {
+                        (Repr::Dynamic(..) | Repr::Any(..), _) => {
                             break 'fallback (lhs.clone(), rhs.clone());
                         }
                         (lhs, rhs) => {
@@ -1741,7 +2039,9 @@ impl Vm {
                     *value = out;
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Dynamic(..) | Repr::Any(..) => {
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1768,7 +2068,9 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (Repr::Any(..), _) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Dynamic(..) | Repr::Any(..), _) => {
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1809,7 +2111,9 @@ impl Vm {
                     (ops.bool)(value, rhs);
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Dynamic(..) | Repr::Any(..) => {
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1833,7 +2137,9 @@ impl Vm {
                     (ops.bool)(lhs, *rhs);
                     return Ok(());
                 }
-                (Repr::Any(..), ..) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Dynamic(..) | Repr::Any(..), ..) => {
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1871,7 +2177,9 @@ impl Vm {
                     *value = out;
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Dynamic(..) | Repr::Any(..) => {
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1893,7 +2201,9 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (Repr::Any(..), _) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Dynamic(..) | Repr::Any(..), _) => {
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -2724,7 +3034,10 @@ impl Vm {
                 return Err(VmError::new(VmErrorKind::MissingFunction { hash }));
             };
 
-            handler.call(&mut self.stack, addr, args, out)?;
+            self.enter_native(hash)?;
+            let result = handler.call(&mut self.stack, addr, args, out);
+            self.exit_native();
+            result?;
             return Ok(());
         };
 
@@ -2796,7 +3109,10 @@ impl Vm {
 
         if let Some(handler) = self.context.function(&hash) {
             self.called_function_hook(hash)?;
-            handler.call(&mut self.stack, addr, args, out)?;
+            self.enter_native(hash)?;
+            let result = handler.call(&mut self.stack, addr, args, out);
+            self.exit_native();
+            result?;
             return Ok(());
         }
 
@@ -2922,6 +3238,14 @@ impl Vm {
 
         let mut budget = budget::acquire();
 
+        // NB: the unit can't change while running, so the counters to record
+        // coverage into are only looked up once.
+        let shared = self.coverage.clone();
+        let counters = shared.as_deref().and_then(|c| c.counters(&self.unit));
+
+        #[cfg(feature = "std")]
+        let profiler = self.profiler.clone();
+
         loop {
             if !budget.take() {
                 return Ok(VmHalt::Limited);
@@ -2934,6 +3258,26 @@ impl Vm {
                 }));
             };
 
+            if let Some(counters) = counters {
+                coverage::record(counters, self.ip);
+            }
+
+            #[cfg(feature = "std")]
+            if let Some(profiler) = &profiler {
+                if self.profile_stack.is_empty() {
+                    profiler.stack(
+                        &self.unit,
+                        self.ip,
+                        &self.call_frames,
+                        &mut self.profile_stack,
+                    )?;
+                }
+
+                if let Some(&node) = self.profile_stack.last() {
+                    profiler.instruction(node);
+                }
+            }
+
             tracing::trace!(ip = ?self.ip, ?inst);
 
             self.ip = self.ip.wrapping_add(inst_len);
@@ -3227,6 +3571,13 @@ impl TryClone for Vm {
             last_ip_len: self.last_ip_len,
             stack: self.stack.try_clone()?,
             call_frames: self.call_frames.try_clone()?,
+            max_call_depth: self.max_call_depth,
+            depth: self.depth,
+            coverage: self.coverage.clone(),
+            #[cfg(feature = "std")]
+            profiler: self.profiler.clone(),
+            #[cfg(feature = "std")]
+            profile_stack: alloc::Vec::new(),
         })
     }
 }
@@ -4,16 +4,16 @@ use core::ops::{
 
 use crate::runtime::{InstArithmeticOp, InstBitwiseOp, InstShiftOp, Protocol, VmErrorKind};
 
-pub(super) struct ArithmeticOps {
+pub(crate) struct ArithmeticOps {
     pub(super) protocol: Protocol,
     pub(super) error: fn() -> VmErrorKind,
-    pub(super) i64: fn(i64, i64) -> Option<i64>,
-    pub(super) u64: fn(u64, u64) -> Option<u64>,
-    pub(super) f64: fn(f64, f64) -> f64,
+    pub(crate) i64: fn(i64, i64) -> Option<i64>,
+    pub(crate) u64: fn(u64, u64) -> Option<u64>,
+    pub(crate) f64: fn(f64, f64) -> f64,
 }
 
 impl ArithmeticOps {
-    pub(super) fn from_op(op: InstArithmeticOp) -> &'static Self {
+    pub(crate) fn from_op(op: InstArithmeticOp) -> &'static Self {
         match op {
             InstArithmeticOp::Add => &Self {
                 protocol: Protocol::ADD,
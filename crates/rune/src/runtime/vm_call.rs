@@ -89,8 +89,9 @@ impl VmCall {
         let context = self.context.unwrap_or_else(|| vm.context().clone());
         let unit = self.unit.unwrap_or_else(|| vm.unit().clone());
 
-        let mut vm = Vm::with_stack(context, unit, new_stack);
-        vm.set_ip(ip);
-        Ok(vm)
+        let mut head = Vm::with_stack(context, unit, new_stack);
+        head.set_ip(ip);
+        head.inherit_call(vm);
+        Ok(head)
     }
 }
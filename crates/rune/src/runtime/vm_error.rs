@@ -477,6 +477,10 @@ pub(crate) enum VmErrorKind {
     MissingEntryHash {
         hash: Hash,
     },
+    StackOverflow {
+        max_call_depth: usize,
+        item: Option<ItemBuf>,
+    },
     MissingFunction {
         hash: Hash,
     },
@@ -705,6 +709,24 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::MissingEntryHash { hash } => {
                 write!(f, "Missing entry with hash `{hash}`")
             }
+            VmErrorKind::StackOverflow {
+                max_call_depth,
+                item: Some(item),
+            } => {
+                write!(
+                    f,
+                    "Stack overflow calling `{item}`, maximum call depth of {max_call_depth} exceeded"
+                )
+            }
+            VmErrorKind::StackOverflow {
+                max_call_depth,
+                item: None,
+            } => {
+                write!(
+                    f,
+                    "Stack overflow, maximum call depth of {max_call_depth} exceeded"
+                )
+            }
             VmErrorKind::MissingFunction { hash } => {
                 write!(f, "Missing function with hash `{hash}`")
             }
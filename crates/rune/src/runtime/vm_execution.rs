@@ -4,6 +4,8 @@ use core::mem::{replace, take};
 use core::pin::{pin, Pin};
 use core::task::{ready, Context, Poll, RawWaker, RawWakerVTable, Waker};
 
+use pin_project::pin_project;
+
 use crate::alloc::prelude::*;
 use crate::async_vm_try;
 use crate::runtime::budget::Budget;
@@ -12,8 +14,8 @@ use crate::shared::AssertSend;
 use crate::sync::Arc;
 
 use super::{
-    Address, GeneratorState, Output, RuntimeContext, Unit, Value, Vm, VmDiagnostics, VmError,
-    VmErrorKind, VmHalt, VmHaltInfo,
+    Address, DebugOutcome, DebugStep, Debugger, GeneratorState, Output, RuntimeContext, Unit,
+    Value, Vm, VmDiagnostics, VmError, VmErrorKind, VmHalt, VmHaltInfo,
 };
 
 static COMPLETE_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
@@ -73,6 +75,9 @@ pub struct VmExecution<T> {
     state: ExecutionState,
     /// Indicates the current stack of suspended contexts.
     states: Vec<VmExecutionState>,
+    /// A task which is being awaited, this is stored in the execution so that
+    /// it can be resumed in case the execution is limited while awaiting.
+    awaited: Option<Awaited>,
 }
 
 impl<T> VmExecution<T> {
@@ -83,6 +88,7 @@ impl<T> VmExecution<T> {
             vm,
             state: ExecutionState::Initial,
             states: Vec::new(),
+            awaited: None,
         }
     }
 
@@ -215,6 +221,22 @@ where
         self.resume().await?.into_complete()
     }
 
+    /// Asynchronously complete the current execution, or error once the given
+    /// `deadline` future completes.
+    ///
+    /// See [`VmResume::with_deadline`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// If anything except the completion of the execution is encountered, this
+    /// will result in an error. This includes the deadline passing.
+    pub async fn async_complete_with_deadline<D>(&mut self, deadline: D) -> Result<Value, VmError>
+    where
+        D: Future<Output = ()>,
+    {
+        self.resume().with_deadline(deadline).await?.into_complete()
+    }
+
     /// Resume the current execution.
     ///
     /// To complete this operation synchronously, use [`VmResume::complete`].
@@ -233,11 +255,26 @@ where
         VmResume {
             execution: self,
             diagnostics: None,
-            awaited: None,
             init: Some(Value::empty()),
         }
     }
 
+    /// Resume the current execution under the control of a debugger.
+    ///
+    /// Execution is resumed until the given `step` completes, a breakpoint in
+    /// the debugger is hit, or the execution yields or completes. See
+    /// [`Debugger`] for an example.
+    pub async fn debug(
+        &mut self,
+        debugger: &mut Debugger,
+        step: DebugStep,
+    ) -> Result<DebugOutcome, VmError>
+    where
+        T: AsRef<Vm>,
+    {
+        debugger.resume(self, step).await
+    }
+
     /// End execution and perform debug checks.
     pub(crate) fn end(&mut self) -> Result<Value, VmError> {
         let ExecutionState::Exited(addr) = self.state else {
@@ -291,12 +328,14 @@ impl VmExecution<&mut Vm> {
     /// Convert the current execution into one which owns its virtual machine.
     pub fn into_owned(self) -> VmExecution<Vm> {
         let stack = take(self.vm.stack_mut());
-        let head = Vm::with_stack(self.vm.context().clone(), self.vm.unit().clone(), stack);
+        let mut head = Vm::with_stack(self.vm.context().clone(), self.vm.unit().clone(), stack);
+        head.inherit(self.vm);
 
         VmExecution {
             vm: head,
             states: self.states,
             state: self.state,
+            awaited: self.awaited,
         }
     }
 }
@@ -377,6 +416,7 @@ where
             vm: self.vm.try_clone()?,
             state: self.state,
             states: self.states.try_clone()?,
+            awaited: None,
         })
     }
 }
@@ -438,7 +478,6 @@ pub struct VmResume<'this, 'diag, T> {
     execution: &'this mut VmExecution<T>,
     diagnostics: Option<&'diag mut dyn VmDiagnostics>,
     init: Option<Value>,
-    awaited: Option<Awaited>,
 }
 
 impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
@@ -447,6 +486,52 @@ impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
         budget::with(budget, self)
     }
 
+    /// Associate a deadline with the resumed execution.
+    ///
+    /// The `deadline` is a future which completes once the deadline has
+    /// passed, such as [`tokio::time::sleep_until`]. If it completes while the
+    /// execution is running, the execution is suspended and resolves to
+    /// [`VmOutcome::Limited`]. Just like with a budget, the execution can then
+    /// be resumed again, in which case any task which was being awaited
+    /// continues from where it left off.
+    ///
+    /// The deadline is only checked when the virtual machine awaits or yields
+    /// control, so it should be combined with [`VmResume::with_budget`] to
+    /// limit scripts which run for long periods without awaiting.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use rune::runtime::VmOutcome;
+    /// use rune::Vm;
+    ///
+    /// # async fn run(mut vm: Vm) -> rune::support::Result<()> {
+    /// let mut execution = vm.execute(["main"], ())?;
+    ///
+    /// let outcome = execution
+    ///     .resume()
+    ///     .with_deadline(tokio::time::sleep(Duration::from_secs(1)))
+    ///     .await?;
+    ///
+    /// if let VmOutcome::Limited = outcome {
+    ///     println!("Script timed out");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`tokio::time::sleep_until`]: https://docs.rs/tokio/1/tokio/time/fn.sleep_until.html
+    pub fn with_deadline<D>(self, deadline: D) -> VmDeadline<'this, 'diag, T, D>
+    where
+        D: Future<Output = ()>,
+    {
+        VmDeadline {
+            resume: self,
+            deadline,
+        }
+    }
+
     /// Associate a value with the resumed execution.
     ///
     /// This is necessary to provide a value for a generator which has yielded.
@@ -466,7 +551,6 @@ impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
             execution: self.execution,
             diagnostics: Some(diagnostics),
             init: self.init,
-            awaited: self.awaited,
         }
     }
 }
@@ -515,10 +599,12 @@ where
         loop {
             let vm = this.execution.vm.as_mut();
 
-            if let Some(awaited) = &mut this.awaited {
+            if let Some(awaited) = &mut this.execution.awaited {
+                // SAFETY: The tasks being awaited are heap allocated, so moving
+                // the awaited value does not move them.
                 let awaited = unsafe { Pin::new_unchecked(awaited) };
                 async_vm_try!(ready!(awaited.poll(cx, vm)));
-                this.awaited = None;
+                this.execution.awaited = None;
             }
 
             let result = vm.run(match this.diagnostics {
@@ -531,7 +617,7 @@ where
                     this.execution.state = ExecutionState::Exited(addr);
                 }
                 VmHalt::Awaited(awaited) => {
-                    this.awaited = Some(awaited);
+                    this.execution.awaited = Some(awaited);
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
@@ -562,6 +648,43 @@ where
     }
 }
 
+/// An execution that has been resumed with a deadline.
+///
+/// See [`VmResume::with_deadline`].
+#[pin_project]
+pub struct VmDeadline<'this, 'diag, T, D> {
+    #[pin]
+    resume: VmResume<'this, 'diag, T>,
+    #[pin]
+    deadline: D,
+}
+
+impl<'this, 'diag, T, D> VmDeadline<'this, 'diag, T, D> {
+    /// Associated a budget with the resumed execution.
+    pub fn with_budget(self, budget: usize) -> Budget<Self> {
+        budget::with(budget, self)
+    }
+}
+
+impl<'this, 'diag, T, D> Future for VmDeadline<'this, 'diag, T, D>
+where
+    T: AsMut<Vm>,
+    D: Future<Output = ()>,
+{
+    type Output = Result<VmOutcome, VmError>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.deadline.poll(cx).is_ready() {
+            return Poll::Ready(Ok(VmOutcome::Limited));
+        }
+
+        this.resume.poll(cx)
+    }
+}
+
 /// A [`VmExecution`] that can be used with a generator api.
 pub struct VmGenerator<T> {
     execution: Option<VmExecution<T>>,
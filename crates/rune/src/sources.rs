@@ -47,6 +47,9 @@ macro_rules! sources {
 pub struct Sources {
     /// Sources associated.
     sources: Vec<Source>,
+    /// Sources which are the library of a dependency, and the name of the
+    /// module they are mounted as.
+    dependencies: Vec<(SourceId, Box<str>)>,
 }
 
 impl Sources {
@@ -55,6 +58,7 @@ impl Sources {
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            dependencies: Vec::new(),
         }
     }
 
@@ -79,6 +83,39 @@ impl Sources {
         Ok(id)
     }
 
+    /// Insert the library source of a dependency and return its
+    /// [`SourceId`].
+    ///
+    /// Instead of being compiled as a root source, the dependency is mounted
+    /// as the module `::name`, so that its items can be imported with `use
+    /// name::item`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Sources, Source};
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("main", "pub fn main() { helpers::answer() }")?)?;
+    /// sources.insert_dependency("helpers", Source::new("helpers", "pub fn answer() { 42 }")?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn insert_dependency(&mut self, name: &str, source: Source) -> alloc::Result<SourceId> {
+        let id = self.insert(source)?;
+        self.dependencies.try_push((id, name.try_into()?))?;
+        Ok(id)
+    }
+
+    /// Get the module name of the dependency associated with the given source
+    /// id, if it was inserted with [`Sources::insert_dependency`].
+    #[inline]
+    pub(crate) fn dependency(&self, id: SourceId) -> Option<&str> {
+        let (_, name) = self.dependencies.iter().find(|(d, _)| *d == id)?;
+        Some(name)
+    }
+
     /// Get the source matching the given source id.
     ///
     /// # Examples
@@ -474,6 +474,8 @@ mod custom_macros;
 #[cfg(not(miri))]
 mod debug_fmt;
 #[cfg(not(miri))]
+mod default_arguments;
+#[cfg(not(miri))]
 mod deprecation;
 #[cfg(not(miri))]
 mod derive_constructor;
@@ -497,8 +499,12 @@ mod getter_setter;
 #[cfg(not(miri))]
 mod iterator;
 #[cfg(not(miri))]
+mod let_else;
+#[cfg(not(miri))]
 mod literals;
 #[cfg(not(miri))]
+mod lowering;
+#[cfg(not(miri))]
 mod macros;
 #[cfg(not(miri))]
 mod moved;
@@ -517,6 +523,8 @@ mod rename_type;
 #[cfg(not(miri))]
 mod result;
 #[cfg(not(miri))]
+mod script_traits;
+#[cfg(not(miri))]
 mod scripts;
 #[cfg(not(miri))]
 mod static_typing;
@@ -524,9 +532,13 @@ mod static_typing;
 mod tuple;
 #[cfg(not(miri))]
 mod type_name_native;
+#[cfg(all(not(miri), feature = "byte-code"))]
+mod unit_artifact;
 #[cfg(not(miri))]
 mod unit_constants;
 #[cfg(not(miri))]
+mod unit_link;
+#[cfg(not(miri))]
 mod unreachable;
 #[cfg(not(miri))]
 mod vm_arithmetic;
@@ -537,10 +549,18 @@ mod vm_async_block;
 #[cfg(not(miri))]
 mod vm_blocks;
 #[cfg(not(miri))]
+mod vm_call_depth;
+#[cfg(not(miri))]
 mod vm_closures;
 #[cfg(not(miri))]
 mod vm_const_exprs;
 #[cfg(not(miri))]
+mod vm_coverage;
+#[cfg(not(miri))]
+mod vm_deadline;
+#[cfg(not(miri))]
+mod vm_debugger;
+#[cfg(not(miri))]
 mod vm_early_termination;
 #[cfg(not(miri))]
 mod vm_function;
@@ -551,6 +571,8 @@ mod vm_general;
 #[cfg(not(miri))]
 mod vm_not_used;
 #[cfg(not(miri))]
+mod vm_profiler;
+#[cfg(not(miri))]
 mod vm_result;
 #[cfg(not(miri))]
 mod vm_test_from_value_derive;
@@ -0,0 +1,281 @@
+prelude!();
+
+use diagnostics::{Diagnostic, FatalDiagnosticKind};
+use ErrorKind::*;
+
+/// Compile the given script with the v2 compiler and get the message of the
+/// first compile error.
+fn v2_error(source: &str, module: Option<Module>) -> String {
+    let mut context = Context::with_default_modules().expect("setting up default modules");
+
+    if let Some(module) = module {
+        context.install(module).expect("installing module");
+    }
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::new("main", source).expect("building source"))
+        .expect("inserting source");
+
+    let mut options = Options::default();
+    options.script(true);
+    options.parse_option("v2").expect("parsing v2 option");
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&options)
+        .build();
+
+    let error = diagnostics
+        .into_diagnostics()
+        .into_iter()
+        .find_map(|d| match d {
+            Diagnostic::Fatal(e) => match e.into_kind() {
+                FatalDiagnosticKind::CompileError(e) => Some(e),
+                _ => None,
+            },
+            _ => None,
+        })
+        .expect("expected a compile error");
+
+    error.to_string()
+}
+
+#[test]
+fn test_default_arguments() {
+    let out: i64 = rune! {
+        fn add(a, b = 10, c = 100) {
+            a + b + c
+        }
+
+        add(1) + add(1, 2) + add(1, 2, 3)
+    };
+
+    assert_eq!(out, 220);
+
+    let out: i64 = rune! {
+        const BASE = 8000;
+
+        fn port(port = BASE + 80) {
+            port
+        }
+
+        port() + port(1)
+    };
+
+    assert_eq!(out, 8081);
+
+    let out: i64 = rune! {
+        struct Server;
+
+        impl Server {
+            fn port(port = 80) {
+                port
+            }
+        }
+
+        Server::port() + Server::port(8000)
+    };
+
+    assert_eq!(out, 8080);
+}
+
+#[test]
+fn test_named_arguments() {
+    let out: i64 = rune! {
+        fn add(a, b = 10, c = 100) {
+            a * 100 + b * 10 + c
+        }
+
+        let values = [add(1, c: 3), add(c: 3, a: 1), add(a: 1), add(1, 2, c: 3)];
+        values[0] + values[1] + values[2] + values[3]
+    };
+
+    assert_eq!(out, 203 + 203 + 300 + 123);
+}
+
+#[test]
+fn test_named_arguments_evaluation_order() {
+    let out: (Vec<i64>, i64) = rune! {
+        fn sub(a, b, c = 0) {
+            a - b - c
+        }
+
+        let log = [];
+        let value = sub(b: { log.push(1); 2 }, c: { log.push(2); 3 }, a: { log.push(3); 10 });
+        (log, value)
+    };
+
+    assert_eq!(out, (vec![1, 2, 3], 5));
+
+    let out: Vec<i64> = rune! {
+        fn f(a, b) {}
+
+        let log = [];
+        f(b: log.push(2), a: log.push(1));
+        log
+    };
+
+    assert_eq!(out, [2, 1]);
+}
+
+#[test]
+fn test_native_default_arguments() -> Result<()> {
+    /// Scale a value by the given factor.
+    #[rune::function]
+    fn scale(value: i64, factor: i64) -> i64 {
+        value * factor
+    }
+
+    let mut m = Module::new();
+
+    m.function("connect", |host: i64, port: i64| host * 100_000 + port)
+        .build()?
+        .argument_names(["host", "port"])?
+        .argument_default("port", 80i64)?;
+
+    m.function_meta(scale)?.argument_default("factor", 2i64)?;
+
+    let out: i64 = rune_n! {
+        mod m,
+        (),
+        pub fn main() {
+            connect(1) + connect(2, port: 8080) + connect(port: 1, host: 0)
+        }
+    };
+
+    assert_eq!(out, 100_080 + 208_080 + 1);
+
+    let out: i64 = rune_n! {
+        mod m,
+        (),
+        pub fn main() {
+            scale(21) + scale(factor: 3, value: 2)
+        }
+    };
+
+    assert_eq!(out, 48);
+    Ok(())
+}
+
+#[test]
+fn test_native_default_argument_errors() {
+    let mut m = Module::new();
+
+    let result = m
+        .function("connect", |host: i64, port: i64| host + port)
+        .build()
+        .and_then(|f| f.argument_default("port", 80i64));
+
+    assert!(matches!(
+        result,
+        Err(ContextError::MissingArgumentName { .. })
+    ));
+
+    let result = m
+        .function("port", |this: i64, port: i64| this + port)
+        .build()
+        .and_then(|f| f.argument_names(["self", "port"]))
+        .and_then(|f| f.argument_default("port", 80i64));
+
+    assert!(matches!(
+        result,
+        Err(ContextError::UnsupportedArgumentDefault { .. })
+    ));
+}
+
+#[test]
+fn test_argument_errors() {
+    assert_errors! {
+        "fn f(a, b = 1) { a + b } f(1, c: 2)",
+        span!(30, 31), Custom { error } => {
+            assert_eq!(error.to_string(), "Function has no argument named `c`");
+        }
+    };
+
+    assert_errors! {
+        "fn f(a, b = 1) { a + b } f(1, a: 2)",
+        span!(30, 34), Custom { error } => {
+            assert_eq!(error.to_string(), "Argument `a` is provided more than once");
+        }
+    };
+
+    assert_errors! {
+        "fn f(a, b = 1) { a + b } f(b: 1, 2)",
+        span!(33, 34), Custom { error } => {
+            assert_eq!(error.to_string(), "Positional arguments must come before named arguments");
+        }
+    };
+
+    assert_errors! {
+        "fn f(a, b = 1) { a + b } f(b: 2)",
+        span!(26, 32), Custom { error } => {
+            assert_eq!(error.to_string(), "Missing argument `a`");
+        }
+    };
+
+    assert_errors! {
+        "let f = |a| a; f(a: 1)",
+        span!(17, 18), Custom { error } => {
+            assert_eq!(error.to_string(), "Named arguments are only supported when calling a function by its path");
+        }
+    };
+
+    assert_errors! {
+        "struct S; impl S { fn port(self, port) { port } } S.port(port: 1)",
+        span!(57, 61), Custom { error } => {
+            assert_eq!(error.to_string(), "Named arguments are only supported when calling a function by its path");
+        }
+    };
+
+    assert_errors! {
+        "struct S; impl S { fn port(self, port = 80) { port } }",
+        span!(33, 42), Custom { error } => {
+            assert_eq!(error.to_string(), "Default values for arguments are not supported in instance functions");
+        }
+    };
+
+    assert_errors! {
+        "fn port(port = 80) { port } let f = port; f()",
+        span!(36, 40), Custom { error } => {
+            assert_eq!(error.to_string(), "Functions with default values for arguments can only be called by their path");
+        }
+    };
+
+    assert_errors! {
+        "trait Port { fn port(self, port = 80); }",
+        span!(27, 36), Custom { error } => {
+            assert_eq!(error.to_string(), "Default values for arguments are not supported in trait functions");
+        }
+    };
+}
+
+#[test]
+fn test_v2_argument_errors() -> Result<()> {
+    assert_eq!(
+        v2_error("fn port(port = 80) { port }", None),
+        "Default values for arguments are not supported by the v2 compiler"
+    );
+
+    assert_eq!(
+        v2_error("fn add(a, b) { a + b } add(1, b: 2)", None),
+        "Named arguments are not supported by the v2 compiler"
+    );
+
+    let mut m = Module::new();
+
+    m.function("connect", |host: i64, port: i64| host + port)
+        .build()?
+        .argument_names(["host", "port"])?
+        .argument_default("port", 80i64)?;
+
+    assert_eq!(
+        v2_error("connect(1)", Some(m)),
+        "Omitting arguments with default values is not supported by the v2 compiler"
+    );
+
+    Ok(())
+}
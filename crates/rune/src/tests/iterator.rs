@@ -147,3 +147,116 @@ fn test_flat_map() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_zip_unzip() {
+    let values: (Vec<i64>, Vec<i64>) = rune! {
+        let it = [1, 2, 3].iter().zip([4, 5]);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        it.unzip()
+    };
+
+    assert_eq!(values, (vec![1, 2], vec![4, 5]));
+}
+
+#[test]
+fn test_unzip_wrong_size_hint() -> Result<()> {
+    /// An iterator which claims to produce more values than it does.
+    #[derive(Any)]
+    struct Pairs {
+        n: i64,
+    }
+
+    impl Pairs {
+        #[rune::function(keep, protocol = NEXT)]
+        fn next(&mut self) -> Option<(i64, i64)> {
+            if self.n == 0 {
+                return None;
+            }
+
+            self.n -= 1;
+            Some((self.n, self.n * 2))
+        }
+
+        #[rune::function(keep, protocol = SIZE_HINT)]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Pairs>()?;
+    module.function_meta(Pairs::next__meta)?;
+    module.function_meta(Pairs::size_hint__meta)?;
+    module.implement_trait::<Pairs>(rune::item!(::std::iter::Iterator))?;
+
+    let values: (Vec<i64>, Vec<i64>) = rune_n! {
+        mod module,
+        (Pairs { n: 3 },),
+        pub fn main(pairs) { pairs.unzip() }
+    };
+
+    assert_eq!(values, (vec![2, 1, 0], vec![4, 2, 0]));
+    Ok(())
+}
+
+#[test]
+fn test_step_by() {
+    let values: Vec<i64> = rune! {
+        let it = (0..10).iter().step_by(3);
+        assert_eq!(it.len(), 4);
+        it.collect::<Vec>()
+    };
+
+    assert_eq!(values, (0..10).step_by(3).collect::<Vec<i64>>());
+}
+
+#[test]
+fn test_take_while_skip_while() {
+    let values: Vec<i64> = rune! {
+        (0..10).iter().skip_while(|n| n < 3).take_while(|n| n < 7).collect::<Vec>()
+    };
+
+    assert_eq!(values, vec![3, 4, 5, 6]);
+}
+
+#[test]
+fn test_scan_cycle() {
+    let values: Vec<i64> = rune! {
+        [1, 2, 3].iter().scan(0, |acc, n| Some((acc + n, acc + n))).cycle().take(7).collect::<Vec>()
+    };
+
+    assert_eq!(values, vec![1, 3, 6, 1, 3, 6, 1]);
+}
+
+#[test]
+fn test_flatten_inspect() {
+    let values: (Vec<i64>, Vec<i64>) = rune! {
+        let seen = [];
+        let values = [[1, 2], [], [3]].iter().flatten().inspect(|n| seen.push(n)).collect::<Vec>();
+        (values, seen)
+    };
+
+    assert_eq!(values, (vec![1, 2, 3], vec![1, 2, 3]));
+}
+
+#[test]
+fn test_min_max_by() {
+    let values: (i64, i64, i64, i64) = rune! {
+        let a = [(1, 'a'), (3, 'b'), (3, 'c'), (1, 'd')];
+        let (max, _) = a.iter().max_by(|a, b| a.0.cmp(b.0)).unwrap();
+        let (min, _) = a.iter().min_by(|a, b| a.0.cmp(b.0)).unwrap();
+        (max, min, [-3, 2].iter().max_by_key(|n| n.abs()).unwrap(), [-3, 2].iter().min_by_key(|n| n.abs()).unwrap())
+    };
+
+    assert_eq!(values, (3, 1, -3, 2));
+}
+
+#[test]
+fn test_partition() {
+    let values: (Vec<i64>, Vec<i64>) = rune! {
+        (0..6).iter().partition(|n| n % 2 == 0)
+    };
+
+    assert_eq!(values, (vec![0, 2, 4], vec![1, 3, 5]));
+}
@@ -0,0 +1,163 @@
+prelude!();
+
+use diagnostics::WarningDiagnosticKind::*;
+use ErrorKind::*;
+use VmErrorKind::*;
+
+#[test]
+fn test_let_else() {
+    let out: i64 = rune! {
+        fn get(value) {
+            let Some(value) = value else {
+                return 0;
+            };
+
+            value + 1
+        }
+
+        get(Some(41)) + get(None)
+    };
+
+    assert_eq!(out, 42);
+
+    let out: i64 = rune! {
+        let sum = 0;
+
+        for value in [Some(1), None, Some(2), Err(3), Some(4)] {
+            let Some(value) = value else {
+                continue;
+            };
+
+            sum += value;
+        }
+
+        sum
+    };
+
+    assert_eq!(out, 7);
+
+    let out: i64 = rune! {
+        let values = [[1, 2], [3, 4], [5]];
+        let sum = 0;
+
+        for value in values {
+            let [a, b] = value else {
+                break;
+            };
+
+            sum += a * b;
+        }
+
+        sum
+    };
+
+    assert_eq!(out, 14);
+}
+
+#[test]
+fn test_let_else_must_diverge() {
+    assert_errors! {
+        "let Some(a) = Some(1) else { let b = 2; };",
+        span!(27, 41), Custom { error } => {
+            assert_eq!(error.to_string(), "The else block in a `let ... else` statement must diverge");
+        }
+    };
+}
+
+#[test]
+fn test_let_else_call() {
+    let out: i64 = rune! {
+        fn get(value) {
+            let Some(value) = value else {
+                panic("missing value")
+            };
+
+            value
+        }
+
+        get(Some(42))
+    };
+
+    assert_eq!(out, 42);
+
+    assert_vm_error!(
+        r#"let Some(a) = None else { panic!("missing {}", 42) };"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing 42");
+        }
+    );
+}
+
+#[test]
+fn test_let_else_call_must_diverge() {
+    assert_errors! {
+        r#"let Some(a) = None else { println("none") };"#,
+        span!(24, 43), Custom { error } => {
+            assert_eq!(error.to_string(), "The else block in a `let ... else` statement must diverge");
+        }
+    };
+
+    assert_errors! {
+        "fn nothing() {} let Some(a) = None else { nothing(); };",
+        span!(40, 54), Custom { error } => {
+            assert_eq!(error.to_string(), "The else block in a `let ... else` statement must diverge");
+        }
+    };
+}
+
+#[test]
+fn test_let_else_irrefutable() {
+    assert_warnings! {
+        "let a = 1 else { return; };",
+        span!(15, 26), UnreachableLetElse { pattern: span!(4, 5), context: Some(span!(0, 27)), .. }
+    };
+}
+
+#[test]
+fn test_if_let_chain() {
+    let out: i64 = rune! {
+        fn check(value) {
+            if let Some(a) = value && a > 3 {
+                a
+            } else {
+                0
+            }
+        }
+
+        check(Some(10)) + check(Some(2)) + check(None)
+    };
+
+    assert_eq!(out, 10);
+
+    let out: i64 = rune! {
+        fn check(value) {
+            if let Some(a) = value && let [b, c] = a && b + c == 3 {
+                b * 10 + c
+            } else if let Some([b]) = value {
+                b
+            } else {
+                -1
+            }
+        }
+
+        check(Some([1, 2])) + check(Some([2, 2])) + check(Some([100])) + check(None)
+    };
+
+    assert_eq!(out, 110);
+}
+
+#[test]
+fn test_while_let_chain() {
+    let out: i64 = rune! {
+        let it = [1, 2, 3, 10, 4].iter();
+        let sum = 0;
+
+        while let Some(value) = it.next() && value < 5 {
+            sum += value;
+        }
+
+        sum
+    };
+
+    assert_eq!(out, 6);
+}
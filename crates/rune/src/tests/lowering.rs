@@ -0,0 +1,178 @@
+prelude!();
+
+use crate::Unit;
+use runtime::{inst, VmError};
+
+fn build(source: &str, level: u8) -> Result<Unit> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let mut options = Options::default();
+    options.parse_option(&format!("lowering={level}"))?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    Ok(unit)
+}
+
+fn call(unit: Unit) -> Result<Value, VmError> {
+    let context = Context::with_default_modules().expect("setting up default modules");
+    let runtime = Arc::try_new(context.runtime().expect("building runtime"))?;
+    let mut vm = Vm::new(runtime, Arc::try_new(unit)?);
+    vm.call(["main"], ())
+}
+
+fn count(unit: &Unit, f: impl Fn(&inst::Kind) -> bool) -> usize {
+    unit.iter_instructions().filter(|(_, i)| f(&i.kind)).count()
+}
+
+/// Assert that every optimization level produces the same output, and that the
+/// highest level produces fewer instructions than the lowest.
+///
+/// Returns the units built with the lowest and highest level.
+fn assert_levels<T>(source: &str, expected: T) -> Result<(Unit, Unit)>
+where
+    T: FromValue + PartialEq + core::fmt::Debug,
+{
+    for level in 0..=3 {
+        let output: T = from_value(call(build(source, level)?)?)?;
+        assert_eq!(output, expected, "output at lowering={level}");
+    }
+
+    let unoptimized = build(source, 0)?;
+    let optimized = build(source, 3)?;
+
+    let before = count(&unoptimized, |_| true);
+    let after = count(&optimized, |_| true);
+    assert!(
+        after < before,
+        "expected fewer than {before} instructions, got {after}"
+    );
+
+    Ok((unoptimized, optimized))
+}
+
+#[test]
+fn constant_folding() -> Result<()> {
+    let (_, unit) = assert_levels(
+        r#"
+        pub fn main() {
+            let a = 2;
+            let b = 3;
+            let c = a * b + 4;
+            let d = -c;
+            if c > 5 && !(d == 0) { c - d } else { 0 }
+        }
+        "#,
+        20i64,
+    )?;
+
+    let arithmetic = count(&unit, |kind| matches!(kind, inst::Kind::Arithmetic { .. }));
+    assert_eq!(arithmetic, 0);
+    Ok(())
+}
+
+#[test]
+fn overflow_is_not_folded() -> Result<()> {
+    const SOURCE: &str = r#"
+    pub fn main() {
+        let a = 9223372036854775807;
+        a + 1
+    }
+    "#;
+
+    for level in 0..=3 {
+        let unit = build(SOURCE, level)?;
+        let arithmetic = count(&unit, |kind| matches!(kind, inst::Kind::Arithmetic { .. }));
+        assert_eq!(arithmetic, 1, "arithmetic at lowering={level}");
+        assert!(call(unit).is_err(), "expected overflow at lowering={level}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn constant_branches() -> Result<()> {
+    let (unoptimized, optimized) = assert_levels(
+        r#"
+        pub fn main() {
+            let debug = false;
+            let n = 2;
+
+            let name = match n {
+                1 => "one",
+                2 => "two",
+                _ => "many",
+            };
+
+            if debug {
+                name + "?"
+            } else {
+                name
+            }
+        }
+        "#,
+        String::from("two"),
+    )?;
+
+    let branches = |unit: &Unit| {
+        count(unit, |kind| {
+            matches!(
+                kind,
+                inst::Kind::JumpIf { .. } | inst::Kind::JumpIfNot { .. }
+            )
+        })
+    };
+
+    assert!(branches(&optimized) < branches(&unoptimized));
+    Ok(())
+}
+
+#[test]
+fn loops() -> Result<()> {
+    assert_levels(
+        r#"
+        pub fn main() {
+            let step = 1;
+            let total = 0;
+            let n = 0;
+
+            while n < 10 {
+                n += step;
+
+                if n % 2 == 0 {
+                    continue;
+                }
+
+                total += n * 2;
+            }
+
+            for value in 0..5 {
+                if value == 3 {
+                    break;
+                }
+
+                total += value;
+            }
+
+            let limit = 4 * 2;
+
+            loop {
+                total += 1;
+
+                if total > limit + 100 {
+                    break total;
+                }
+            }
+        }
+        "#,
+        109i64,
+    )?;
+
+    Ok(())
+}
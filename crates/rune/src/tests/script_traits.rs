@@ -0,0 +1,194 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn test_script_trait_default_and_required() {
+    let out: (i64, i64) = rune! {
+        trait Shape {
+            fn area(self);
+
+            fn double_area(self) {
+                self.area() * 2
+            }
+        }
+
+        struct Square { side }
+        struct Rect { w, h }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        impl Shape for Rect {
+            fn area(self) {
+                self.w * self.h
+            }
+
+            fn double_area(self) {
+                0
+            }
+        }
+
+        (Square { side: 3 }.double_area(), Rect { w: 2, h: 4 }.double_area())
+    };
+
+    assert_eq!(out, (18, 0));
+}
+
+#[test]
+fn test_impl_iterator() {
+    let out: i64 = rune! {
+        use std::iter::Iterator;
+
+        struct Counter { n }
+
+        impl Iterator for Counter {
+            fn next(self) {
+                if self.n == 0 {
+                    return None;
+                }
+
+                self.n -= 1;
+                Some(self.n)
+            }
+        }
+
+        let counter = Counter { n: 5 };
+        let sum = 0;
+
+        for n in counter {
+            sum += n;
+        }
+
+        sum
+    };
+
+    assert_eq!(out, 10);
+}
+
+#[test]
+fn test_impl_partial_eq_and_ord() {
+    let out: (bool, bool, Vec<i64>) = rune! {
+        use std::cmp::{Ord, PartialEq, PartialOrd};
+
+        struct Version { major, ignored }
+
+        impl PartialEq for Version {
+            fn eq(self, other) {
+                self.major == other.major
+            }
+        }
+
+        impl PartialOrd for Version {
+            fn partial_cmp(self, other) {
+                self.major.partial_cmp(other.major)
+            }
+        }
+
+        impl Ord for Version {
+            fn cmp(self, other) {
+                self.major.cmp(other.major)
+            }
+        }
+
+        let a = Version { major: 1, ignored: 10 };
+        let b = Version { major: 1, ignored: 20 };
+        let c = Version { major: 2, ignored: 0 };
+
+        let values = [c, a, b];
+        values.sort();
+
+        (a == b, a < c, values.iter().map(|v| v.major).collect::<Vec>())
+    };
+
+    assert_eq!(out, (true, true, vec![1, 1, 2]));
+}
+
+#[test]
+fn test_impl_operators() {
+    let out: ((i64, i64), i64, i64, String) = rune! {
+        use std::fmt::Display;
+        use std::ops::{Add, AddAssign, Index, Neg};
+
+        struct Money { cents }
+
+        impl Add for Money {
+            fn add(self, other) {
+                Money { cents: self.cents + other.cents }
+            }
+        }
+
+        impl AddAssign for Money {
+            fn add_assign(self, other) {
+                self.cents += other.cents;
+            }
+        }
+
+        impl Neg for Money {
+            fn neg(self) {
+                Money { cents: -self.cents }
+            }
+        }
+
+        impl Index for Money {
+            fn index_get(self, index) {
+                self.cents * index
+            }
+        }
+
+        impl Display for Money {
+            fn fmt(self, f) {
+                f.write_str(format!("${}.{}", self.cents / 100, self.cents % 100))
+            }
+        }
+
+        let a = Money { cents: 150 };
+        let b = Money { cents: 275 };
+        let c = a + b;
+        let d = -a;
+        a += b;
+
+        ((c.cents, d.cents), a.cents, b[2], format!("{}", c))
+    };
+
+    assert_eq!(out, ((425, -150), 425, 550, String::from("$4.25")));
+}
+
+#[test]
+fn test_trait_errors() {
+    assert_errors! {
+        r#"
+        trait Shape { fn area(self); }
+        struct Square;
+        impl Shape for Square {}
+        "#,
+        _,
+        MissingTraitFunction { name, .. } => assert_eq!(&*name, "area")
+    };
+
+    assert_errors! {
+        r#"
+        trait Shape { fn area(self); }
+        struct Square;
+        impl Shape for Square {
+            fn area(self) { 1 }
+            fn perimeter(self) { 4 }
+        }
+        "#,
+        _,
+        NotTraitFunction { name, .. } => assert_eq!(&*name, "perimeter")
+    };
+
+    assert_errors! {
+        r#"
+        struct Square;
+        struct Other;
+        impl Other for Square {}
+        "#,
+        _,
+        ExpectedMeta { .. }
+    };
+}
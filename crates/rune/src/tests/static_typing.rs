@@ -1,23 +1,361 @@
 prelude!();
 
+use ast::{Span, Spanned};
+use diagnostics::{Diagnostic, FatalDiagnosticKind};
 use ErrorKind::*;
 
+/// Compile the given script with type checks enabled.
+fn check(source: &str, module: Option<Module>) -> Diagnostics {
+    let mut context = Context::with_default_modules().expect("setting up default modules");
+
+    if let Some(module) = module {
+        context.install(module).expect("installing module");
+    }
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::new("main", source).expect("building source"))
+        .expect("inserting source");
+
+    let mut options = Options::default();
+    options.script(true);
+    options
+        .parse_option("type-checks")
+        .expect("parsing type-checks option");
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&options)
+        .build();
+
+    diagnostics
+}
+
+/// Get the only compile error produced by type checking the given script.
+fn check_error(source: &str, module: Option<Module>) -> (Span, ErrorKind) {
+    let mut errors = check(source, module)
+        .into_diagnostics()
+        .into_iter()
+        .filter_map(|d| match d {
+            Diagnostic::Fatal(e) => match e.into_kind() {
+                FatalDiagnosticKind::CompileError(e) => Some((e.span(), e.into_kind())),
+                _ => None,
+            },
+            _ => None,
+        });
+
+    let error = errors.next().expect("expected a compile error");
+    assert!(errors.next().is_none(), "expected exactly one error");
+    error
+}
+
+#[track_caller]
+fn assert_mismatch(actual: (Span, ErrorKind), span: Span, expected: &Item, found: &Item) {
+    match actual {
+        (
+            s,
+            TypeMismatch {
+                expected: e,
+                actual: a,
+            },
+        ) => {
+            assert_eq!(s, span);
+            assert_eq!(&*e, expected);
+            assert_eq!(&*a, found);
+        }
+        actual => panic!("expected type mismatch, got {actual:?}"),
+    }
+}
+
+#[test]
+fn annotations_are_erased() {
+    let out: i64 = rune! {
+        struct Point { x: i64, y: i64 }
+
+        fn add(a: i64, b: i64 = 2) -> i64 {
+            a + b
+        }
+
+        let point: Point = Point { x: 1, y: 2 };
+        let (a, b): (i64, i64) = (point.x, point.y);
+        add(a) + add(b, 3)
+    };
+
+    assert_eq!(out, 8);
+
+    // Annotations are not checked at runtime.
+    let out: String = rune! {
+        fn f(a: i64) -> i64 {
+            a
+        }
+
+        f("hello")
+    };
+
+    assert_eq!(out, "hello");
+}
+
 #[test]
-fn deny_static_typing_function() {
-    assert_errors! {
-        "fn foo() -> Bar {}",
-        span!(0, 18), Custom { error } => {
-            assert_eq!(error.to_string(), "Adding a return type in functions is not supported");
+fn type_checks_pass() {
+    let diagnostics = check(
+        r#"
+        struct Point { x: i64, y: i64 }
+        enum Shape { Circle(f64), Square(f64) }
+
+        fn area(shape: Shape) -> f64 {
+            match shape {
+                Shape::Circle(r) => r * r * 3.14,
+                Shape::Square(s) => s * s,
+            }
+        }
+
+        fn origin() -> Point {
+            Point { x: 0, y: 0 }
+        }
+
+        fn unknown(value) {
+            value
         }
+
+        let point: Point = origin();
+        let x: i64 = point.x;
+        let shape: Shape = Shape::Circle(1.0);
+        let size: f64 = area(shape);
+        let name: String = `x = {x}`;
+        let any: String = unknown(1);
+        "#,
+        None,
+    );
+
+    assert!(!diagnostics.has_error());
+}
+
+#[test]
+fn type_checks_disabled_by_default() {
+    assert_parse!(r#"let x: i64 = "hello";"#);
+}
+
+#[test]
+fn type_check_local() {
+    assert_mismatch(
+        check_error(r#"let x: i64 = "hello";"#, None),
+        span!(13, 20),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+
+    assert_mismatch(
+        check_error(r#"let x: String = "a"; x = 42;"#, None),
+        span!(25, 27),
+        rune::item!(::std::string::String),
+        rune::item!(::std::i64),
+    );
+}
+
+#[test]
+fn type_check_return() {
+    assert_mismatch(
+        check_error("fn f() -> String { 1 }", None),
+        span!(19, 20),
+        rune::item!(::std::string::String),
+        rune::item!(::std::i64),
+    );
+
+    assert_mismatch(
+        check_error("fn f(a) -> bool { if a { return 1; } true }", None),
+        span!(32, 33),
+        rune::item!(::std::bool),
+        rune::item!(::std::i64),
+    );
+
+    assert_mismatch(
+        check_error(r#"fn f() -> i64 { let s: String = "a"; s }"#, None),
+        span!(37, 38),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+}
+
+#[test]
+fn type_check_arguments() {
+    assert_mismatch(
+        check_error(r#"fn f(a: i64) { a } f("hello")"#, None),
+        span!(21, 28),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+}
+
+#[test]
+fn type_check_fields() {
+    assert_mismatch(
+        check_error(
+            r#"struct Point { x: i64, y: i64 } Point { x: 1, y: "two" }"#,
+            None,
+        ),
+        span!(49, 54),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+}
+
+#[test]
+fn type_check_missing_type() {
+    match check_error("let x: Missing = 1;", None) {
+        (span, MissingItem { .. }) => assert_eq!(span, span!(7, 14)),
+        actual => panic!("expected missing item, got {actual:?}"),
     }
 }
 
 #[test]
-fn deny_static_typing_field() {
-    assert_errors! {
-        "struct Struct { foo: Bar }",
-        span!(16, 24), Custom { error } => {
-            assert_eq!(error.to_string(), "Static typing on fields is not supported");
+#[cfg(feature = "doc")]
+fn type_check_native_signatures() -> Result<()> {
+    #[rune::function]
+    fn scale(value: i64, factor: i64) -> i64 {
+        value * factor
+    }
+
+    let module = || -> Result<Module> {
+        let mut m = Module::new();
+        m.function_meta(scale)?;
+        Ok(m)
+    };
+
+    let diagnostics = check("let n: i64 = scale(1, 2);", Some(module()?));
+    assert!(!diagnostics.has_error());
+
+    assert_mismatch(
+        check_error("let s: String = scale(1, 2);", Some(module()?)),
+        span!(16, 27),
+        rune::item!(::std::string::String),
+        rune::item!(::std::i64),
+    );
+
+    assert_mismatch(
+        check_error(r#"scale("one", 2)"#, Some(module()?)),
+        span!(6, 11),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn type_check_integers() {
+    assert_mismatch(
+        check_error("let x: u64 = 1;", None),
+        span!(13, 14),
+        rune::item!(::std::u64),
+        rune::item!(::std::i64),
+    );
+
+    assert_mismatch(
+        check_error("fn f(a: i64) { a } f(1u64)", None),
+        span!(21, 25),
+        rune::item!(::std::i64),
+        rune::item!(::std::u64),
+    );
+}
+
+#[test]
+fn type_check_methods() {
+    const COUNTER: &str = r#"
+        struct Counter { value: i64 }
+
+        impl Counter {
+            fn add(self, amount: i64) -> i64 {
+                self.value + amount
+            }
+        }
+
+        let counter: Counter = Counter { value: 1 };
+    "#;
+
+    let diagnostics = check(&format!("{COUNTER} let n: i64 = counter.add(2);"), None);
+    assert!(!diagnostics.has_error());
+
+    let source = format!(r#"{COUNTER} counter.add("two")"#);
+    let start = source.find(r#""two""#).expect("missing argument");
+
+    assert_mismatch(
+        check_error(&source, None),
+        Span::new(start, start + 5),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+
+    let source = format!(r#"{COUNTER} let s: String = counter.add(2);"#);
+    let start = source.find("counter.add").expect("missing call");
+
+    assert_mismatch(
+        check_error(&source, None),
+        Span::new(start, start + 14),
+        rune::item!(::std::string::String),
+        rune::item!(::std::i64),
+    );
+}
+
+#[test]
+#[cfg(feature = "doc")]
+fn type_check_native_methods() -> Result<()> {
+    #[derive(Any)]
+    struct Counter {
+        value: i64,
+    }
+
+    impl Counter {
+        #[rune::function(path = Self::new)]
+        fn new() -> Counter {
+            Counter { value: 0 }
+        }
+
+        #[rune::function(instance)]
+        fn add(&mut self, amount: i64) -> i64 {
+            self.value += amount;
+            self.value
         }
     }
+
+    let module = || -> Result<Module> {
+        let mut m = Module::new();
+        m.ty::<Counter>()?;
+        m.function_meta(Counter::new)?;
+        m.function_meta(Counter::add)?;
+        Ok(m)
+    };
+
+    const NEW: &str = "let counter: Counter = Counter::new();";
+
+    // Integers are converted when passed into native functions.
+    let diagnostics = check(
+        &format!("{NEW} let n: i64 = counter.add(1u64);"),
+        Some(module()?),
+    );
+    assert!(!diagnostics.has_error());
+
+    let source = format!(r#"{NEW} counter.add("one")"#);
+    let start = source.find(r#""one""#).expect("missing argument");
+
+    assert_mismatch(
+        check_error(&source, Some(module()?)),
+        Span::new(start, start + 5),
+        rune::item!(::std::i64),
+        rune::item!(::std::string::String),
+    );
+
+    let source = format!("{NEW} let s: String = counter.add(1);");
+    let start = source.find("counter.add").expect("missing call");
+
+    assert_mismatch(
+        check_error(&source, Some(module()?)),
+        Span::new(start, start + 14),
+        rune::item!(::std::string::String),
+        rune::item!(::std::i64),
+    );
+
+    Ok(())
 }
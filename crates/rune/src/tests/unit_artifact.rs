@@ -0,0 +1,93 @@
+prelude!();
+
+use crate::Unit;
+use runtime::unit::ArtifactError;
+
+fn build(context: &Context, source: &str) -> Result<Unit> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+    Ok(prepare(&mut sources).with_context(context).build()?)
+}
+
+#[test]
+fn round_trip() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = context.runtime()?;
+
+    let unit = build(
+        &context,
+        "pub fn main(n) { let values = [n, 2, 3]; values[0] + values[1] + values[2] }",
+    )?;
+
+    let bytes = unit.encode_artifact(false)?;
+    let unit = Unit::decode_artifact(&bytes, &runtime)?;
+    assert!(unit.debug_info().is_some());
+
+    let mut vm = Vm::new(Arc::try_new(runtime)?, Arc::try_new(unit)?);
+    let output: i64 = from_value(vm.call(["main"], (1i64,))?)?;
+    assert_eq!(output, 6);
+    Ok(())
+}
+
+#[test]
+fn strip_debug() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = context.runtime()?;
+
+    let unit = build(&context, "pub fn main() { 42 }")?;
+
+    let full = unit.encode_artifact(false)?;
+    let stripped = unit.encode_artifact(true)?;
+    assert!(stripped.len() < full.len());
+
+    let unit = Unit::decode_artifact(&stripped, &runtime)?;
+    assert!(unit.debug_info().is_none());
+
+    let mut vm = Vm::new(Arc::try_new(runtime)?, Arc::try_new(unit)?);
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 42);
+    Ok(())
+}
+
+#[test]
+fn corrupted() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = context.runtime()?;
+
+    let unit = build(&context, "pub fn main() { 42 }")?;
+    let bytes = unit.encode_artifact(false)?;
+
+    let mut flipped = bytes.try_clone()?;
+    let last = flipped.len() - 1;
+    flipped[last] ^= 0xff;
+
+    let error = Unit::decode_artifact(&flipped, &runtime).unwrap_err();
+    assert!(error.to_string().contains("checksum"), "{error}");
+
+    let error = Unit::decode_artifact(&bytes[..bytes.len() - 1], &runtime).unwrap_err();
+    assert!(error.to_string().contains("truncated"), "{error}");
+
+    let error = Unit::decode_artifact(b"NOTAUNIT", &runtime).unwrap_err();
+    assert!(error.to_string().contains("Not a unit"), "{error}");
+    Ok(())
+}
+
+#[test]
+fn missing_native_function() -> Result<()> {
+    let mut module = Module::new();
+    module.function("native", || 42i64).build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let unit = build(&context, "pub fn main() { native() }")?;
+    let bytes = unit.encode_artifact(false)?;
+
+    let runtime = context.runtime()?;
+    assert!(Unit::decode_artifact(&bytes, &runtime).is_ok());
+
+    let runtime = Context::with_default_modules()?.runtime()?;
+    let error: ArtifactError = Unit::decode_artifact(&bytes, &runtime).unwrap_err();
+    assert_eq!(error.missing_functions(), &[Hash::type_hash(["native"])]);
+    Ok(())
+}
@@ -0,0 +1,119 @@
+prelude!();
+
+use crate::Unit;
+use runtime::unit::UnresolvedKind;
+
+#[derive(Any)]
+#[rune(item = ::thing)]
+struct Thing;
+
+fn thing_context() -> Result<Context> {
+    let mut module = Module::with_crate("thing")?;
+    module.ty::<Thing>()?;
+    module.function("new", || Thing).build()?;
+    module.associated_function("frobnicate", |_this: &Thing| 1i64)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    Ok(context)
+}
+
+#[test]
+fn resolved_script_types() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            struct Counter { n }
+
+            impl Counter {
+                fn get(self) {
+                    self.n
+                }
+            }
+
+            pub fn main() {
+                let total = Counter { n: 1 }.get();
+
+                for value in [1, 2, 3] {
+                    total += value;
+                }
+
+                let f = |n| n + total;
+                f([1, 2].len())
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    unit.link(&context.runtime()?)?;
+    Ok(())
+}
+
+#[test]
+fn unresolved_with_spans() -> Result<()> {
+    const SOURCE: &str = r#"
+pub fn main() {
+    let t = thing::new();
+    t.frobnicate()
+}
+"#;
+
+    let context = thing_context()?;
+
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    unit.link(&context.runtime()?)?;
+
+    let runtime = Context::with_default_modules()?.runtime()?;
+    let error = unit.link(&runtime).unwrap_err();
+    let unresolved = error.unresolved();
+
+    assert_eq!(unresolved.len(), 2);
+
+    assert_eq!(
+        unresolved[0].kind,
+        UnresolvedKind::Function {
+            hash: Hash::type_hash(rune::item!(::thing::new))
+        }
+    );
+
+    assert!(matches!(
+        unresolved[1].kind,
+        UnresolvedKind::InstanceFunction { .. }
+    ));
+
+    let (id, span) = unresolved[0].location.expect("missing location");
+    assert_eq!(id, source_id);
+    assert!(SOURCE[span.range()].contains("thing::new"));
+
+    let (id, span) = unresolved[1].location.expect("missing location");
+    assert_eq!(id, source_id);
+    assert!(SOURCE[span.range()].contains("frobnicate"));
+    Ok(())
+}
+
+#[test]
+fn stripped_debug_info() -> Result<()> {
+    let context = thing_context()?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                thing::new()
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Unit::from_parts(unit.logic().try_clone()?, None)?;
+
+    let runtime = Context::with_default_modules()?.runtime()?;
+    let error = unit.link(&runtime).unwrap_err();
+
+    assert_eq!(error.unresolved().len(), 1);
+    assert!(error.unresolved()[0].location.is_none());
+    Ok(())
+}
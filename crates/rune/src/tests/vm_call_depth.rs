@@ -0,0 +1,162 @@
+prelude!();
+
+use crate::tests::{sources, vm};
+
+use runtime::VmError;
+
+const SOURCE: &str = r#"
+fn recurse(n) {
+    if n == 0 {
+        return 0;
+    }
+
+    recurse(n - 1) + 1
+}
+
+pub fn main(n) {
+    recurse(n)
+}
+"#;
+
+fn setup(source: &str, max_call_depth: Option<usize>) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = sources(source);
+    let mut diagnostics = Diagnostics::new();
+    let mut vm = vm(&context, &mut sources, &mut diagnostics, false)?;
+    vm.set_max_call_depth(max_call_depth);
+    Ok(vm)
+}
+
+#[track_caller]
+fn assert_stack_overflow(error: VmError, expected: &Item) {
+    match error.into_kind() {
+        VmErrorKind::StackOverflow {
+            max_call_depth,
+            item,
+        } => {
+            assert_eq!(max_call_depth, 16);
+            assert_eq!(item.as_deref(), Some(expected));
+        }
+        actual => panic!("expected stack overflow, got {actual}"),
+    }
+}
+
+#[test]
+fn within_call_depth() -> Result<()> {
+    let mut vm = setup(SOURCE, Some(16))?;
+    let output: i64 = from_value(vm.call(["main"], (10i64,))?)?;
+    assert_eq!(output, 10);
+    Ok(())
+}
+
+#[test]
+fn unlimited_call_depth() -> Result<()> {
+    let mut vm = setup(SOURCE, None)?;
+    let output: i64 = from_value(vm.call(["main"], (1000i64,))?)?;
+    assert_eq!(output, 1000);
+    Ok(())
+}
+
+#[test]
+fn stack_overflow() -> Result<()> {
+    let mut vm = setup(SOURCE, Some(16))?;
+    let error = vm.call(["main"], (100i64,)).unwrap_err();
+    assert_stack_overflow(error, rune::item!(recurse));
+    Ok(())
+}
+
+#[test]
+fn async_stack_overflow() -> Result<()> {
+    let mut vm = setup(
+        r#"
+        async fn recurse(n) {
+            if n == 0 {
+                return 0;
+            }
+
+            recurse(n - 1).await + 1
+        }
+
+        pub async fn main(n) {
+            recurse(n).await
+        }
+        "#,
+        Some(16),
+    )?;
+
+    let output: i64 = from_value(block_on(vm.async_call(["main"], (10i64,)))?)?;
+    assert_eq!(output, 10);
+
+    let error = block_on(vm.async_call(["main"], (100i64,))).unwrap_err();
+    assert_stack_overflow(error, rune::item!(recurse));
+    Ok(())
+}
+
+#[test]
+fn generator_stack_overflow() -> Result<()> {
+    let mut vm = setup(
+        r#"
+        fn recurse(n) {
+            if n > 0 {
+                for value in recurse(n - 1) {
+                    yield value;
+                }
+            }
+
+            yield n;
+        }
+
+        pub fn main(n) {
+            let count = 0;
+
+            for _ in recurse(n) {
+                count += 1;
+            }
+
+            count
+        }
+        "#,
+        Some(16),
+    )?;
+
+    let output: i64 = from_value(vm.call(["main"], (10i64,))?)?;
+    assert_eq!(output, 11);
+
+    let error = vm.call(["main"], (100i64,)).unwrap_err();
+    assert_stack_overflow(error, rune::item!(recurse));
+    Ok(())
+}
+
+#[test]
+fn native_stack_overflow() -> Result<()> {
+    let mut vm = setup(
+        r#"
+        fn recurse(n) {
+            if n == 0 {
+                return 0;
+            }
+
+            let values = [n];
+            values.iter().map(|n| recurse(n - 1)).next().unwrap() + 1
+        }
+
+        pub fn main(n) {
+            recurse(n)
+        }
+        "#,
+        Some(16),
+    )?;
+
+    let output: i64 = from_value(vm.call(["main"], (5i64,))?)?;
+    assert_eq!(output, 5);
+
+    let error = vm.call(["main"], (100i64,)).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::StackOverflow {
+            max_call_depth: 16,
+            ..
+        }
+    ));
+    Ok(())
+}
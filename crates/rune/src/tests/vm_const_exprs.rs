@@ -1,5 +1,8 @@
 prelude!();
 
+use compile::IrErrorKind;
+use ErrorKind::*;
+
 macro_rules! test_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -262,3 +265,172 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_match() {
+    let result: String = eval(
+        r#"
+        const fn name(value) {
+            match value {
+                0 => "zero",
+                "one" => "string",
+                [a, b] => `pair ${a} ${b}`,
+                (a, ..) => `tuple ${a}`,
+                n if n < 0 => "negative",
+                _ => "many",
+            }
+        }
+
+        const VALUE = `${name(0)}, ${name(-1)}, ${name("one")}, ${name([1, 2])}, ${name((3, 4))}, ${name(10)}`;
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, "zero, negative, string, pair 1 2, tuple 3, many");
+}
+
+#[test]
+fn test_const_match_unmatched() {
+    assert_errors! {
+        "const fn name(value) { match value { 0 => 1 } } const VALUE = name(1); VALUE",
+        span!(23, 45), Ir(IrErrorKind::UnmatchedPattern)
+    };
+}
+
+#[test]
+fn test_const_for() {
+    let result: i64 = eval(
+        r#"
+        const fn sum(values) {
+            let total = 0;
+
+            for value in values {
+                total += value;
+            }
+
+            total
+        }
+
+        const VALUE = sum([1, 2, 3]) + sum((4, 5));
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, 15);
+
+    let result: i64 = eval(
+        r#"
+        const fn ranges() {
+            let total = 0;
+
+            for n in 0..=10 {
+                if n == 5 {
+                    break;
+                }
+
+                total += n;
+            }
+
+            for n in 0..3 {
+                total += n * 100;
+            }
+
+            total
+        }
+
+        const VALUE = ranges();
+
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, 310);
+
+    let result: i64 = eval(
+        r#"
+        const fn labeled() {
+            let total = 0;
+
+            'outer: for n in 0..10 {
+                for m in 0..10 {
+                    if m == 2 {
+                        break 'outer;
+                    }
+
+                    total += n * 10 + m;
+                }
+            }
+
+            total
+        }
+
+        const VALUE = labeled();
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, 1);
+}
+
+#[test]
+fn test_const_index() {
+    let result: i64 = eval(
+        r#"
+        const VALUES = [10, 20, 30];
+        const OBJECT = #{ "a": 1, "b": 2 };
+        const VALUE = VALUES[1] + (3, 4)[0] + OBJECT["b"];
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, 25);
+}
+
+#[test]
+fn test_const_string_methods() {
+    let result: String = eval(
+        r#"
+        const NAME = "  Hello World  ".trim();
+        const VALUE = `${NAME.to_uppercase()} ${NAME.len()} ${NAME.starts_with("Hello")} ${NAME.replace("World", "Rune")}`;
+        VALUE
+        "#,
+    );
+
+    assert_eq!(result, "HELLO WORLD 11 true Hello Rune");
+}
+
+#[rune::function]
+fn double(value: i64) -> i64 {
+    value * 2
+}
+
+#[rune::function]
+fn triple(value: i64) -> i64 {
+    value * 3
+}
+
+#[test]
+fn test_const_native_function() -> Result<()> {
+    let mut m = Module::new();
+    m.function_meta(double)?.const_safe();
+    m.function_meta(triple)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let result: i64 = run(&context, "const VALUE = double(21); VALUE", (), true)?;
+    assert_eq!(result, 42);
+
+    let Err(error) = run::<i64>(&context, "const VALUE = triple(21); VALUE", (), true) else {
+        panic!("expected calling a function not marked as const-safe to fail");
+    };
+
+    assert!(
+        error
+            .to_string()
+            .contains("can't be called in constant contexts"),
+        "unexpected error: {error}"
+    );
+
+    Ok(())
+}
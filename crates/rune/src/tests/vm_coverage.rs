@@ -0,0 +1,76 @@
+prelude!();
+
+use runtime::{Coverage, DebugLocation};
+
+const SOURCE: &str = r#"
+async fn double(n) {
+    n * 2
+}
+
+pub fn unused(n) {
+    n + 1
+}
+
+pub async fn main() {
+    double(2).await + double(3).await
+}
+"#;
+
+fn hits(lines: &[(DebugLocation, usize)], line: usize) -> Option<usize> {
+    lines
+        .iter()
+        .find(|(l, _)| l.line == line)
+        .map(|&(_, hits)| hits)
+}
+
+#[test]
+fn records_across_async_calls() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+
+    let mut coverage = Coverage::new();
+    coverage.track(unit.clone())?;
+    let coverage = Arc::try_new(coverage)?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit.clone());
+    vm.set_coverage(Some(coverage.clone()));
+
+    let output: i64 = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(output, 10);
+
+    let lines = coverage.lines(&unit, &sources)?;
+
+    assert_eq!(hits(&lines, 2), Some(2));
+    assert_eq!(hits(&lines, 6), Some(0));
+    assert_eq!(hits(&lines, 10), Some(1));
+    Ok(())
+}
+
+#[test]
+fn untracked_unit() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+
+    let coverage = Arc::try_new(Coverage::new())?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit.clone());
+    vm.set_coverage(Some(coverage.clone()));
+    block_on(vm.async_call(["main"], ()))?;
+
+    assert_eq!(coverage.units().count(), 0);
+    assert!(coverage
+        .lines(&unit, &sources)?
+        .iter()
+        .all(|&(_, hits)| hits == 0));
+    Ok(())
+}
@@ -0,0 +1,73 @@
+prelude!();
+
+use std::time::Duration;
+
+use runtime::{VmHaltInfo, VmOutcome};
+
+fn setup() -> Result<Vm> {
+    let mut module = Module::new();
+
+    module
+        .function("stall", || async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            41i64
+        })
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub async fn main() {
+                let value = stall().await;
+                value + 1
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(runtime, Arc::try_new(unit)?))
+}
+
+#[tokio::test]
+async fn deadline_limits_and_resumes() -> Result<()> {
+    let mut vm = setup()?;
+    let mut execution = vm.execute(["main"], ())?;
+
+    let outcome = execution
+        .resume()
+        .with_deadline(tokio::time::sleep(Duration::from_millis(10)))
+        .await?;
+
+    assert!(matches!(outcome, VmOutcome::Limited));
+
+    let value = execution
+        .resume()
+        .with_deadline(tokio::time::sleep(Duration::from_secs(10)))
+        .await?
+        .into_complete()?;
+
+    assert_eq!(value.as_signed()?, 42);
+    Ok(())
+}
+
+#[tokio::test]
+async fn deadline_complete_errors() -> Result<()> {
+    let mut vm = setup()?;
+    let mut execution = vm.execute(["main"], ())?;
+
+    let error = execution
+        .async_complete_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Limited
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,214 @@
+prelude!();
+
+use runtime::{budget, DebugOutcome, DebugStep, Debugger};
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    let c = a + b;
+    c
+}
+
+pub fn main() {
+    let a = 1;
+    let b = add(a, 2);
+    let c = add(b, 3);
+    c
+}
+"#;
+
+fn setup() -> Result<(Sources, Arc<rune::Unit>, Context)> {
+    setup_source(SOURCE)
+}
+
+fn setup_source(source: &str) -> Result<(Sources, Arc<rune::Unit>, Context)> {
+    setup_with(source, &Options::default())
+}
+
+fn setup_with(source: &str, options: &Options) -> Result<(Sources, Arc<rune::Unit>, Context)> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(options)
+        .build()?;
+
+    Ok((sources, Arc::try_new(unit)?, context))
+}
+
+fn line(debugger: &Debugger, vm: &Vm) -> usize {
+    debugger.location(vm.ip()).expect("missing location").line
+}
+
+#[test]
+fn breakpoints_and_locals() -> Result<()> {
+    let (sources, unit, context) = setup()?;
+    let source_id = sources.source_ids().next().expect("missing source");
+
+    let mut debugger = Debugger::new(&unit, &sources)?;
+    let id = debugger.add_breakpoint(source_id, 9)?.expect("breakpoint");
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+    let mut execution = vm.execute(["main"], ())?;
+
+    let outcome = block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+    assert!(matches!(outcome, DebugOutcome::Breakpoint(hit) if hit == id));
+    assert_eq!(line(&debugger, execution.vm()), 9);
+
+    {
+        let locals = execution.vm().locals()?;
+        let names = locals.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(locals[1].1.as_integer::<i64>()?, 3);
+    }
+
+    let outcome = block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+
+    let DebugOutcome::Complete(value) = outcome else {
+        panic!("expected completion, got {outcome:?}");
+    };
+
+    assert_eq!(value.as_integer::<i64>()?, 6);
+    Ok(())
+}
+
+#[test]
+fn step_into_over_and_out() -> Result<()> {
+    let (sources, unit, context) = setup()?;
+    let source_id = sources.source_ids().next().expect("missing source");
+
+    let mut debugger = Debugger::new(&unit, &sources)?;
+    debugger.add_breakpoint(source_id, 8)?.expect("breakpoint");
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+    let mut execution = vm.execute(["main"], ())?;
+
+    block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+    assert_eq!(line(&debugger, execution.vm()), 8);
+
+    let depth = execution.vm().call_frames().len();
+
+    block_on(execution.debug(&mut debugger, DebugStep::Into))?;
+    assert_eq!(line(&debugger, execution.vm()), 2);
+    assert_eq!(execution.vm().call_frames().len(), depth + 1);
+
+    {
+        let locals = execution.vm().locals()?;
+        let names = locals.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    block_on(execution.debug(&mut debugger, DebugStep::Out))?;
+    assert_eq!(execution.vm().call_frames().len(), depth);
+    assert_eq!(line(&debugger, execution.vm()), 9);
+
+    // Stepping over the call lands on the return of `main`, which is
+    // associated with the whole function.
+    block_on(execution.debug(&mut debugger, DebugStep::Over))?;
+    assert_eq!(line(&debugger, execution.vm()), 6);
+    assert_eq!(execution.vm().call_frames().len(), depth);
+    Ok(())
+}
+
+#[test]
+fn locals_out_of_scope() -> Result<()> {
+    let (sources, unit, context) = setup_source(
+        r#"
+pub fn main() {
+    let a = 1;
+    {
+        let inner = a + 1;
+    }
+    let b = a + 2;
+    b
+}
+"#,
+    )?;
+
+    let source_id = sources.source_ids().next().expect("missing source");
+
+    let mut debugger = Debugger::new(&unit, &sources)?;
+    debugger.add_breakpoint(source_id, 6)?.expect("breakpoint");
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+    let mut execution = vm.execute(["main"], ())?;
+
+    block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+    assert_eq!(line(&debugger, execution.vm()), 6);
+
+    let locals = execution.vm().locals()?;
+    let names = locals.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    assert_eq!(names, ["a"]);
+    Ok(())
+}
+
+#[test]
+fn locals_out_of_scope_optimized() -> Result<()> {
+    // The dead branch is removed by the optimizer, which moves the end of the
+    // scope of `inner`.
+    let mut options = Options::default();
+    options.parse_option("lowering=1")?;
+
+    let (sources, unit, context) = setup_with(
+        r#"
+pub fn main() {
+    let a = 1;
+    if false {
+        let x = a * 100;
+        x;
+    }
+    {
+        let inner = a + 1;
+        inner;
+    }
+    let b = a + 2;
+    b
+}
+"#,
+        &options,
+    )?;
+
+    let source_id = sources.source_ids().next().expect("missing source");
+
+    let mut debugger = Debugger::new(&unit, &sources)?;
+    debugger.add_breakpoint(source_id, 11)?.expect("breakpoint");
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+    let mut execution = vm.execute(["main"], ())?;
+
+    block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+    assert_eq!(line(&debugger, execution.vm()), 11);
+
+    let locals = execution.vm().locals()?;
+    let names = locals.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    assert_eq!(names, ["a"]);
+    Ok(())
+}
+
+#[test]
+fn host_budget() -> Result<()> {
+    let (sources, unit, context) = setup()?;
+
+    let mut debugger = Debugger::new(&unit, &sources)?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, unit);
+    let mut execution = vm.execute(["main"], ())?;
+
+    let outcome = block_on(budget::with(
+        2,
+        execution.debug(&mut debugger, DebugStep::Continue),
+    ))?;
+
+    assert!(matches!(outcome, DebugOutcome::Limited));
+
+    let outcome = block_on(execution.debug(&mut debugger, DebugStep::Continue))?;
+
+    let DebugOutcome::Complete(value) = outcome else {
+        panic!("expected completion, got {outcome:?}");
+    };
+
+    assert_eq!(value.as_integer::<i64>()?, 6);
+    Ok(())
+}
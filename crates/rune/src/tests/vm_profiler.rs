@@ -0,0 +1,77 @@
+prelude!();
+
+use runtime::{Profile, ProfileFrame, Profiler};
+
+const SOURCE: &str = r#"
+async fn work(n) {
+    let out = [];
+
+    for i in 0..n {
+        out.push(i);
+    }
+
+    out.len()
+}
+
+pub async fn main() {
+    work(10).await + work(20).await
+}
+"#;
+
+fn position(profile: &Profile, frame: impl Fn(&ProfileFrame) -> bool) -> Option<usize> {
+    profile.frames.iter().position(frame)
+}
+
+fn function(name: &Item) -> impl Fn(&ProfileFrame) -> bool + '_ {
+    move |frame| matches!(frame, ProfileFrame::Function(item) if *item == *name)
+}
+
+#[test]
+fn attributes_functions_and_natives() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let profiler = Arc::try_new(Profiler::new())?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+    vm.set_profiler(Some(profiler.clone()));
+
+    let output: i64 = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(output, 30);
+
+    let profile = profiler.profile()?;
+
+    let work = position(&profile, function(rune::item!(work))).expect("missing work");
+    let main = position(&profile, function(rune::item!(main))).expect("missing main");
+
+    let push = Hash::associated_function(runtime::Vec::HASH, "push");
+    let native = position(
+        &profile,
+        |f| matches!(f, ProfileFrame::Native(h) if *h == push),
+    )
+    .expect("missing Vec::push");
+
+    // Async functions run on separate virtual machines, so `work` is a root.
+    let instructions = profile
+        .stacks
+        .iter()
+        .filter(|s| s.frames.first() == Some(&work))
+        .map(|s| s.instructions)
+        .sum::<u64>();
+
+    assert!(instructions > 0);
+
+    let pushes = profile
+        .stacks
+        .iter()
+        .filter(|s| s.frames == [work, native])
+        .count();
+
+    assert_eq!(pushes, 1);
+
+    assert!(profile.stacks.iter().any(|s| s.frames.as_slice() == [main]));
+    Ok(())
+}
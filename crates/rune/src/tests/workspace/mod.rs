@@ -5,13 +5,22 @@ prelude!();
 use std::path::PathBuf;
 
 use crate::workspace::{
-    Diagnostics, FileSourceLoader, FoundKind, Manifest, ManifestLoader, WorkspaceFilter,
-    MANIFEST_FILE,
+    Diagnostic, Diagnostics, FileSourceLoader, FoundKind, Manifest, ManifestLoader,
+    WorkspaceFilter, MANIFEST_FILE,
 };
 
 fn load_manifest() -> Manifest {
+    let (manifest, diagnostics) = load_manifest_in("");
+    assert!(!diagnostics.has_errors());
+    manifest
+}
+
+/// Load the manifest in the given directory, relative to the workspace
+/// fixtures.
+fn load_manifest_in(dir: &str) -> (Manifest, Diagnostics) {
     let manifest_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("src/tests/workspace")
+        .join(dir)
         .join(MANIFEST_FILE);
 
     let source = Source::from_path(&manifest_file).unwrap();
@@ -30,8 +39,9 @@ fn load_manifest() -> Manifest {
         &mut manifest,
     );
     loader.load_manifest().unwrap();
+    loader.resolve_dependencies().unwrap();
 
-    manifest
+    (manifest, diagnostics)
 }
 
 #[test]
@@ -273,3 +283,95 @@ pub fn manifest_all_filtered() {
 
     assert_eq!(found_packages.as_slice(), EXPECTED);
 }
+
+#[test]
+pub fn manifest_dependencies() {
+    let manifest = load_manifest();
+
+    let a = manifest.packages.iter().find(|p| p.name == "a").unwrap();
+    let b = manifest.packages.iter().find(|p| p.name == "b").unwrap();
+
+    assert_eq!(a.dependencies.len(), 1);
+    assert_eq!(a.dependencies[0].name, "b");
+    assert!(b.dependencies.is_empty());
+
+    let libs = manifest.find_dependency_libs(a).unwrap();
+    let libs = libs
+        .iter()
+        .map(|found| (found.kind, found.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(libs, [(FoundKind::Library, "b")]);
+
+    let found_packages = manifest
+        .find_by_kind(WorkspaceFilter::Name("a"), FoundKind::Binary)
+        .unwrap();
+    assert_eq!(found_packages.len(), 1);
+    assert_eq!(found_packages[0].dependencies.len(), 1);
+}
+
+#[test]
+pub fn manifest_dependency_mounted() {
+    let manifest = load_manifest();
+    let a = manifest.packages.iter().find(|p| p.name == "a").unwrap();
+    let libs = manifest.find_dependency_libs(a).unwrap();
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::memory("pub fn main() { b::get_name() }").unwrap())
+        .unwrap();
+
+    for lib in &libs {
+        let source = Source::from_path(&lib.path).unwrap();
+        sources.insert_dependency(&lib.name, source).unwrap();
+    }
+
+    let unit = prepare(&mut sources).build().unwrap();
+    let mut vm = Vm::without_runtime(Arc::try_new(unit).unwrap()).unwrap();
+    let output: String = from_value(vm.call(["main"], ()).unwrap()).unwrap();
+    assert_eq!(output, "b/lib");
+}
+
+#[test]
+pub fn manifest_external_dependency() {
+    let (manifest, diagnostics) = load_manifest_in("external/app");
+
+    // The dependency is not a member, so its `[workspace]` section is ignored
+    // and none of its targets are found.
+    let packages = manifest
+        .packages
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(packages, ["app"]);
+
+    let dependencies = manifest
+        .dependencies
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(dependencies, ["lib"]);
+
+    let app = &manifest.packages[0];
+    let libs = manifest.find_dependency_libs(app).unwrap();
+    let libs = libs
+        .iter()
+        .map(|found| (found.kind, found.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(libs, [(FoundKind::Library, "lib")]);
+
+    let all = manifest.find_all(WorkspaceFilter::Name("lib")).unwrap();
+    assert!(all.is_empty());
+
+    let errors = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|d| match d {
+            Diagnostic::Fatal(fatal) => fatal.error().to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        ["Dependency `lib` requires version `^0.2`, but version `0.1.0` was found"]
+    );
+}
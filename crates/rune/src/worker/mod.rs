@@ -2,6 +2,7 @@
 
 mod import;
 mod task;
+mod trait_impl;
 mod wildcard_import;
 
 use rust_alloc::rc::Rc;
@@ -9,15 +10,15 @@ use rust_alloc::rc::Rc;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, HashMap, Vec, VecDeque};
 use crate::ast::{self, Kind, Span, Spanned};
-use crate::compile::{self, ItemId, ModId, WithSpan};
+use crate::compile::{self, ItemId, ItemMeta, ModId, WithSpan};
 use crate::grammar::{Node, Stream};
 use crate::indexing::{index, index2};
 use crate::internal_macros::resolve_context;
 use crate::macros::{MacroContext, TokenStream};
 use crate::parse::Resolve;
 use crate::query::{
-    BuiltInLiteral, BuiltInMacro2, DeferEntry, ExpandMacroBuiltin, ExpandedMacro,
-    GenericsParameters, ImplItem, ImplItemKind, Query, Used,
+    Build, BuildEntry, BuiltInLiteral, BuiltInMacro2, DeferEntry, ExpandMacroBuiltin,
+    ExpandedMacro, GenericsParameters, ImplItem, ImplItemKind, Query, Used,
 };
 use crate::SourceId;
 
@@ -267,7 +268,11 @@ impl<'a, 'arena> Worker<'a, 'arena> {
         // we might introduce bounds which would not be communicated
         // through `Self`.
         match this.kind {
-            ImplItemKind::Ast { path, functions } => {
+            ImplItemKind::Ast {
+                path,
+                trait_,
+                functions,
+            } => {
                 let named = self
                     .q
                     .convert_path_with(&path, true, Used::Used, Used::Unused)?;
@@ -287,12 +292,33 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                     GenericsParameters::default(),
                 )?;
 
+                let trait_impl = match &trait_ {
+                    Some(trait_path) => Some(trait_impl::bind(
+                        &mut self.q,
+                        this.location,
+                        trait_path,
+                        meta.hash,
+                        &functions,
+                    )?),
+                    None => None,
+                };
+
                 let empty = Rc::default();
                 let mut idx = indexer!(&empty, named, meta);
 
                 for f in functions {
                     index::item_fn(&mut idx, f)?;
                 }
+
+                if let Some(trait_impl) = trait_impl {
+                    self.q.inner.queue.try_push_back(BuildEntry {
+                        item_meta: ItemMeta {
+                            location: this.location,
+                            ..meta.item_meta
+                        },
+                        build: Build::TraitImpl(trait_impl),
+                    })?;
+                }
             }
             ImplItemKind::Node { path, functions } => {
                 let named =
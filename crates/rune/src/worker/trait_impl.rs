@@ -0,0 +1,374 @@
+//! Binding of `impl Trait for Type` blocks.
+
+use crate::alloc::prelude::*;
+use crate::alloc::Vec;
+use crate::ast::{self, Spanned};
+use crate::compile::{self, meta, ErrorKind, Location};
+use crate::internal_macros::resolve_context;
+use crate::item::ComponentRef;
+use crate::parse::Resolve;
+use crate::query::{GenericsParameters, Query, TraitImpl, Used};
+use crate::runtime::Protocol;
+use crate::{Hash, Item};
+
+/// A function of a native trait which can be implemented by scripts.
+struct NativeFunction {
+    /// The name of the function.
+    name: &'static str,
+    /// The protocol the function is bound to.
+    protocol: Option<&'static Protocol>,
+    /// Whether the function must be implemented.
+    required: bool,
+}
+
+/// A native trait which can be implemented by scripts.
+struct NativeTrait {
+    /// The path of the trait.
+    path: &'static [ComponentRef<'static>],
+    /// Functions which can be implemented.
+    functions: &'static [NativeFunction],
+    /// Protocols bound to a function which is provided through another trait
+    /// implementation.
+    derived: &'static [(&'static Protocol, &'static str)],
+    /// Whether the trait is an iterator.
+    is_iterator: bool,
+}
+
+const fn required(name: &'static str, protocol: &'static Protocol) -> NativeFunction {
+    NativeFunction {
+        name,
+        protocol: Some(protocol),
+        required: true,
+    }
+}
+
+const fn provided(name: &'static str, protocol: Option<&'static Protocol>) -> NativeFunction {
+    NativeFunction {
+        name,
+        protocol,
+        required: false,
+    }
+}
+
+/// A trait in `::std::ops` which binds a single function to an operator
+/// protocol.
+///
+/// This is a macro so that the path of the trait is a promoted constant.
+macro_rules! operator {
+    ($name:literal, $functions:expr $(,)?) => {
+        NativeTrait {
+            path: &[
+                ComponentRef::Crate("std"),
+                ComponentRef::Str("ops"),
+                ComponentRef::Str($name),
+            ],
+            functions: $functions,
+            derived: &[],
+            is_iterator: false,
+        }
+    };
+}
+
+static NATIVE_TRAITS: &[NativeTrait] = &[
+    operator!("Neg", &[required("neg", &Protocol::NEG)]),
+    operator!("Add", &[required("add", &Protocol::ADD)]),
+    operator!("Sub", &[required("sub", &Protocol::SUB)]),
+    operator!("Mul", &[required("mul", &Protocol::MUL)]),
+    operator!("Div", &[required("div", &Protocol::DIV)]),
+    operator!("Rem", &[required("rem", &Protocol::REM)]),
+    operator!("BitAnd", &[required("bitand", &Protocol::BIT_AND)]),
+    operator!("BitOr", &[required("bitor", &Protocol::BIT_OR)]),
+    operator!("BitXor", &[required("bitxor", &Protocol::BIT_XOR)]),
+    operator!("Shl", &[required("shl", &Protocol::SHL)]),
+    operator!("Shr", &[required("shr", &Protocol::SHR)]),
+    operator!(
+        "AddAssign",
+        &[required("add_assign", &Protocol::ADD_ASSIGN)],
+    ),
+    operator!(
+        "SubAssign",
+        &[required("sub_assign", &Protocol::SUB_ASSIGN)],
+    ),
+    operator!(
+        "MulAssign",
+        &[required("mul_assign", &Protocol::MUL_ASSIGN)],
+    ),
+    operator!(
+        "DivAssign",
+        &[required("div_assign", &Protocol::DIV_ASSIGN)],
+    ),
+    operator!(
+        "RemAssign",
+        &[required("rem_assign", &Protocol::REM_ASSIGN)],
+    ),
+    operator!(
+        "BitAndAssign",
+        &[required("bitand_assign", &Protocol::BIT_AND_ASSIGN)],
+    ),
+    operator!(
+        "BitOrAssign",
+        &[required("bitor_assign", &Protocol::BIT_OR_ASSIGN)],
+    ),
+    operator!(
+        "BitXorAssign",
+        &[required("bitxor_assign", &Protocol::BIT_XOR_ASSIGN)],
+    ),
+    operator!(
+        "ShlAssign",
+        &[required("shl_assign", &Protocol::SHL_ASSIGN)],
+    ),
+    operator!(
+        "ShrAssign",
+        &[required("shr_assign", &Protocol::SHR_ASSIGN)],
+    ),
+    operator!("Index", &[required("index_get", &Protocol::INDEX_GET)]),
+    operator!("IndexMut", &[required("index_set", &Protocol::INDEX_SET)]),
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("fmt"),
+            ComponentRef::Str("Display"),
+        ],
+        functions: &[required("fmt", &Protocol::DISPLAY_FMT)],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("cmp"),
+            ComponentRef::Str("PartialEq"),
+        ],
+        functions: &[required("eq", &Protocol::PARTIAL_EQ), provided("ne", None)],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("cmp"),
+            ComponentRef::Str("Eq"),
+        ],
+        functions: &[],
+        derived: &[(&Protocol::EQ, "eq")],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("cmp"),
+            ComponentRef::Str("PartialOrd"),
+        ],
+        functions: &[
+            required("partial_cmp", &Protocol::PARTIAL_CMP),
+            provided("lt", Some(&Protocol::LT)),
+            provided("le", Some(&Protocol::LE)),
+            provided("gt", Some(&Protocol::GT)),
+            provided("ge", Some(&Protocol::GE)),
+        ],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("cmp"),
+            ComponentRef::Str("Ord"),
+        ],
+        functions: &[
+            required("cmp", &Protocol::CMP),
+            provided("max", Some(&Protocol::MAX)),
+            provided("min", Some(&Protocol::MIN)),
+        ],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("clone"),
+            ComponentRef::Str("Clone"),
+        ],
+        functions: &[required("clone", &Protocol::CLONE)],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("iter"),
+            ComponentRef::Str("Iterator"),
+        ],
+        functions: &[
+            required("next", &Protocol::NEXT),
+            provided("nth", Some(&Protocol::NTH)),
+            provided("size_hint", Some(&Protocol::SIZE_HINT)),
+        ],
+        derived: &[],
+        is_iterator: true,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("iter"),
+            ComponentRef::Str("DoubleEndedIterator"),
+        ],
+        functions: &[
+            required("next_back", &Protocol::NEXT_BACK),
+            provided("nth_back", Some(&Protocol::NTH_BACK)),
+        ],
+        derived: &[],
+        is_iterator: false,
+    },
+    NativeTrait {
+        path: &[
+            ComponentRef::Crate("std"),
+            ComponentRef::Str("iter"),
+            ComponentRef::Str("ExactSizeIterator"),
+        ],
+        functions: &[required("len", &Protocol::LEN)],
+        derived: &[],
+        is_iterator: false,
+    },
+];
+
+/// Resolve the trait being implemented by an `impl Trait for Type` block and
+/// calculate how its functions should be bound for the implementing type.
+pub(super) fn bind(
+    q: &mut Query<'_, '_>,
+    location: Location,
+    trait_path: &ast::Path,
+    type_hash: Hash,
+    functions: &[ast::ItemFn],
+) -> compile::Result<TraitImpl> {
+    let named = q.convert_path_with(trait_path, true, Used::Used, Used::Unused)?;
+
+    if let Some((spanned, _)) = named.parameters.into_iter().flatten().next() {
+        return Err(compile::Error::new(
+            spanned.span(),
+            ErrorKind::UnsupportedGenerics,
+        ));
+    }
+
+    let meta = q.lookup_meta(
+        &Location::new(location.source_id, trait_path.span()),
+        named.item,
+        GenericsParameters::default(),
+    )?;
+
+    if !matches!(meta.kind, meta::Kind::Trait) {
+        return Err(compile::Error::expected_meta(
+            trait_path,
+            meta.info(q.pool)?,
+            "a trait",
+        ));
+    }
+
+    let mut names = Vec::new();
+
+    for f in functions {
+        names.try_push((f, f.name.resolve(resolve_context!(q))?))?;
+    }
+
+    let trait_item = q.pool.item(named.item);
+
+    let mut trait_impl = TraitImpl {
+        type_hash,
+        aliases: Vec::new(),
+        into_iter: false,
+    };
+
+    if let Some(trait_) = q.get_trait(named.item) {
+        for (f, name) in &names {
+            if !trait_.functions.iter().any(|t| *t.name == **name) {
+                return Err(compile::Error::new(
+                    f.name,
+                    ErrorKind::NotTraitFunction {
+                        name: (*name).try_into()?,
+                        trait_item: trait_item.try_to_owned()?,
+                    },
+                ));
+            }
+        }
+
+        for function in &trait_.functions {
+            if names.iter().any(|(_, name)| **name == *function.name) {
+                continue;
+            }
+
+            if !function.is_default {
+                return Err(compile::Error::new(
+                    location.span,
+                    ErrorKind::MissingTraitFunction {
+                        name: function.name.try_clone()?,
+                        trait_item: trait_item.try_to_owned()?,
+                    },
+                ));
+            }
+
+            let target = trait_item.extended(&*function.name)?;
+
+            trait_impl.aliases.try_push((
+                Hash::associated_function(type_hash, &*function.name),
+                Hash::type_hash(&*target),
+            ))?;
+        }
+
+        return Ok(trait_impl);
+    }
+
+    let Some(native) = NATIVE_TRAITS.iter().find(|t| is_item(trait_item, t.path)) else {
+        return Err(compile::Error::new(
+            trait_path,
+            ErrorKind::UnsupportedTraitImpl {
+                trait_item: trait_item.try_to_owned()?,
+            },
+        ));
+    };
+
+    for (f, name) in &names {
+        let Some(function) = native.functions.iter().find(|n| n.name == *name) else {
+            return Err(compile::Error::new(
+                f.name,
+                ErrorKind::NotTraitFunction {
+                    name: (*name).try_into()?,
+                    trait_item: trait_item.try_to_owned()?,
+                },
+            ));
+        };
+
+        if let Some(protocol) = function.protocol {
+            trait_impl.aliases.try_push((
+                Hash::associated_function(type_hash, protocol),
+                Hash::associated_function(type_hash, function.name),
+            ))?;
+        }
+    }
+
+    for function in native.functions {
+        if function.required && !names.iter().any(|(_, name)| *name == function.name) {
+            return Err(compile::Error::new(
+                location.span,
+                ErrorKind::MissingTraitFunction {
+                    name: function.name.try_into()?,
+                    trait_item: trait_item.try_to_owned()?,
+                },
+            ));
+        }
+    }
+
+    for &(protocol, name) in native.derived {
+        trait_impl.aliases.try_push((
+            Hash::associated_function(type_hash, protocol),
+            Hash::associated_function(type_hash, name),
+        ))?;
+    }
+
+    trait_impl.into_iter = native.is_iterator;
+    Ok(trait_impl)
+}
+
+/// Test if the given item matches the given path.
+fn is_item(item: &Item, path: &[ComponentRef<'_>]) -> bool {
+    item.iter().eq(path.iter().copied())
+}
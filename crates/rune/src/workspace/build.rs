@@ -114,6 +114,15 @@ impl<'a> Build<'a> {
             }
         }
 
+        if let Some(id) = self.sources.source_ids().next() {
+            let mut loader =
+                Loader::new(id, self.sources, diagnostics, source_loader, &mut manifest);
+
+            if let Err(error) = loader.resolve_dependencies() {
+                diagnostics.fatal(id, WorkspaceError::new(Span::empty(), error))?;
+            }
+        }
+
         if diagnostics.has_errors() {
             return Err(BuildError::DEFAULT);
         }
@@ -2,6 +2,8 @@ use core::fmt;
 
 use std::path::Path;
 
+use semver::{Version, VersionReq};
+
 use crate::alloc::{self, Box, String};
 use crate::ast::{Span, Spanned};
 use crate::compile::HasSpan;
@@ -124,6 +126,15 @@ pub(crate) enum WorkspaceErrorKind {
     UnsupportedKey {
         key: String,
     },
+    DependenciesWithoutPackage,
+    DependencyWithoutPackage {
+        name: String,
+    },
+    DependencyVersionMismatch {
+        name: String,
+        expected: VersionReq,
+        actual: Version,
+    },
     AllocError {
         error: alloc::Error,
     },
@@ -176,6 +187,22 @@ impl fmt::Display for WorkspaceErrorKind {
             ),
             WorkspaceErrorKind::ExpectedTable => write!(f, "Expected table"),
             WorkspaceErrorKind::UnsupportedKey { key } => write!(f, "Key `{key}` not supported",),
+            WorkspaceErrorKind::DependenciesWithoutPackage => write!(
+                f,
+                "Element `[dependencies]` can only be used in manifests with a `[package]`"
+            ),
+            WorkspaceErrorKind::DependencyWithoutPackage { name } => write!(
+                f,
+                "Dependency `{name}` does not have a `[package]` in its manifest"
+            ),
+            WorkspaceErrorKind::DependencyVersionMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Dependency `{name}` requires version `{expected}`, but version `{actual}` was found"
+            ),
             WorkspaceErrorKind::AllocError { error } => error.fmt(f),
         }
     }
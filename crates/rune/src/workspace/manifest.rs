@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
 use relative_path::{RelativePath, RelativePathBuf};
-use semver::Version;
+use semver::{Version, VersionReq};
 use serde::de::IntoDeserializer;
 use serde::Deserialize;
 use serde_hashkey as key;
@@ -92,6 +92,9 @@ pub struct FoundPackage<'a> {
     pub found: Found,
     /// Index of the package build belongs to.
     pub package: &'a Package,
+    /// Libraries of the direct and transitive dependencies of the package,
+    /// where the name of each library is the module it should be mounted as.
+    pub dependencies: Vec<Found>,
 }
 
 impl WorkspaceFilter<'_> {
@@ -117,6 +120,9 @@ impl<T> Spanned for toml::Spanned<T> {
 pub struct Manifest {
     /// List of packages found.
     pub packages: Vec<Package>,
+    /// Packages which are not members of the workspace, but which members
+    /// depend on.
+    pub dependencies: Vec<Package>,
 }
 
 impl Manifest {
@@ -130,7 +136,13 @@ impl Manifest {
 
         for package in self.packages.iter() {
             for found in package.find_by_kind(filter, kind)? {
-                output.try_push(FoundPackage { found, package })?;
+                let dependencies = self.find_dependency_libs(package)?;
+
+                output.try_push(FoundPackage {
+                    found,
+                    package,
+                    dependencies,
+                })?;
             }
         }
 
@@ -145,6 +157,84 @@ impl Manifest {
         }
         Ok(output)
     }
+
+    /// Find the package whose root is the given path, among both members and
+    /// dependencies.
+    pub fn find_by_root(&self, path: &Path) -> Option<&Package> {
+        self.packages_and_dependencies().find(|package| {
+            package
+                .root
+                .as_deref()
+                .is_some_and(|root| same_path(root, path))
+        })
+    }
+
+    /// Iterate over all packages, followed by the packages which are only
+    /// loaded as dependencies.
+    fn packages_and_dependencies(&self) -> impl Iterator<Item = &Package> {
+        self.packages.iter().chain(&self.dependencies)
+    }
+
+    /// Find the libraries of all direct and transitive dependencies of the
+    /// given package.
+    ///
+    /// The name of each returned library is the module it should be mounted
+    /// as, which is the name of the dependency with `-` replaced by `_`.
+    pub fn find_dependency_libs(&self, package: &Package) -> Result<Vec<Found>> {
+        let mut output = Vec::new();
+        let mut visited = Vec::new();
+        let mut queue = Vec::new();
+        visited.try_push(package)?;
+        queue.try_push(package)?;
+
+        while let Some(package) = queue.pop() {
+            for dependency in &package.dependencies {
+                let Some(found) = self.find_by_root(&dependency.path) else {
+                    continue;
+                };
+
+                if visited.iter().any(|p| core::ptr::eq(*p, found)) {
+                    continue;
+                }
+
+                visited.try_push(found)?;
+                queue.try_push(found)?;
+
+                if let Some(mut lib) = found.find_lib(WorkspaceFilter::All)? {
+                    lib.name = dependency.module_name()?;
+                    output.try_push(lib)?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// A path dependency of a package.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Dependency {
+    /// The name of the dependency.
+    pub name: String,
+    /// The root directory of the dependency.
+    pub path: PathBuf,
+    /// The version requirement of the dependency, if any.
+    pub version: Option<VersionReq>,
+    /// The manifest the dependency is declared in.
+    source_id: SourceId,
+    /// The span of the dependency declaration.
+    span: Span,
+}
+
+impl Dependency {
+    /// The name of the module the dependency is mounted as.
+    pub fn module_name(&self) -> alloc::Result<String> {
+        self.name
+            .chars()
+            .map(|c| if c == '-' { '_' } else { c })
+            .try_collect()
+    }
 }
 
 /// A single package.
@@ -167,6 +257,8 @@ pub struct Package {
     pub auto_examples: bool,
     /// Automatically detect benches.
     pub auto_benches: bool,
+    /// Path dependencies of the package.
+    pub dependencies: Vec<Dependency>,
 }
 
 impl Package {
@@ -344,12 +436,56 @@ impl<'a> Loader<'a> {
 
     /// Load a manifest.
     pub(crate) fn load_manifest(&mut self) -> Result<()> {
+        let Some((mut table, root)) = self.load_table()? else {
+            return Ok(());
+        };
+
+        let root = root.as_deref();
+
+        // If manifest is a package, add it here.
+        if let Some(package) = self.load_package_section(&mut table, root)? {
+            self.manifest.packages.try_push(package)?;
+        }
+
+        // Load the [workspace] section.
+        if let Some((mut table, span)) = table
+            .remove("workspace")
+            .map(|value| self.ensure_table(value))
+            .transpose()?
+            .flatten()
+        {
+            match &root {
+                Some(root) => {
+                    if let Some(members) = self.load_members(&mut table, root)? {
+                        for (span, path) in members {
+                            self.load_member(span, &path)?;
+                        }
+                    }
+                }
+                None => {
+                    self.fatal(WorkspaceError::new(
+                        span,
+                        WorkspaceErrorKind::MissingManifestPath,
+                    ))?;
+                }
+            }
+
+            self.ensure_empty(table)?;
+        }
+
+        self.ensure_empty(table)?;
+        Ok(())
+    }
+
+    /// Parse the current manifest into a table, returning it together with the
+    /// directory the manifest is located in.
+    fn load_table(&mut self) -> Result<Option<(Table, Option<PathBuf>)>> {
         let Some(source) = self.sources.get(self.id) else {
             self.fatal(WorkspaceError::new(
                 Span::empty(),
                 WorkspaceErrorKind::MissingSourceId { source_id: self.id },
             ))?;
-            return Ok(());
+            return Ok(None);
         };
 
         let value: SpannedValue = match toml::from_str(source.as_str()) {
@@ -361,7 +497,7 @@ impl<'a> Loader<'a> {
                 };
 
                 self.fatal(WorkspaceError::new(span, e))?;
-                return Ok(());
+                return Ok(None);
             }
         };
 
@@ -369,52 +505,51 @@ impl<'a> Loader<'a> {
             .path()
             .and_then(|p| p.parent().map(TryToOwned::try_to_owned))
             .transpose()?;
-        let root = root.as_deref();
 
-        let Some((mut table, _)) = self.ensure_table(value)? else {
-            return Ok(());
+        let Some((table, _)) = self.ensure_table(value)? else {
+            return Ok(None);
         };
 
-        // If manifest is a package, add it here.
+        Ok(Some((table, root)))
+    }
+
+    /// Load the `[package]` and `[dependencies]` sections of a manifest.
+    fn load_package_section(
+        &mut self,
+        table: &mut Table,
+        root: Option<&Path>,
+    ) -> Result<Option<Package>> {
+        let dependencies = table.remove("dependencies");
+
         if let Some((package, span)) = table
             .remove("package")
             .map(|value| self.ensure_table(value))
             .transpose()?
             .flatten()
         {
-            if let Some(package) = self.load_package(package, span, root)? {
-                self.manifest.packages.try_push(package)?;
-            }
-        }
+            let Some(mut package) = self.load_package(package, span, root)? else {
+                return Ok(None);
+            };
 
-        // Load the [workspace] section.
-        if let Some((mut table, span)) = table
-            .remove("workspace")
-            .map(|value| self.ensure_table(value))
-            .transpose()?
-            .flatten()
-        {
-            match &root {
-                Some(root) => {
-                    if let Some(members) = self.load_members(&mut table, root)? {
-                        for (span, path) in members {
-                            self.load_member(span, &path)?;
-                        }
-                    }
-                }
-                None => {
-                    self.fatal(WorkspaceError::new(
-                        span,
-                        WorkspaceErrorKind::MissingManifestPath,
-                    ))?;
-                }
+            if let Some((dependencies, span)) = dependencies
+                .map(|value| self.ensure_table(value))
+                .transpose()?
+                .flatten()
+            {
+                package.dependencies = self.load_dependencies(dependencies, span, root)?;
             }
 
-            self.ensure_empty(table)?;
+            return Ok(Some(package));
         }
 
-        self.ensure_empty(table)?;
-        Ok(())
+        if let Some(dependencies) = dependencies {
+            self.fatal(WorkspaceError::new(
+                Spanned::span(&dependencies),
+                WorkspaceErrorKind::DependenciesWithoutPackage,
+            ))?;
+        }
+
+        Ok(None)
     }
 
     /// Load members from the given workspace configuration.
@@ -519,6 +654,62 @@ impl<'a> Loader<'a> {
         Ok(())
     }
 
+    /// Try to load the given path as the manifest of a dependency named
+    /// `name`, which is not a member of the workspace.
+    ///
+    /// Only the package is loaded, so any `[workspace]` section of the manifest
+    /// is ignored.
+    fn load_dependency(&mut self, name: &str, span: Span, path: &Path) -> Result<()> {
+        let source = match self.source_loader.load(span, path) {
+            Ok(source) => source,
+            Err(error) => {
+                self.fatal(error)?;
+                return Ok(());
+            }
+        };
+
+        let id = self.sources.insert(source)?;
+        let old = std::mem::replace(&mut self.id, id);
+        let result = self.load_dependency_package();
+        self.id = old;
+
+        match result? {
+            Some(Some(package)) => {
+                self.manifest.dependencies.try_push(package)?;
+            }
+            Some(None) => {
+                self.fatal(WorkspaceError::new(
+                    span,
+                    WorkspaceErrorKind::DependencyWithoutPackage {
+                        name: name.try_to_owned()?,
+                    },
+                ))?;
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Load the package of the current manifest, returning `None` if the
+    /// manifest could not be loaded.
+    fn load_dependency_package(&mut self) -> Result<Option<Option<Package>>> {
+        let Some((mut table, root)) = self.load_table()? else {
+            return Ok(None);
+        };
+
+        if !table.contains_key("package") {
+            return Ok(Some(None));
+        }
+
+        let package = self.load_package_section(&mut table, root.as_deref())?;
+        table.remove("workspace");
+        self.ensure_empty(table)?;
+
+        // Errors in the package section have already been reported.
+        Ok(package.map(Some))
+    }
+
     /// Load a package from a value.
     fn load_package(
         &mut self,
@@ -543,9 +734,137 @@ impl<'a> Loader<'a> {
             auto_tests: true,
             auto_examples: true,
             auto_benches: true,
+            dependencies: Vec::new(),
         }))
     }
 
+    /// Load the `[dependencies]` table of a package.
+    fn load_dependencies(
+        &mut self,
+        table: Table,
+        span: Span,
+        root: Option<&Path>,
+    ) -> alloc::Result<Vec<Dependency>> {
+        let mut output = Vec::new();
+
+        let Some(root) = root else {
+            self.fatal(WorkspaceError::new(
+                span,
+                WorkspaceErrorKind::MissingManifestPath,
+            ))?;
+            return Ok(output);
+        };
+
+        for (key, value) in table {
+            let span = Spanned::span(&key);
+            let name = key.into_inner();
+
+            let Some((mut table, table_span)) = self.ensure_table(value)? else {
+                continue;
+            };
+
+            let path = self.field::<RelativePathBuf>(&mut table, table_span, "path")?;
+
+            let version = match table.remove("version") {
+                Some(value) => match deserialize::<VersionReq>(value) {
+                    Ok(version) => Some(version),
+                    Err(error) => {
+                        self.fatal(error)?;
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            self.ensure_empty(table)?;
+
+            let Some(path) = path else {
+                continue;
+            };
+
+            output.try_push(Dependency {
+                name: name.as_str().try_into()?,
+                path: path.to_path(root),
+                version,
+                source_id: self.id,
+                span,
+            })?;
+        }
+
+        Ok(output)
+    }
+
+    /// Load manifests of dependencies which are not members of the workspace,
+    /// and check that the version requirements of all dependencies are
+    /// satisfied.
+    pub(crate) fn resolve_dependencies(&mut self) -> Result<()> {
+        let mut index = 0;
+
+        // Dependencies which are loaded are appended, so their dependencies
+        // are resolved in turn.
+        loop {
+            let Some(package) = self.manifest.packages_and_dependencies().nth(index) else {
+                break;
+            };
+
+            let mut missing = Vec::new();
+
+            for dependency in &package.dependencies {
+                if self.manifest.find_by_root(&dependency.path).is_none() {
+                    missing.try_push((
+                        dependency.name.try_clone()?,
+                        dependency.source_id,
+                        dependency.span,
+                        dependency.path.join(MANIFEST_FILE),
+                    ))?;
+                }
+            }
+
+            for (name, source_id, span, path) in missing {
+                if path
+                    .parent()
+                    .is_some_and(|root| self.manifest.find_by_root(root).is_some())
+                {
+                    continue;
+                }
+
+                let old = std::mem::replace(&mut self.id, source_id);
+                let result = self.load_dependency(&name, span, &path);
+                self.id = old;
+                result?;
+            }
+
+            index += 1;
+        }
+
+        for package in self.manifest.packages_and_dependencies() {
+            for dependency in &package.dependencies {
+                let error = match self.manifest.find_by_root(&dependency.path) {
+                    Some(found) => match &dependency.version {
+                        Some(version) if !version.matches(&found.version) => {
+                            WorkspaceErrorKind::DependencyVersionMismatch {
+                                name: dependency.name.try_clone()?,
+                                expected: version.clone(),
+                                actual: found.version.clone(),
+                            }
+                        }
+                        _ => continue,
+                    },
+                    // An error has already been reported when loading the
+                    // manifest of the dependency.
+                    None => continue,
+                };
+
+                self.diagnostics.fatal(
+                    dependency.source_id,
+                    WorkspaceError::new(dependency.span, error),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Ensure that a table is empty and mark any additional elements as erroneous.
     fn ensure_empty(&mut self, table: Table) -> alloc::Result<()> {
         for (key, _) in table {
@@ -633,6 +952,19 @@ where
     Ok(value)
 }
 
+/// Test if two paths refer to the same location, falling back to comparing
+/// them as-is if they can't be canonicalized.
+fn same_path(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
 /// Find binary entry points in the given directory
 fn find_binary_entry_points(path: &Path) -> Result<Vec<(PathBuf, String)>> {
     let mut entry_points = Vec::new();